@@ -71,8 +71,10 @@ impl Config {
                 .arg(
                     Arg::with_name("tag_url")
                         .long("tag_url")
-                        .help("Sets the url to for databend tags")
-                        .default_value("https://api.github.com/repos/datafuselabs/databend/tags")
+                        .help("Sets the url to for databend releases")
+                        .default_value(
+                            "https://api.github.com/repos/datafuselabs/databend/releases",
+                        )
                         .env("DOWNLOAD_URL")
                         .global(true)
                         .takes_value(true),
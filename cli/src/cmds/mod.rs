@@ -32,6 +32,7 @@ pub use env::Env;
 pub use helps::help::HelpCommand;
 pub use packages::fetch::FetchCommand;
 pub use packages::list::ListCommand;
+pub use packages::list_versions::ListVersionsCommand;
 pub use packages::package::PackageCommand;
 pub use packages::switch::SwitchCommand;
 pub use processor::Processor;
@@ -14,18 +14,28 @@
 
 use std::fs;
 use std::fs::File;
+use std::fs::OpenOptions;
 use std::io;
+use std::io::Cursor;
 use std::path::Path;
 
 use clap::ArgMatches;
 use flate2::read::GzDecoder;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
+use pgp::types::KeyTrait;
+use pgp::Deserializable;
+use pgp::SignedPublicKey;
+use pgp::StandaloneSignature;
+use sha2::Digest;
+use sha2::Sha256;
 use tar::Archive;
 
 use crate::cmds::Config;
+use crate::cmds::ListVersionsCommand;
 use crate::cmds::SwitchCommand;
 use crate::cmds::Writer;
+use crate::error::CliError;
 use crate::error::Result;
 
 #[derive(Clone)]
@@ -38,40 +48,174 @@ impl FetchCommand {
         FetchCommand { conf }
     }
 
-    //(TODO(zhihanz)) general get_architecture similar to install-databend.sh
     fn get_architecture(&self) -> Result<String> {
-        let os = std::env::consts::OS;
-        let arch = std::env::consts::ARCH;
-        let mut clib = "gnu";
-
-        // Check musl
-        let (_, musl, _) = run_script::run_script!(r#"ldd --version 2>&1 | grep -q 'musl'"#)?;
-        if !musl.is_empty() {
-            clib = "musl";
-        }
+        Ok(Self::target_triple(
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            Self::is_musl(),
+        ))
+    }
 
-        // Check rosetta
-        let (_, rosetta, _) = run_script::run_script!(r#"uname -a"#)?;
-        if rosetta.contains("Darwin") && rosetta.contains("arm64") {
-            return Ok("aarch64-apple-darwin".to_string());
-        }
+    /// The target triple of the release binaries, e.g. `x86_64-unknown-linux-gnu`.
+    pub(crate) fn target_triple(os: &str, arch: &str, musl: bool) -> String {
         let os = match os {
-            "darwin" => "apple-darwin".to_string(),
-            "macos" => "apple-darwin".to_string(),
-            "linux" => format!("unknown-linux-{}", clib),
+            "macos" | "darwin" => "apple-darwin".to_string(),
+            "linux" if musl => "unknown-linux-musl".to_string(),
+            "linux" => "unknown-linux-gnu".to_string(),
             _ => os.to_string(),
         };
+        format!("{}-{}", arch, os)
+    }
 
-        Ok(format!("{}-{}", arch, os))
+    // A musl system has its dynamic linker at /lib/ld-musl-<arch>.so.1
+    fn is_musl() -> bool {
+        fs::read_dir("/lib")
+            .map(|entries| {
+                entries.filter_map(|e| e.ok()).any(|e| {
+                    e.file_name()
+                        .to_str()
+                        .map_or(false, |name| name.starts_with("ld-musl-"))
+                })
+            })
+            .unwrap_or(false)
     }
 
     fn get_latest_tag(&self) -> Result<String> {
-        let tag_url = self.conf.tag_url.clone();
-        let resp = ureq::get(tag_url.as_str()).call()?;
-        let json: serde_json::Value = resp.into_json().unwrap();
+        ListVersionsCommand::get_latest_tag(&self.conf)
+    }
+
+    // The published checksum of a release file is at `{url}.sha256`, as `<hex digest>  <file name>`
+    fn get_checksum(&self, binary_url: &str) -> Result<String> {
+        let checksum_url = format!("{}.sha256", binary_url);
+        let resp = ureq::get(checksum_url.as_str()).call()?;
+        let content = resp.into_string()?;
+        match content.split_whitespace().next() {
+            Some(checksum) => Ok(checksum.to_lowercase()),
+            None => Err(CliError::Checksum(format!(
+                "Empty checksum file {}",
+                checksum_url
+            ))),
+        }
+    }
+
+    pub(crate) fn sha256_file(path: &str) -> Result<String> {
+        let mut file = File::open(path)?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher)?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Checks the SHA-256 of the file, which is deleted if it does not match.
+    pub(crate) fn verify_checksum(path: &str, expected: &str) -> Result<()> {
+        let actual = Self::sha256_file(path)?;
+        if actual != expected {
+            fs::remove_file(path)?;
+            return Err(CliError::Checksum(format!(
+                "Checksum mismatch of {}, expected {} but got {}, the file is deleted",
+                path, expected, actual
+            )));
+        }
+        Ok(())
+    }
+
+    /// Downloads the url into the `.part` file, resuming from what a previous attempt left there.
+    pub(crate) fn download(url: &str, part_file: &str) -> Result<()> {
+        let downloaded = fs::metadata(part_file).map(|m| m.len()).unwrap_or(0);
+        let request = ureq::get(url);
+        let res = if downloaded > 0 {
+            match request
+                .set("Range", format!("bytes={}-", downloaded).as_str())
+                .call()
+            {
+                // The previous attempt got the whole file.
+                Err(ureq::Error::Status(416, _)) => return Ok(()),
+                res => res?,
+            }
+        } else {
+            request.call()?
+        };
+
+        // Servers not supporting ranges send the whole file again.
+        let resumed = res.status() == 206;
+        let downloaded = if resumed { downloaded } else { 0 };
+        let remaining: u64 = res
+            .header("content-length")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+
+        let pb = ProgressBar::new(downloaded + remaining);
+        pb.set_style(ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})")
+            .progress_chars("#>-"));
+        pb.set_position(downloaded);
 
-        Ok(format!("{}", json[0]["name"]).replace("\"", ""))
+        let mut out = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(part_file)?;
+        io::copy(&mut pb.wrap_read(res.into_reader()), &mut out)?;
+        pb.finish();
+        Ok(())
+    }
+
+    /// Moves the downloaded `.part` file to its final name, once its checksum is verified.
+    pub(crate) fn finalize(part_file: &str, bin_file: &str, checksum: &str) -> Result<()> {
+        Self::verify_checksum(part_file, checksum)?;
+        fs::rename(part_file, bin_file)?;
+        Ok(())
+    }
+
+    /// Verifies the armored detached signature of the file, against the armored public key.
+    pub(crate) fn verify_signature(file: &str, signature_file: &str, key_file: &str) -> Result<()> {
+        let signature_error = |e: pgp::errors::Error| {
+            CliError::Signature(format!("Cannot verify the signature of {}: {}", file, e))
+        };
+
+        let key = fs::read(key_file)?;
+        let (key, _) =
+            SignedPublicKey::from_armor_single(Cursor::new(key)).map_err(signature_error)?;
+        let signature = fs::read(signature_file)?;
+        let (signature, _) = StandaloneSignature::from_armor_single(Cursor::new(signature))
+            .map_err(signature_error)?;
+        let content = fs::read(file)?;
+
+        // The release may be signed by the primary key or by a signing subkey.
+        if signature.verify(&key, &content).is_ok()
+            || key
+                .public_subkeys
+                .iter()
+                .any(|subkey| signature.verify(subkey, &content).is_ok())
+        {
+            return Ok(());
+        }
+        Err(CliError::Signature(format!(
+            "Bad signature of {}, it is not signed by the key {:?}",
+            file,
+            key.key_id()
+        )))
     }
+
+    fn fetch_and_verify_signature(
+        &self,
+        binary_url: &str,
+        bin_file: &str,
+        matches: &ArgMatches,
+    ) -> Result<()> {
+        let key_file = matches.value_of("signature-key").ok_or_else(|| {
+            CliError::Signature("--verify-signature requires --signature-key".to_string())
+        })?;
+
+        let signature_url = format!("{}.asc", binary_url);
+        let signature_file = format!("{}.asc", bin_file);
+        let res = ureq::get(signature_url.as_str()).call()?;
+        let mut out = File::create(&signature_file)?;
+        io::copy(&mut res.into_reader(), &mut out)?;
+
+        Self::verify_signature(bin_file, &signature_file, key_file)
+    }
+
     pub fn exec_match(&self, writer: &mut Writer, args: Option<&ArgMatches>) -> Result<()> {
         match args {
             Some(matches) => {
@@ -99,28 +243,32 @@ impl FetchCommand {
 
                 let bin_name = format!("databend-{}-{}.tar.gz", current_tag, arch);
                 let bin_file = format!("{}/{}", bin_download_dir, bin_name);
-                let exists = Path::new(bin_file.as_str()).exists();
+                let binary_url = format!(
+                    "{}/{}/{}",
+                    self.conf.download_url.clone(),
+                    current_tag,
+                    bin_name,
+                );
+                let checksum = self.get_checksum(&binary_url)?;
+                // Only a file of the published checksum is reused.
+                let exists = Path::new(bin_file.as_str()).exists()
+                    && Self::sha256_file(&bin_file)? == checksum;
                 // Download.
                 if !exists {
-                    let binary_url = format!(
-                        "{}/{}/{}",
-                        self.conf.download_url.clone(),
-                        current_tag,
-                        bin_name,
-                    );
-                    let res = ureq::get(binary_url.as_str()).call()?;
-                    let total_size: u64 = res.header("content-length").unwrap().parse().unwrap();
-                    let pb = ProgressBar::new(total_size);
-                    pb.set_style(ProgressStyle::default_bar()
-                        .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-                        .progress_chars("#>-"));
-
-                    let mut out = File::create(bin_file.clone()).unwrap();
-                    io::copy(&mut pb.wrap_read(res.into_reader()), &mut out).unwrap();
+                    let part_file = format!("{}.part", bin_file);
+                    Self::download(&binary_url, &part_file)?;
                     writer.write_ok(format!("Download {}", binary_url).as_str());
+
+                    Self::finalize(&part_file, &bin_file, &checksum)?;
+                    writer.write_ok(format!("Checksum {}", checksum).as_str());
                 }
                 writer.write_ok(format!("Binary {}", bin_file).as_str());
 
+                if matches.is_present("verify-signature") {
+                    self.fetch_and_verify_signature(&binary_url, &bin_file, matches)?;
+                    writer.write_ok(format!("Signature {}.asc", binary_url).as_str());
+                }
+
                 // Unpack.
                 let tar_gz = File::open(bin_file)?;
                 let tar = GzDecoder::new(tar_gz);
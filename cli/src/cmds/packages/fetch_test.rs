@@ -0,0 +1,142 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::net::TcpListener;
+use std::path::Path;
+use std::thread;
+
+use tempfile::tempdir;
+
+use crate::cmds::FetchCommand;
+use crate::error::Result;
+
+// SHA-256 of "databend"
+const DATABEND_SHA256: &str = "29613b1bdc118f0b91fa1f08454c5a47ef3568891173763a9ad39720a7439ad4";
+
+#[test]
+fn test_target_triple() -> Result<()> {
+    let cases = vec![
+        ("linux", "x86_64", false, "x86_64-unknown-linux-gnu"),
+        ("linux", "x86_64", true, "x86_64-unknown-linux-musl"),
+        ("linux", "aarch64", false, "aarch64-unknown-linux-gnu"),
+        ("macos", "x86_64", false, "x86_64-apple-darwin"),
+        ("macos", "aarch64", false, "aarch64-apple-darwin"),
+        // libc only matters on linux
+        ("macos", "aarch64", true, "aarch64-apple-darwin"),
+    ];
+    for (os, arch, musl, expect) in cases {
+        assert_eq!(FetchCommand::target_triple(os, arch, musl), expect);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_verify_checksum() -> Result<()> {
+    let t = tempdir()?;
+    let file = t.path().join("databend.tar.gz");
+    let file = file.to_str().unwrap();
+
+    fs::write(file, "databend")?;
+    assert_eq!(FetchCommand::sha256_file(file)?, DATABEND_SHA256);
+    FetchCommand::verify_checksum(file, DATABEND_SHA256)?;
+    assert!(Path::new(file).exists());
+
+    // a garbage checksum is rejected, and the file is deleted
+    let garbage = "0".repeat(64);
+    assert!(FetchCommand::verify_checksum(file, &garbage).is_err());
+    assert!(!Path::new(file).exists());
+
+    Ok(())
+}
+
+// Serves `content` to a single request, honoring its range header.
+fn serve_once(content: &'static [u8]) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/databend.tar.gz", listener.local_addr()?);
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut from = 0;
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(range) = line.strip_prefix("range: bytes=") {
+                from = range.trim_end_matches('-').parse::<usize>().unwrap();
+            }
+        }
+
+        let status = if from > 0 {
+            "206 Partial Content"
+        } else {
+            "200 OK"
+        };
+        let body = &content[from..];
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(body).unwrap();
+    });
+    Ok(url)
+}
+
+#[test]
+fn test_resume_download() -> Result<()> {
+    let t = tempdir()?;
+    let part_file = t.path().join("databend.tar.gz.part");
+    let part_file = part_file.to_str().unwrap();
+    let bin_file = t.path().join("databend.tar.gz");
+    let bin_file = bin_file.to_str().unwrap();
+
+    // an interrupted download left the first bytes
+    fs::write(part_file, "data")?;
+
+    let url = serve_once(b"databend")?;
+    FetchCommand::download(&url, part_file)?;
+    assert_eq!(fs::read(part_file)?, b"databend");
+
+    FetchCommand::finalize(part_file, bin_file, DATABEND_SHA256)?;
+    assert!(!Path::new(part_file).exists());
+    assert_eq!(fs::read(bin_file)?, b"databend");
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_signature() -> Result<()> {
+    let fixtures = format!("{}/tests/data/gpg", env!("CARGO_MANIFEST_DIR"));
+    let key = format!("{}/public_key.asc", fixtures);
+    let signature = format!("{}/databend.tar.gz.asc", fixtures);
+
+    FetchCommand::verify_signature(&format!("{}/databend.tar.gz", fixtures), &signature, &key)?;
+
+    // the signature does not match other content
+    let tampered = format!("{}/tampered.tar.gz", fixtures);
+    assert!(FetchCommand::verify_signature(&tampered, &signature, &key).is_err());
+
+    // nor is a key a signature
+    assert!(FetchCommand::verify_signature(&tampered, &key, &key).is_err());
+    Ok(())
+}
@@ -0,0 +1,141 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::ArgMatches;
+use comfy_table::Cell;
+use comfy_table::Table;
+
+use crate::cmds::Config;
+use crate::cmds::Writer;
+use crate::error::CliError;
+use crate::error::Result;
+
+// The most the GitHub API returns in a page.
+const RELEASES_PER_PAGE: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Release {
+    pub tag: String,
+    // Empty if the endpoint does not tell, e.g. the tags endpoint.
+    pub published_at: String,
+}
+
+#[derive(Clone)]
+pub struct ListVersionsCommand {
+    conf: Config,
+}
+
+impl ListVersionsCommand {
+    pub fn create(conf: Config) -> Self {
+        ListVersionsCommand { conf }
+    }
+
+    /// Parses the payload of the GitHub releases (or tags) endpoint, newest first.
+    pub fn parse_releases(json: &serde_json::Value) -> Result<Vec<Release>> {
+        let entries = json.as_array().ok_or_else(|| {
+            CliError::Unknown(format!("Unexpected payload of releases: {}", json))
+        })?;
+
+        let mut releases = entries
+            .iter()
+            .filter_map(|entry| {
+                // a draft is not downloadable
+                if entry["draft"].as_bool() == Some(true) {
+                    return None;
+                }
+                let tag = entry["tag_name"]
+                    .as_str()
+                    .or_else(|| entry["name"].as_str())?;
+                Some(Release {
+                    tag: tag.to_string(),
+                    published_at: entry["published_at"].as_str().unwrap_or("").to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Self::sort_releases(&mut releases);
+        Ok(releases)
+    }
+
+    // RFC 3339 timestamps sort as strings, the sort is stable for the undated ones
+    fn sort_releases(releases: &mut [Release]) {
+        releases.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+    }
+
+    /// Collects the releases of the pages returned by `fetch_page`, from the first one, until
+    /// there are at least `limit` releases or a page is not full, i.e. it is the last one.
+    pub fn collect_releases<F>(mut fetch_page: F, limit: usize) -> Result<Vec<Release>>
+    where F: FnMut(usize) -> Result<serde_json::Value> {
+        let mut releases = vec![];
+        for page in 1.. {
+            let json = fetch_page(page)?;
+            releases.extend(Self::parse_releases(&json)?);
+            let entries = json
+                .as_array()
+                .map(|entries| entries.len())
+                .unwrap_or_default();
+            if entries < RELEASES_PER_PAGE || releases.len() >= limit {
+                break;
+            }
+        }
+
+        Self::sort_releases(&mut releases);
+        Ok(releases)
+    }
+
+    /// The releases of the `tag_url` endpoint, newest first. Only the pages needed for `limit`
+    /// releases are fetched.
+    pub fn get_releases(conf: &Config, limit: usize) -> Result<Vec<Release>> {
+        let fetch_page = |page: usize| -> Result<serde_json::Value> {
+            let resp = ureq::get(conf.tag_url.as_str())
+                .query("per_page", &RELEASES_PER_PAGE.to_string())
+                .query("page", &page.to_string())
+                .call()?;
+            Ok(resp.into_json()?)
+        };
+        Self::collect_releases(fetch_page, limit)
+    }
+
+    pub fn get_latest_tag(conf: &Config) -> Result<String> {
+        match Self::get_releases(conf, 1)?.into_iter().next() {
+            Some(release) => Ok(release.tag),
+            None => Err(CliError::Unknown("No released version found".to_string())),
+        }
+    }
+
+    pub fn exec_match(&self, writer: &mut Writer, args: Option<&ArgMatches>) -> Result<()> {
+        let limit = match args.and_then(|m| m.value_of("limit")) {
+            None => usize::MAX,
+            Some(v) => v
+                .parse::<usize>()
+                .map_err(|_| CliError::Unknown(format!("Invalid limit: {}", v)))?,
+        };
+
+        let mut table = Table::new();
+        table.load_preset("||--+-++|    ++++++");
+        table.set_header(vec![Cell::new("Version"), Cell::new("Published")]);
+        for release in Self::get_releases(&self.conf, limit)?
+            .into_iter()
+            .take(limit)
+        {
+            table.add_row(vec![
+                Cell::new(release.tag.as_str()),
+                Cell::new(release.published_at.as_str()),
+            ]);
+        }
+        writer.writeln(&table.trim_fmt());
+
+        Ok(())
+    }
+}
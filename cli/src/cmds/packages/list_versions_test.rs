@@ -0,0 +1,101 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::cmds::packages::list_versions::Release;
+use crate::cmds::ListVersionsCommand;
+use crate::error::Result;
+
+#[test]
+fn test_parse_releases() -> Result<()> {
+    let json: serde_json::Value = serde_json::from_str(
+        r#"[
+            {"tag_name": "v0.5.1-nightly", "published_at": "2021-10-20T01:00:00Z", "draft": false},
+            {"tag_name": "v0.5.2-nightly", "published_at": "2021-10-21T01:00:00Z", "draft": false},
+            {"tag_name": "v0.5.0-nightly", "published_at": "2021-10-19T01:00:00Z", "draft": false}
+        ]"#,
+    )?;
+    let expect = vec![
+        Release {
+            tag: "v0.5.2-nightly".to_string(),
+            published_at: "2021-10-21T01:00:00Z".to_string(),
+        },
+        Release {
+            tag: "v0.5.1-nightly".to_string(),
+            published_at: "2021-10-20T01:00:00Z".to_string(),
+        },
+        Release {
+            tag: "v0.5.0-nightly".to_string(),
+            published_at: "2021-10-19T01:00:00Z".to_string(),
+        },
+    ];
+    assert_eq!(ListVersionsCommand::parse_releases(&json)?, expect);
+
+    // the tags endpoint has names only, in its own order
+    let json: serde_json::Value =
+        serde_json::from_str(r#"[{"name": "v0.5.2-nightly"}, {"name": "v0.5.1-nightly"}]"#)?;
+    let tags = ListVersionsCommand::parse_releases(&json)?
+        .into_iter()
+        .map(|r| r.tag)
+        .collect::<Vec<_>>();
+    assert_eq!(tags, vec!["v0.5.2-nightly", "v0.5.1-nightly"]);
+
+    assert!(ListVersionsCommand::parse_releases(&serde_json::json!({})).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_collect_releases() -> Result<()> {
+    let release = |i: usize| {
+        serde_json::json!({
+            "tag_name": format!("v0.{}.0-nightly", i),
+            "published_at": format!("2021-10-{:02}T00:00:00Z", i % 28 + 1),
+            "draft": i == 0,
+        })
+    };
+    // two full pages and a last one with a single release
+    let pages: Vec<serde_json::Value> = (0..3)
+        .map(|p| {
+            let n = if p == 2 { 1 } else { 100 };
+            serde_json::Value::Array((0..n).map(|i| release(p * 100 + i)).collect())
+        })
+        .collect();
+
+    let mut fetched = vec![];
+    let releases = ListVersionsCommand::collect_releases(
+        |page| {
+            fetched.push(page);
+            Ok(pages[page - 1].clone())
+        },
+        usize::MAX,
+    )?;
+    assert_eq!(fetched, vec![1, 2, 3]);
+    // the draft is skipped
+    assert_eq!(releases.len(), 200);
+    assert!(releases
+        .windows(2)
+        .all(|w| w[0].published_at >= w[1].published_at));
+
+    // the first page is enough for the latest release
+    let mut fetched = vec![];
+    let releases = ListVersionsCommand::collect_releases(
+        |page| {
+            fetched.push(page);
+            Ok(pages[page - 1].clone())
+        },
+        1,
+    )?;
+    assert_eq!(fetched, vec![1]);
+    assert_eq!(releases.len(), 99);
+    Ok(())
+}
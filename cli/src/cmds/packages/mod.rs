@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod fetch_test;
+#[cfg(test)]
+mod list_versions_test;
+
 pub mod fetch;
 pub mod list;
+pub mod list_versions;
 pub mod package;
 pub mod switch;
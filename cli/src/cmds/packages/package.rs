@@ -23,6 +23,7 @@ use crate::cmds::command::Command;
 use crate::cmds::Config;
 use crate::cmds::FetchCommand;
 use crate::cmds::ListCommand;
+use crate::cmds::ListVersionsCommand;
 use crate::cmds::SwitchCommand;
 use crate::cmds::Writer;
 use crate::error::Result;
@@ -48,7 +49,15 @@ impl PackageCommand {
                     .setting(AppSettings::DisableVersion)
                     .setting(AppSettings::ColoredHelp)
                     .about("Fetch the given version binary package")
-                    .arg(Arg::with_name("version").help("Version of databend package to fetch").default_value("latest")),
+                    .arg(Arg::with_name("version").help("Version of databend package to fetch").default_value("latest"))
+                    .arg(Arg::with_name("verify-signature").long("verify-signature").help("Verify the GPG signature of the package before unpacking it"))
+                    .arg(
+                        Arg::with_name("signature-key")
+                            .long("signature-key")
+                            .help("Path of the trusted armored GPG public key, used with --verify-signature")
+                            .env("DATABEND_SIGNATURE_KEY")
+                            .takes_value(true),
+                    ),
             )
             .subcommand(
                 App::new("list")
@@ -56,6 +65,13 @@ impl PackageCommand {
                     .setting(AppSettings::ColoredHelp)
                     .about("List all the packages"),
             )
+            .subcommand(
+                App::new("list-versions")
+                    .setting(AppSettings::DisableVersion)
+                    .setting(AppSettings::ColoredHelp)
+                    .about("List the released versions, newest first")
+                    .arg(Arg::with_name("limit").long("limit").takes_value(true).help("Max number of versions to list")),
+            )
             .subcommand(
                 App::new("switch")
                     .setting(AppSettings::DisableVersion)
@@ -78,6 +94,11 @@ impl PackageCommand {
                     let list = ListCommand::create(self.conf.clone());
                     list.exec_match(writer, matches.subcommand_matches("list"))?;
                 }
+                Some("list-versions") => {
+                    let list_versions = ListVersionsCommand::create(self.conf.clone());
+                    list_versions
+                        .exec_match(writer, matches.subcommand_matches("list-versions"))?;
+                }
                 Some("switch") => {
                     let switch = SwitchCommand::create(self.conf.clone());
                     switch.exec_match(writer, matches.subcommand_matches("switch"))?;
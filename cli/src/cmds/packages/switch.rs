@@ -18,6 +18,7 @@ use clap::ArgMatches;
 
 use crate::cmds::Config;
 use crate::cmds::ListCommand;
+use crate::cmds::ListVersionsCommand;
 use crate::cmds::Status;
 use crate::cmds::Writer;
 use crate::error::Result;
@@ -33,11 +34,7 @@ impl SwitchCommand {
     }
 
     fn get_latest_tag(&self) -> Result<String> {
-        let tag_url = self.conf.tag_url.clone();
-        let resp = ureq::get(tag_url.as_str()).call()?;
-        let json: serde_json::Value = resp.into_json().unwrap();
-
-        Ok(format!("{}", json[0]["name"]).replace("\"", ""))
+        ListVersionsCommand::get_latest_tag(&self.conf)
     }
 
     pub fn exec_match(&self, writer: &mut Writer, args: Option<&ArgMatches>) -> Result<()> {
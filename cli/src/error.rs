@@ -32,6 +32,12 @@ pub enum CliError {
 
     #[error("Serde error: {0}")]
     Serde(Box<serde_json::Error>),
+
+    #[error("Checksum error: {0}")]
+    Checksum(String),
+
+    #[error("Signature error: {0}")]
+    Signature(String),
 }
 
 impl From<ureq::Error> for CliError {
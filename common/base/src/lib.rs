@@ -15,23 +15,44 @@
 #[cfg(test)]
 mod runtime_test;
 
+#[cfg(test)]
+mod profiling_test;
+
 #[cfg(test)]
 mod progress_test;
 
+#[cfg(test)]
+mod ring_buffer_test;
+
+#[cfg(test)]
+mod semaphore_test;
+
 #[cfg(test)]
 mod stoppable_test;
 
+#[cfg(test)]
+mod uniq_id_test;
+
 mod profiling;
 mod progress;
+mod ring_buffer;
 mod runtime;
+mod semaphore;
 
 pub use profiling::Profiling;
+pub use profiling::DEFAULT_PROFILING_FREQUENCY;
+pub use progress::throttled;
 pub use progress::Progress;
 pub use progress::ProgressCallback;
 pub use progress::ProgressValues;
+pub use ring_buffer::RingBuffer;
 pub use runtime::Dropper;
 pub use runtime::Runtime;
+pub use runtime::RuntimeMetrics;
 pub use runtime::TrySpawn;
+pub use semaphore::PermitGuard;
+pub use semaphore::Semaphore;
+pub use semaphore::SemaphoreValues;
 pub use tokio;
 pub use uuid;
 
@@ -40,6 +61,7 @@ mod stoppable;
 mod uniq_id;
 
 pub use stop_handle::StopHandle;
+pub use stop_handle::StopPhase;
 pub use stoppable::Stoppable;
 pub use uniq_id::GlobalSequence;
 pub use uniq_id::GlobalUniqName;
@@ -12,12 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write;
 use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use lazy_static::lazy_static;
 use pprof::protos::Message;
+use tokio::sync::Mutex;
 
+lazy_static! {
+    // The sampler is process wide, it can not be started again before the running one stops.
+    static ref SAMPLER: Mutex<()> = Mutex::new(());
+}
+
+/// The default sampling frequency, in Hz.
+pub const DEFAULT_PROFILING_FREQUENCY: i32 = 99;
+
+/// Samples the stacks of all the threads of the process for a duration, e.g., to let
+/// operators capture a CPU profile of a running node.
 pub struct Profiling {
     duration: Duration,
     frequency: i32,
@@ -31,7 +46,26 @@ impl Profiling {
         }
     }
 
+    /// Samples for `duration` at the default frequency, returns the flamegraph in SVG.
+    pub async fn flamegraph(duration: Duration) -> Result<Vec<u8>> {
+        Profiling::create(duration, DEFAULT_PROFILING_FREQUENCY)
+            .dump_flamegraph()
+            .await
+    }
+
+    /// Samples for `duration` at the default frequency, returns the gzip-compressed pprof
+    /// profile, which can be read by `go tool pprof` etc.
+    ///
+    /// To sample at another frequency, use `Profiling::create(duration, frequency).dump_proto()`.
+    pub async fn pprof_proto(duration: Duration) -> Result<Vec<u8>> {
+        Profiling::create(duration, DEFAULT_PROFILING_FREQUENCY)
+            .dump_proto()
+            .await
+    }
+
+    /// Profiles are captured one at a time, a capture waits for the running one to finish.
     pub async fn report(&self) -> Result<pprof::Report> {
+        let _sampler = SAMPLER.lock().await;
         let guard = pprof::ProfilerGuard::new(self.frequency)
             .map_err(|e| ErrorCode::UnknownException(e.to_string()))?;
         tokio::time::sleep(self.duration).await;
@@ -52,6 +86,7 @@ impl Profiling {
         Ok(body)
     }
 
+    /// Returns the gzip-compressed pprof profile.
     pub async fn dump_proto(&self) -> Result<Vec<u8>> {
         let mut body: Vec<u8> = Vec::new();
 
@@ -63,6 +98,8 @@ impl Profiling {
             .encode(&mut body)
             .map_err(|e| ErrorCode::UnknownException(e.to_string()))?;
 
-        Ok(body)
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&body)?;
+        Ok(encoder.finish()?)
     }
 }
@@ -0,0 +1,81 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use flate2::read::GzDecoder;
+use pprof::protos::Message;
+use pprof::protos::Profile;
+
+use crate::*;
+
+// Keeps a thread busy until the returned flag is set, so that there are stacks to sample.
+fn busy_loop() -> (Arc<AtomicBool>, JoinHandle<u64>) {
+    let stop = Arc::new(AtomicBool::new(false));
+    let handle = std::thread::spawn({
+        let stop = stop.clone();
+        move || {
+            let mut n = 0u64;
+            while !stop.load(Ordering::Relaxed) {
+                n = n.wrapping_mul(31).wrapping_add(7);
+            }
+            n
+        }
+    });
+    (stop, handle)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_profiling_flamegraph() -> Result<()> {
+    let (stop, handle) = busy_loop();
+    let svg = Profiling::flamegraph(Duration::from_millis(500)).await;
+    stop.store(true, Ordering::Relaxed);
+    let _ = handle.join();
+
+    let svg = svg?;
+    assert!(!svg.is_empty());
+    assert!(String::from_utf8_lossy(&svg).contains("<svg"));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_profiling_pprof_proto() -> Result<()> {
+    let (stop, handle) = busy_loop();
+    let default_freq = Profiling::pprof_proto(Duration::from_millis(500)).await;
+    let custom_freq = Profiling::create(Duration::from_millis(500), 199)
+        .dump_proto()
+        .await;
+    stop.store(true, Ordering::Relaxed);
+    let _ = handle.join();
+
+    for gzipped in [default_freq?, custom_freq?] {
+        // gzip magic
+        assert_eq!(&gzipped[..2], &[0x1f, 0x8b]);
+
+        let mut encoded = vec![];
+        GzDecoder::new(gzipped.as_slice()).read_to_end(&mut encoded)?;
+        let profile = Profile::decode(encoded.as_slice())
+            .map_err(|e| ErrorCode::UnknownException(e.to_string()))?;
+        assert!(!profile.sample.is_empty());
+        assert!(!profile.string_table.is_empty());
+    }
+    Ok(())
+}
@@ -12,12 +12,71 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
 
 /// Progress callback is called with progress about the stream read progress.
 pub type ProgressCallback = Box<dyn FnMut(&ProgressValues) + Send + Sync + 'static>;
 
+/// Wraps `callback` to forward at most one update per `min_interval`.
+///
+/// The updates in between are coalesced, i.e. summed up, and forwarded with the next one.
+/// Whatever is still pending when the returned callback is dropped is forwarded then, so the
+/// last update is never lost.
+pub fn throttled(callback: ProgressCallback, min_interval: Duration) -> ProgressCallback {
+    let mut throttled = ThrottledCallback {
+        callback,
+        min_interval,
+        last_forwarded: None,
+        pending: None,
+    };
+    Box::new(move |values: &ProgressValues| throttled.update(values))
+}
+
+struct ThrottledCallback {
+    callback: ProgressCallback,
+    min_interval: Duration,
+    last_forwarded: Option<Instant>,
+    pending: Option<ProgressValues>,
+}
+
+impl ThrottledCallback {
+    fn update(&mut self, values: &ProgressValues) {
+        let pending = self.pending.get_or_insert(ProgressValues {
+            read_rows: 0,
+            read_bytes: 0,
+            total_rows_to_read: 0,
+        });
+        pending.read_rows += values.read_rows;
+        pending.read_bytes += values.read_bytes;
+        pending.total_rows_to_read += values.total_rows_to_read;
+
+        let due = match self.last_forwarded {
+            None => true,
+            Some(last) => last.elapsed() >= self.min_interval,
+        };
+        if due {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            (self.callback)(&pending);
+            self.last_forwarded = Some(Instant::now());
+        }
+    }
+}
+
+impl Drop for ThrottledCallback {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
 #[derive(Debug)]
 pub struct ProgressValues {
     pub read_rows: usize,
@@ -30,6 +89,9 @@ pub struct Progress {
     read_rows: AtomicUsize,
     read_bytes: AtomicUsize,
     total_rows_to_read: AtomicUsize,
+    created: Instant,
+    // Start of the measurement, in nanos since `created`, moved forward by reset.
+    start_nanos: AtomicU64,
 }
 
 impl Progress {
@@ -38,6 +100,8 @@ impl Progress {
             read_rows: AtomicUsize::new(0),
             read_bytes: AtomicUsize::new(0),
             total_rows_to_read: AtomicUsize::new(0),
+            created: Instant::now(),
+            start_nanos: AtomicU64::new(0),
         }
     }
 
@@ -65,12 +129,58 @@ impl Progress {
         self.read_rows.store(0, Ordering::Relaxed);
         self.read_bytes.store(0, Ordering::Relaxed);
         self.total_rows_to_read.store(0, Ordering::Relaxed);
+        self.restart();
+    }
+
+    /// Time since the progress was created, or last reset.
+    pub fn elapsed(&self) -> Duration {
+        let start = Duration::from_nanos(self.start_nanos.load(Ordering::Relaxed));
+        self.created.elapsed().saturating_sub(start)
+    }
+
+    pub fn rows_per_second(&self) -> f64 {
+        Self::per_second(self.read_rows.load(Ordering::Relaxed), self.elapsed())
+    }
+
+    pub fn bytes_per_second(&self) -> f64 {
+        Self::per_second(self.read_bytes.load(Ordering::Relaxed), self.elapsed())
+    }
+
+    /// Estimated time to read the rest of `total_rows`, at the current rate.
+    ///
+    /// Returns `None` if nothing has been read yet.
+    pub fn eta(&self, total_rows: u64) -> Option<Duration> {
+        let read_rows = self.read_rows.load(Ordering::Relaxed) as u64;
+        if read_rows >= total_rows {
+            return Some(Duration::ZERO);
+        }
+        let rate = self.rows_per_second();
+        if rate <= 0.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(
+            (total_rows - read_rows) as f64 / rate,
+        ))
+    }
+
+    fn per_second(value: usize, elapsed: Duration) -> f64 {
+        let secs = elapsed.as_secs_f64();
+        if secs <= 0.0 {
+            return 0.0;
+        }
+        value as f64 / secs
+    }
+
+    fn restart(&self) {
+        let now = self.created.elapsed().as_nanos() as u64;
+        self.start_nanos.store(now, Ordering::Relaxed);
     }
 
     pub fn get_and_reset(&self) -> ProgressValues {
         let read_rows = self.read_rows.fetch_and(0, Ordering::Relaxed) as usize;
         let read_bytes = self.read_bytes.fetch_and(0, Ordering::Relaxed) as usize;
         let total_rows_to_read = self.total_rows_to_read.fetch_and(0, Ordering::Relaxed) as usize;
+        self.restart();
         ProgressValues {
             read_rows,
             read_bytes,
@@ -35,3 +35,76 @@ fn test_progress() -> Result<()> {
     assert_eq!(0, progress.get_values().read_bytes);
     Ok(())
 }
+
+#[test]
+fn test_progress_rate() -> Result<()> {
+    use std::time::Duration;
+
+    use crate::*;
+
+    let progress = Progress::create();
+    assert_eq!(progress.eta(100), None);
+
+    std::thread::sleep(Duration::from_millis(200));
+    progress.incr(&ProgressValues {
+        read_rows: 100,
+        read_bytes: 1000,
+        total_rows_to_read: 0,
+    });
+
+    // 100 rows in a bit more than 200ms, ~500 rows/s
+    let rows_per_second = progress.rows_per_second();
+    assert!(rows_per_second > 300.0 && rows_per_second <= 500.0);
+    let bytes_per_second = progress.bytes_per_second();
+    assert!(bytes_per_second > 3000.0 && bytes_per_second <= 5000.0);
+
+    // 200 more rows, ~400ms
+    let eta = progress.eta(300).unwrap();
+    assert!(eta >= Duration::from_millis(400) && eta < Duration::from_millis(700));
+    assert_eq!(progress.eta(100), Some(Duration::ZERO));
+
+    progress.reset();
+    assert_eq!(progress.eta(100), None);
+    assert!(progress.elapsed() < Duration::from_millis(200));
+    Ok(())
+}
+
+#[test]
+fn test_progress_throttled_callback() -> Result<()> {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::*;
+
+    let forwarded = Arc::new(AtomicUsize::new(0));
+    let forwarded_rows = Arc::new(AtomicUsize::new(0));
+    let callback: ProgressCallback = {
+        let forwarded = forwarded.clone();
+        let forwarded_rows = forwarded_rows.clone();
+        Box::new(move |values: &ProgressValues| {
+            forwarded.fetch_add(1, Ordering::Relaxed);
+            forwarded_rows.fetch_add(values.read_rows, Ordering::Relaxed);
+        })
+    };
+
+    let mut callback = throttled(callback, Duration::from_secs(60));
+    for _ in 0..1000 {
+        callback(&ProgressValues {
+            read_rows: 1,
+            read_bytes: 8,
+            total_rows_to_read: 0,
+        });
+    }
+
+    // only the first one gets through within the interval
+    assert_eq!(forwarded.load(Ordering::Relaxed), 1);
+    assert_eq!(forwarded_rows.load(Ordering::Relaxed), 1);
+
+    // the rest is flushed at last
+    drop(callback);
+    assert_eq!(forwarded.load(Ordering::Relaxed), 2);
+    assert_eq!(forwarded_rows.load(Ordering::Relaxed), 1000);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use common_infallible::Mutex;
+
+/// Keeps the last `capacity` items pushed, the oldest one is evicted to make room for a new one.
+///
+/// Pushes and snapshots are serialized, it is meant for the records written once in a while and
+/// read by the monitoring, e.g. a log of the completed queries.
+#[derive(Debug)]
+pub struct RingBuffer<T> {
+    capacity: usize,
+    items: Mutex<VecDeque<T>>,
+}
+
+impl<T: Clone> RingBuffer<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        RingBuffer {
+            capacity,
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Pushes an item, a buffer of zero capacity keeps nothing.
+    pub fn push(&self, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut items = self.items.lock();
+        if items.len() == self.capacity {
+            items.pop_front();
+        }
+        items.push_back(item);
+    }
+
+    /// The items in the buffer, the oldest first.
+    pub fn snapshot(&self) -> Vec<T> {
+        self.items.lock().iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.lock().is_empty()
+    }
+}
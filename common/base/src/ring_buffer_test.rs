@@ -0,0 +1,39 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RingBuffer;
+
+#[test]
+fn test_ring_buffer_evicts_oldest() {
+    let buffer = RingBuffer::with_capacity(3);
+    assert!(buffer.is_empty());
+
+    buffer.push(1);
+    buffer.push(2);
+    assert_eq!(buffer.snapshot(), vec![1, 2]);
+
+    buffer.push(3);
+    buffer.push(4);
+    buffer.push(5);
+    assert_eq!(buffer.len(), 3);
+    assert_eq!(buffer.snapshot(), vec![3, 4, 5]);
+}
+
+#[test]
+fn test_ring_buffer_zero_capacity() {
+    let buffer = RingBuffer::with_capacity(0);
+    buffer.push("a");
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.snapshot(), Vec::<&str>::new());
+}
@@ -13,17 +13,27 @@
 // limitations under the License.
 
 use std::future::Future;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::channel;
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
+use std::time::Instant;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 use tokio::runtime::Handle;
+use tokio::sync::broadcast;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
+use crate::Stoppable;
+
+/// How long `Stoppable::stop` waits for the tasks of a `Runtime` to finish
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Methods to spawn tasks.
 pub trait TrySpawn {
     /// Tries to spawn a new asynchronous task, returning a tokio::JoinHandle for it.
@@ -45,10 +55,18 @@ pub trait TrySpawn {
         self.try_spawn(task).unwrap()
     }
 
+    /// Tries to spawn a blocking (or CPU heavy) function on the blocking thread pool,
+    /// where it does not starve the asynchronous tasks, returning a tokio::JoinHandle for it.
+    fn try_spawn_blocking<F, R>(&self, f: F) -> Result<JoinHandle<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static;
+
     /// Blocks until a task is finished.
     ///
     /// The default impl is a poor man's `runtime::block_on`.
     /// This is mainly used to wrap an async function into sync function.
+    /// To run sync code from async code, prefer `try_spawn_blocking`.
     fn block_on<F>(&self, f: F, timeout: Option<Duration>) -> Result<F::Output>
     where
         F: Future + Send + 'static,
@@ -78,6 +96,14 @@ impl<S: TrySpawn> TrySpawn for Arc<S> {
         self.as_ref().try_spawn(task)
     }
 
+    fn try_spawn_blocking<F, R>(&self, f: F) -> Result<JoinHandle<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.as_ref().try_spawn_blocking(f)
+    }
+
     fn spawn<T>(&self, task: T) -> JoinHandle<T::Output>
     where
         T: Future + Send + 'static,
@@ -95,17 +121,30 @@ impl<S: TrySpawn> TrySpawn for Arc<S> {
     }
 }
 
+/// Task counters of a `Runtime`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuntimeMetrics {
+    /// Tasks spawned but not finished yet
+    pub active_tasks: usize,
+    /// Tasks ever spawned
+    pub spawned_total: usize,
+}
+
 /// Tokio Runtime wrapper.
 /// If a runtime is in an asynchronous context, shutdown it first.
 pub struct Runtime {
     // Handle to runtime.
     handle: Handle,
     // Use to receive a drop signal when dropper is dropped.
-    _dropper: Dropper,
+    dropper: Dropper,
+    active_tasks: Arc<AtomicUsize>,
+    spawned_total: Arc<AtomicUsize>,
+    // No more tasks are accepted once set.
+    stopped: AtomicBool,
 }
 
 impl Runtime {
-    fn create(builder: &mut tokio::runtime::Builder) -> Result<Self> {
+    fn create(name: Option<String>, builder: &mut tokio::runtime::Builder) -> Result<Self> {
         let runtime = builder
             .build()
             .map_err(|tokio_error| ErrorCode::TokioError(format!("{}", tokio_error)))?;
@@ -115,13 +154,20 @@ impl Runtime {
         let handle = runtime.handle().clone();
 
         // Block the runtime to shutdown.
-        let _ = thread::spawn(move || runtime.block_on(recv_stop));
+        let mut driver = thread::Builder::new();
+        if let Some(name) = name {
+            driver = driver.name(format!("{}-driver", name));
+        }
+        let _ = driver.spawn(move || runtime.block_on(recv_stop))?;
 
         Ok(Runtime {
             handle,
-            _dropper: Dropper {
+            dropper: Dropper {
                 close: Some(send_stop),
             },
+            active_tasks: Arc::new(AtomicUsize::new(0)),
+            spawned_total: Arc::new(AtomicUsize::new(0)),
+            stopped: AtomicBool::new(false),
         })
     }
 
@@ -131,13 +177,70 @@ impl Runtime {
     pub fn with_default_worker_threads() -> Result<Self> {
         let mut runtime = tokio::runtime::Builder::new_multi_thread();
         let builder = runtime.enable_all();
-        Self::create(builder)
+        Self::create(None, builder)
     }
 
     pub fn with_worker_threads(workers: usize) -> Result<Self> {
         let mut runtime = tokio::runtime::Builder::new_multi_thread();
         let builder = runtime.enable_all().worker_threads(workers);
-        Self::create(builder)
+        Self::create(None, builder)
+    }
+
+    /// Same as `with_worker_threads`, the threads are named `{name}-{n}` (worker threads)
+    /// and `{name}-driver` (the thread blocking the runtime), to tell the runtimes apart in
+    /// debuggers and profilers.
+    pub fn with_worker_threads_named(workers: usize, name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        let thread_name = name.clone();
+        let thread_id = AtomicUsize::new(0);
+        let mut runtime = tokio::runtime::Builder::new_multi_thread();
+        let builder = runtime
+            .enable_all()
+            .worker_threads(workers)
+            .thread_name_fn(move || {
+                let id = thread_id.fetch_add(1, Ordering::Relaxed);
+                format!("{}-{}", thread_name, id)
+            });
+        Self::create(Some(name), builder)
+    }
+
+    /// Stops accepting new tasks, waits up to `timeout` for the spawned tasks to finish,
+    /// then shuts the runtime down, abandoning the tasks still running.
+    ///
+    /// Returns whether all the tasks finished in time. It should not be awaited on the runtime
+    /// itself, the awaiting task would never finish in time.
+    pub async fn shutdown(&mut self, timeout: Duration) -> bool {
+        self.shutdown_or_force(timeout, None).await
+    }
+
+    async fn shutdown_or_force(
+        &mut self,
+        timeout: Duration,
+        mut force: Option<broadcast::Receiver<()>>,
+    ) -> bool {
+        self.stopped.store(true, Ordering::Relaxed);
+
+        let deadline = Instant::now() + timeout;
+        let drained = loop {
+            if self.active_tasks.load(Ordering::Relaxed) == 0 {
+                break true;
+            }
+            let forced = force.as_mut().map(|f| f.try_recv().is_ok()) == Some(true);
+            if forced || Instant::now() >= deadline {
+                break false;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+
+        self.dropper.close.take().map(|v| v.send(()));
+        drained
+    }
+
+    pub fn metrics(&self) -> RuntimeMetrics {
+        RuntimeMetrics {
+            active_tasks: self.active_tasks.load(Ordering::Relaxed),
+            spawned_total: self.spawned_total.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -147,7 +250,63 @@ impl TrySpawn for Runtime {
         T: Future + Send + 'static,
         T::Output: Send + 'static,
     {
-        Ok(self.handle.spawn(task))
+        self.check_not_stopped()?;
+        self.spawned_total.fetch_add(1, Ordering::Relaxed);
+        self.active_tasks.fetch_add(1, Ordering::Relaxed);
+        // the task is not active any more once finished, or dropped (e.g. cancelled or panicked)
+        let guard = ActiveTaskGuard(self.active_tasks.clone());
+        Ok(self.handle.spawn(async move {
+            let _guard = guard;
+            task.await
+        }))
+    }
+
+    fn try_spawn_blocking<F, R>(&self, f: F) -> Result<JoinHandle<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.check_not_stopped()?;
+        self.spawned_total.fetch_add(1, Ordering::Relaxed);
+        self.active_tasks.fetch_add(1, Ordering::Relaxed);
+        let guard = ActiveTaskGuard(self.active_tasks.clone());
+        Ok(self.handle.spawn_blocking(move || {
+            let _guard = guard;
+            f()
+        }))
+    }
+}
+
+impl Runtime {
+    fn check_not_stopped(&self) -> Result<()> {
+        if self.stopped.load(Ordering::Relaxed) {
+            return Err(ErrorCode::AlreadyStopped("Runtime is shutting down"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Stoppable for Runtime {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Shuts down once the tasks are finished, or at once if forced.
+    async fn stop(&mut self, force: Option<broadcast::Receiver<()>>) -> Result<()> {
+        if !self.stopped.load(Ordering::Relaxed) {
+            self.shutdown_or_force(DRAIN_TIMEOUT, force).await;
+            return Ok(());
+        }
+        Err(ErrorCode::AlreadyStopped("Runtime is already stopped"))
+    }
+}
+
+struct ActiveTaskGuard(Arc<AtomicUsize>);
+
+impl Drop for ActiveTaskGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
     }
 }
 
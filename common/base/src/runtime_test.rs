@@ -105,3 +105,91 @@ fn test_block_on() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_runtime_thread_names() -> Result<()> {
+    let rt = Runtime::with_worker_threads_named(2, "test-rt")?;
+    let name = rt.block_on(
+        async { std::thread::current().name().map(|n| n.to_string()) },
+        None,
+    )?;
+    let name = name.unwrap_or_default();
+    assert!(name.starts_with("test-rt-"), "thread name: {}", name);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_runtime_metrics() -> Result<()> {
+    let rt = Runtime::with_worker_threads(2)?;
+    assert_eq!(rt.metrics(), RuntimeMetrics {
+        active_tasks: 0,
+        spawned_total: 0,
+    });
+
+    // tasks blocked until released
+    let mut senders = vec![];
+    let mut handles = vec![];
+    for _ in 0..5 {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        senders.push(tx);
+        handles.push(rt.spawn(async move {
+            let _ = rx.await;
+        }));
+    }
+    assert_eq!(rt.metrics(), RuntimeMetrics {
+        active_tasks: 5,
+        spawned_total: 5,
+    });
+
+    for tx in senders {
+        let _ = tx.send(());
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    assert_eq!(rt.metrics(), RuntimeMetrics {
+        active_tasks: 0,
+        spawned_total: 5,
+    });
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_try_spawn_blocking() -> Result<()> {
+    let rt = Runtime::with_worker_threads(1)?;
+    let handle = rt.try_spawn_blocking(|| {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        42
+    })?;
+    assert_eq!(handle.await.unwrap(), 42);
+    assert_eq!(rt.metrics().active_tasks, 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_runtime_shutdown() -> Result<()> {
+    // the task finishes within the window
+    {
+        let mut rt = Runtime::with_worker_threads(1)?;
+        rt.spawn(async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        });
+        assert!(rt.shutdown(Duration::from_secs(5)).await);
+        assert_eq!(rt.metrics().active_tasks, 0);
+
+        // no more tasks are accepted
+        assert!(rt.try_spawn(async {}).is_err());
+    }
+
+    // the task exceeds the window
+    {
+        let mut rt = Runtime::with_worker_threads(1)?;
+        rt.spawn(async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        let start = Instant::now();
+        assert!(!rt.shutdown(Duration::from_millis(100)).await);
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+    Ok(())
+}
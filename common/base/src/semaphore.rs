@@ -0,0 +1,103 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::TryAcquireError;
+
+#[derive(Debug)]
+pub struct SemaphoreValues {
+    pub acquired: u64,
+    pub wait_time: Duration,
+}
+
+/// Bounds the number of concurrent operations, e.g. the connections or reads of a fan-out.
+///
+/// Cloned semaphores share the permits and the wait metrics.
+#[derive(Clone, Debug)]
+pub struct Semaphore {
+    inner: Arc<tokio::sync::Semaphore>,
+    acquired: Arc<AtomicU64>,
+    wait_nanos: Arc<AtomicU64>,
+}
+
+impl Semaphore {
+    pub fn create(permits: usize) -> Self {
+        Semaphore {
+            inner: Arc::new(tokio::sync::Semaphore::new(permits)),
+            acquired: Arc::new(AtomicU64::new(0)),
+            wait_nanos: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Waits until a permit is available, permits are handed out in the order of the requests.
+    pub async fn acquire(&self) -> Result<PermitGuard> {
+        let start = Instant::now();
+        let permit = self
+            .inner
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| ErrorCode::AlreadyStopped("Semaphore is closed"))?;
+        self.record(start.elapsed());
+        Ok(PermitGuard { _permit: permit })
+    }
+
+    /// Takes a permit if one is available right now.
+    pub fn try_acquire(&self) -> Result<PermitGuard> {
+        match self.inner.clone().try_acquire_owned() {
+            Ok(permit) => {
+                self.record(Duration::ZERO);
+                Ok(PermitGuard { _permit: permit })
+            }
+            Err(TryAcquireError::NoPermits) => Err(ErrorCode::Timeout("No permits available")),
+            Err(TryAcquireError::Closed) => Err(ErrorCode::AlreadyStopped("Semaphore is closed")),
+        }
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.inner.available_permits()
+    }
+
+    /// Wakes up the waiters with an error, no more permits are handed out.
+    pub fn close(&self) {
+        self.inner.close()
+    }
+
+    pub fn get_values(&self) -> SemaphoreValues {
+        SemaphoreValues {
+            acquired: self.acquired.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    fn record(&self, wait_time: Duration) {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos
+            .fetch_add(wait_time.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+/// The permit is given back to the semaphore when the guard is dropped.
+#[derive(Debug)]
+pub struct PermitGuard {
+    _permit: OwnedSemaphorePermit,
+}
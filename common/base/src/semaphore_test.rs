@@ -0,0 +1,78 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_exception::Result;
+use tokio::time::Duration;
+
+use crate::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_semaphore_acquire_release() -> Result<()> {
+    let semaphore = Semaphore::create(2);
+
+    let p1 = semaphore.acquire().await?;
+    let p2 = semaphore.try_acquire()?;
+    assert_eq!(semaphore.available_permits(), 0);
+    assert!(semaphore.try_acquire().is_err());
+
+    drop(p1);
+    assert_eq!(semaphore.available_permits(), 1);
+    let _p3 = semaphore.try_acquire()?;
+
+    drop(p2);
+    assert_eq!(semaphore.available_permits(), 1);
+    assert_eq!(semaphore.get_values().acquired, 3);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_semaphore_blocks_when_exhausted() -> Result<()> {
+    let semaphore = Semaphore::create(1);
+    let order = Arc::new(Mutex::new(vec![]));
+
+    let permit = semaphore.acquire().await?;
+
+    let mut waiters = vec![];
+    for i in 0..3 {
+        let semaphore = semaphore.clone();
+        let order = order.clone();
+        waiters.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+            order.lock().unwrap().push(i);
+        }));
+        // make sure the waiters queue up in order
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    // nobody gets through while the permit is held
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(order.lock().unwrap().is_empty());
+
+    drop(permit);
+    for waiter in waiters {
+        waiter.await.unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+
+    let values = semaphore.get_values();
+    assert_eq!(values.acquired, 4);
+    assert!(values.wait_time >= Duration::from_millis(50));
+
+    semaphore.close();
+    assert!(semaphore.acquire().await.is_err());
+    Ok(())
+}
@@ -15,6 +15,7 @@
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_tracing::tracing;
@@ -23,28 +24,66 @@ use tokio::sync::broadcast;
 
 use crate::Stoppable;
 
+/// The phases of a shutdown, in the order they are run.
+///
+/// A task depends on the tasks of the later phases, e.g. a listener hands the accepted
+/// connections over to the session registry, which runs the sessions on a runtime.
+///
+/// A phase is a well-known stop priority, the custom priorities can be put in between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StopPhase {
+    /// Tasks accepting new work, e.g. the MySQL listener
+    Listener = 100,
+    /// Tasks serving the accepted work, e.g. the session registry
+    Session = 200,
+    /// Tasks everything else runs on, e.g. the runtime
+    Runtime = 300,
+}
+
 /// Handle a group of `Stoppable` tasks.
 /// When a user press ctrl-c, it calls the `stop()` method on every task to close them.
+/// The tasks are stopped in the ascending order of their priorities, the tasks of the same priority
+/// are stopped concurrently, and the next priority starts once all of them are stopped, or the
+/// stage timeout is reached.
 /// If a second ctrl-c is pressed, it sends a `()` through the `force` channel to notify tasks to shutdown at once.
 ///
 /// Once `StopHandle` is dropped, it triggers a force stop on every tasks in it.
 pub struct StopHandle {
     stopping: Arc<AtomicBool>,
-    pub(crate) stoppable_tasks: Vec<Box<dyn Stoppable + Send>>,
+    stage_timeout: Option<Duration>,
+    pub(crate) stoppable_tasks: Vec<(i32, Box<dyn Stoppable + Send>)>,
 }
 
 impl StopHandle {
     pub fn create() -> StopHandle {
         StopHandle {
             stopping: Arc::new(AtomicBool::new(false)),
+            stage_timeout: None,
             stoppable_tasks: vec![],
         }
     }
 
+    /// The max time the tasks of a priority may take to stop, beyond it they are given up
+    /// and the tasks of the next priority are stopped.
+    pub fn set_stage_timeout(&mut self, timeout: Duration) {
+        self.stage_timeout = Some(timeout);
+    }
+
+    /// Stops all the tasks, the future resolves to the first error of them, e.g. the timeout
+    /// of a stage, after all the stages are run.
     pub fn stop_all(
         &mut self,
         force_tx: Option<broadcast::Sender<()>>,
-    ) -> Result<impl Future<Output = ()> + Send + '_, ErrorCode> {
+    ) -> Result<impl Future<Output = Result<(), ErrorCode>> + Send + '_, ErrorCode> {
+        let stage_timeout = self.stage_timeout;
+        self.stop_stages(force_tx, stage_timeout)
+    }
+
+    fn stop_stages(
+        &mut self,
+        force_tx: Option<broadcast::Sender<()>>,
+        stage_timeout: Option<Duration>,
+    ) -> Result<impl Future<Output = Result<(), ErrorCode>> + Send + '_, ErrorCode> {
         if self
             .stopping
             .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
@@ -53,15 +92,46 @@ impl StopHandle {
             return Err(ErrorCode::AlreadyStopped("StopHandle is shutting down"));
         }
 
-        let mut handles = vec![];
-        for s in &mut self.stoppable_tasks {
+        // stable sort: the tasks of a priority keep the order they are pushed in
+        self.stoppable_tasks.sort_by_key(|(priority, _)| *priority);
+
+        // subscribe in advance, the tasks of the later priorities must not miss a force stop sent in the meantime
+        let mut stages: Vec<(i32, Vec<_>)> = vec![];
+        for (priority, s) in self.stoppable_tasks.iter_mut() {
             let rx = force_tx.as_ref().map(|x| x.subscribe());
-            handles.push(s.stop(rx));
+            match stages.last_mut() {
+                Some((last, stage)) if *last == *priority => stage.push((s, rx)),
+                _ => stages.push((*priority, vec![(s, rx)])),
+            }
         }
 
-        let join_all = futures::future::join_all(handles);
         Ok(async move {
-            let _ = join_all.await;
+            let mut errors = vec![];
+            for (priority, stage) in stages {
+                let stops = futures::future::join_all(stage.into_iter().map(|(s, rx)| s.stop(rx)));
+                let results = match stage_timeout {
+                    None => stops.await,
+                    Some(timeout) => match tokio::time::timeout(timeout, stops).await {
+                        Ok(results) => results,
+                        Err(_) => {
+                            errors.push(ErrorCode::Timeout(format!(
+                                "Stopping the tasks of priority {} exceeds {:?}",
+                                priority, timeout
+                            )));
+                            continue;
+                        }
+                    },
+                };
+                errors.extend(results.into_iter().filter_map(|res| res.err()));
+            }
+
+            for error in &errors {
+                tracing::warn!("Error on stopping the tasks: {}", error);
+            }
+            match errors.into_iter().next() {
+                None => Ok(()),
+                Some(error) => Err(error),
+            }
         })
     }
 
@@ -83,7 +153,7 @@ impl StopHandle {
             // It is the task's responsibility to decide whether to deal with it.
             let fut = self.stop_all(Some(signal));
             if let Ok(f) = fut {
-                f.await;
+                let _ = f.await;
             }
         }
     }
@@ -103,8 +173,13 @@ impl StopHandle {
         tx
     }
 
-    pub fn push(&mut self, s: Box<dyn Stoppable + Send>) {
-        self.stoppable_tasks.push(s);
+    pub fn push(&mut self, s: Box<dyn Stoppable + Send>, phase: StopPhase) {
+        self.push_with_priority(s, phase as i32);
+    }
+
+    /// The tasks of the lower priorities are stopped first.
+    pub fn push_with_priority(&mut self, s: Box<dyn Stoppable + Send>, priority: i32) {
+        self.stoppable_tasks.push((priority, s));
     }
 }
 
@@ -113,11 +188,12 @@ impl Drop for StopHandle {
         let (tx, _rx) = broadcast::channel::<()>(16);
 
         // let every task subscribe the channel, then send a force stop signal `()`
-        let fut = self.stop_all(Some(tx.clone()));
+        // no stage timeout, it may be out of a tokio runtime
+        let fut = self.stop_stages(Some(tx.clone()), None);
 
         if let Ok(fut) = fut {
             let _ = tx.send(());
-            futures::executor::block_on(fut);
+            let _ = futures::executor::block_on(fut);
         }
     }
 }
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+use std::sync::Mutex;
+
 use common_exception::Result;
 use common_tracing::tracing;
 use tokio;
@@ -21,6 +24,7 @@ use tokio::sync::oneshot::error::TryRecvError;
 use tokio::time::Duration;
 
 use crate::stop_handle::StopHandle;
+use crate::stop_handle::StopPhase;
 use crate::Stoppable;
 
 /// A task that takes 100 years to gracefully stop.
@@ -110,8 +114,8 @@ async fn test_stop_handle() -> Result<()> {
     let (fin_tx, mut fin_rx) = oneshot::channel::<()>();
 
     let mut h = StopHandle::create();
-    h.push(Box::new(t1));
-    h.push(Box::new(t2));
+    h.push(Box::new(t1), StopPhase::Listener);
+    h.push(Box::new(t2), StopPhase::Listener);
 
     // Block on waiting for the handle to finish.
 
@@ -159,7 +163,104 @@ async fn test_stop_handle_drop() -> Result<()> {
     assert!(t1.start().await.is_ok());
 
     let mut h = StopHandle::create();
-    h.push(Box::new(t1));
+    h.push(Box::new(t1), StopPhase::Listener);
+
+    Ok(())
+}
+
+/// A task that records when it is stopped.
+struct RecordTask {
+    name: &'static str,
+    stopped: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[async_trait::async_trait]
+impl Stoppable for RecordTask {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self, _force: Option<broadcast::Receiver<()>>) -> Result<()> {
+        // a slow stop, the tasks of the next phase must wait for it
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.stopped.lock().unwrap().push(self.name);
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stop_handle_phases() -> Result<()> {
+    let stopped = Arc::new(Mutex::new(vec![]));
+    let task = |name| {
+        Box::new(RecordTask {
+            name,
+            stopped: stopped.clone(),
+        })
+    };
+
+    // pushed out of order
+    let mut h = StopHandle::create();
+    h.push(task("runtime"), StopPhase::Runtime);
+    h.push(task("session"), StopPhase::Session);
+    h.push(task("mysql"), StopPhase::Listener);
+    h.push(task("clickhouse"), StopPhase::Listener);
+
+    h.stop_all(None)?.await?;
+
+    let stopped = stopped.lock().unwrap().clone();
+    assert_eq!(stopped.len(), 4);
+    // the listeners are stopped concurrently, in any order
+    let mut listeners = stopped[0..2].to_vec();
+    listeners.sort_unstable();
+    assert_eq!(listeners, vec!["clickhouse", "mysql"]);
+    assert_eq!(stopped[2..], ["session", "runtime"]);
+
+    // stopping twice is an error
+    assert!(h.stop_all(None).is_err());
+    Ok(())
+}
+
+/// A task that never stops by itself.
+struct HungTask {}
+
+#[async_trait::async_trait]
+impl Stoppable for HungTask {
+    async fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&mut self, _force: Option<broadcast::Receiver<()>>) -> Result<()> {
+        futures::future::pending::<()>().await;
+        Ok(())
+    }
+}
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stop_handle_priorities_with_timeout() -> Result<()> {
+    let stopped = Arc::new(Mutex::new(vec![]));
+    let task = |name| {
+        Box::new(RecordTask {
+            name,
+            stopped: stopped.clone(),
+        })
+    };
+
+    let mut h = StopHandle::create();
+    h.set_stage_timeout(Duration::from_millis(200));
+    h.push_with_priority(task("runtime"), 3);
+    h.push_with_priority(Box::new(HungTask {}), 2);
+    h.push_with_priority(task("mysql"), 1);
+    h.push_with_priority(task("session"), 2);
+
+    // the hung stage is given up, the error is surfaced after the rest are stopped
+    let res = h.stop_all(None)?.await;
+    assert_eq!(
+        res.err().map(|e| e.message()),
+        Some("Stopping the tasks of priority 2 exceeds 200ms".to_string())
+    );
+
+    // the task sharing the priority with the hung one is stopped in time
+    let stopped = stopped.lock().unwrap().clone();
+    assert_eq!(stopped, vec!["mysql", "session", "runtime"]);
     Ok(())
 }
@@ -29,8 +29,16 @@ pub struct GlobalUniqName;
 
 impl GlobalUniqName {
     pub fn unique() -> String {
+        Self::unique_with_prefix("")
+    }
+
+    /// The unique name starts with `prefix`, e.g. `q-` for the queries, so that the names in the
+    /// logs tell what they belong to. The rest of the name is alphanumeric, the prefix is
+    /// expected to end with a separator such as `-` to be parsed back by `prefix_of`.
+    pub fn unique_with_prefix(prefix: &str) -> String {
         let mut uuid = uuid::Uuid::new_v4().as_u128();
-        let mut unique_name = Vec::with_capacity(22);
+        let mut unique_name = Vec::with_capacity(prefix.len() + 22);
+        unique_name.extend(prefix.chars());
 
         loop {
             let m = (uuid % 62) as u8;
@@ -48,4 +56,14 @@ impl GlobalUniqName {
             }
         }
     }
+
+    /// The prefix of a name generated by `unique_with_prefix`, empty if it has none.
+    pub fn prefix_of(name: &str) -> &str {
+        let unique_len = name
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .count();
+        &name[..name.len() - unique_len]
+    }
 }
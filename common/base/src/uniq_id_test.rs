@@ -0,0 +1,41 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::GlobalSequence;
+use crate::GlobalUniqName;
+
+#[test]
+fn test_global_uniq_name_with_prefix() {
+    let name1 = GlobalUniqName::unique_with_prefix("q-");
+    let name2 = GlobalUniqName::unique_with_prefix("q-");
+    assert_ne!(name1, name2);
+    assert!(name1.starts_with("q-"));
+    assert_eq!(GlobalUniqName::prefix_of(&name1), "q-");
+    assert_eq!(GlobalUniqName::prefix_of(&name2), "q-");
+
+    let name = GlobalUniqName::unique_with_prefix("sess-");
+    assert_eq!(GlobalUniqName::prefix_of(&name), "sess-");
+
+    // without prefix
+    let name = GlobalUniqName::unique();
+    assert!(name.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_eq!(GlobalUniqName::prefix_of(&name), "");
+}
+
+#[test]
+fn test_global_sequence() {
+    let seq1 = GlobalSequence::next();
+    let seq2 = GlobalSequence::next();
+    assert!(seq2 > seq1);
+}
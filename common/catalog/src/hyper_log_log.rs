@@ -0,0 +1,172 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::hash::Hasher;
+
+use serde::de;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use twox_hash::XxHash64;
+
+/// Number of bits of the hash that select a register, the standard error is about 1.04 / sqrt(2^P), i.e. 3.25%
+const P: u32 = 10;
+
+/// The range of the precisions, the rank of a register is at most 64 - P + 1 and fits in a char
+const MIN_P: u32 = 4;
+const MAX_P: u32 = 18;
+
+/// The seed of the hash, it must never change, or the persisted sketches are no longer mergeable
+const HASH_SEED: u64 = 0;
+
+/// A register is at most 64 - P + 1 and is serialized as one of these chars
+const REGISTER_CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A HyperLogLog sketch, which estimates the number of distinct values added to it.
+///
+/// The precision P is the number of bits of the hash that select one of the 2^P registers,
+/// it is given by the length of a serialized sketch.
+/// Sketches (of the same precision) can be merged, the result estimates the distinct values of the union.
+/// The hash is xxHash64 with a fixed seed, such that sketches persisted by different processes,
+/// or built by different versions, are mergeable.
+/// It is serialized as a string of one char per register.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self::with_precision(P)
+    }
+
+    /// A sketch of 2^precision registers, whose standard error is about 1.04 / sqrt(2^precision).
+    pub fn with_precision(precision: u32) -> Self {
+        assert!(
+            (MIN_P..=MAX_P).contains(&precision),
+            "HyperLogLog precision {} is out of [{}, {}]",
+            precision,
+            MIN_P,
+            MAX_P
+        );
+        HyperLogLog {
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    pub fn precision(&self) -> u32 {
+        self.registers.len().trailing_zeros()
+    }
+
+    /// Adds a value by its bytes, e.g., in a serialized form.
+    pub fn add(&mut self, bytes: &[u8]) {
+        let mut hasher = XxHash64::with_seed(HASH_SEED);
+        hasher.write(bytes);
+        self.add_hash(hasher.finish());
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        let p = self.precision();
+        let index = (hash >> (64 - p)) as usize;
+        // the sentinel bit caps the rank at 64 - P + 1
+        let rest = (hash << p) | (1 << (p - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        if self.registers[index] < rank {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        debug_assert_eq!(self.precision(), other.precision());
+        for (r, o) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *r < *o {
+                *r = *o;
+            }
+        }
+    }
+
+    pub fn count(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum = self
+            .registers
+            .iter()
+            .map(|r| 2f64.powi(-(*r as i32)))
+            .sum::<f64>();
+        let estimate = alpha * m * m / sum;
+
+        // small cardinalities are better estimated by linear counting
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+}
+
+impl Serialize for HyperLogLog {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let chars = self
+            .registers
+            .iter()
+            .map(|r| REGISTER_CHARS[*r as usize] as char)
+            .collect::<String>();
+        serializer.serialize_str(&chars)
+    }
+}
+
+impl<'de> Deserialize<'de> for HyperLogLog {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(HyperLogLogVisitor)
+    }
+}
+
+struct HyperLogLogVisitor;
+
+impl<'de> de::Visitor<'de> for HyperLogLogVisitor {
+    type Value = HyperLogLog;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a HyperLogLog sketch of 2^P registers, P in [{}, {}]",
+            MIN_P, MAX_P
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let registers = v
+            .bytes()
+            .map(|c| match REGISTER_CHARS.iter().position(|x| *x == c) {
+                Some(r) => Ok(r as u8),
+                None => Err(E::invalid_value(de::Unexpected::Char(c as char), &self)),
+            })
+            .collect::<Result<Vec<_>, E>>()?;
+        let precision = registers.len().trailing_zeros();
+        if !registers.len().is_power_of_two() || !(MIN_P..=MAX_P).contains(&precision) {
+            return Err(E::invalid_length(registers.len(), &self));
+        }
+        Ok(HyperLogLog { registers })
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::HyperLogLog;
+
+fn assert_estimate(sketch: &HyperLogLog, expected: u64) {
+    let estimate = sketch.count() as f64;
+    let error = (estimate - expected as f64).abs() / expected as f64;
+    assert!(
+        error < 0.1,
+        "estimate {} of {} distinct values",
+        estimate,
+        expected
+    );
+}
+
+#[test]
+fn test_hyper_log_log() {
+    let mut sketch = HyperLogLog::new();
+    assert_eq!(sketch.count(), 0);
+
+    // duplicates are not counted
+    for _ in 0..3 {
+        for i in 0..100u64 {
+            sketch.add(&i.to_le_bytes());
+        }
+    }
+    assert_estimate(&sketch, 100);
+
+    let mut sketch = HyperLogLog::new();
+    for i in 0..100_000u64 {
+        sketch.add(&i.to_le_bytes());
+    }
+    assert_estimate(&sketch, 100_000);
+}
+
+#[test]
+fn test_hyper_log_log_merge() {
+    // overlapping ranges: [0, 60_000) and [40_000, 100_000)
+    let mut left = HyperLogLog::new();
+    for i in 0..60_000u64 {
+        left.add(&i.to_le_bytes());
+    }
+    let mut right = HyperLogLog::new();
+    for i in 40_000..100_000u64 {
+        right.add(&i.to_le_bytes());
+    }
+
+    left.merge(&right);
+    assert_estimate(&left, 100_000);
+}
+
+#[test]
+fn test_hyper_log_log_serde() -> serde_json::Result<()> {
+    let mut sketch = HyperLogLog::new();
+    assert_eq!(
+        serde_json::to_string(&sketch)?,
+        format!("\"{}\"", "A".repeat(1024))
+    );
+
+    // the hash is stable: xxHash64 of the empty bytes with seed 0 is 0xEF46DB3751D8E999,
+    // its top 10 bits select the register 957, the rest bits have 3 leading zeros
+    sketch.add(b"");
+    let json = serde_json::to_string(&sketch)?;
+    let expected = format!("\"{}E{}\"", "A".repeat(957), "A".repeat(1024 - 958));
+    assert_eq!(json, expected);
+
+    for i in 0..1000u64 {
+        sketch.add(&i.to_le_bytes());
+    }
+    let json = serde_json::to_string(&sketch)?;
+    // one char per register
+    assert_eq!(json.len(), 1024 + 2);
+    let deserialized: HyperLogLog = serde_json::from_str(&json)?;
+    assert_eq!(sketch, deserialized);
+
+    // invalid sketches are rejected
+    assert!(serde_json::from_str::<HyperLogLog>("\"AAA\"").is_err());
+    assert!(serde_json::from_str::<HyperLogLog>(&format!("\"{}\"", "-".repeat(1024))).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_hyper_log_log_precision() -> serde_json::Result<()> {
+    let mut sketch = HyperLogLog::with_precision(14);
+    assert_eq!(sketch.precision(), 14);
+    for i in 0..100_000u64 {
+        sketch.add(&i.to_le_bytes());
+    }
+    // the standard error of 2^14 registers is about 0.81%
+    let error = (sketch.count() as f64 - 100_000f64).abs() / 100_000f64;
+    assert!(error < 0.02, "estimate {}", sketch.count());
+
+    // the precision is kept by the serialized sketch
+    let json = serde_json::to_string(&sketch)?;
+    assert_eq!(json.len(), (1 << 14) + 2);
+    let deserialized: HyperLogLog = serde_json::from_str(&json)?;
+    assert_eq!(deserialized.precision(), 14);
+    assert_eq!(sketch, deserialized);
+
+    // not a power of two
+    assert!(serde_json::from_str::<HyperLogLog>(&format!("\"{}\"", "A".repeat(1000))).is_err());
+    Ok(())
+}
@@ -14,8 +14,15 @@
 
 //! `catalog` defines catalog related data types, such as table or database.
 
+#[cfg(test)]
+mod hyper_log_log_test;
+#[cfg(test)]
+mod table_snapshot_test;
+
+mod hyper_log_log;
 mod table_snapshot;
 
+pub use hyper_log_log::HyperLogLog;
 pub use table_snapshot::BlockLocation;
 pub use table_snapshot::BlockMeta;
 pub use table_snapshot::ColStats;
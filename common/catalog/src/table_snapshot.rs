@@ -22,6 +22,8 @@ use serde::Deserialize;
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::HyperLogLog;
+
 pub type SnapshotId = Uuid;
 pub type ColumnId = u32;
 pub type Location = String;
@@ -52,6 +54,7 @@ impl TableSnapshot {
 
     pub fn append_segment(mut self, location: Location) -> TableSnapshot {
         self.segments.push(location);
+        self.prev_snapshot_id = Some(self.snapshot_id);
         self.snapshot_id = Uuid::new_v4();
         self
     }
@@ -80,16 +83,19 @@ pub struct Stats {
 }
 
 /// Meta information of a block (currently, the parquet file)
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct BlockMeta {
     /// Pointer of the data Block
     pub row_count: u64,
     pub block_size: u64,
+    /// Size of the data block file, 0 if the meta is written without it
+    #[serde(default)]
+    pub file_size: u64,
     pub col_stats: HashMap<ColumnId, ColStats>,
     pub location: BlockLocation,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct BlockLocation {
     pub location: Location,
     // for parquet, this filed can be used to fetch the meta data without seeking around
@@ -102,6 +108,13 @@ pub struct ColStats {
     pub max: DataValue,
     pub null_count: usize,
     pub row_count: usize,
+    /// Estimated number of distinct non-null values, 0 if unknown
+    #[serde(default)]
+    pub distinct_of_values: u64,
+    /// Sketch of the distinct values, by which the estimates of blocks are merged into the summaries,
+    /// None if the stats are written without it
+    #[serde(default)]
+    pub distinct_sketch: Option<HyperLogLog>,
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,76 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use pretty_assertions::assert_eq;
+
+use crate::BlockLocation;
+use crate::BlockMeta;
+use crate::ColStats;
+use crate::HyperLogLog;
+
+#[test]
+fn test_col_stats_serde_compatible() -> serde_json::Result<()> {
+    let mut distinct_sketch = HyperLogLog::new();
+    distinct_sketch.add(b"1");
+    let stats = ColStats {
+        min: DataValue::UInt64(Some(1)),
+        max: DataValue::UInt64(Some(1)),
+        null_count: 0,
+        row_count: 1,
+        distinct_of_values: 1,
+        distinct_sketch: Some(distinct_sketch),
+    };
+
+    // the stats written before the distinct estimates are introduced
+    let mut json = serde_json::to_value(&stats)?;
+    let fields = json.as_object_mut().unwrap();
+    fields.remove("distinct_of_values");
+    fields.remove("distinct_sketch");
+
+    let deserialized: ColStats = serde_json::from_value(json)?;
+    assert_eq!(stats.min, deserialized.min);
+    assert_eq!(stats.max, deserialized.max);
+    assert_eq!(stats.row_count, deserialized.row_count);
+    assert_eq!(0, deserialized.distinct_of_values);
+    assert_eq!(None, deserialized.distinct_sketch);
+    Ok(())
+}
+
+#[test]
+fn test_block_meta_serde_compatible() -> serde_json::Result<()> {
+    let meta = BlockMeta {
+        row_count: 1,
+        block_size: 8,
+        file_size: 40,
+        col_stats: HashMap::new(),
+        location: BlockLocation {
+            location: "_b/1.parquet".to_string(),
+            meta_size: 0,
+        },
+    };
+
+    // the meta written before the file size is introduced
+    let mut json = serde_json::to_value(&meta)?;
+    json.as_object_mut().unwrap().remove("file_size");
+
+    let deserialized: BlockMeta = serde_json::from_value(json)?;
+    assert_eq!(meta.row_count, deserialized.row_count);
+    assert_eq!(meta.block_size, deserialized.block_size);
+    assert_eq!(meta.location.location, deserialized.location.location);
+    assert_eq!(0, deserialized.file_size);
+    Ok(())
+}
@@ -17,6 +17,7 @@ use std::io::Seek;
 use std::io::Write;
 use std::sync::mpsc::channel;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use common_base::TrySpawn;
 use common_exception::ErrorCode;
@@ -43,6 +44,15 @@ pub trait SeekableReader: Read + Seek {}
 
 impl<T> SeekableReader for T where T: Read + Seek {}
 
+/// Meta data of a stored object, as listed by `DataAccessor::list`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectMeta {
+    /// Location of the object, which can be passed to the other methods of `DataAccessor`
+    pub path: String,
+    pub size: u64,
+    pub last_modified: SystemTime,
+}
+
 #[async_trait::async_trait]
 pub trait DataAccessor: Send + Sync {
     fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>>;
@@ -53,6 +63,13 @@ pub trait DataAccessor: Send + Sync {
 
     async fn get(&self, path: &str) -> Result<Bytes>;
 
+    /// Reads `len` bytes from `offset` of the object, e.g. the footer of a parquet file,
+    /// without downloading the whole object.
+    ///
+    /// Fewer bytes are returned if the object ends before the range does, reading (a non-empty
+    /// range) from the end of the object or beyond is an error.
+    async fn read_range(&self, path: &str, offset: u64, len: u64) -> Result<Bytes>;
+
     async fn put(&self, path: &str, content: Vec<u8>) -> Result<()>;
 
     async fn put_stream(
@@ -64,6 +81,17 @@ pub trait DataAccessor: Send + Sync {
         stream_len: usize,
     ) -> Result<()>;
 
+    /// Uploads `content` in parts of (at most) `part_size` bytes, which are assembled into
+    /// one object once all of them are uploaded.
+    ///
+    /// For large objects, which are too large (or too fragile) to be put in one request.
+    async fn put_multipart(&self, path: &str, content: Vec<u8>, part_size: usize) -> Result<()>;
+
+    /// Lists the objects directly under `prefix`, which is treated as a directory.
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>>;
+
+    async fn remove(&self, path: &str) -> Result<()>;
+
     async fn read(&self, location: &str) -> Result<Vec<u8>> {
         let mut input_stream = self.get_input_stream(location, None).await?;
         let mut buffer = vec![];
@@ -72,6 +100,18 @@ pub trait DataAccessor: Send + Sync {
     }
 }
 
+/// Clips the range read by `DataAccessor::read_range` to the object of `size` bytes,
+/// returns the length to read.
+pub(crate) fn clip_range(path: &str, size: u64, offset: u64, len: u64) -> Result<u64> {
+    if len > 0 && offset >= size {
+        return Err(ErrorCode::BadArguments(format!(
+            "Range out of the object {}, offset: {}, size: {}",
+            path, offset, size
+        )));
+    }
+    Ok(len.min(size.saturating_sub(offset)))
+}
+
 #[derive(Clone)]
 pub struct ObjectAccessor {
     data_accessor: Arc<dyn DataAccessor>,
@@ -14,6 +14,7 @@
 //
 
 use std::io::Write;
+use std::time::SystemTime;
 
 use common_base::tokio::io::AsyncReadExt;
 use common_exception::ErrorCode;
@@ -21,14 +22,23 @@ use futures::Stream;
 use futures::StreamExt;
 use rusoto_core::ByteStream;
 use rusoto_core::Region;
+use rusoto_s3::AbortMultipartUploadRequest;
+use rusoto_s3::CompleteMultipartUploadRequest;
+use rusoto_s3::CompletedMultipartUpload;
+use rusoto_s3::CompletedPart;
+use rusoto_s3::CreateMultipartUploadRequest;
+use rusoto_s3::DeleteObjectRequest;
 use rusoto_s3::GetObjectRequest;
+use rusoto_s3::ListObjectsV2Request;
 use rusoto_s3::PutObjectRequest;
 use rusoto_s3::S3Client;
+use rusoto_s3::UploadPartRequest;
 use rusoto_s3::S3 as RusotoS3;
 
 use crate::Bytes;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 use crate::S3InputStream;
 use crate::SeekableReader;
 
@@ -65,6 +75,38 @@ impl S3 {
             .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
         Ok(())
     }
+
+    async fn upload_parts(
+        &self,
+        path: &str,
+        upload_id: &str,
+        content: &[u8],
+        part_size: usize,
+    ) -> common_exception::Result<Vec<CompletedPart>> {
+        let mut completed_parts = vec![];
+        // part numbers start from 1
+        for (part_number, part) in (1..).zip(content.chunks(part_size.max(1))) {
+            let req = UploadPartRequest {
+                key: path.to_string(),
+                bucket: self.bucket.to_string(),
+                upload_id: upload_id.to_string(),
+                part_number,
+                content_length: Some(part.len() as i64),
+                body: Some(ByteStream::from(part.to_vec())),
+                ..Default::default()
+            };
+            let output = self
+                .client
+                .upload_part(req)
+                .await
+                .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+            completed_parts.push(CompletedPart {
+                e_tag: output.e_tag,
+                part_number: Some(part_number),
+            });
+        }
+        Ok(completed_parts)
+    }
 }
 
 #[async_trait::async_trait]
@@ -115,6 +157,38 @@ impl DataAccessor for S3 {
         }
     }
 
+    async fn read_range(
+        &self,
+        path: &str,
+        offset: u64,
+        len: u64,
+    ) -> common_exception::Result<Bytes> {
+        if len == 0 {
+            return Ok(vec![]);
+        }
+
+        // the range is inclusive, S3 clips it to the end of the object
+        let req = GetObjectRequest {
+            key: path.to_string(),
+            bucket: self.bucket.to_string(),
+            range: Some(format!("bytes={}-{}", offset, offset + len - 1)),
+            ..Default::default()
+        };
+        let output = self
+            .client
+            .get_object(req)
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        match output.body {
+            Some(stream) => {
+                let mut res = vec![];
+                stream.into_async_read().read_to_end(&mut res).await?;
+                Ok(res)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
     async fn put(&self, path: &str, content: Vec<u8>) -> common_exception::Result<()> {
         self.put_byte_stream(path, ByteStream::from(content)).await
     }
@@ -131,4 +205,108 @@ impl DataAccessor for S3 {
         self.put_byte_stream(path, ByteStream::new_with_size(s, stream_len))
             .await
     }
+
+    async fn put_multipart(
+        &self,
+        path: &str,
+        content: Vec<u8>,
+        part_size: usize,
+    ) -> common_exception::Result<()> {
+        let req = CreateMultipartUploadRequest {
+            key: path.to_string(),
+            bucket: self.bucket.to_string(),
+            ..Default::default()
+        };
+        let upload_id = self
+            .client
+            .create_multipart_upload(req)
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?
+            .upload_id
+            .ok_or_else(|| ErrorCode::DALTransportError("multipart upload without upload id"))?;
+
+        let parts = match self
+            .upload_parts(path, &upload_id, &content, part_size)
+            .await
+        {
+            Ok(parts) => parts,
+            Err(e) => {
+                // the uploaded parts are charged until the upload is aborted
+                let req = AbortMultipartUploadRequest {
+                    key: path.to_string(),
+                    bucket: self.bucket.to_string(),
+                    upload_id,
+                    ..Default::default()
+                };
+                let _ = self.client.abort_multipart_upload(req).await;
+                return Err(e);
+            }
+        };
+
+        let req = CompleteMultipartUploadRequest {
+            key: path.to_string(),
+            bucket: self.bucket.to_string(),
+            upload_id,
+            multipart_upload: Some(CompletedMultipartUpload { parts: Some(parts) }),
+            ..Default::default()
+        };
+        self.client
+            .complete_multipart_upload(req)
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> common_exception::Result<Vec<ObjectMeta>> {
+        let prefix = format!("{}/", prefix.trim_end_matches('/'));
+        let mut res = vec![];
+        let mut continuation_token = None;
+        loop {
+            let req = ListObjectsV2Request {
+                bucket: self.bucket.to_string(),
+                prefix: Some(prefix.clone()),
+                delimiter: Some("/".to_string()),
+                continuation_token,
+                ..Default::default()
+            };
+            let output = self
+                .client
+                .list_objects_v2(req)
+                .await
+                .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+
+            for object in output.contents.unwrap_or_default() {
+                let last_modified = match object.last_modified {
+                    Some(v) => chrono::DateTime::parse_from_rfc3339(&v)
+                        .map(SystemTime::from)
+                        .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?,
+                    None => SystemTime::UNIX_EPOCH,
+                };
+                res.push(ObjectMeta {
+                    path: object.key.unwrap_or_default(),
+                    size: object.size.unwrap_or_default() as u64,
+                    last_modified,
+                });
+            }
+
+            continuation_token = output.next_continuation_token;
+            if output.is_truncated != Some(true) || continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(res)
+    }
+
+    async fn remove(&self, path: &str) -> common_exception::Result<()> {
+        let req = DeleteObjectRequest {
+            key: path.to_string(),
+            bucket: self.bucket.to_string(),
+            ..Default::default()
+        };
+        self.client
+            .delete_object(req)
+            .await
+            .map_err(|e| ErrorCode::DALTransportError(e.to_string()))?;
+        Ok(())
+    }
 }
@@ -0,0 +1,166 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::time::SystemTime;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::data_accessor::clip_range;
+use crate::Bytes;
+use crate::DataAccessor;
+use crate::InputStream;
+use crate::ObjectMeta;
+use crate::SeekableReader;
+
+struct Object {
+    content: Bytes,
+    last_modified: SystemTime,
+}
+
+/// A DataAccessor that keeps the objects in memory, for test purpose only.
+#[derive(Default)]
+pub struct InMemory {
+    objects: RwLock<HashMap<String, Object>>,
+}
+
+impl InMemory {
+    pub fn new() -> InMemory {
+        InMemory::default()
+    }
+
+    fn get_content(&self, path: &str) -> Result<Bytes> {
+        match self.objects.read().get(path) {
+            Some(object) => Ok(object.content.clone()),
+            None => Err(ErrorCode::from(Error::new(
+                ErrorKind::NotFound,
+                format!("object not found: {}", path),
+            ))),
+        }
+    }
+
+    fn put_content(&self, path: &str, content: Bytes) {
+        self.objects.write().insert(path.to_string(), Object {
+            content,
+            last_modified: SystemTime::now(),
+        });
+    }
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for InMemory {
+    fn get_reader(&self, path: &str, _len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        Ok(Box::new(std::io::Cursor::new(self.get_content(path)?)))
+    }
+
+    fn get_writer(&self, _path: &str) -> Result<Box<dyn Write>> {
+        Err(ErrorCode::UnImplement(
+            "get_writer is not supported by the in-memory data accessor",
+        ))
+    }
+
+    async fn get_input_stream(&self, path: &str, _stream_len: Option<u64>) -> Result<InputStream> {
+        Ok(Box::new(futures::io::Cursor::new(self.get_content(path)?)))
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        self.get_content(path)
+    }
+
+    async fn read_range(&self, path: &str, offset: u64, len: u64) -> Result<Bytes> {
+        let objects = self.objects.read();
+        let content = match objects.get(path) {
+            Some(object) => &object.content,
+            None => {
+                return Err(ErrorCode::from(Error::new(
+                    ErrorKind::NotFound,
+                    format!("object not found: {}", path),
+                )))
+            }
+        };
+        let len = clip_range(path, content.len() as u64, offset, len)?;
+        // an empty range may start anywhere, even past the end of the object
+        if len == 0 {
+            return Ok(vec![]);
+        }
+        let start = offset as usize;
+        Ok(content[start..start + len as usize].to_vec())
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.put_content(path, content);
+        Ok(())
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<Bytes, std::io::Error>> + Send + Unpin + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        let mut content = Vec::with_capacity(stream_len);
+        let mut s = Box::pin(input_stream);
+        while let Some(v) = s.next().await {
+            content.extend_from_slice(&v?);
+        }
+        self.put_content(path, content);
+        Ok(())
+    }
+
+    async fn put_multipart(&self, path: &str, content: Vec<u8>, _part_size: usize) -> Result<()> {
+        self.put_content(path, content);
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        let dir = prefix.trim_end_matches('/');
+        let objects = self.objects.read();
+        let mut res = vec![];
+        for (path, object) in objects.iter() {
+            let name = if dir.is_empty() {
+                Some(path.as_str())
+            } else {
+                path.strip_prefix(dir).and_then(|s| s.strip_prefix('/'))
+            };
+            // only the objects directly under the prefix
+            if matches!(name, Some(name) if !name.is_empty() && !name.contains('/')) {
+                res.push(ObjectMeta {
+                    path: path.clone(),
+                    size: object.content.len() as u64,
+                    last_modified: object.last_modified,
+                });
+            }
+        }
+        Ok(res)
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        match self.objects.write().remove(path) {
+            Some(_) => Ok(()),
+            None => Err(ErrorCode::from(Error::new(
+                ErrorKind::NotFound,
+                format!("object not found: {}", path),
+            ))),
+        }
+    }
+}
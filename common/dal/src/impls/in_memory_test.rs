@@ -0,0 +1,90 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::SeekFrom;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+
+use crate::DataAccessor;
+use crate::InMemory;
+
+#[tokio::test]
+async fn test_in_memory_put_and_range_read() -> Result<()> {
+    let accessor = InMemory::new();
+    let content = (0..100u8).collect::<Vec<_>>();
+    accessor.put("a/1.data", content.clone()).await?;
+    assert_eq!(accessor.get("a/1.data").await?, content);
+
+    let mut input = accessor.get_input_stream("a/1.data", None).await?;
+    input.seek(SeekFrom::Start(90)).await?;
+    let mut buf = vec![0; 5];
+    input.read_exact(&mut buf).await?;
+    assert_eq!(buf, vec![90, 91, 92, 93, 94]);
+
+    assert!(accessor.get("a/2.data").await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_in_memory_list_and_remove() -> Result<()> {
+    let accessor = InMemory::new();
+    accessor.put("a/1.data", vec![1]).await?;
+    accessor.put("a/2.data", vec![1, 2]).await?;
+    accessor.put("a/b/3.data", vec![1, 2, 3]).await?;
+    accessor.put("ab/4.data", vec![1]).await?;
+
+    assert!(accessor.list("not_exists").await?.is_empty());
+
+    // only objects directly under the prefix are listed
+    let mut objects = accessor.list("a/").await?;
+    objects.sort_by(|l, r| l.path.cmp(&r.path));
+    let listed = objects
+        .iter()
+        .map(|o| (o.path.as_str(), o.size))
+        .collect::<Vec<_>>();
+    assert_eq!(listed, vec![("a/1.data", 1), ("a/2.data", 2)]);
+
+    accessor.remove("a/1.data").await?;
+    assert_eq!(accessor.list("a").await?.len(), 1);
+    assert!(accessor.remove("a/1.data").await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_in_memory_read_range() -> Result<()> {
+    let accessor = InMemory::new();
+    let content = (0..100u8).collect::<Vec<_>>();
+    accessor.put("a/1.data", content.clone()).await?;
+
+    for (offset, len) in [(0, 100), (0, 1), (10, 20), (99, 1), (42, 0)] {
+        let bytes = accessor.read_range("a/1.data", offset, len).await?;
+        let (start, end) = (offset as usize, (offset + len) as usize);
+        assert_eq!(bytes, content[start..end].to_vec(), "{}, {}", offset, len);
+    }
+
+    // clipped to the end of the object
+    assert_eq!(
+        accessor.read_range("a/1.data", 90, 20).await?,
+        content[90..].to_vec()
+    );
+    assert!(accessor.read_range("a/1.data", 100, 1).await.is_err());
+    assert!(accessor.read_range("a/2.data", 0, 1).await.is_err());
+
+    // an empty range past the end of the object
+    assert!(accessor.read_range("a/1.data", 150, 0).await?.is_empty());
+    Ok(())
+}
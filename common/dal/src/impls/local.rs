@@ -15,7 +15,10 @@
 
 use std::io::Error;
 use std::io::ErrorKind;
+use std::io::SeekFrom;
 use std::io::Write;
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
 
 use async_compat::CompatExt;
@@ -25,29 +28,44 @@ use common_exception::Result;
 use futures::Stream;
 use futures::StreamExt;
 use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 use tokio::io::AsyncWriteExt;
 
+use crate::data_accessor::clip_range;
 use crate::Bytes;
 use crate::DataAccessor;
 use crate::InputStream;
+use crate::ObjectMeta;
 use crate::SeekableReader;
 
 pub struct Local {
     root: PathBuf,
+    fsync_on_append: bool,
 }
 
 impl Local {
     pub fn new(root: &str) -> Local {
         Local {
             root: PathBuf::from(root),
+            fsync_on_append: false,
         }
     }
+
+    /// If enabled, `put` and `put_stream` do not return until the file and
+    /// its parent directory have been fsync-ed, i.e. the data survives a crash.
+    pub fn with_fsync_on_append(mut self, fsync_on_append: bool) -> Local {
+        self.fsync_on_append = fsync_on_append;
+        self
+    }
 }
 
 impl Local {
     fn prefix_with_root(&self, path: &str) -> Result<PathBuf> {
-        let path = self.root.join(path).canonicalize()?;
-        if path.starts_with(&self.root) {
+        // the path may not exist yet (e.g. `put`), thus it is checked lexically,
+        // instead of being canonicalized
+        let path = self.root.join(path);
+        let escaped = path.components().any(|c| matches!(c, Component::ParentDir));
+        if !escaped && path.starts_with(&self.root) {
             Ok(path)
         } else {
             // TODO customize error code
@@ -57,15 +75,27 @@ impl Local {
             )))
         }
     }
+
+    async fn sync_all(&self, file: &tokio::fs::File, parent: &Path) -> Result<()> {
+        if self.fsync_on_append {
+            file.sync_all().await?;
+            // the directory entry of a newly created file is durable only after
+            // the directory itself is synced
+            tokio::fs::File::open(parent).await?.sync_all().await?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
 impl DataAccessor for Local {
     fn get_reader(&self, path: &str, _len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        let path = self.prefix_with_root(path)?;
         Ok(Box::new(std::fs::File::open(path)?))
     }
 
     fn get_writer(&self, path: &str) -> common_exception::Result<Box<dyn Write>> {
+        let path = self.prefix_with_root(path)?;
         Ok(Box::new(std::fs::File::create(path)?))
     }
 
@@ -82,6 +112,18 @@ impl DataAccessor for Local {
         Ok(contents)
     }
 
+    async fn read_range(&self, path: &str, offset: u64, len: u64) -> Result<Bytes> {
+        let file_path = self.prefix_with_root(path)?;
+        let mut file = tokio::fs::File::open(file_path).await?;
+        let size = file.metadata().await?.len();
+        let len = clip_range(path, size, offset, len)?;
+
+        file.seek(SeekFrom::Start(offset)).await?;
+        let mut contents = vec![];
+        file.take(len).read_to_end(&mut contents).await?;
+        Ok(contents)
+    }
+
     // not "atomic", for test purpose only
     async fn put(&self, path: &str, content: Vec<u8>) -> common_exception::Result<()> {
         let path = self.prefix_with_root(path)?;
@@ -89,9 +131,10 @@ impl DataAccessor for Local {
             .parent()
             .ok_or_else(|| ErrorCode::UnknownException(""))?; // TODO customized error code
         tokio::fs::create_dir_all(parent).await?;
-        let mut new_file = tokio::fs::File::create(path).await?;
+        let mut new_file = tokio::fs::File::create(&path).await?;
         new_file.write_all(&content).await?;
-        Ok(())
+        new_file.flush().await?;
+        self.sync_all(&new_file, parent).await
     }
 
     // not "atomic", for test purpose only
@@ -108,11 +151,57 @@ impl DataAccessor for Local {
             .parent()
             .ok_or_else(|| ErrorCode::UnknownException(""))?; // TODO customized error code
         tokio::fs::create_dir_all(parent).await?;
-        let mut new_file = tokio::fs::File::create(path).await?;
+        let mut new_file = tokio::fs::File::create(&path).await?;
         let mut s = Box::pin(input_stream);
-        while let Some(Ok(v)) = s.next().await {
-            new_file.write_all(&v).await?
+        while let Some(v) = s.next().await {
+            new_file.write_all(&v?).await?
+        }
+        new_file.flush().await?;
+        self.sync_all(&new_file, parent).await
+    }
+
+    // not "atomic", for test purpose only
+    async fn put_multipart(&self, path: &str, content: Vec<u8>, part_size: usize) -> Result<()> {
+        let path = self.prefix_with_root(path)?;
+        let parent = path
+            .parent()
+            .ok_or_else(|| ErrorCode::UnknownException(""))?; // TODO customized error code
+        tokio::fs::create_dir_all(parent).await?;
+        let mut new_file = tokio::fs::File::create(&path).await?;
+        for part in content.chunks(part_size.max(1)) {
+            new_file.write_all(part).await?;
         }
+        new_file.flush().await?;
+        self.sync_all(&new_file, parent).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        let dir = self.prefix_with_root(prefix)?;
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut res = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let path = Path::new(prefix).join(entry.file_name());
+            res.push(ObjectMeta {
+                path: path.to_string_lossy().to_string(),
+                size: metadata.len(),
+                last_modified: metadata.modified()?,
+            });
+        }
+        Ok(res)
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        let path = self.prefix_with_root(path)?;
+        tokio::fs::remove_file(path).await?;
         Ok(())
     }
 }
@@ -0,0 +1,155 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use common_base::tokio;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use futures::AsyncSeekExt;
+
+use crate::DataAccessor;
+use crate::Local;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_put_stream_fsync_on_append() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let root = dir.path().to_str().unwrap();
+    let content = b"some data to be durable".to_vec();
+
+    {
+        let local = Local::new(root).with_fsync_on_append(true);
+        let len = content.len();
+        let input_stream = futures::stream::iter(vec![Ok::<_, std::io::Error>(content.clone())]);
+        local
+            .put_stream("a/b/c.data", Box::new(input_stream), len)
+            .await?;
+        local.put("a/d.data", content.clone()).await?;
+        // the accessor goes away, as if the process exits
+    }
+
+    // "restart": a brand new accessor upon the same root
+    let local = Local::new(root);
+    assert_eq!(content, local.get("a/b/c.data").await?);
+    assert_eq!(content, local.get("a/d.data").await?);
+    assert_eq!(content, std::fs::read(dir.path().join("a/b/c.data"))?);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_reject_path_outside_root() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local = Local::new(dir.path().to_str().unwrap()).with_fsync_on_append(true);
+    let r = local.put("../escaped.data", vec![1, 2, 3]).await;
+    assert!(r.is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_list_and_remove() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local = Local::new(dir.path().to_str().unwrap());
+    local.put("a/1.data", vec![1]).await?;
+    local.put("a/2.data", vec![1, 2]).await?;
+    local.put("a/b/3.data", vec![1, 2, 3]).await?;
+
+    // listing a directory that does not exist is not an error
+    assert!(local.list("not_exists").await?.is_empty());
+
+    // only files directly under the prefix are listed
+    let mut objects = local.list("a").await?;
+    objects.sort_by(|l, r| l.path.cmp(&r.path));
+    let listed = objects
+        .iter()
+        .map(|o| (o.path.as_str(), o.size))
+        .collect::<Vec<_>>();
+    assert_eq!(listed, vec![("a/1.data", 1), ("a/2.data", 2)]);
+
+    local.remove("a/1.data").await?;
+    let objects = local.list("a").await?;
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].path, "a/2.data");
+    assert!(local.get("a/1.data").await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_put_multipart() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local = Local::new(dir.path().to_str().unwrap());
+    let content = (0..100u8).collect::<Vec<_>>();
+
+    // the last part is smaller than the others
+    local.put_multipart("a/1.data", content.clone(), 7).await?;
+    assert_eq!(local.get("a/1.data").await?, content);
+
+    // a single part
+    local
+        .put_multipart("a/2.data", content.clone(), 1024)
+        .await?;
+    assert_eq!(local.get("a/2.data").await?, content);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_paths_relative_to_root() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local = Local::new(dir.path().to_str().unwrap());
+    let content = (0..100u8).collect::<Vec<_>>();
+    local.put("a/1.data", content.clone()).await?;
+
+    // the sync reader resolves the path against the root, as the async ones do
+    let mut reader = local.get_reader("a/1.data", None)?;
+    reader.seek(SeekFrom::Start(90))?;
+    let mut buf = vec![0; 5];
+    reader.read_exact(&mut buf)?;
+    assert_eq!(buf, vec![90, 91, 92, 93, 94]);
+
+    let mut input = local.get_input_stream("a/1.data", None).await?;
+    input.seek(SeekFrom::Start(10)).await?;
+    input.read_exact(&mut buf).await?;
+    assert_eq!(buf, vec![10, 11, 12, 13, 14]);
+
+    assert!(local.get_reader("../1.data", None).is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_local_read_range() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let local = Local::new(dir.path().to_str().unwrap());
+    let content = (0..=255u8).cycle().take(10000).collect::<Vec<_>>();
+    local.put("a/b.data", content.clone()).await?;
+
+    for (offset, len) in [(0, 10000), (0, 8), (1234, 4321), (9992, 8), (500, 0)] {
+        let bytes = local.read_range("a/b.data", offset, len).await?;
+        let (start, end) = (offset as usize, (offset + len) as usize);
+        assert_eq!(bytes, content[start..end].to_vec(), "{}, {}", offset, len);
+    }
+
+    // clipped to the end of the file
+    assert_eq!(
+        local.read_range("a/b.data", 9990, 100).await?,
+        content[9990..].to_vec()
+    );
+    assert!(local.read_range("a/b.data", 10000, 1).await.is_err());
+    assert!(local.read_range("a/not_exists.data", 0, 1).await.is_err());
+    assert!(local.read_range("../escaped.data", 0, 1).await.is_err());
+    Ok(())
+}
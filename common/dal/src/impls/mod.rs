@@ -12,5 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod in_memory_test;
+#[cfg(test)]
+mod local_test;
+
 pub mod aws_s3;
+pub mod in_memory;
 pub mod local;
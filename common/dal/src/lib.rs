@@ -23,8 +23,10 @@ pub use data_accessor::DataAccessorBuilder;
 pub use data_accessor::DefaultDataAccessorBuilder;
 pub use data_accessor::InputStream;
 pub use data_accessor::ObjectAccessor;
+pub use data_accessor::ObjectMeta;
 pub use data_accessor::SeekableReader;
 pub use impls::aws_s3::S3InputStream;
 pub use impls::aws_s3::S3;
+pub use impls::in_memory::InMemory;
 pub use impls::local::Local;
 pub use schemes::StorageScheme;
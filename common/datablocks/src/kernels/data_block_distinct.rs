@@ -0,0 +1,54 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::kernels::HashMethodSerializer;
+use crate::DataBlock;
+use crate::HashMethod;
+
+impl DataBlock {
+    /// Keeps the first seen row of each distinct tuple of the key columns, e.g. `SELECT DISTINCT a, b`
+    /// within a single block. The rows kept are in their original order.
+    pub fn distinct_by(&self, key_indices: &[usize]) -> Result<DataBlock> {
+        let mut key_columns = Vec::with_capacity(key_indices.len());
+        for index in key_indices {
+            if *index >= self.num_columns() {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "Distinct key column index {} out of bounds, the block has {} columns",
+                    index,
+                    self.num_columns()
+                )));
+            }
+            key_columns.push(self.column(*index));
+        }
+
+        let keys = HashMethodSerializer::default().build_keys(&key_columns, self.num_rows())?;
+        let mut seen = HashSet::with_capacity(keys.len());
+        let indices = keys
+            .into_iter()
+            .enumerate()
+            .filter_map(|(row, key)| seen.insert(key).then(|| row as u32))
+            .collect::<Vec<_>>();
+
+        if indices.len() == self.num_rows() {
+            return Ok(self.clone());
+        }
+        // Safety: the indices are the rows of the block
+        unsafe { DataBlock::take_unchecked(self, &[], &indices) }
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::*;
+
+#[test]
+fn test_data_block_distinct_by() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+        DataField::new("c", DataType::Int64, false),
+    ]);
+
+    let raw = DataBlock::create(schema, vec![
+        Series::new(vec![1i64, 2, 1, 2, 1, 3]).into(),
+        Series::new(vec!["x", "y", "x", "z", "x", "y"]).into(),
+        Series::new(vec![10i64, 20, 30, 40, 50, 60]).into(),
+    ]);
+
+    // (1, x) shows up three times, the first seen row (c = 10) is kept
+    let distinct = raw.distinct_by(&[0, 1])?;
+    let expected = vec![
+        "+---+---+----+",
+        "| a | b | c  |",
+        "+---+---+----+",
+        "| 1 | x | 10 |",
+        "| 2 | y | 20 |",
+        "| 2 | z | 40 |",
+        "| 3 | y | 60 |",
+        "+---+---+----+",
+    ];
+    crate::assert_blocks_eq(expected, &[distinct]);
+
+    let distinct = raw.distinct_by(&[1])?;
+    let expected = vec![
+        "+---+---+----+",
+        "| a | b | c  |",
+        "+---+---+----+",
+        "| 1 | x | 10 |",
+        "| 2 | y | 20 |",
+        "| 2 | z | 40 |",
+        "+---+---+----+",
+    ];
+    crate::assert_blocks_eq(expected, &[distinct]);
+
+    // all the rows are distinct
+    let distinct = raw.distinct_by(&[2])?;
+    assert_eq!(distinct.num_rows(), 6);
+
+    // index out of bounds
+    let result = raw.distinct_by(&[3]);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Distinct key column index 3 out of bounds, the block has 3 columns"
+    );
+    Ok(())
+}
@@ -0,0 +1,31 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::DataBlock;
+
+impl DataBlock {
+    /// Keeps the rows of `raw` whose `predicate` is true, the nulls are taken as false.
+    pub fn block_filter(raw: &DataBlock, predicate: &DFBooleanArray) -> Result<DataBlock> {
+        let arrays = raw
+            .columns()
+            .iter()
+            .map(|column| column.to_array())
+            .collect::<Result<Vec<_>>>()?;
+        let arrays = DataArrayFilter::filter_batch_array(arrays, predicate)?;
+        Ok(DataBlock::create_by_array(raw.schema().clone(), arrays))
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::*;
+
+#[test]
+fn test_data_block_filter() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    let raw = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1i64, 2, 3]),
+        Series::new(vec!["b1", "b2", "b3"]),
+    ]);
+
+    let predicate = DFBooleanArray::new_from_opt_slice(&[Some(true), None, Some(false)]);
+    let filtered = DataBlock::block_filter(&raw, &predicate)?;
+    assert_eq!(raw.schema(), filtered.schema());
+
+    let expected = vec![
+        "+---+----+",
+        "| a | b  |",
+        "+---+----+",
+        "| 1 | b1 |",
+        "+---+----+",
+    ];
+    crate::assert_blocks_eq(expected, &[filtered]);
+
+    let predicate = DFBooleanArray::new_from_slice(&[false, false, false]);
+    let filtered = DataBlock::block_filter(&raw, &predicate)?;
+    assert_eq!(filtered.num_rows(), 0);
+
+    Ok(())
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::DataBlock;
@@ -52,4 +53,41 @@ impl DataBlock {
 
         Ok(scattered_blocks)
     }
+
+    /// Scatters the rows into `bounds.len() + 1` blocks by the ranges of their `key_column`,
+    /// the `bounds` are sorted ascending.
+    ///
+    /// The i-th block holds the rows whose key is in `[bounds[i - 1], bounds[i])`, the first one
+    /// holds the keys less than `bounds[0]` and the nulls, as the nulls go first when sorting.
+    pub fn scatter_by_range(
+        block: &DataBlock,
+        key_column: &str,
+        bounds: &[DataValue],
+    ) -> Result<Vec<DataBlock>> {
+        let key = block.try_column_by_name(key_column)?;
+        let rows = block.num_rows();
+
+        // the bucket of a row is the number of the bounds its key is greater than or equal to
+        let mut buckets = vec![0u64; rows];
+        for bound in bounds {
+            if bound.is_null() {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "Scatter bound of column {} can not be null",
+                    key_column
+                )));
+            }
+            let bound = DataColumn::Constant(bound.clone(), rows);
+            let ge = key
+                .compare(DataValueComparisonOperator::GtEq, &bound)?
+                .to_array()?;
+            for (bucket, ge) in buckets.iter_mut().zip(ge.bool()?.into_iter()) {
+                if ge == Some(true) {
+                    *bucket += 1;
+                }
+            }
+        }
+
+        let indices = DataColumn::Array(Series::new(buckets));
+        Self::scatter_block(block, &indices, bounds.len() + 1)
+    }
 }
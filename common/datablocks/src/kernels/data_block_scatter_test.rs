@@ -50,3 +50,69 @@ fn test_data_block_scatter() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_data_block_scatter_by_range() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, true),
+        DataField::new("b", DataType::String, false),
+    ]);
+
+    let raw = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![
+            Some(25i64),
+            Some(5),
+            None,
+            Some(10),
+            Some(15),
+            Some(20),
+            Some(-1),
+        ]),
+        Series::new(vec!["b1", "b2", "b3", "b4", "b5", "b6", "b7"]),
+    ]);
+
+    let bounds = vec![DataValue::Int64(Some(10)), DataValue::Int64(Some(20))];
+    let scattered = DataBlock::scatter_by_range(&raw, "a", &bounds)?;
+    assert_eq!(scattered.len(), 3);
+    for block in &scattered {
+        assert_eq!(raw.schema(), block.schema());
+    }
+    let rows = scattered.iter().map(|b| b.num_rows()).collect::<Vec<_>>();
+    assert_eq!(rows, vec![3, 2, 2]);
+
+    // the keys of each bucket fall in its range
+    let ranges = [(i64::MIN, 10), (10, 20), (20, i64::MAX)];
+    for (block, (lower, upper)) in scattered.iter().zip(ranges) {
+        for row in 0..block.num_rows() {
+            let key = block.column(0).try_get(row)?;
+            if !key.is_null() {
+                let key = key.as_i64()?;
+                assert!(
+                    lower <= key && key < upper,
+                    "{} in [{}, {})",
+                    key,
+                    lower,
+                    upper
+                );
+            }
+        }
+    }
+
+    // the buckets in order are sorted by range, nulls go first
+    let expected = vec![
+        "+------+----+",
+        "| a    | b  |",
+        "+------+----+",
+        "| 5    | b2 |",
+        "| NULL | b3 |",
+        "| -1   | b7 |",
+        "| 10   | b4 |",
+        "| 15   | b5 |",
+        "| 25   | b1 |",
+        "| 20   | b6 |",
+        "+------+----+",
+    ];
+    crate::assert_blocks_eq(expected, &scattered);
+
+    Ok(())
+}
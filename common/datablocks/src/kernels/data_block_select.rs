@@ -0,0 +1,60 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::DataBlock;
+
+impl DataBlock {
+    /// Picks the columns at the given indices, and names them with the given names,
+    /// e.g. `SELECT b AS x, a AS y`, in one pass.
+    pub fn select(&self, items: &[(usize, String)]) -> Result<DataBlock> {
+        let mut names = HashSet::with_capacity(items.len());
+        let mut fields = Vec::with_capacity(items.len());
+        let mut columns = Vec::with_capacity(items.len());
+
+        for (index, name) in items {
+            if *index >= self.num_columns() {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "Select column index {} out of bounds, the block has {} columns",
+                    index,
+                    self.num_columns()
+                )));
+            }
+            if !names.insert(name.as_str()) {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "Duplicate select column name: {}",
+                    name
+                )));
+            }
+
+            let field = self.schema().field(*index);
+            fields.push(DataField::new(
+                name,
+                field.data_type().clone(),
+                field.is_nullable(),
+            ));
+            columns.push(self.column(*index).clone());
+        }
+
+        let schema = DataSchema::new_from(fields, self.schema().meta().clone());
+        Ok(DataBlock::create(Arc::new(schema), columns))
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::*;
+
+#[test]
+fn test_data_block_select() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Float64, false),
+        DataField::new("c", DataType::String, true),
+    ]);
+
+    let raw = DataBlock::create(schema, vec![
+        Series::new(vec![1i64, 2, 3]).into(),
+        Series::new(vec![1.0f64, 2., 3.]).into(),
+        Series::new(vec!["x", "y", "z"]).into(),
+    ]);
+
+    let selected = raw.select(&[(2, "z".to_string()), (0, "x".to_string())])?;
+    assert_eq!(
+        selected.schema(),
+        &DataSchemaRefExt::create(vec![
+            DataField::new("z", DataType::String, true),
+            DataField::new("x", DataType::Int64, false),
+        ])
+    );
+
+    let expected = vec![
+        "+---+---+",
+        "| z | x |",
+        "+---+---+",
+        "| x | 1 |",
+        "| y | 2 |",
+        "| z | 3 |",
+        "+---+---+",
+    ];
+    crate::assert_blocks_eq(expected, &[selected]);
+
+    // the same column can be selected more than once, under different names
+    let selected = raw.select(&[(1, "b1".to_string()), (1, "b2".to_string())])?;
+    assert_eq!(selected.num_columns(), 2);
+
+    // index out of bounds
+    let result = raw.select(&[(3, "d".to_string())]);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Select column index 3 out of bounds, the block has 3 columns"
+    );
+
+    // duplicate output names
+    let result = raw.select(&[(0, "x".to_string()), (1, "x".to_string())]);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Duplicate select column name: x"
+    );
+
+    Ok(())
+}
@@ -58,7 +58,8 @@ impl DataBlock {
             .collect::<Result<Vec<_>>>()?;
 
         let indices = arrow_sort::lexsort_to_indices(&order_arrays, limit)?;
-        DataBlock::block_take_by_indices(block, &[], indices.values())
+        // Safety: the indices are the rows of the block sorted
+        unsafe { DataBlock::take_unchecked(block, &[], indices.values()) }
     }
 
     pub fn merge_sort_block(
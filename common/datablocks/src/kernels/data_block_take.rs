@@ -13,15 +13,39 @@
 // limitations under the License.
 
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::DataBlock;
 
 impl DataBlock {
+    /// Takes the rows at `indices` of `raw`, an index out of the rows of `raw` is rejected.
     pub fn block_take_by_indices(
         raw: &DataBlock,
         constant_columns: &[String],
         indices: &[u32],
+    ) -> Result<DataBlock> {
+        let rows = raw.num_rows();
+        if let Some(index) = indices.iter().find(|i| **i as usize >= rows) {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "Take index {} out of bounds, the block has {} rows",
+                index, rows
+            )));
+        }
+        // Safety: the indices are checked above
+        unsafe { Self::take_unchecked(raw, constant_columns, indices) }
+    }
+
+    /// `block_take_by_indices` without checking the indices, for the hot loops taking the
+    /// indices from `raw` itself.
+    ///
+    /// # Safety
+    ///
+    /// All the `indices` must be less than the number of rows of `raw`.
+    pub unsafe fn take_unchecked(
+        raw: &DataBlock,
+        constant_columns: &[String],
+        indices: &[u32],
     ) -> Result<DataBlock> {
         if indices.is_empty() {
             return Ok(DataBlock::empty_with_schema(raw.schema().clone()));
@@ -38,7 +62,7 @@ impl DataBlock {
                     match column {
                         DataColumn::Array(array) => {
                             let mut indices = indices.iter().map(|f| *f as usize);
-                            let series = unsafe { array.take_iter_unchecked(&mut indices) }?;
+                            let series = array.take_iter_unchecked(&mut indices)?;
                             Ok(DataColumn::Array(series))
                         }
                         DataColumn::Constant(v, _) => {
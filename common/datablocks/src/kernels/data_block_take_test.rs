@@ -44,3 +44,21 @@ fn test_data_block_take() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_data_block_take_out_of_bounds() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    let raw = DataBlock::create_by_array(schema, vec![Series::new(vec![1i64, 2, 3])]);
+
+    let take = DataBlock::block_take_by_indices(&raw, &[], &[2, 0])?;
+    assert_eq!(take.num_rows(), 2);
+
+    // index out of bounds
+    let result = DataBlock::block_take_by_indices(&raw, &[], &[0, 3, 1]);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Take index 3 out of bounds, the block has 3 rows"
+    );
+
+    Ok(())
+}
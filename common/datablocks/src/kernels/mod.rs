@@ -15,12 +15,18 @@
 #[cfg(test)]
 mod data_block_concat_test;
 #[cfg(test)]
+mod data_block_distinct_test;
+#[cfg(test)]
+mod data_block_filter_test;
+#[cfg(test)]
 mod data_block_group_by_hash_test;
 #[cfg(test)]
 mod data_block_group_by_test;
 #[cfg(test)]
 mod data_block_scatter_test;
 #[cfg(test)]
+mod data_block_select_test;
+#[cfg(test)]
 mod data_block_slice_test;
 #[cfg(test)]
 mod data_block_sort_test;
@@ -28,9 +34,12 @@ mod data_block_sort_test;
 mod data_block_take_test;
 
 mod data_block_concat;
+mod data_block_distinct;
+mod data_block_filter;
 mod data_block_group_by;
 mod data_block_group_by_hash;
 mod data_block_scatter;
+mod data_block_select;
 mod data_block_slice;
 mod data_block_sort;
 mod data_block_take;
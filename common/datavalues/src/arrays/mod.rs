@@ -14,6 +14,8 @@
 
 #[cfg(test)]
 mod arithmetic_test;
+#[cfg(all(test, feature = "rayon"))]
+mod upstream_traits_test;
 
 #[macro_use]
 mod arithmetic;
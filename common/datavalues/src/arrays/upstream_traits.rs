@@ -18,6 +18,12 @@ use std::borrow::Cow;
 use std::iter::FromIterator;
 
 use common_arrow::arrow::array::*;
+#[cfg(feature = "rayon")]
+use rayon::iter::FromParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::iter::IntoParallelIterator;
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
 
 use super::get_list_builder;
 use crate::prelude::*;
@@ -68,6 +74,32 @@ where T: DFPrimitiveType
     }
 }
 
+/// FromParallelIterator trait
+
+// Same semantics as `FromIterator<T> for NoNull<DFPrimitiveArray<T>>`, but each rayon thread
+// builds its own AlignedVec, which are concatenated (in order) at last.
+#[cfg(feature = "rayon")]
+impl<T> FromParallelIterator<T> for DFPrimitiveArray<T>
+where T: DFPrimitiveType
+{
+    fn from_par_iter<I: IntoParallelIterator<Item = T>>(iter: I) -> Self {
+        let vectors = iter
+            .into_par_iter()
+            .fold(AlignedVec::<T>::new, |mut av, v| {
+                av.push(v);
+                av
+            })
+            .collect::<Vec<_>>();
+
+        let capacity = vectors.iter().map(|av| av.len()).sum();
+        let mut values = AlignedVec::<T>::with_capacity(capacity);
+        for av in vectors {
+            values.extend_from_slice(av.as_slice());
+        }
+        DFPrimitiveArray::<T>::new_from_aligned_vec(values)
+    }
+}
+
 impl FromIterator<Option<bool>> for DFBooleanArray {
     fn from_iter<I: IntoIterator<Item = Option<bool>>>(iter: I) -> Self {
         let arr = BooleanArray::from_iter(iter);
@@ -0,0 +1,28 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rayon::prelude::*;
+
+use crate::prelude::*;
+use crate::utils::NoNull;
+
+#[test]
+fn test_primitive_array_from_par_iter() {
+    let sequential: NoNull<DFUInt64Array> = (0..1_000_000u64).map(|v| v * 2).collect();
+    let parallel: DFUInt64Array = (0..1_000_000u64).into_par_iter().map(|v| v * 2).collect();
+
+    assert_eq!(1_000_000, parallel.len());
+    assert_eq!(0, parallel.null_count());
+    assert_eq!(sequential.inner(), parallel.inner());
+}
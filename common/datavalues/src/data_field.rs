@@ -15,16 +15,25 @@
 use std::collections::BTreeMap;
 
 use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_exception::ErrorCode;
+use common_exception::Result;
 
 use crate::DataType;
+use crate::DataValue;
 
-#[derive(
-    serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Hash, Eq, PartialOrd, Ord,
-)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]
 pub struct DataField {
     name: String,
     data_type: DataType,
     nullable: bool,
+    /// The SQL text of the default value expression, it is evaluated (as a constant) for the
+    /// rows that omit the column.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_expr: Option<String>,
+    /// The default value in JSON, evaluated once when the column is added to an existing table.
+    /// The rows written before the column is added read it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_value: Option<String>,
 }
 
 impl DataField {
@@ -33,8 +42,21 @@ impl DataField {
             name: name.to_string(),
             data_type,
             nullable,
+            default_expr: None,
+            default_value: None,
         }
     }
+
+    pub fn with_default_expr(mut self, default_expr: Option<String>) -> Self {
+        self.default_expr = default_expr;
+        self
+    }
+
+    pub fn with_default_value(mut self, default_value: Option<&DataValue>) -> Result<Self> {
+        self.default_value = default_value.map(serde_json::to_string).transpose()?;
+        Ok(self)
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
@@ -47,6 +69,24 @@ impl DataField {
         self.nullable
     }
 
+    pub fn default_expr(&self) -> Option<&String> {
+        self.default_expr.as_ref()
+    }
+
+    pub fn default_value(&self) -> Result<Option<DataValue>> {
+        self.default_value
+            .as_ref()
+            .map(|v| {
+                serde_json::from_str(v).map_err(|e| {
+                    ErrorCode::IllegalSchema(format!(
+                        "Invalid default value of column `{}`: {}",
+                        self.name, e
+                    ))
+                })
+            })
+            .transpose()
+    }
+
     /// Check to see if `self` is a superset of `other` field. Superset is defined as:
     ///
     /// * if nullability doesn't match, self needs to be nullable
@@ -77,8 +117,8 @@ impl DataField {
         };
 
         let mut f = ArrowField::new(&self.name, self.data_type.to_arrow(), self.nullable);
+        let mut mp = BTreeMap::new();
         if let Some(custom_name) = custom_name {
-            let mut mp = BTreeMap::new();
             mp.insert(
                 "ARROW:extension:databend_name".to_string(),
                 custom_name.to_string(),
@@ -87,6 +127,20 @@ impl DataField {
             if let Some(m) = custom_metadata {
                 mp.insert("ARROW:extension:databend_metadata".to_string(), m);
             }
+        }
+        if let Some(default_expr) = &self.default_expr {
+            mp.insert(
+                "ARROW:extension:databend_default".to_string(),
+                default_expr.clone(),
+            );
+        }
+        if let Some(default_value) = &self.default_value {
+            mp.insert(
+                "ARROW:extension:databend_default_value".to_string(),
+                default_value.clone(),
+            );
+        }
+        if !mp.is_empty() {
             f = f.with_metadata(mp);
         }
 
@@ -97,7 +151,11 @@ impl DataField {
 impl From<&ArrowField> for DataField {
     fn from(f: &ArrowField) -> Self {
         let mut dt: DataType = f.data_type().into();
+        let mut default_expr = None;
+        let mut default_value = None;
         if let Some(m) = f.metadata() {
+            default_expr = m.get("ARROW:extension:databend_default").cloned();
+            default_value = m.get("ARROW:extension:databend_default_value").cloned();
             if let Some(custom_name) = m.get("ARROW:extension:databend_name") {
                 let metatada = m.get("ARROW:extension:databend_metadata");
                 match custom_name.as_str() {
@@ -108,7 +166,28 @@ impl From<&ArrowField> for DataField {
                 }
             }
         }
-        DataField::new(f.name(), dt, f.is_nullable())
+        DataField {
+            default_expr,
+            default_value,
+            ..DataField::new(f.name(), dt, f.is_nullable())
+        }
+    }
+}
+
+// The default expression and value are left out unless there is one, most of the fields have none.
+impl std::fmt::Debug for DataField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut s = f.debug_struct("DataField");
+        s.field("name", &self.name)
+            .field("data_type", &self.data_type)
+            .field("nullable", &self.nullable);
+        if let Some(default_expr) = &self.default_expr {
+            s.field("default_expr", default_expr);
+        }
+        if let Some(default_value) = &self.default_value {
+            s.field("default_value", default_value);
+        }
+        s.finish()
     }
 }
 
@@ -247,6 +247,25 @@ impl DataValue {
             ))),
         }
     }
+
+    pub fn as_f64(&self) -> Result<f64> {
+        match self {
+            DataValue::Float32(Some(v)) => Ok(*v as f64),
+            DataValue::Float64(Some(v)) => Ok(*v),
+            DataValue::Int8(_)
+            | DataValue::Int16(_)
+            | DataValue::Int32(_)
+            | DataValue::Int64(_) => self.as_i64().map(|v| v as f64),
+            DataValue::UInt8(_)
+            | DataValue::UInt16(_)
+            | DataValue::UInt32(_)
+            | DataValue::UInt64(_) => self.as_u64().map(|v| v as f64),
+            other => Result::Err(ErrorCode::BadDataValueType(format!(
+                "Unexpected type:{:?} to get f64 number",
+                other.data_type()
+            ))),
+        }
+    }
 }
 
 // Did not use std::convert:TryFrom
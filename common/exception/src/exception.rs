@@ -28,6 +28,7 @@ use tonic::Status;
 
 pub static ABORT_SESSION: u16 = 42;
 pub static ABORT_QUERY: u16 = 43;
+pub static ABORT_CONNECTION: u16 = 58;
 
 #[derive(Clone)]
 pub enum ErrorCodeBacktrace {
@@ -178,6 +179,11 @@ build_exceptions! {
     TLSConfigurationFailure(52),
     UnknownSession(53),
     UnexpectedError(54),
+    TableIsFull(55),
+    UnknownTableSnapshot(56),
+    TableIsReadOnly(57),
+    AbortedConnection(ABORT_CONNECTION),
+    TableVersionMismatched(59),
 
     // uncategorized
     UnexpectedResponseType(600),
@@ -254,6 +260,8 @@ build_exceptions! {
     NamespaceNodeAlreadyExists(4009),
     NamespaceIllegalNodeFormat(4010),
 
+    DatabaseNotEmpty(4011),
+
     // storage-api error codes
     IllegalScanPlan(5000),
     ReadFileError(5001),
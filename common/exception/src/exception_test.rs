@@ -61,6 +61,20 @@ fn test_derive_from_std_error() {
     );
 }
 
+#[test]
+fn test_derive_from_io_error() {
+    use std::io::ErrorKind;
+
+    use crate::exception::ErrorCode;
+
+    // the kind is not kept, a disconnect is told by the io of the connection
+    let error = ErrorCode::from(std::io::Error::new(ErrorKind::ConnectionReset, "reset"));
+    assert_eq!("Code: 1002, displayText = reset.", format!("{}", error));
+
+    let error = ErrorCode::from(std::io::Error::new(ErrorKind::NotFound, "not found"));
+    assert_eq!("Code: 1002, displayText = not found.", format!("{}", error));
+}
+
 #[test]
 fn test_derive_from_display() {
     use crate::exception::ErrorCode;
@@ -0,0 +1,176 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+use std::io::Read;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use common_catalog::HyperLogLog;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+
+// 2^14 registers, the standard error of the estimate is 1.04 / sqrt(2^14) ~= 0.81%
+const HLL_PRECISION: u32 = 14;
+
+/// The same sketch as the distinct estimates of the column statistics, of a higher precision.
+pub struct AggregateApproxCountDistinctState {
+    sketch: HyperLogLog,
+}
+
+impl AggregateApproxCountDistinctState {
+    fn serialize(&self, writer: &mut BytesMut) -> Result<()> {
+        writer.write_binary(&serde_json::to_vec(&self.sketch)?)
+    }
+
+    fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        let size = reader.read_uvarint()? as usize;
+        let mut bytes = vec![0; size];
+        reader.read_exact(&mut bytes)?;
+        let sketch: HyperLogLog = serde_json::from_slice(&bytes)?;
+        if sketch.precision() != HLL_PRECISION {
+            return Err(ErrorCode::BadBytes(format!(
+                "Invalid HyperLogLog sketch, expects precision {}, but got {}",
+                HLL_PRECISION,
+                sketch.precision()
+            )));
+        }
+        self.sketch = sketch;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateApproxCountDistinctFunction {
+    display_name: String,
+}
+
+impl AggregateApproxCountDistinctFunction {
+    pub fn try_create(
+        display_name: &str,
+        _params: Vec<DataValue>,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        Ok(Arc::new(AggregateApproxCountDistinctFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> AggregateFunctionDescription {
+        AggregateFunctionDescription::creator(Box::new(Self::try_create))
+    }
+
+    // the bytes of each row that are added to the sketch, None for the nulls, which are not counted
+    fn keys(&self, array: &Series) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut keys = vec![vec![]; array.len()];
+        if array.serialize(&mut keys).is_err() {
+            // not every type is serializable as group by keys
+            for (row, key) in keys.iter_mut().enumerate() {
+                *key = serde_json::to_vec(&array.try_get(row)?)?;
+            }
+        }
+        Ok(keys
+            .into_iter()
+            .enumerate()
+            .map(|(row, key)| (!array.is_null(row)).then(|| key))
+            .collect())
+    }
+}
+
+impl AggregateFunction for AggregateApproxCountDistinctFunction {
+    fn name(&self) -> &str {
+        "AggregateApproxCountDistinctFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateApproxCountDistinctState {
+            sketch: HyperLogLog::with_precision(HLL_PRECISION),
+        });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateApproxCountDistinctState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateApproxCountDistinctState>();
+        for key in self.keys(&arrays[0])?.iter().flatten() {
+            state.sketch.add(key);
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let keys = self.keys(&arrays[0])?;
+        for (place, key) in places.iter().zip(keys.iter()) {
+            if let Some(key) = key {
+                let place = place.next(offset);
+                let state = place.get::<AggregateApproxCountDistinctState>();
+                state.sketch.add(key);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateApproxCountDistinctState>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateApproxCountDistinctState>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateApproxCountDistinctState>();
+        let rhs = rhs.get::<AggregateApproxCountDistinctState>();
+        state.sketch.merge(&rhs.sketch);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateApproxCountDistinctState>();
+        Ok(state.sketch.count().into())
+    }
+}
+
+impl fmt::Display for AggregateApproxCountDistinctFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
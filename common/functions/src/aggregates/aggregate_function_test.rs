@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use bumpalo::Bump;
+use bytes::BytesMut;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use pretty_assertions::assert_eq;
@@ -327,6 +328,28 @@ fn test_aggregate_function_on_empty_data() -> Result<()> {
             expect: DataValue::UInt64(Some(0)),
             error: "",
         },
+        Test {
+            name: "approx_count_distinct-passed",
+            eval_nums: 1,
+            params: vec![],
+            args: vec![args[0].clone()],
+            display: "approx_count_distinct",
+            func_name: "approx_count_distinct",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::UInt64(Some(0)),
+            error: "",
+        },
+        Test {
+            name: "quantile-passed",
+            eval_nums: 1,
+            params: vec![DataValue::Float64(Some(0.95))],
+            args: vec![args[0].clone()],
+            display: "quantile",
+            func_name: "quantile",
+            arrays: vec![arrays[0].clone()],
+            expect: DataValue::Float64(None),
+            error: "",
+        },
         Test {
             name: "std-passed",
             eval_nums: 1,
@@ -396,3 +419,129 @@ fn test_aggregate_function_on_empty_data() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_aggregate_approx_count_distinct() -> Result<()> {
+    let arena = Bump::new();
+    let factory = AggregateFunctionFactory::instance();
+    let args = vec![DataField::new("a", DataType::UInt64, true)];
+    let func = factory.get("approx_count_distinct", vec![], args)?;
+
+    // 100k distinct values with duplicates and nulls, accumulated by two partial states
+    let values = (0..100_000u64)
+        .map(|v| if v % 10 == 0 { None } else { Some(v) })
+        .chain((0..100_000u64).map(Some))
+        .collect::<Vec<_>>();
+    let (left, right) = values.split_at(values.len() / 2);
+    let left = Series::new(left.to_vec());
+    let right = Series::new(right.to_vec());
+
+    let addr1 = arena.alloc_layout(func.state_layout());
+    func.init_state(addr1.into());
+    func.accumulate(addr1.into(), &[left.clone()], left.len())?;
+
+    let addr2 = arena.alloc_layout(func.state_layout());
+    func.init_state(addr2.into());
+    func.accumulate(addr2.into(), &[right.clone()], right.len())?;
+
+    // the partial state is shipped to the final stage serialized
+    let mut writer = BytesMut::new();
+    func.serialize(addr2.into(), &mut writer)?;
+    let addr3 = arena.alloc_layout(func.state_layout());
+    func.init_state(addr3.into());
+    func.deserialize(addr3.into(), &mut writer.as_ref())?;
+
+    func.merge(addr1.into(), addr3.into())?;
+    let estimate = match func.merge_result(addr1.into())? {
+        DataValue::UInt64(Some(estimate)) => estimate as f64,
+        other => panic!("unexpected result: {:?}", other),
+    };
+    let error = (estimate - 100_000f64).abs() / 100_000f64;
+    assert!(error < 0.02, "estimate: {}, error: {}", estimate, error);
+
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_quantile() -> Result<()> {
+    let arena = Bump::new();
+    let factory = AggregateFunctionFactory::instance();
+    let args = vec![DataField::new("a", DataType::UInt64, true)];
+
+    // 0..10000 shuffled, accumulated by two partial states
+    let values = (0..10000u64)
+        .map(|v| (v * 7919) % 10000)
+        .collect::<Vec<_>>();
+    let (left, right) = values.split_at(values.len() / 2);
+    let left = Series::new(left.to_vec());
+    let right = Series::new(right.to_vec());
+
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+    let exact = |level: f64| {
+        let pos = level * (sorted.len() - 1) as f64;
+        let (index, fraction) = (pos.floor() as usize, pos.fract());
+        match sorted.get(index + 1) {
+            None => sorted[index] as f64,
+            Some(next) => sorted[index] as f64 + (*next as f64 - sorted[index] as f64) * fraction,
+        }
+    };
+
+    for level in [0.0, 0.01, 0.25, 0.5, 0.75, 0.95, 0.99, 1.0] {
+        let params = vec![DataValue::Float64(Some(level))];
+        let func = factory.get("quantile", params, args.clone())?;
+
+        let addr1 = arena.alloc_layout(func.state_layout());
+        func.init_state(addr1.into());
+        func.accumulate(addr1.into(), &[left.clone()], left.len())?;
+
+        let addr2 = arena.alloc_layout(func.state_layout());
+        func.init_state(addr2.into());
+        func.accumulate(addr2.into(), &[right.clone()], right.len())?;
+
+        // the partial state is shipped to the final stage serialized
+        let mut writer = BytesMut::new();
+        func.serialize(addr2.into(), &mut writer)?;
+        let addr3 = arena.alloc_layout(func.state_layout());
+        func.init_state(addr3.into());
+        func.deserialize(addr3.into(), &mut writer.as_ref())?;
+
+        func.merge(addr1.into(), addr3.into())?;
+        let result = match func.merge_result(addr1.into())? {
+            DataValue::Float64(Some(result)) => result,
+            other => panic!("unexpected result: {:?}", other),
+        };
+        // within 0.5% of the range
+        let expect = exact(level);
+        assert!(
+            (result - expect).abs() <= 50.0,
+            "level: {}, expect: {}, got: {}",
+            level,
+            expect,
+            result
+        );
+    }
+
+    // a single value is the result of all the levels
+    let func = factory.get(
+        "quantile",
+        vec![DataValue::Float64(Some(0.95))],
+        args.clone(),
+    )?;
+    let addr = arena.alloc_layout(func.state_layout());
+    func.init_state(addr.into());
+    func.accumulate(addr.into(), &[Series::new(vec![Some(42u64), None])], 2)?;
+    assert_eq!(
+        func.merge_result(addr.into())?,
+        DataValue::Float64(Some(42.0))
+    );
+
+    // the level is between 0 and 1
+    let result = factory.get("quantile", vec![DataValue::Float64(Some(1.5))], args);
+    assert_eq!(
+        result.err().map(|e| e.message()),
+        Some("The level of quantile must be between 0 and 1, but got 1.5".to_string())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+use num::cast::AsPrimitive;
+
+use super::StateAddr;
+use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::with_match_primitive_type;
+
+// the larger the compression, the more centroids are kept, the more accurate the result is
+const TDIGEST_COMPRESSION: f64 = 100.0;
+// the values added are buffered, they are merged into the centroids once the buffer is full
+const TDIGEST_BUFFER_SIZE: usize = 500;
+
+#[derive(Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// A merging t-digest, the centroids are small at the tails, so that the extreme quantiles
+/// are more accurate than the median ones.
+struct AggregateQuantileState {
+    centroids: Vec<Centroid>,
+    buffer: Vec<Centroid>,
+    min: f64,
+    max: f64,
+}
+
+impl AggregateQuantileState {
+    fn add(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.buffer.push(Centroid {
+            mean: value,
+            weight: 1.0,
+        });
+
+        if self.buffer.len() >= TDIGEST_BUFFER_SIZE {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, rhs: &Self) {
+        self.min = self.min.min(rhs.min);
+        self.max = self.max.max(rhs.max);
+        self.buffer.extend_from_slice(&rhs.centroids);
+        self.buffer.extend_from_slice(&rhs.buffer);
+        self.compress();
+    }
+
+    // Merges the buffered centroids into the digest, the adjacent centroids are merged as long
+    // as the weight of the merged one stays under the bound of its quantile.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let mut centroids = std::mem::take(&mut self.centroids);
+        centroids.append(&mut self.buffer);
+        centroids.sort_by(|l, r| l.mean.partial_cmp(&r.mean).unwrap_or(Ordering::Equal));
+
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let mut merged: Vec<Centroid> = Vec::with_capacity(centroids.len());
+        let mut cumulative = 0.0;
+        for centroid in centroids {
+            if let Some(last) = merged.last_mut() {
+                let weight = last.weight + centroid.weight;
+                let q = (cumulative + weight / 2.0) / total;
+                if weight <= 4.0 * total * q * (1.0 - q) / TDIGEST_COMPRESSION {
+                    last.mean += (centroid.mean - last.mean) * centroid.weight / weight;
+                    last.weight = weight;
+                    continue;
+                }
+                cumulative += last.weight;
+            }
+            merged.push(centroid);
+        }
+        self.centroids = merged;
+    }
+
+    // Interpolates between the centers of the adjacent centroids, the min and max values are
+    // the bounds of the first and the last one.
+    fn quantile(&mut self, level: f64) -> Option<f64> {
+        self.compress();
+        if self.centroids.is_empty() {
+            return None;
+        }
+
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let target = level * total;
+        let mut cumulative = 0.0;
+        let mut prev_center = 0.0;
+        let mut prev_mean = self.min;
+        for centroid in &self.centroids {
+            let center = cumulative + centroid.weight / 2.0;
+            if target < center {
+                let ratio = (target - prev_center) / (center - prev_center);
+                return Some(prev_mean + (centroid.mean - prev_mean) * ratio);
+            }
+            prev_center = center;
+            prev_mean = centroid.mean;
+            cumulative += centroid.weight;
+        }
+
+        if total <= prev_center {
+            return Some(self.max);
+        }
+        let ratio = (target - prev_center) / (total - prev_center);
+        Some(prev_mean + (self.max - prev_mean) * ratio)
+    }
+
+    fn serialize(&mut self, writer: &mut BytesMut) -> Result<()> {
+        self.compress();
+        self.min.serialize_to_buf(writer)?;
+        self.max.serialize_to_buf(writer)?;
+        writer.write_uvarint(self.centroids.len() as u64)?;
+        for centroid in &self.centroids {
+            centroid.mean.serialize_to_buf(writer)?;
+            centroid.weight.serialize_to_buf(writer)?;
+        }
+        Ok(())
+    }
+
+    fn deserialize(&mut self, reader: &mut &[u8]) -> Result<()> {
+        self.min = f64::deserialize(reader)?;
+        self.max = f64::deserialize(reader)?;
+        let size = reader.read_uvarint()?;
+        self.buffer.clear();
+        self.centroids.clear();
+        for _i in 0..size {
+            let mean = f64::deserialize(reader)?;
+            let weight = f64::deserialize(reader)?;
+            self.centroids.push(Centroid { mean, weight });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct AggregateQuantileFunction<T> {
+    display_name: String,
+    level: f64,
+    t: PhantomData<T>,
+}
+
+impl<T> AggregateFunction for AggregateQuantileFunction<T>
+where T: DFPrimitiveType + AsPrimitive<f64>
+{
+    fn name(&self) -> &str {
+        "AggregateQuantileFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateQuantileState {
+            centroids: vec![],
+            buffer: vec![],
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateQuantileState>()
+    }
+
+    fn accumulate(&self, place: StateAddr, arrays: &[Series], _input_rows: usize) -> Result<()> {
+        let state = place.get::<AggregateQuantileState>();
+        let array: &DFPrimitiveArray<T> = arrays[0].static_cast();
+        array
+            .iter()
+            .flatten()
+            .for_each(|value| state.add(value.as_()));
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offset: usize,
+        arrays: &[Series],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let array: &DFPrimitiveArray<T> = arrays[0].static_cast();
+        array.iter().zip(places.iter()).for_each(|(value, place)| {
+            if let Some(value) = value {
+                let place = place.next(offset);
+                let state = place.get::<AggregateQuantileState>();
+                state.add(value.as_());
+            }
+        });
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateQuantileState>();
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateQuantileState>();
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = place.get::<AggregateQuantileState>();
+        let rhs = rhs.get::<AggregateQuantileState>();
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = place.get::<AggregateQuantileState>();
+        Ok(DataValue::Float64(state.quantile(self.level)))
+    }
+}
+
+impl<T> fmt::Display for AggregateQuantileFunction<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
+
+impl<T> AggregateQuantileFunction<T>
+where T: DFPrimitiveType + AsPrimitive<f64>
+{
+    pub fn try_create(display_name: &str, level: f64) -> Result<AggregateFunctionRef> {
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            level,
+            t: PhantomData,
+        }))
+    }
+}
+
+pub fn try_create_aggregate_quantile_function(
+    display_name: &str,
+    params: Vec<DataValue>,
+    arguments: Vec<DataField>,
+) -> Result<Arc<dyn AggregateFunction>> {
+    assert_unary_arguments(display_name, arguments.len())?;
+
+    // the median by default
+    let level = match params.len() {
+        0 => 0.5,
+        1 => params[0].as_f64()?,
+        n => {
+            return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "{} expect to have at most one param, but got {}",
+                display_name, n
+            )))
+        }
+    };
+    if !(0.0..=1.0).contains(&level) {
+        return Err(ErrorCode::BadArguments(format!(
+            "The level of {} must be between 0 and 1, but got {}",
+            display_name, level
+        )));
+    }
+
+    let data_type = arguments[0].data_type();
+    with_match_primitive_type!(data_type, |$T| {
+        AggregateQuantileFunction::<$T>::try_create(display_name, level)
+    },
+
+    {
+        Err(ErrorCode::BadDataValueType(format!(
+            "AggregateQuantileFunction does not support type '{:?}'",
+            data_type
+        )))
+    })
+}
+
+pub fn aggregate_quantile_function_desc() -> AggregateFunctionDescription {
+    AggregateFunctionDescription::creator(Box::new(try_create_aggregate_quantile_function))
+}
@@ -18,9 +18,11 @@ use crate::aggregates::aggregate_avg::aggregate_avg_function_desc;
 use crate::aggregates::aggregate_function_factory::AggregateFunctionFactory;
 use crate::aggregates::aggregate_min_max::aggregate_max_function_desc;
 use crate::aggregates::aggregate_min_max::aggregate_min_function_desc;
+use crate::aggregates::aggregate_quantile::aggregate_quantile_function_desc;
 use crate::aggregates::aggregate_stddev_pop::aggregate_stddev_pop_function_desc;
 use crate::aggregates::aggregate_sum::aggregate_sum_function_desc;
 use crate::aggregates::aggregate_window_funnel::aggregate_window_funnel_function_desc;
+use crate::aggregates::AggregateApproxCountDistinctFunction;
 use crate::aggregates::AggregateCountFunction;
 use crate::aggregates::AggregateDistinctCombinator;
 use crate::aggregates::AggregateIfCombinator;
@@ -40,8 +42,13 @@ impl Aggregators {
         factory.register("std", aggregate_stddev_pop_function_desc());
         factory.register("stddev", aggregate_stddev_pop_function_desc());
         factory.register("stddev_pop", aggregate_stddev_pop_function_desc());
+        factory.register("quantile", aggregate_quantile_function_desc());
         factory.register("windowFunnel", aggregate_window_funnel_function_desc());
         factory.register("uniq", AggregateDistinctCombinator::uniq_desc());
+        factory.register(
+            "approx_count_distinct",
+            AggregateApproxCountDistinctFunction::desc(),
+        );
     }
 
     pub fn register_combinator(factory: &mut AggregateFunctionFactory) {
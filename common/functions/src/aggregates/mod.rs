@@ -17,6 +17,7 @@ mod aggregate_combinator_test;
 #[cfg(test)]
 mod aggregate_function_test;
 
+mod aggregate_approx_count_distinct;
 mod aggregate_arg_min_max;
 mod aggregate_avg;
 mod aggregate_combinator_distinct;
@@ -26,6 +27,7 @@ mod aggregate_function;
 mod aggregate_function_factory;
 mod aggregate_function_state;
 mod aggregate_min_max;
+mod aggregate_quantile;
 mod aggregate_window_funnel;
 
 // mod aggregate_min_max;
@@ -37,6 +39,7 @@ mod aggregator_common;
 #[macro_use]
 mod macros;
 
+pub use aggregate_approx_count_distinct::AggregateApproxCountDistinctFunction;
 pub use aggregate_arg_min_max::AggregateArgMinMaxFunction;
 pub use aggregate_avg::AggregateAvgFunction;
 pub use aggregate_combinator_distinct::AggregateDistinctCombinator;
@@ -49,6 +52,7 @@ pub use aggregate_function_state::get_layout_offsets;
 pub use aggregate_function_state::StateAddr;
 pub use aggregate_function_state::StateAddrs;
 pub use aggregate_min_max::AggregateMinMaxFunction;
+pub use aggregate_quantile::AggregateQuantileFunction;
 pub use aggregate_stddev_pop::AggregateStddevPopFunction;
 pub use aggregate_sum::AggregateSumFunction;
 pub use aggregator::Aggregators;
@@ -67,6 +67,62 @@ fn test_to_start_of_function() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_date_time_functions_with_timezone() -> Result<()> {
+    // 2021-08-31 16:30:00 UTC, it is 2021-09-01 00:30:00 in Asia/Shanghai
+    let time = 1630427400u32;
+    let tests: Vec<(Box<dyn Function>, &str, DataColumn)> = vec![
+        (
+            ToYYYYMMFunction::try_create("toYYYYMM")?,
+            "UTC",
+            Series::new(vec![202108u32]).into(),
+        ),
+        (
+            ToYYYYMMFunction::try_create("toYYYYMM")?,
+            "Asia/Shanghai",
+            Series::new(vec![202109u32]).into(),
+        ),
+        (
+            ToDayOfMonthFunction::try_create("toDayOfMonth")?,
+            "Asia/Shanghai",
+            Series::new(vec![1u8]).into(),
+        ),
+        (
+            ToStartOfMonthFunction::try_create("toStartOfMonth")?,
+            "Asia/Shanghai",
+            Series::new(vec![18871u16]).into(),
+        ),
+        (
+            RoundFunction::try_create("toStartOfDay", 60 * 60 * 24)?,
+            "UTC",
+            Series::new(vec![1630368000u32]).into(),
+        ),
+        (
+            RoundFunction::try_create("toStartOfDay", 60 * 60 * 24)?,
+            "Asia/Shanghai",
+            Series::new(vec![1630425600u32]).into(),
+        ),
+        // 22:00:00 in Asia/Kolkata, whose offset is +05:30
+        (
+            RoundFunction::try_create("toStartOfHour", 60 * 60)?,
+            "Asia/Kolkata",
+            Series::new(vec![1630427400u32]).into(),
+        ),
+    ];
+
+    for (func, tz, expect) in tests {
+        let field = DataField::new("a", DataType::DateTime32(Some(tz.to_string())), false);
+        let columns = vec![DataColumnWithField::new(
+            Series::new(vec![time]).into(),
+            field,
+        )];
+        let actual = func.eval(&columns, 1)?;
+        assert_eq!(&expect, &actual, "{} in {}", func, tz);
+    }
+
+    Ok(())
+}
+
 fn do_test(t: Test) -> Result<()> {
     let dummy = DataField::new("dummy", DataType::DateTime32(None), false);
     let rows = t.columns[0].len();
@@ -21,6 +21,7 @@ use common_datavalues::chrono::TimeZone;
 use common_datavalues::chrono::Timelike;
 use common_datavalues::chrono::Utc;
 use common_datavalues::prelude::*;
+use common_datavalues::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -341,9 +342,10 @@ where
                     Ok(result.into())
                 }
             }
-            DataType::DateTime32(_) => {
+            DataType::DateTime32(tz) => {
+                let tz = parse_tz(tz)?;
                 if let DataColumn::Constant(v, _) = columns[0].column() {
-                    let date_time = Utc.timestamp(v.as_u64()? as i64, 0_u32);
+                    let date_time = local_date_time(v.as_u64()? as i64, &tz);
                     let constant_result = T::to_constant_value(date_time);
                     Ok(DataColumn::Constant(constant_result, input_rows))
                 } else {
@@ -351,7 +353,7 @@ where
                         .to_array()?
                         .u32()?
                         .apply_cast_numeric(|v| {
-                            let date_time = Utc.timestamp(v as i64, 0_u32);
+                            let date_time = local_date_time(v as i64, &tz);
                             T::to_number(date_time)
                         }
                         );
@@ -373,6 +375,22 @@ impl<T, R> fmt::Display for NumberFunction<T, R> {
     }
 }
 
+/// The timezone of a DateTime value, it is UTC if the type has none.
+pub(crate) fn parse_tz(tz: &Option<String>) -> Result<Tz> {
+    match tz {
+        None => Ok(Tz::UTC),
+        Some(tz) => tz
+            .parse::<Tz>()
+            .map_err(|_| ErrorCode::BadArguments(format!("Unknown timezone: {:?}", tz))),
+    }
+}
+
+/// The wall clock time of the timestamp in `tz`. It is returned as UTC, so that its fields,
+/// e.g. the day and the hour, are the ones in `tz`.
+pub(crate) fn local_date_time(secs: i64, tz: &Tz) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&tz.timestamp(secs, 0).naive_local())
+}
+
 fn get_day(date: DateTime<Utc>) -> u32 {
     let start: DateTime<Utc> = Utc.ymd(1970, 1, 1).and_hms(0, 0, 0);
     let duration = date.signed_duration_since(start);
@@ -14,10 +14,15 @@
 
 use std::fmt;
 
+use common_datavalues::chrono::NaiveDateTime;
+use common_datavalues::chrono::Offset;
+use common_datavalues::chrono::TimeZone;
 use common_datavalues::prelude::*;
+use common_datavalues::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use super::number_function::parse_tz;
 use crate::scalars::Function;
 
 #[derive(Clone)]
@@ -36,12 +41,16 @@ impl RoundFunction {
         Ok(Box::new(s))
     }
 
-    // TODO: (sundy-li)
-    // Consider about the timezones/offsets
-    // Currently: assuming timezone offset is a multiple of round.
+    // Rounds the wall clock time in `tz`, e.g. the start of the day is the local midnight.
     #[inline]
-    fn execute(&self, time: u32) -> u32 {
-        time / self.round * self.round
+    fn execute(&self, time: u32, tz: &Tz) -> u32 {
+        let offset = tz
+            .offset_from_utc_datetime(&NaiveDateTime::from_timestamp(time as i64, 0))
+            .fix()
+            .local_minus_utc() as i64;
+        let round = self.round as i64;
+        let local = time as i64 + offset;
+        (local.div_euclid(round) * round - offset) as u32
     }
 }
 
@@ -52,7 +61,7 @@ impl Function for RoundFunction {
 
     fn return_type(&self, args: &[DataType]) -> Result<DataType> {
         match args[0] {
-            DataType::DateTime32(_) => Ok(DataType::DateTime32(None)),
+            DataType::DateTime32(ref tz) => Ok(DataType::DateTime32(tz.clone())),
             _ => Err(ErrorCode::BadDataValueType(format!(
                 "Function {} must have a DateTime type as argument, but got {}",
                 self.display_name, args[0],
@@ -65,10 +74,15 @@ impl Function for RoundFunction {
     }
 
     fn eval(&self, columns: &DataColumnsWithField, _input_rows: usize) -> Result<DataColumn> {
+        let tz = match columns[0].data_type() {
+            DataType::DateTime32(tz) => parse_tz(tz)?,
+            _ => Tz::UTC,
+        };
+
         match columns[0].column() {
             DataColumn::Array(array) => {
                 let array = array.u32()?;
-                let arr = array.apply(|x| self.execute(x));
+                let arr = array.apply(|x| self.execute(x, &tz));
                 Ok(DataColumn::Array(arr.into_series()))
             }
             DataColumn::Constant(v, rows) => {
@@ -77,7 +91,7 @@ impl Function for RoundFunction {
                 }
                 let value = v.as_u64()?;
                 Ok(DataColumn::Constant(
-                    DataValue::UInt32(Some(self.execute(value as u32))),
+                    DataValue::UInt32(Some(self.execute(value as u32, &tz))),
                     *rows,
                 ))
             }
@@ -25,6 +25,8 @@ use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use super::number_function::local_date_time;
+use super::number_function::parse_tz;
 use crate::scalars::function_factory::FunctionDescription;
 use crate::scalars::function_factory::FunctionFeatures;
 use crate::scalars::Function;
@@ -165,9 +167,10 @@ where
                     Ok(result.into())
                 }
             },
-            DataType::DateTime32(_) => {
+            DataType::DateTime32(tz) => {
+                let tz = parse_tz(tz)?;
                 if let DataColumn::Constant(v, _) = columns[0].column() {
-                    let date_time = Utc.timestamp(v.as_i64()?, 0_u32);
+                    let date_time = local_date_time(v.as_i64()?, &tz);
                     let constant_result = T::to_constant_value(date_time, mode);
                     Ok(DataColumn::Constant(constant_result, input_rows))
                 } else {
@@ -175,7 +178,7 @@ where
                         .to_array()?
                         .u32()?
                         .apply_cast_numeric(|v| {
-                            let date_time = Utc.timestamp(v as i64, 0_u32);
+                            let date_time = local_date_time(v as i64, &tz);
                             T::to_number(date_time, mode)
                         }
                         );
@@ -16,10 +16,12 @@
 use async_trait::async_trait;
 use common_kv_api_vo::GetKVActionResult;
 use common_kv_api_vo::MGetKVActionResult;
+use common_kv_api_vo::MGetKVCheckedReply;
 use common_kv_api_vo::PrefixListReply;
 use common_kv_api_vo::UpsertKVActionResult;
 use common_metatypes::KVMeta;
 use common_metatypes::MatchSeq;
+use common_metatypes::SeqMismatch;
 
 #[async_trait]
 pub trait KVApi: Send + Sync {
@@ -29,6 +31,7 @@ pub trait KVApi: Send + Sync {
         seq: MatchSeq,
         value: Option<Vec<u8>>,
         value_meta: Option<KVMeta>,
+        content_type: Option<String>,
     ) -> common_exception::Result<UpsertKVActionResult>;
 
     async fn update_kv_meta(
@@ -43,5 +46,20 @@ pub trait KVApi: Send + Sync {
     // mockall complains about AsRef... so we use String here
     async fn mget_kv(&self, key: &[String]) -> common_exception::Result<MGetKVActionResult>;
 
+    /// Reads the keys by one `mget_kv`, with the seq of each of them checked against the
+    /// `MatchSeq` given, the stale ones get a `SeqMismatch` so that the caller can retry them.
+    async fn mget_kv_checked(
+        &self,
+        keys: &[(String, MatchSeq)],
+    ) -> common_exception::Result<MGetKVCheckedReply> {
+        let names = keys.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        let reply = self.mget_kv(&names).await?;
+        Ok(keys
+            .iter()
+            .zip(reply.result)
+            .map(|((key, seq), sv)| SeqMismatch::check(key, *seq, sv))
+            .collect())
+    }
+
     async fn prefix_list_kv(&self, prefix: &str) -> common_exception::Result<PrefixListReply>;
 }
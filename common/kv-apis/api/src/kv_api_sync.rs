@@ -19,6 +19,7 @@ use async_trait::async_trait;
 use common_base::TrySpawn;
 use common_kv_api_vo::GetKVActionResult;
 use common_kv_api_vo::MGetKVActionResult;
+use common_kv_api_vo::MGetKVCheckedReply;
 use common_kv_api_vo::PrefixListReply;
 use common_kv_api_vo::UpsertKVActionResult;
 use common_metatypes::KVMeta;
@@ -37,11 +38,15 @@ where Self: Clone + 'static
         seq: MatchSeq,
         value: Option<Vec<u8>>,
         value_meta: Option<KVMeta>,
+        content_type: Option<String>,
     ) -> common_exception::Result<UpsertKVActionResult> {
         let me = self.clone();
         let key = key.to_owned();
         STORE_RUNTIME.block_on(
-            async move { me.upsert_kv(&key, seq, value, value_meta).await },
+            async move {
+                me.upsert_kv(&key, seq, value, value_meta, content_type)
+                    .await
+            },
             STORE_SYNC_CALL_TIMEOUT.as_ref().cloned(),
         )?
     }
@@ -98,8 +103,11 @@ impl KVApi for Arc<dyn KVApi> {
         seq: MatchSeq,
         value: Option<Vec<u8>>,
         value_meta: Option<KVMeta>,
+        content_type: Option<String>,
     ) -> common_exception::Result<UpsertKVActionResult> {
-        self.as_ref().upsert_kv(key, seq, value, value_meta).await
+        self.as_ref()
+            .upsert_kv(key, seq, value, value_meta, content_type)
+            .await
     }
 
     async fn update_kv_meta(
@@ -119,6 +127,13 @@ impl KVApi for Arc<dyn KVApi> {
         self.as_ref().mget_kv(key).await
     }
 
+    async fn mget_kv_checked(
+        &self,
+        keys: &[(String, MatchSeq)],
+    ) -> common_exception::Result<MGetKVCheckedReply> {
+        self.as_ref().mget_kv_checked(keys).await
+    }
+
     async fn prefix_list_kv(&self, prefix: &str) -> common_exception::Result<PrefixListReply> {
         self.as_ref().prefix_list_kv(prefix).await
     }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_metatypes::KVValue;
+use common_metatypes::SeqMismatch;
 use common_metatypes::SeqValue;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -34,3 +35,6 @@ pub struct MGetKVActionResult {
 }
 
 pub type PrefixListReply = Vec<(String, SeqValue<KVValue>)>;
+
+/// The values of the keys read by `mget_kv_checked`, or the mismatches of the stale ones.
+pub type MGetKVCheckedReply = Vec<Result<Option<SeqValue<KVValue>>, SeqMismatch>>;
@@ -24,6 +24,7 @@ use common_kv_api::KVApi;
 use common_kv_api_util::STORE_RUNTIME;
 use common_kv_api_vo::GetKVActionResult;
 use common_kv_api_vo::MGetKVActionResult;
+use common_kv_api_vo::MGetKVCheckedReply;
 use common_kv_api_vo::PrefixListReply;
 use common_kv_api_vo::UpsertKVActionResult;
 use common_metatypes::Cmd;
@@ -107,12 +108,14 @@ impl KVApi for KV {
         seq: MatchSeq,
         value: Option<Vec<u8>>,
         value_meta: Option<KVMeta>,
+        content_type: Option<String>,
     ) -> Result<UpsertKVActionResult> {
         let cmd = Cmd::UpsertKV {
             key: key.to_string(),
             seq,
             value: value.into(),
             value_meta,
+            content_type,
         };
 
         let mut sm = self.inner.lock().await;
@@ -137,6 +140,7 @@ impl KVApi for KV {
             seq,
             value: Operation::AsIs,
             value_meta,
+            content_type: None,
         };
 
         let mut sm = self.inner.lock().await;
@@ -162,6 +166,11 @@ impl KVApi for KV {
         Ok(MGetKVActionResult { result: res })
     }
 
+    async fn mget_kv_checked(&self, keys: &[(String, MatchSeq)]) -> Result<MGetKVCheckedReply> {
+        let sm = self.inner.lock().await;
+        sm.mget_kv_checked(keys)
+    }
+
     async fn prefix_list_kv(&self, prefix: &str) -> Result<PrefixListReply> {
         let sm = self.inner.lock().await;
         let res = sm.prefix_list_kv(prefix)?;
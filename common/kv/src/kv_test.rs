@@ -25,6 +25,7 @@ use common_kv_api_vo::UpsertKVActionResult;
 use common_metatypes::KVMeta;
 use common_metatypes::KVValue;
 use common_metatypes::MatchSeq;
+use common_metatypes::SeqMismatch;
 use common_sled_store::init_temp_sled_db;
 use common_tracing::tracing;
 
@@ -49,6 +50,7 @@ async fn test_kv_async_api() -> Result<()> {
             MatchSeq::Any,
             Some(b"upsert-value".to_vec()),
             None,
+            None,
         )
         .await?;
 
@@ -58,6 +60,7 @@ async fn test_kv_async_api() -> Result<()> {
             result: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             }))
         },
         res
@@ -80,10 +83,12 @@ async fn test_kv_async_api() -> Result<()> {
             prev: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
             result: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             }))
         },
         res,
@@ -107,12 +112,14 @@ async fn test_kv_async_api() -> Result<()> {
             prev: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
             result: Some((2, KVValue {
                 meta: Some(KVMeta {
                     expire_at: Some(now + 20)
                 }),
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
         },
         res
@@ -128,6 +135,7 @@ async fn test_kv_async_api() -> Result<()> {
                     expire_at: Some(now + 20)
                 }),
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
         },
         res
@@ -141,6 +149,7 @@ async fn test_kv_async_api() -> Result<()> {
             MatchSeq::Any,
             Some(b"upsert-value-2".to_vec()),
             None,
+            None,
         )
         .await?;
 
@@ -160,10 +169,12 @@ async fn test_kv_async_api() -> Result<()> {
                         expire_at: Some(now + 20)
                     }),
                     value: b"upsert-value".to_vec(),
+                    content_type: None,
                 })),
                 Some((3, KVValue {
                     meta: None,
                     value: b"upsert-value-2".to_vec(),
+                    content_type: None,
                 })),
                 None
             ]
@@ -171,6 +182,41 @@ async fn test_kv_async_api() -> Result<()> {
         res
     );
 
+    tracing::info!("--- mget_kv_checked");
+
+    let res = api
+        .mget_kv_checked(&[
+            ("upsert-key".to_string(), MatchSeq::Exact(2)),
+            ("upsert-key-2".to_string(), MatchSeq::Exact(1)),
+            ("nonexistent".to_string(), MatchSeq::Exact(0)),
+            ("upsert-key-2".to_string(), MatchSeq::GE(4)),
+        ])
+        .await?;
+
+    assert_eq!(
+        vec![
+            Ok(Some((2, KVValue {
+                meta: Some(KVMeta {
+                    expire_at: Some(now + 20)
+                }),
+                value: b"upsert-value".to_vec(),
+                content_type: None,
+            }))),
+            Err(SeqMismatch {
+                key: "upsert-key-2".to_string(),
+                want: MatchSeq::Exact(1),
+                got: 3,
+            }),
+            Ok(None),
+            Err(SeqMismatch {
+                key: "upsert-key-2".to_string(),
+                want: MatchSeq::GE(4),
+                got: 3,
+            }),
+        ],
+        res
+    );
+
     tracing::info!("--- prefix_list_kv");
 
     let res = api.prefix_list_kv("upsert-key-").await?;
@@ -180,6 +226,7 @@ async fn test_kv_async_api() -> Result<()> {
             (3, KVValue {
                 meta: None,
                 value: b"upsert-value-2".to_vec(),
+                content_type: None,
             })
         )],
         res
@@ -206,6 +253,7 @@ fn test_kv_sync_api() -> Result<()> {
         MatchSeq::Any,
         Some(b"upsert-value".to_vec()),
         None,
+        None,
     )?;
 
     assert_eq!(
@@ -214,6 +262,7 @@ fn test_kv_sync_api() -> Result<()> {
             result: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             }))
         },
         res
@@ -234,10 +283,12 @@ fn test_kv_sync_api() -> Result<()> {
             prev: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
             result: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             }))
         },
         res,
@@ -259,12 +310,14 @@ fn test_kv_sync_api() -> Result<()> {
             prev: Some((1, KVValue {
                 meta: None,
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
             result: Some((2, KVValue {
                 meta: Some(KVMeta {
                     expire_at: Some(now + 20)
                 }),
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
         },
         res
@@ -280,6 +333,7 @@ fn test_kv_sync_api() -> Result<()> {
                     expire_at: Some(now + 20)
                 }),
                 value: b"upsert-value".to_vec(),
+                content_type: None,
             })),
         },
         res
@@ -292,6 +346,7 @@ fn test_kv_sync_api() -> Result<()> {
         MatchSeq::Any,
         Some(b"upsert-value-2".to_vec()),
         None,
+        None,
     )?;
 
     let res = api.sync_mget_kv(&[
@@ -308,10 +363,12 @@ fn test_kv_sync_api() -> Result<()> {
                         expire_at: Some(now + 20)
                     }),
                     value: b"upsert-value".to_vec(),
+                    content_type: None,
                 })),
                 Some((3, KVValue {
                     meta: None,
                     value: b"upsert-value-2".to_vec(),
+                    content_type: None,
                 })),
                 None
             ]
@@ -328,6 +385,7 @@ fn test_kv_sync_api() -> Result<()> {
             (3, KVValue {
                 meta: None,
                 value: b"upsert-value-2".to_vec(),
+                content_type: None,
             })
         )],
         res
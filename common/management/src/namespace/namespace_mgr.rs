@@ -13,10 +13,8 @@
 // limitations under the License.
 //
 
-use std::ops::Add;
 use std::sync::Arc;
 use std::time::Duration;
-use std::time::UNIX_EPOCH;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -116,15 +114,7 @@ impl NamespaceMgr {
     }
 
     fn new_lift_time(&self) -> KVMeta {
-        let now = std::time::SystemTime::now();
-        let expire_at = now
-            .add(self.lift_time)
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards");
-
-        KVMeta {
-            expire_at: Some(expire_at.as_secs()),
-        }
+        KVMeta::with_ttl(self.lift_time, std::time::SystemTime::now())
     }
 }
 
@@ -140,7 +130,7 @@ impl NamespaceApi for NamespaceMgr {
             self.namespace_prefix,
             Self::escape_for_key(&node.id)?
         );
-        let upsert_node = self.kv_api.upsert_kv(&node_key, seq, value, meta);
+        let upsert_node = self.kv_api.upsert_kv(&node_key, seq, value, meta, None);
 
         match upsert_node.await? {
             UpsertKVActionResult {
@@ -184,7 +174,9 @@ impl NamespaceApi for NamespaceMgr {
             self.namespace_prefix,
             Self::escape_for_key(&node_id)?
         );
-        let upsert_node = self.kv_api.upsert_kv(&node_key, seq.into(), None, None);
+        let upsert_node = self
+            .kv_api
+            .upsert_kv(&node_key, seq.into(), None, None, None);
 
         match upsert_node.await? {
             UpsertKVActionResult {
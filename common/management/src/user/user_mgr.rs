@@ -54,7 +54,7 @@ impl UserMgrApi for UserMgr {
 
         let res = self
             .kv_api
-            .sync_upsert_kv(&key, match_seq, Some(value), None)?;
+            .sync_upsert_kv(&key, match_seq, Some(value), None, None)?;
 
         match res {
             UpsertKVActionResult {
@@ -136,7 +136,7 @@ impl UserMgrApi for UserMgr {
         };
         let res = self
             .kv_api
-            .sync_upsert_kv(&key, match_seq, Some(value), None)?;
+            .sync_upsert_kv(&key, match_seq, Some(value), None, None)?;
         match res.result {
             Some((s, _)) => Ok(Some(s)),
             None => Err(ErrorCode::UnknownUser(format!(
@@ -148,7 +148,9 @@ impl UserMgrApi for UserMgr {
 
     fn drop_user(&self, username: String, seq: Option<u64>) -> Result<()> {
         let key = format!("{}/{}", self.user_prefix, username);
-        let res = self.kv_api.sync_upsert_kv(&key, seq.into(), None, None)?;
+        let res = self
+            .kv_api
+            .sync_upsert_kv(&key, seq.into(), None, None, None)?;
         if res.prev.is_some() && res.result.is_none() {
             Ok(())
         } else {
@@ -42,7 +42,8 @@ mock! {
             key: &str,
             seq: MatchSeq,
             value: Option<Vec<u8>>,
-            value_meta: Option<KVMeta>
+            value_meta: Option<KVMeta>,
+            content_type: Option<String>
         ) -> common_exception::Result<UpsertKVActionResult>;
 
         async fn update_kv_meta(
@@ -93,9 +94,10 @@ mod add {
                     predicate::eq(test_seq),
                     predicate::eq(value.clone()),
                     predicate::eq(None),
+                    predicate::eq(None),
                 )
                 .times(1)
-                .return_once(|_u, _s, _salt, _meta| {
+                .return_once(|_u, _s, _salt, _meta, _content_type| {
                     Ok(UpsertKVActionResult {
                         prev: None,
                         result: None,
@@ -121,13 +123,15 @@ mod add {
                     predicate::eq(test_seq),
                     predicate::eq(value.clone()),
                     predicate::eq(None),
+                    predicate::eq(None),
                 )
                 .times(1)
-                .returning(|_u, _s, _salt, _meta| {
+                .returning(|_u, _s, _salt, _meta, _content_type| {
                     Ok(UpsertKVActionResult {
                         prev: Some((1, KVValue {
                             meta: None,
                             value: vec![],
+                            content_type: None,
                         })),
                         result: None,
                     })
@@ -159,9 +163,10 @@ mod add {
                     predicate::eq(test_seq),
                     predicate::eq(value.clone()),
                     predicate::eq(None),
+                    predicate::eq(None),
                 )
                 .times(1)
-                .returning(|_u, _s, _salt, _meta| {
+                .returning(|_u, _s, _salt, _meta, _content_type| {
                     Ok(UpsertKVActionResult {
                         prev: None,
                         result: None,
@@ -210,7 +215,11 @@ mod get {
             .times(1)
             .return_once(move |_k| {
                 Ok(GetKVActionResult {
-                    result: Some((1, KVValue { meta: None, value })),
+                    result: Some((1, KVValue {
+                        meta: None,
+                        value,
+                        content_type: None,
+                    })),
                 })
             });
 
@@ -240,7 +249,11 @@ mod get {
             .times(1)
             .return_once(move |_k| {
                 Ok(GetKVActionResult {
-                    result: Some((100, KVValue { meta: None, value })),
+                    result: Some((100, KVValue {
+                        meta: None,
+                        value,
+                        content_type: None,
+                    })),
                 })
             });
 
@@ -284,6 +297,7 @@ mod get {
                     result: Some((1, KVValue {
                         meta: None,
                         value: vec![],
+                        content_type: None,
                     })),
                 })
             });
@@ -310,6 +324,7 @@ mod get {
                     result: Some((1, KVValue {
                         meta: None,
                         value: vec![1],
+                        content_type: None,
                     })),
                 })
             });
@@ -350,6 +365,7 @@ mod get_users {
                 (i, KVValue {
                     meta: None,
                     value: serde_json::to_vec(&user_info)?,
+                    content_type: None,
                 }),
             ));
             user_infos.push((i, user_info));
@@ -387,6 +403,7 @@ mod get_users {
                 (0, KVValue {
                     meta: None,
                     value: b"some arbitrary str".to_vec(),
+                    content_type: None,
                 }),
             ),
         );
@@ -427,13 +444,15 @@ mod drop {
                 predicate::eq(MatchSeq::Any),
                 predicate::eq(None),
                 predicate::eq(None),
+                predicate::eq(None),
             )
             .times(1)
-            .returning(|_k, _seq, _none, _meta| {
+            .returning(|_k, _seq, _none, _meta, _content_type| {
                 Ok(UpsertKVActionResult {
                     prev: Some((1, KVValue {
                         meta: None,
                         value: vec![],
+                        content_type: None,
                     })),
                     result: None,
                 })
@@ -456,9 +475,10 @@ mod drop {
                 predicate::eq(MatchSeq::Any),
                 predicate::eq(None),
                 predicate::eq(None),
+                predicate::eq(None),
             )
             .times(1)
-            .returning(|_k, _seq, _none, _meta| {
+            .returning(|_k, _seq, _none, _meta, _content_type| {
                 Ok(UpsertKVActionResult {
                     prev: None,
                     result: None,
@@ -505,6 +525,7 @@ mod update {
                         result: Some((0, KVValue {
                             meta: None,
                             value: prev_value,
+                            content_type: None,
                         })),
                     })
                 });
@@ -526,14 +547,16 @@ mod update {
                 predicate::eq(MatchSeq::GE(1)),
                 predicate::eq(Some(new_value_with_old_salt)),
                 predicate::eq(None),
+                predicate::eq(None),
             )
             .times(1)
-            .return_once(|_, _, _, _meta| {
+            .return_once(|_, _, _, _meta, _content_type| {
                 Ok(UpsertKVActionResult {
                     prev: None,
                     result: Some((0, KVValue {
                         meta: None,
                         value: vec![],
+                        content_type: None,
                     })),
                 })
             });
@@ -578,14 +601,16 @@ mod update {
                 predicate::eq(MatchSeq::GE(1)),
                 predicate::eq(Some(new_value)),
                 predicate::eq(None),
+                predicate::eq(None),
             )
             .times(1)
-            .return_once(|_, _, _, _meta| {
+            .return_once(|_, _, _, _meta, _content_type| {
                 Ok(UpsertKVActionResult {
                     prev: None,
                     result: Some((0, KVValue {
                         meta: None,
                         value: vec![],
+                        content_type: None,
                     })),
                 })
             });
@@ -663,9 +688,10 @@ mod update {
                 predicate::eq(MatchSeq::GE(1)),
                 predicate::always(), // a little bit relax here, as we've covered it before
                 predicate::eq(None),
+                predicate::eq(None),
             )
             .times(1)
-            .returning(|_u, _s, _salt, _meta| {
+            .returning(|_u, _s, _salt, _meta, _content_type| {
                 Ok(UpsertKVActionResult {
                     prev: None,
                     result: None,
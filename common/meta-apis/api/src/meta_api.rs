@@ -22,6 +22,7 @@ use common_meta_api_vo::GetTablesReply;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
@@ -45,6 +46,8 @@ pub trait MetaApi: Send + Sync {
 
     async fn drop_table(&self, plan: DropTablePlan) -> Result<()>;
 
+    async fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> Result<()>;
+
     async fn get_table(&self, db: &str, table: &str) -> Result<TableInfo>;
 
     async fn get_tables(&self, db: &str) -> Result<GetTablesReply>;
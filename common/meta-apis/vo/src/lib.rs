@@ -14,7 +14,12 @@
 //
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -27,6 +32,7 @@ pub struct DatabaseInfo {
     pub database_id: u64,
     pub db: String,
     pub engine: String,
+    pub options: HashMap<String, String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -38,10 +44,49 @@ pub struct CreateTableReply {
 pub struct TableInfo {
     pub table_id: u64,
     pub db: String,
+    /// `table` is the name of it in a read plan serialized before it has a table info.
+    #[serde(alias = "table")]
     pub name: String,
     pub schema: DataSchemaRef,
+    #[serde(default)]
     pub engine: String,
+    #[serde(default)]
     pub options: HashMap<String, String>,
+    /// Serialized in RFC3339, the unix epoch if unknown, e.g. of the system tables.
+    #[serde(default = "unix_epoch")]
+    pub created_on: DateTime<Utc>,
+    #[serde(default = "unix_epoch")]
+    pub updated_on: DateTime<Utc>,
+}
+
+fn unix_epoch() -> DateTime<Utc> {
+    Utc.timestamp(0, 0)
+}
+
+impl TableInfo {
+    pub fn simple(db: &str, name: &str, schema: DataSchemaRef) -> TableInfo {
+        TableInfo {
+            db: db.to_string(),
+            name: name.to_string(),
+            schema,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for TableInfo {
+    fn default() -> Self {
+        TableInfo {
+            table_id: 0,
+            db: "".to_string(),
+            name: "".to_string(),
+            schema: Arc::new(DataSchema::empty()),
+            engine: "".to_string(),
+            options: HashMap::new(),
+            created_on: unix_epoch(),
+            updated_on: unix_epoch(),
+        }
+    }
 }
 
 pub type GetDatabasesReply = Vec<DatabaseInfo>;
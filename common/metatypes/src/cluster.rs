@@ -22,7 +22,7 @@ use serde::Serialize;
 /// A slot is a virtual and intermediate allocation unit in a distributed storage.
 /// The key of an object is mapped to a slot by some hashing algo.
 /// A slot is assigned to several physical servers(normally 3 for durability).
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Slot {
     pub node_ids: Vec<NodeId>,
 }
@@ -31,6 +31,18 @@ pub struct Slot {
 pub struct Node {
     pub name: String,
     pub address: String,
+
+    /// The relative capacity of the node, a node is assigned slots in proportion to it.
+    /// 0, the default, is taken as 1, e.g., by the nodes added before the weight is introduced.
+    #[serde(default)]
+    pub weight: u64,
+}
+
+impl Node {
+    /// The weight used by placement, which is at least 1.
+    pub fn placement_weight(&self) -> u64 {
+        self.weight.max(1)
+    }
 }
 
 impl fmt::Display for Node {
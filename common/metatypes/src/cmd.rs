@@ -15,6 +15,8 @@
 use std::fmt;
 
 use async_raft::NodeId;
+use chrono::DateTime;
+use chrono::Utc;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -38,9 +40,16 @@ pub enum Cmd {
     /// Increment the sequence number generator specified by `key` and returns the new value.
     IncrSeq { key: String },
 
+    /// Increment the sequence number generator specified by `key` by `delta` and returns the new value.
+    /// The ids in range `[new - delta + 1, new]` are reserved by the caller.
+    IncrSeqBy { key: String, delta: u64 },
+
     /// Add node if absent
     AddNode { node_id: NodeId, node: Node },
 
+    /// Reassign slots to the current nodes, e.g., after a node joins or leaves.
+    Rebalance,
+
     /// Add a database if absent
     CreateDatabase {
         // TODO(ariesdevil): add `seq` for distinguish between the results of the execution of
@@ -76,6 +85,16 @@ pub enum Cmd {
         if_exists: bool,
     },
 
+    /// Replace the schema of a table, e.g. to add a column
+    UpdateTableSchema {
+        db_name: String,
+        table_name: String,
+        /// serialized schema
+        schema: Vec<u8>,
+        /// when the schema is replaced, stamped by the proposer so that every replica applies the same
+        updated_on: DateTime<Utc>,
+    },
+
     /// Update or insert a general purpose kv store
     UpsertKV {
         key: String,
@@ -91,6 +110,11 @@ pub enum Cmd {
 
         /// Meta data of a value.
         value_meta: Option<KVMeta>,
+
+        /// The encoding of the value, e.g. "json", stored along with it for the readers.
+        /// With `Operation::AsIs`, a `None` keeps the content type of the current value.
+        #[serde(default)]
+        content_type: Option<String>,
     },
 }
 
@@ -106,9 +130,15 @@ impl fmt::Display for Cmd {
             Cmd::IncrSeq { key } => {
                 write!(f, "incr_seq:{}", key)
             }
+            Cmd::IncrSeqBy { key, delta } => {
+                write!(f, "incr_seq:{}+{}", key, delta)
+            }
             Cmd::AddNode { node_id, node } => {
                 write!(f, "add_node:{}={}", node_id, node)
             }
+            Cmd::Rebalance => {
+                write!(f, "rebalance")
+            }
             Cmd::CreateDatabase {
                 name,
                 if_not_exists,
@@ -146,16 +176,24 @@ impl fmt::Display for Cmd {
                     db_name, table_name, if_exists
                 )
             }
+            Cmd::UpdateTableSchema {
+                db_name,
+                table_name,
+                ..
+            } => {
+                write!(f, "update_table_schema:{}-{}", db_name, table_name)
+            }
             Cmd::UpsertKV {
                 key,
                 seq,
                 value,
                 value_meta,
+                content_type,
             } => {
                 write!(
                     f,
-                    "upsert_kv: {}({:?}) = {:?} ({:?})",
-                    key, seq, value, value_meta
+                    "upsert_kv: {}({:?}) = {:?} ({:?}, {:?})",
+                    key, seq, value, value_meta, content_type
                 )
             }
         }
@@ -16,8 +16,37 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::MatchSeq;
+use crate::MatchSeqExt;
+use crate::SeqValue;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum ConflictSeq {
     NotMatch { want: MatchSeq, got: u64 },
 }
+
+/// The seq of a key read does not match the one the reader expects, i.e. the read is stale.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SeqMismatch {
+    pub key: String,
+    pub want: MatchSeq,
+    /// The seq of the current value, 0 if the key does not exist.
+    pub got: u64,
+}
+
+impl SeqMismatch {
+    /// Checks the value `sv` of `key` read against `want`.
+    pub fn check<U>(
+        key: &str,
+        want: MatchSeq,
+        sv: Option<SeqValue<U>>,
+    ) -> Result<Option<SeqValue<U>>, SeqMismatch> {
+        match want.match_seq(&sv) {
+            Ok(_) => Ok(sv),
+            Err(ConflictSeq::NotMatch { want, got }) => Err(SeqMismatch {
+                key: key.to_string(),
+                want,
+                got,
+            }),
+        }
+    }
+}
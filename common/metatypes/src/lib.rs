@@ -20,6 +20,9 @@ use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
+use chrono::DateTime;
+use chrono::TimeZone;
+use chrono::Utc;
 pub use cluster::Node;
 pub use cluster::Slot;
 pub use cmd::Cmd;
@@ -27,6 +30,7 @@ pub use common_sled_store::KVMeta;
 pub use common_sled_store::KVValue;
 pub use common_sled_store::SeqValue;
 pub use errors::ConflictSeq;
+pub use errors::SeqMismatch;
 pub use log_entry::LogEntry;
 pub use match_seq::MatchSeq;
 pub use match_seq::MatchSeqExt;
@@ -57,6 +61,10 @@ pub struct Database {
     /// engine name of db
     pub database_engine: String,
 
+    /// options passed to the engine of db
+    #[serde(default)]
+    pub database_options: HashMap<String, String>,
+
     /// tables belong to this database.
     pub tables: HashMap<String, u64>,
 }
@@ -67,7 +75,7 @@ impl fmt::Display for Database {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Table {
     pub table_id: u64,
 
@@ -82,6 +90,33 @@ pub struct Table {
 
     /// name of parts that belong to this table.
     pub parts: HashSet<String>,
+
+    /// when the table is created, in RFC3339 when serialized.
+    /// The tables persisted before the timestamps were added are loaded with the unix epoch.
+    #[serde(default = "unix_epoch")]
+    pub created_on: DateTime<Utc>,
+
+    /// when the table is created or altered the last time, in RFC3339 when serialized.
+    #[serde(default = "unix_epoch")]
+    pub updated_on: DateTime<Utc>,
+}
+
+fn unix_epoch() -> DateTime<Utc> {
+    Utc.timestamp(0, 0)
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table {
+            table_id: 0,
+            schema: vec![],
+            table_engine: "".to_string(),
+            table_options: HashMap::new(),
+            parts: HashSet::new(),
+            created_on: unix_epoch(),
+            updated_on: unix_epoch(),
+        }
+    }
 }
 
 impl fmt::Display for Table {
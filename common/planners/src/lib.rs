@@ -17,6 +17,8 @@ mod plan_aggregator_test;
 #[cfg(test)]
 mod plan_builder_test;
 #[cfg(test)]
+mod plan_cost_test;
+#[cfg(test)]
 mod plan_describe_table_test;
 #[cfg(test)]
 mod plan_display_test;
@@ -35,6 +37,8 @@ mod plan_limit_test;
 #[cfg(test)]
 mod plan_projection_test;
 #[cfg(test)]
+mod plan_read_datasource_test;
+#[cfg(test)]
 mod plan_rewriter_test;
 #[cfg(test)]
 mod plan_scan_test;
@@ -48,6 +52,7 @@ mod plan_aggregator_partial;
 mod plan_broadcast;
 mod plan_builder;
 mod plan_builder_scan;
+mod plan_cost;
 mod plan_database_create;
 mod plan_database_drop;
 mod plan_describe_table;
@@ -82,13 +87,17 @@ mod plan_rewriter;
 mod plan_scan;
 mod plan_select;
 mod plan_setting;
+mod plan_show_databases;
 mod plan_show_table_create;
+mod plan_show_tables;
 mod plan_sort;
 mod plan_stage;
 mod plan_statistics;
 mod plan_subqueries_set;
+mod plan_table_alter;
 mod plan_table_create;
 mod plan_table_drop;
+mod plan_table_optimize;
 mod plan_truncate_table;
 mod plan_use_database;
 mod plan_visitor;
@@ -98,6 +107,7 @@ pub use plan_aggregator_partial::AggregatorPartialPlan;
 pub use plan_broadcast::BroadcastPlan;
 pub use plan_builder::PlanBuilder;
 pub use plan_builder_scan::TableScanInfo;
+pub use plan_cost::PlanCost;
 pub use plan_database_create::CreateDatabasePlan;
 pub use plan_database_create::DatabaseOptions;
 pub use plan_database_drop::DropDatabasePlan;
@@ -152,15 +162,20 @@ pub use plan_scan::ScanPlan;
 pub use plan_select::SelectPlan;
 pub use plan_setting::SettingPlan;
 pub use plan_setting::VarValue;
+pub use plan_show_databases::ShowDatabasesPlan;
 pub use plan_show_table_create::ShowCreateTablePlan;
+pub use plan_show_tables::ShowTablesPlan;
 pub use plan_sort::SortPlan;
 pub use plan_stage::StageKind;
 pub use plan_stage::StagePlan;
+pub use plan_statistics::ColumnStatistics;
 pub use plan_statistics::Statistics;
 pub use plan_subqueries_set::SubQueriesSetPlan;
+pub use plan_table_alter::AlterTableAddColumnPlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_drop::DropTablePlan;
+pub use plan_table_optimize::OptimizeTablePlan;
 pub use plan_truncate_table::TruncateTablePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_visitor::PlanVisitor;
@@ -0,0 +1,189 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::ColumnStatistics;
+use crate::Expression;
+use crate::PlanNode;
+
+/// Selectivity of a predicate that we know nothing about.
+const DEFAULT_SELECTIVITY: f64 = 0.1;
+/// Selectivity of a range predicate, such as `a > 1`.
+const RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// The estimated output cardinality of a plan node, and the relative cost to produce it.
+///
+/// The cost is counted in rows processed by the node and all of its inputs, so that the
+/// costs of different plans of the same query can be compared with each other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanCost {
+    pub rows: f64,
+    pub cost: f64,
+    /// The rows are exact rather than estimated.
+    pub is_exact: bool,
+    columns: HashMap<String, ColumnStatistics>,
+}
+
+impl PlanCost {
+    pub fn estimate(node: &PlanNode) -> PlanCost {
+        match node {
+            PlanNode::ReadSource(plan) => {
+                let rows = plan.statistics.read_rows as f64;
+                PlanCost {
+                    rows,
+                    cost: rows,
+                    is_exact: plan.statistics.is_exact,
+                    columns: plan.statistics.column_statistics.clone(),
+                }
+            }
+            PlanNode::Filter(plan) => Self::estimate(&plan.input).filter(&plan.predicate),
+            PlanNode::Having(plan) => Self::estimate(&plan.input).filter(&plan.predicate),
+            PlanNode::Projection(plan) => Self::estimate(&plan.input).process(),
+            PlanNode::Expression(plan) => Self::estimate(&plan.input).process(),
+            PlanNode::AggregatorPartial(plan) => {
+                Self::estimate(&plan.input).aggregate(&plan.group_expr)
+            }
+            PlanNode::AggregatorFinal(plan) => {
+                Self::estimate(&plan.input).aggregate(&plan.group_expr)
+            }
+            PlanNode::Sort(plan) => {
+                let input = Self::estimate(&plan.input);
+                let sort_cost = input.rows * input.rows.max(2.0).log2();
+                PlanCost {
+                    cost: input.cost + sort_cost,
+                    ..input
+                }
+            }
+            PlanNode::Limit(plan) => {
+                let input = Self::estimate(&plan.input);
+                match plan.n {
+                    Some(n) => {
+                        let rows = (input.rows - plan.offset as f64).max(0.0).min(n as f64);
+                        PlanCost { rows, ..input }
+                    }
+                    None => {
+                        let rows = (input.rows - plan.offset as f64).max(0.0);
+                        PlanCost { rows, ..input }
+                    }
+                }
+            }
+            other => {
+                let inputs = other.inputs();
+                match inputs.first() {
+                    Some(input) => Self::estimate(input),
+                    None => PlanCost {
+                        rows: 0.0,
+                        cost: 0.0,
+                        is_exact: true,
+                        columns: HashMap::new(),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Every input row is processed once, the cardinality is not changed.
+    fn process(self) -> PlanCost {
+        PlanCost {
+            cost: self.cost + self.rows,
+            ..self
+        }
+    }
+
+    fn filter(self, predicate: &Expression) -> PlanCost {
+        let selectivity = self.selectivity(predicate);
+        PlanCost {
+            rows: self.rows * selectivity,
+            cost: self.cost + self.rows,
+            is_exact: false,
+            columns: self.columns,
+        }
+    }
+
+    fn aggregate(self, group_by: &[Expression]) -> PlanCost {
+        let groups = group_by.iter().fold(1.0, |acc, expr| match expr {
+            Expression::Column(name) => match self.columns.get(name) {
+                Some(stats) if stats.distinct_count > 0 => acc * stats.distinct_count as f64,
+                _ => acc * self.rows,
+            },
+            _ => acc * self.rows,
+        });
+
+        PlanCost {
+            rows: groups.min(self.rows).max(1.0),
+            cost: self.cost + self.rows,
+            is_exact: group_by.is_empty(),
+            columns: self.columns,
+        }
+    }
+
+    /// The fraction of the rows satisfying the predicate.
+    fn selectivity(&self, predicate: &Expression) -> f64 {
+        match predicate {
+            Expression::Alias(_, expr) => self.selectivity(expr),
+            Expression::BinaryExpression { left, op, right } => match op.to_lowercase().as_str() {
+                "and" => self.selectivity(left) * self.selectivity(right),
+                "or" => {
+                    let (l, r) = (self.selectivity(left), self.selectivity(right));
+                    l + r - l * r
+                }
+                "=" => self.equal_selectivity(left, right),
+                "!=" | "<>" => 1.0 - self.equal_selectivity(left, right),
+                "<" | "<=" | ">" | ">=" => RANGE_SELECTIVITY,
+                _ => DEFAULT_SELECTIVITY,
+            },
+            Expression::UnaryExpression { op, expr } if op.eq_ignore_ascii_case("not") => {
+                1.0 - self.selectivity(expr)
+            }
+            Expression::ScalarFunction { op, args } if args.len() == 1 => {
+                match (op.to_lowercase().as_str(), self.null_fraction(&args[0])) {
+                    ("isnull", Some(fraction)) => fraction,
+                    ("isnotnull", Some(fraction)) => 1.0 - fraction,
+                    _ => DEFAULT_SELECTIVITY,
+                }
+            }
+            _ => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    fn equal_selectivity(&self, left: &Expression, right: &Expression) -> f64 {
+        let distinct = |expr: &Expression| match expr {
+            Expression::Column(name) => self
+                .columns
+                .get(name)
+                .map(|stats| stats.distinct_count)
+                .filter(|distinct_count| *distinct_count > 0),
+            _ => None,
+        };
+
+        match distinct(left).or_else(|| distinct(right)) {
+            Some(distinct_count) => {
+                let non_null = 1.0 - self.null_fraction(left).unwrap_or(0.0);
+                non_null / distinct_count as f64
+            }
+            None => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    fn null_fraction(&self, expr: &Expression) -> Option<f64> {
+        match expr {
+            Expression::Column(name) if self.rows > 0.0 => self
+                .columns
+                .get(name)
+                .map(|stats| (stats.null_count as f64 / self.rows).min(1.0)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+use crate::*;
+
+fn source_with_distinct(total: usize, distinct_count: u64) -> Result<PlanNode> {
+    let mut source = Test::create().generate_source_plan_for_test(total)?;
+    if let PlanNode::ReadSource(plan) = &mut source {
+        plan.statistics
+            .column_statistics
+            .insert("number".to_string(), ColumnStatistics {
+                null_count: 0,
+                distinct_count,
+                min: None,
+                max: None,
+            });
+    }
+    Ok(source)
+}
+
+#[test]
+fn test_plan_cost_scan() -> Result<()> {
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let cost = PlanCost::estimate(&source);
+    assert_eq!(cost.rows, 10000.0);
+    assert_eq!(cost.cost, 10000.0);
+    assert!(cost.is_exact);
+    Ok(())
+}
+
+#[test]
+fn test_plan_cost_filter() -> Result<()> {
+    // without column statistics, the default selectivity
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let plan = PlanBuilder::from(&source)
+        .filter(col("number").eq(lit(1i64)))?
+        .build()?;
+    let cost = PlanCost::estimate(&plan);
+    assert_eq!(cost.rows, 1000.0);
+    assert_eq!(cost.cost, 20000.0);
+    assert!(!cost.is_exact);
+
+    // with the distinct count of the column
+    let source = source_with_distinct(10000, 100)?;
+    let plan = PlanBuilder::from(&source)
+        .filter(col("number").eq(lit(1i64)))?
+        .project(&[col("number")])?
+        .build()?;
+    let cost = PlanCost::estimate(&plan);
+    assert_eq!(cost.rows, 100.0);
+    assert_eq!(cost.cost, 20100.0);
+
+    let plan = PlanBuilder::from(&source)
+        .filter(col("number").not_eq(lit(1i64)))?
+        .build()?;
+    assert_eq!(PlanCost::estimate(&plan).rows, 9900.0);
+    Ok(())
+}
+
+#[test]
+fn test_plan_cost_display() -> Result<()> {
+    let source = source_with_distinct(10000, 100)?;
+    let plan = PlanBuilder::from(&source)
+        .filter(col("number").eq(lit(1i64)))?
+        .project(&[col("number")])?
+        .build()?;
+
+    let expect ="\
+    Projection: number:UInt64 (rows: ~100, cost: 20100)\
+    \n  Filter: (number = 1) (rows: ~100, cost: 20000)\
+    \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000] (rows: 10000, cost: 10000)";
+    let actual = format!("{}", plan.display_indent_format_with_cost());
+    assert_eq!(expect, actual);
+    Ok(())
+}
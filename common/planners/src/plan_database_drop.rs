@@ -21,6 +21,8 @@ use common_datavalues::DataSchemaRef;
 pub struct DropDatabasePlan {
     pub if_exists: bool,
     pub db: String,
+    /// Drops the tables of the database as well, otherwise only an empty database can be dropped
+    pub cascade: bool,
 }
 
 impl DropDatabasePlan {
@@ -25,6 +25,10 @@ impl PlanNode {
         PlanNodeIndentFormatDisplay::create(0, self, false)
     }
 
+    pub fn display_indent_format_with_cost(&self) -> impl fmt::Display + '_ {
+        PlanNodeIndentFormatDisplay::create(0, self, false).with_cost(true)
+    }
+
     pub fn display_graphviz(&self) -> impl fmt::Display + '_ {
         struct Wrapper<'a>(&'a PlanNode);
         impl<'a> fmt::Display for Wrapper<'a> {
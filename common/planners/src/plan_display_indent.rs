@@ -18,6 +18,7 @@ use std::fmt::Formatter;
 use crate::plan_broadcast::BroadcastPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTableAddColumnPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DropDatabasePlan;
@@ -25,6 +26,7 @@ use crate::DropTablePlan;
 use crate::Expression;
 use crate::ExpressionPlan;
 use crate::LimitPlan;
+use crate::PlanCost;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
@@ -36,6 +38,7 @@ pub struct PlanNodeIndentFormatDisplay<'a> {
     indent: usize,
     node: &'a PlanNode,
     printed_indent: bool,
+    with_cost: bool,
 }
 
 impl<'a> PlanNodeIndentFormatDisplay<'a> {
@@ -44,8 +47,15 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
             indent,
             node,
             printed_indent: printed,
+            with_cost: false,
         }
     }
+
+    /// Annotates each node with its estimated rows and cost.
+    pub fn with_cost(mut self, with_cost: bool) -> Self {
+        self.with_cost = with_cost;
+        self
+    }
 }
 
 impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
@@ -71,6 +81,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::DropDatabase(plan) => Self::format_drop_database(f, plan),
             PlanNode::CreateTable(plan) => Self::format_create_table(f, plan),
             PlanNode::DropTable(plan) => Self::format_drop_table(f, plan),
+            PlanNode::AlterTableAddColumn(plan) => Self::format_alter_table_add_column(f, plan),
             _ => {
                 let mut printed = true;
 
@@ -84,6 +95,7 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
                     }
 
                     PlanNodeIndentFormatDisplay::create(self.indent, input.as_ref(), printed)
+                        .with_cost(self.with_cost)
                         .fmt(f)?;
                     printed = true;
                 }
@@ -92,6 +104,10 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
         }?;
 
+        if self.with_cost {
+            Self::format_cost(f, &PlanCost::estimate(self.node))?;
+        }
+
         let new_indent = self.indent + 1;
         for input in self.node.inputs() {
             if matches!(input.as_ref(), PlanNode::Empty(_)) {
@@ -99,7 +115,9 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
 
             writeln!(f)?;
-            PlanNodeIndentFormatDisplay::create(new_indent, &input, false).fmt(f)?;
+            PlanNodeIndentFormatDisplay::create(new_indent, &input, false)
+                .with_cost(self.with_cost)
+                .fmt(f)?;
         }
 
         fmt::Result::Ok(())
@@ -107,6 +125,15 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
 }
 
 impl<'a> PlanNodeIndentFormatDisplay<'a> {
+    fn format_cost(f: &mut Formatter, cost: &PlanCost) -> fmt::Result {
+        let approx = if cost.is_exact { "" } else { "~" };
+        write!(
+            f,
+            " (rows: {}{:.0}, cost: {:.0})",
+            approx, cost.rows, cost.cost
+        )
+    }
+
     fn format_stage(f: &mut Formatter, plan: &StagePlan) -> fmt::Result {
         write!(f, "RedistributeStage[expr: {:?}]", plan.scatters_expr)
     }
@@ -208,7 +235,7 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
             f,
             "ReadDataSource: scan partitions: [{}], scan schema: {}, statistics: [read_rows: {:?}, read_bytes: {:?}]",
             plan.parts.len(),
-            PlanNode::display_schema(plan.schema.as_ref()),
+            PlanNode::display_schema(plan.table_info.schema.as_ref()),
             plan.statistics.read_rows,
             plan.statistics.read_bytes,
         )
@@ -223,7 +250,8 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
 
     fn format_drop_database(f: &mut Formatter, plan: &DropDatabasePlan) -> fmt::Result {
         write!(f, "Drop database {:},", plan.db)?;
-        write!(f, " if_exists:{:}", plan.if_exists)
+        write!(f, " if_exists:{:},", plan.if_exists)?;
+        write!(f, " cascade:{:}", plan.cascade)
     }
 
     fn format_create_table(f: &mut Formatter, plan: &CreateTablePlan) -> fmt::Result {
@@ -239,4 +267,17 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         write!(f, "Drop table {:}.{:},", plan.db, plan.table)?;
         write!(f, " if_exists:{:}", plan.if_exists)
     }
+
+    fn format_alter_table_add_column(
+        f: &mut Formatter,
+        plan: &AlterTableAddColumnPlan,
+    ) -> fmt::Result {
+        write!(f, "Alter table {:}.{:},", plan.db, plan.table)?;
+        write!(
+            f,
+            " add column {:}:{:?}",
+            plan.field.name(),
+            plan.field.data_type()
+        )
+    }
 }
@@ -16,11 +16,16 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::Result;
 use common_infallible::Mutex;
 use common_metatypes::MetaId;
 
-type BlockStream =
-    std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
+use crate::PlanNode;
+
+// an error in the stream fails the insert, the tables store nothing of it
+type BlockStream = std::pin::Pin<
+    Box<dyn futures::stream::Stream<Item = Result<DataBlock>> + Sync + Send + 'static>,
+>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct InsertIntoPlan {
@@ -28,6 +33,8 @@ pub struct InsertIntoPlan {
     pub tbl_name: String,
     pub tbl_id: MetaId,
     pub schema: DataSchemaRef,
+    // `INSERT INTO ... SELECT`, the rows are produced by the select instead of the input stream
+    pub select_plan: Option<Box<PlanNode>>,
 
     #[serde(skip, default = "InsertIntoPlan::empty_stream")]
     pub input_stream: Arc<Mutex<Option<BlockStream>>>,
@@ -22,6 +22,7 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTableAddColumnPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
@@ -36,6 +37,7 @@ use crate::InsertIntoPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::OptimizeTablePlan;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::RemotePlan;
@@ -43,6 +45,8 @@ use crate::ScanPlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
+use crate::ShowDatabasesPlan;
+use crate::ShowTablesPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
@@ -72,13 +76,17 @@ pub enum PlanNode {
     CreateTable(CreateTablePlan),
     DescribeTable(DescribeTablePlan),
     DropTable(DropTablePlan),
+    AlterTableAddColumn(AlterTableAddColumnPlan),
     TruncateTable(TruncateTablePlan),
+    OptimizeTable(OptimizeTablePlan),
     UseDatabase(UseDatabasePlan),
     SetVariable(SettingPlan),
     InsertInto(InsertIntoPlan),
     ShowCreateTable(ShowCreateTablePlan),
     SubQueryExpression(SubQueriesSetPlan),
     Kill(KillPlan),
+    ShowDatabases(ShowDatabasesPlan),
+    ShowTables(ShowTablesPlan),
 }
 
 impl PlanNode {
@@ -105,8 +113,10 @@ impl PlanNode {
             PlanNode::DropDatabase(v) => v.schema(),
             PlanNode::CreateTable(v) => v.schema(),
             PlanNode::DropTable(v) => v.schema(),
+            PlanNode::AlterTableAddColumn(v) => v.schema(),
             PlanNode::DescribeTable(v) => v.schema(),
             PlanNode::TruncateTable(v) => v.schema(),
+            PlanNode::OptimizeTable(v) => v.schema(),
             PlanNode::SetVariable(v) => v.schema(),
             PlanNode::Sort(v) => v.schema(),
             PlanNode::UseDatabase(v) => v.schema(),
@@ -114,6 +124,8 @@ impl PlanNode {
             PlanNode::ShowCreateTable(v) => v.schema(),
             PlanNode::SubQueryExpression(v) => v.schema(),
             PlanNode::Kill(v) => v.schema(),
+            PlanNode::ShowDatabases(v) => v.schema(),
+            PlanNode::ShowTables(v) => v.schema(),
         }
     }
 
@@ -140,7 +152,9 @@ impl PlanNode {
             PlanNode::CreateTable(_) => "CreateTablePlan",
             PlanNode::DescribeTable(_) => "DescribeTablePlan",
             PlanNode::DropTable(_) => "DropTablePlan",
+            PlanNode::AlterTableAddColumn(_) => "AlterTableAddColumnPlan",
             PlanNode::TruncateTable(_) => "TruncateTablePlan",
+            PlanNode::OptimizeTable(_) => "OptimizeTablePlan",
             PlanNode::SetVariable(_) => "SetVariablePlan",
             PlanNode::Sort(_) => "SortPlan",
             PlanNode::UseDatabase(_) => "UseDatabasePlan",
@@ -148,6 +162,8 @@ impl PlanNode {
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
             PlanNode::SubQueryExpression(_) => "CreateSubQueriesSets",
             PlanNode::Kill(_) => "KillQuery",
+            PlanNode::ShowDatabases(_) => "ShowDatabasesPlan",
+            PlanNode::ShowTables(_) => "ShowTablesPlan",
         }
     }
 
@@ -18,4 +18,7 @@ pub type Partitions = Vec<Part>;
 pub struct Part {
     pub name: String,
     pub version: u64,
+    /// The names of the parts a coalesced part covers, empty if the part is not coalesced
+    #[serde(default)]
+    pub coalesced: Vec<String>,
 }
@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
+use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
 
@@ -28,12 +29,9 @@ use crate::Statistics;
 // TODO: Delete the scan plan field, but it depends on plan_parser:L394
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct ReadDataSourcePlan {
-    // TODO encapsulate these 5 fields into TableInfo
-    pub db: String,
-    pub table: String,
-    pub table_id: MetaId,
-    pub table_version: Option<MetaVersion>,
-    pub schema: DataSchemaRef,
+    // the fields of the table info are kept at the top level of the serialized plan
+    #[serde(flatten)]
+    pub table_info: TableInfo,
 
     pub parts: Partitions,
     pub statistics: Statistics,
@@ -46,13 +44,12 @@ pub struct ReadDataSourcePlan {
 }
 
 impl ReadDataSourcePlan {
-    pub fn empty(table_id: u64, table_version: Option<u64>) -> ReadDataSourcePlan {
+    pub fn empty(table_id: MetaId, table_version: Option<MetaVersion>) -> ReadDataSourcePlan {
         ReadDataSourcePlan {
-            db: "".to_string(),
-            table: "".to_string(),
-            table_id,
-            table_version,
-            schema: Arc::from(DataSchema::empty()),
+            table_info: TableInfo {
+                table_id,
+                ..TableInfo::simple("", "", Arc::from(DataSchema::empty()))
+            },
             parts: vec![],
             statistics: Statistics::default(),
             description: "".to_string(),
@@ -64,6 +61,6 @@ impl ReadDataSourcePlan {
     }
 
     pub fn schema(&self) -> DataSchemaRef {
-        self.schema.clone()
+        self.table_info.schema.clone()
     }
 }
@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::test::Test;
+use crate::*;
+
+#[test]
+fn test_read_datasource_plan_serde() -> Result<()> {
+    let plan = match Test::create().generate_source_plan_for_test(10)? {
+        PlanNode::ReadSource(plan) => plan,
+        _ => unreachable!(),
+    };
+
+    let json = serde_json::to_value(&plan)?;
+    for key in ["db", "name", "table_id", "schema", "engine", "options"] {
+        assert!(json.get(key).is_some(), "{} is not flattened", key);
+    }
+    assert!(json.get("table_info").is_none());
+
+    let deserialized: ReadDataSourcePlan = serde_json::from_value(json)?;
+    assert_eq!(plan, deserialized);
+    Ok(())
+}
+
+#[test]
+fn test_read_datasource_plan_serde_compatible() -> Result<()> {
+    let plan = match Test::create().generate_source_plan_for_test(10)? {
+        PlanNode::ReadSource(plan) => plan,
+        _ => unreachable!(),
+    };
+
+    // a plan serialized before the table info is introduced
+    let mut json = serde_json::to_value(&plan)?;
+    let fields = json.as_object_mut().unwrap();
+    for key in ["name", "engine", "options", "created_on", "updated_on"] {
+        fields.remove(key);
+    }
+    fields.insert("table".to_string(), plan.table_info.name.clone().into());
+    fields.insert("table_version".to_string(), serde_json::Value::Null);
+
+    let deserialized: ReadDataSourcePlan = serde_json::from_value(json)?;
+    let table_info = &deserialized.table_info;
+    assert_eq!(plan.table_info.db, table_info.db);
+    assert_eq!(plan.table_info.name, table_info.name);
+    assert_eq!(plan.table_info.table_id, table_info.table_id);
+    assert_eq!(plan.table_info.schema, table_info.schema);
+    assert_eq!("", table_info.engine);
+    assert!(table_info.options.is_empty());
+    assert_eq!(plan.parts, deserialized.parts);
+    assert_eq!(plan.statistics, deserialized.statistics);
+    Ok(())
+}
@@ -25,6 +25,7 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTableAddColumnPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
@@ -41,6 +42,7 @@ use crate::InsertIntoPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::OptimizeTablePlan;
 use crate::PlanBuilder;
 use crate::PlanNode;
 use crate::ProjectionPlan;
@@ -50,6 +52,8 @@ use crate::ScanPlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
+use crate::ShowDatabasesPlan;
+use crate::ShowTablesPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
@@ -100,12 +104,16 @@ pub trait PlanRewriter {
             PlanNode::Expression(plan) => self.rewrite_expression(plan),
             PlanNode::DescribeTable(plan) => self.rewrite_describe_table(plan),
             PlanNode::DropTable(plan) => self.rewrite_drop_table(plan),
+            PlanNode::AlterTableAddColumn(plan) => self.rewrite_alter_table_add_column(plan),
             PlanNode::DropDatabase(plan) => self.rewrite_drop_database(plan),
             PlanNode::InsertInto(plan) => self.rewrite_insert_into(plan),
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
             PlanNode::SubQueryExpression(plan) => self.rewrite_sub_queries_sets(plan),
             PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
+            PlanNode::OptimizeTable(plan) => self.rewrite_optimize_table(plan),
             PlanNode::Kill(plan) => self.rewrite_kill(plan),
+            PlanNode::ShowDatabases(plan) => self.rewrite_show_databases(plan),
+            PlanNode::ShowTables(plan) => self.rewrite_show_tables(plan),
         }
     }
 
@@ -332,6 +340,13 @@ pub trait PlanRewriter {
         Ok(PlanNode::DropTable(plan.clone()))
     }
 
+    fn rewrite_alter_table_add_column(
+        &mut self,
+        plan: &AlterTableAddColumnPlan,
+    ) -> Result<PlanNode> {
+        Ok(PlanNode::AlterTableAddColumn(plan.clone()))
+    }
+
     fn rewrite_drop_database(&mut self, plan: &DropDatabasePlan) -> Result<PlanNode> {
         Ok(PlanNode::DropDatabase(plan.clone()))
     }
@@ -348,9 +363,21 @@ pub trait PlanRewriter {
         Ok(PlanNode::TruncateTable(plan.clone()))
     }
 
+    fn rewrite_optimize_table(&mut self, plan: &OptimizeTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::OptimizeTable(plan.clone()))
+    }
+
     fn rewrite_kill(&mut self, plan: &KillPlan) -> Result<PlanNode> {
         Ok(PlanNode::Kill(plan.clone()))
     }
+
+    fn rewrite_show_databases(&mut self, plan: &ShowDatabasesPlan) -> Result<PlanNode> {
+        Ok(PlanNode::ShowDatabases(plan.clone()))
+    }
+
+    fn rewrite_show_tables(&mut self, plan: &ShowTablesPlan) -> Result<PlanNode> {
+        Ok(PlanNode::ShowTables(plan.clone()))
+    }
 }
 
 pub struct RewriteHelper {}
@@ -12,7 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Debug, Default)]
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Clone, Debug, Default)]
+pub struct ColumnStatistics {
+    /// Number of null values of the column.
+    pub null_count: u64,
+    /// Estimated number of distinct non-null values of the column.
+    pub distinct_count: u64,
+    /// The minimum non-null value of the column, if it is known.
+    #[serde(default)]
+    pub min: Option<DataValue>,
+    /// The maximum non-null value of the column, if it is known.
+    #[serde(default)]
+    pub max: Option<DataValue>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Clone, Debug, Default)]
 pub struct Statistics {
     /// Total rows of the query read.
     pub read_rows: usize,
@@ -20,6 +38,9 @@ pub struct Statistics {
     pub read_bytes: usize,
     /// Is the statistics exact.
     pub is_exact: bool,
+    /// Per column statistics by column name, if the table keeps them.
+    #[serde(default)]
+    pub column_statistics: HashMap<String, ColumnStatistics>,
 }
 
 impl Statistics {
@@ -28,6 +49,7 @@ impl Statistics {
             read_rows,
             read_bytes,
             is_exact: false,
+            column_statistics: HashMap::new(),
         }
     }
 
@@ -36,6 +58,7 @@ impl Statistics {
             read_rows,
             read_bytes,
             is_exact: true,
+            column_statistics: HashMap::new(),
         }
     }
 
@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-
-use common_datavalues::DataSchema;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct TruncateTablePlan {
@@ -26,6 +26,11 @@ pub struct TruncateTablePlan {
 
 impl TruncateTablePlan {
     pub fn schema(&self) -> DataSchemaRef {
-        Arc::new(DataSchema::empty())
+        // The number of rows removed, as MySQL affected rows.
+        DataSchemaRefExt::create(vec![DataField::new(
+            "affected_rows",
+            DataType::UInt64,
+            false,
+        )])
     }
 }
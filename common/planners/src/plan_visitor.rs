@@ -18,6 +18,7 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::AlterTableAddColumnPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
@@ -33,6 +34,7 @@ use crate::InsertIntoPlan;
 use crate::KillPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
+use crate::OptimizeTablePlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
@@ -41,6 +43,8 @@ use crate::ScanPlan;
 use crate::SelectPlan;
 use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
+use crate::ShowDatabasesPlan;
+use crate::ShowTablesPlan;
 use crate::SortPlan;
 use crate::StagePlan;
 use crate::TruncateTablePlan;
@@ -106,8 +110,10 @@ pub trait PlanVisitor {
             PlanNode::DropDatabase(plan) => self.visit_drop_database(plan),
             PlanNode::CreateTable(plan) => self.visit_create_table(plan),
             PlanNode::DropTable(plan) => self.visit_drop_table(plan),
+            PlanNode::AlterTableAddColumn(plan) => self.visit_alter_table_add_column(plan),
             PlanNode::DescribeTable(plan) => self.visit_describe_table(plan),
             PlanNode::TruncateTable(plan) => self.visit_truncate_table(plan),
+            PlanNode::OptimizeTable(plan) => self.visit_optimize_table(plan),
             PlanNode::UseDatabase(plan) => self.visit_use_database(plan),
             PlanNode::SetVariable(plan) => self.visit_set_variable(plan),
             PlanNode::Stage(plan) => self.visit_stage(plan),
@@ -119,6 +125,8 @@ pub trait PlanVisitor {
             PlanNode::ShowCreateTable(plan) => self.visit_show_create_table(plan),
             PlanNode::SubQueryExpression(plan) => self.visit_sub_queries_sets(plan),
             PlanNode::Kill(plan) => self.visit_kill_query(plan),
+            PlanNode::ShowDatabases(plan) => self.visit_show_databases(plan),
+            PlanNode::ShowTables(plan) => self.visit_show_tables(plan),
         }
     }
 
@@ -250,6 +258,10 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_alter_table_add_column(&mut self, _: &AlterTableAddColumnPlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_use_database(&mut self, _: &UseDatabasePlan) -> Result<()> {
         Ok(())
     }
@@ -270,7 +282,19 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_optimize_table(&mut self, _: &OptimizeTablePlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_kill_query(&mut self, _: &KillPlan) -> Result<()> {
         Ok(())
     }
+
+    fn visit_show_databases(&mut self, _: &ShowDatabasesPlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_show_tables(&mut self, _: &ShowTablesPlan) -> Result<()> {
+        Ok(())
+    }
 }
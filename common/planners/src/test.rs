@@ -18,6 +18,7 @@ use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 
 use crate::Part;
 use crate::Partitions;
@@ -41,14 +42,11 @@ impl Test {
             read_rows: total,
             read_bytes: total * 8,
             is_exact: true,
+            column_statistics: Default::default(),
         };
 
         Ok(PlanNode::ReadSource(ReadDataSourcePlan {
-            db: "system".to_string(),
-            table: "numbers_mt".to_string(),
-            table_id: 0,
-            table_version: None,
-            schema,
+            table_info: TableInfo::simple("system", "numbers_mt", schema),
             parts: Self::generate_partitions(8, total as u64),
             statistics: statistics.clone(),
             description: format!(
@@ -71,6 +69,7 @@ impl Test {
             partitions.push(Part {
                 name: format!("{}-{}-{}", total, 0, total,),
                 version: 0,
+                coalesced: vec![],
             })
         } else {
             for part in 0..workers {
@@ -82,6 +81,7 @@ impl Test {
                 partitions.push(Part {
                     name: format!("{}-{}-{}", total, part_begin, part_end,),
                     version: 0,
+                    coalesced: vec![],
                 })
             }
         }
@@ -111,6 +111,15 @@ pub struct RaftConfig {
     )]
     pub id: NodeId,
 
+    #[structopt(
+    long,
+    env = "KVSRV_NODE_WEIGHT",
+    default_value = "1",
+    help = concat!("The relative capacity of this node, slots are assigned to nodes in proportion to it.",
+    " It is recorded when this node is added to the cluster.")
+    )]
+    pub node_weight: u64,
+
     #[structopt(
         long,
         default_value = "",
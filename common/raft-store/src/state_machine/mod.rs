@@ -18,6 +18,7 @@ pub use sm::Replication;
 pub use sm::SerializableSnapshot;
 pub use sm::SnapshotKeyValue;
 pub use sm::StateMachine;
+pub use sm::SNAPSHOT_IMPORT_CHUNK_SIZE;
 pub use snapshot::Snapshot;
 pub use state_machine_meta::StateMachineMetaKey;
 pub use state_machine_meta::StateMachineMetaValue;
@@ -83,3 +83,39 @@ pub fn rand_n_from_m(m: usize, n: usize) -> anyhow::Result<Vec<usize>> {
 
     Ok(chosen)
 }
+
+/// Chooses `n` distinct elements from `weights.len()` elements, one at a time,
+/// each time an element is chosen with a probability in proportion to its weight among the ones left.
+///
+/// The indexes of the chosen elements are returned in ascending order, as `rand_n_from_m` does.
+pub fn weighted_rand_n_from_m(weights: &[u64], n: usize) -> anyhow::Result<Vec<usize>> {
+    let m = weights.len();
+    if m < n {
+        return Err(anyhow::anyhow!("m={} must >= n={}", m, n));
+    }
+    if weights.iter().any(|w| *w == 0) {
+        return Err(anyhow::anyhow!("weights must be positive: {:?}", weights));
+    }
+
+    let mut left = (0..m).collect::<Vec<_>>();
+    let mut chosen = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        let total = left.iter().map(|i| weights[*i] as u128).sum::<u128>();
+        let mut r = rand::random::<u128>() % total;
+
+        let mut pos = left.len() - 1;
+        for (p, i) in left.iter().enumerate() {
+            let w = weights[*i] as u128;
+            if r < w {
+                pos = p;
+                break;
+            }
+            r -= w;
+        }
+        chosen.push(left.remove(pos));
+    }
+
+    chosen.sort_unstable();
+    Ok(chosen)
+}
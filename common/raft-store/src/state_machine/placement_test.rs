@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::state_machine::placement::rand_n_from_m;
+use crate::state_machine::placement::weighted_rand_n_from_m;
 
 #[test]
 fn test_rand_n_from_m() -> anyhow::Result<()> {
@@ -37,3 +38,36 @@ fn test_rand_n_from_m() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_weighted_rand_n_from_m() -> anyhow::Result<()> {
+    // - Choose n distinct elts in ascending order.
+    // - An elt is chosen in proportion to its weight.
+    // - Invalid arguments are rejected.
+
+    let weights = [1, 3, 1, 2];
+    for n in 0..=weights.len() {
+        let got = weighted_rand_n_from_m(&weights, n)?;
+        assert_eq!(n, got.len());
+        assert!(got.windows(2).all(|w| w[0] < w[1]), "{:?}", got);
+    }
+
+    let mut counts = [0; 4];
+    for _ in 0..7000 {
+        let got = weighted_rand_n_from_m(&weights, 1)?;
+        counts[got[0]] += 1;
+    }
+    // expected: 1000, 3000, 1000, 2000
+    for (i, want) in [1000, 3000, 1000, 2000].iter().enumerate() {
+        assert!(
+            counts[i] > want * 85 / 100 && counts[i] < want * 115 / 100,
+            "{:?}",
+            counts
+        );
+    }
+
+    assert!(weighted_rand_n_from_m(&weights, 5).is_err());
+    assert!(weighted_rand_n_from_m(&[1, 0], 1).is_err());
+
+    Ok(())
+}
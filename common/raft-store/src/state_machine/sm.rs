@@ -12,27 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
 use async_raft::raft::Entry;
 use async_raft::raft::EntryPayload;
 use async_raft::raft::MembershipConfig;
+use common_base::ProgressCallback;
+use common_base::ProgressValues;
 use common_dfs_api_vo::DataPartInfo;
 use common_exception::prelude::ErrorCode;
 use common_exception::ToErrorCode;
 use common_metatypes::Cmd;
 use common_metatypes::Database;
-use common_metatypes::KVMeta;
 use common_metatypes::KVValue;
 use common_metatypes::LogEntry;
 use common_metatypes::LogId;
+use common_metatypes::MatchSeq;
 use common_metatypes::MatchSeqExt;
 use common_metatypes::Node;
 use common_metatypes::NodeId;
 use common_metatypes::Operation;
+use common_metatypes::SeqMismatch;
 use common_metatypes::SeqValue;
 use common_metatypes::Slot;
 use common_metatypes::Table;
@@ -41,8 +46,17 @@ use common_sled_store::sled;
 use common_sled_store::AsKeySpace;
 use common_sled_store::SledTree;
 use common_tracing::tracing;
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::de::IgnoredAny;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
 use sled::IVec;
 
 use crate::config::RaftConfig;
@@ -52,6 +66,7 @@ use crate::sled_key_spaces::Nodes;
 use crate::sled_key_spaces::Sequences;
 use crate::sled_key_spaces::StateMachineMeta;
 use crate::state_machine::placement::rand_n_from_m;
+use crate::state_machine::placement::weighted_rand_n_from_m;
 use crate::state_machine::AppliedState;
 use crate::state_machine::Placement;
 use crate::state_machine::StateMachineMetaKey;
@@ -74,6 +89,9 @@ const SEQ_DATABASE_META_ID: &str = "database_meta_id";
 // const TREE_META: &str = "meta";
 const TREE_STATE_MACHINE: &str = "state_machine";
 
+/// The number of kv pairs written by one batch when installing a snapshot.
+pub const SNAPSHOT_IMPORT_CHUNK_SIZE: usize = 1024;
+
 /// Replication defines the replication strategy.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Replication {
@@ -173,9 +191,110 @@ pub type SnapshotKeyValue = Vec<Vec<u8>>;
 pub struct SerializableSnapshot {
     /// A list of kv pairs.
     pub kvs: Vec<SnapshotKeyValue>,
+
+    /// Hex encoded SHA-256 of `kvs`.
+    /// It is absent in the snapshots built by the older versions, which are loaded without verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
 }
 
 impl SerializableSnapshot {
+    pub fn new(kvs: Vec<SnapshotKeyValue>) -> Self {
+        let checksum = Some(Self::compute_checksum(&kvs));
+        SerializableSnapshot { kvs, checksum }
+    }
+
+    pub fn compute_checksum(kvs: &[SnapshotKeyValue]) -> String {
+        let mut hasher = Sha256::new();
+        for kv in kvs {
+            Self::update_checksum(&mut hasher, kv);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn update_checksum(hasher: &mut Sha256, kv: &SnapshotKeyValue) {
+        hasher.update((kv.len() as u64).to_le_bytes());
+        for x in kv {
+            // length prefixed, thus moving bytes between key and value changes the checksum
+            hasher.update((x.len() as u64).to_le_bytes());
+            hasher.update(x);
+        }
+    }
+
+    /// Check the kv pairs against the checksum, if there is one.
+    pub fn verify(&self) -> common_exception::Result<()> {
+        match &self.checksum {
+            None => Ok(()),
+            Some(checksum) => {
+                let actual = Self::compute_checksum(&self.kvs);
+                if *checksum == actual {
+                    Ok(())
+                } else {
+                    Err(ErrorCode::MetaStoreDamaged(format!(
+                        "snapshot checksum mismatch, expect: {}, actual: {}",
+                        checksum, actual
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Imports the kv pairs of the serialized snapshot `data` into `tree`, `chunk_size` of them
+    /// by a batch at a time.
+    /// The kv pairs are deserialized one by one as they are imported, the snapshot is never held
+    /// in memory as a whole. The checksum is verified once all of them are imported, the caller
+    /// discards `tree` if it fails.
+    ///
+    /// `progress` is called after each batch with the number of the kv pairs and the bytes it
+    /// imported, the total number of the kv pairs is reported with the first call, it is counted
+    /// by a pass over `data` before the import.
+    /// Returns the number of the kv pairs imported.
+    pub fn import(
+        data: &[u8],
+        tree: &sled::Tree,
+        chunk_size: usize,
+        progress: Option<ProgressCallback>,
+    ) -> common_exception::Result<usize> {
+        let total_to_report = match progress {
+            Some(_) => serde_json::from_slice::<SnapshotKvsCount>(data)?.kvs,
+            None => 0,
+        };
+        let mut importer = SnapshotImporter {
+            tree,
+            chunk_size: chunk_size.max(1),
+            progress,
+            total_to_report,
+            batch: sled::Batch::default(),
+            rows: 0,
+            bytes: 0,
+            imported: 0,
+            hasher: Sha256::new(),
+            error: None,
+        };
+
+        let mut de = serde_json::Deserializer::from_slice(data);
+        let res = (&mut de)
+            .deserialize_map(SnapshotVisitor(&mut importer))
+            .and_then(|checksum| de.end().map(|_| checksum));
+        let checksum = match res {
+            Ok(checksum) => checksum,
+            // the error of the import, rather than the one it is reported as to serde
+            Err(cause) => return Err(importer.error.take().unwrap_or_else(|| cause.into())),
+        };
+        importer.flush()?;
+
+        if let Some(checksum) = checksum {
+            let actual = format!("{:x}", importer.hasher.finalize());
+            if checksum != actual {
+                return Err(ErrorCode::MetaStoreDamaged(format!(
+                    "snapshot checksum mismatch, expect: {}, actual: {}",
+                    checksum, actual
+                )));
+            }
+        }
+        Ok(importer.imported)
+    }
+
     /// Convert the snapshot to a `Vec<(type, name, iter)>` format for sled to import.
     pub fn sled_importable(self) -> Vec<(Vec<u8>, Vec<u8>, impl Iterator<Item = Vec<Vec<u8>>>)> {
         vec![(
@@ -186,6 +305,157 @@ impl SerializableSnapshot {
     }
 }
 
+/// The number of the kv pairs of a serialized snapshot, they are counted without being
+/// deserialized.
+#[derive(Deserialize)]
+struct SnapshotKvsCount {
+    #[serde(default, deserialize_with = "count_seq")]
+    kvs: usize,
+}
+
+fn count_seq<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    struct CountVisitor;
+
+    impl<'de> Visitor<'de> for CountVisitor {
+        type Value = usize;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list of kv pairs")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<usize, A::Error> {
+            let mut n = 0;
+            while seq.next_element::<IgnoredAny>()?.is_some() {
+                n += 1;
+            }
+            Ok(n)
+        }
+    }
+
+    deserializer.deserialize_seq(CountVisitor)
+}
+
+/// Imports the kv pairs of a snapshot into a tree by batches, as they are deserialized.
+struct SnapshotImporter<'a> {
+    tree: &'a sled::Tree,
+    chunk_size: usize,
+    progress: Option<ProgressCallback>,
+    total_to_report: usize,
+
+    // the batch being filled, and the number of the kv pairs and the bytes in it
+    batch: sled::Batch,
+    rows: usize,
+    bytes: usize,
+
+    imported: usize,
+    hasher: Sha256,
+    // the error that fails the import, serde only reports its message
+    error: Option<ErrorCode>,
+}
+
+impl SnapshotImporter<'_> {
+    fn add(&mut self, kv: SnapshotKeyValue) -> common_exception::Result<()> {
+        SerializableSnapshot::update_checksum(&mut self.hasher, &kv);
+
+        let mut kv = kv.into_iter();
+        let (k, v) = match (kv.next(), kv.next()) {
+            (Some(k), Some(v)) => (k, v),
+            _ => {
+                return Err(ErrorCode::MetaStoreDamaged(
+                    "snapshot kv pair without a key or value",
+                ))
+            }
+        };
+        self.rows += 1;
+        self.bytes += k.len() + v.len();
+        self.batch.insert(k, v);
+
+        if self.rows >= self.chunk_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> common_exception::Result<()> {
+        if self.rows == 0 {
+            return Ok(());
+        }
+
+        let batch = std::mem::take(&mut self.batch);
+        self.tree
+            .apply_batch(batch)
+            .map_err_to_code(ErrorCode::MetaStoreDamaged, || "fail to insert snapshot")?;
+        self.imported += self.rows;
+
+        if let Some(progress) = self.progress.as_mut() {
+            progress(&ProgressValues {
+                read_rows: self.rows,
+                read_bytes: self.bytes,
+                total_rows_to_read: self.total_to_report,
+            });
+        }
+        self.total_to_report = 0;
+        self.rows = 0;
+        self.bytes = 0;
+        Ok(())
+    }
+}
+
+/// Visits the fields of a serialized `SerializableSnapshot`, the kv pairs are imported as they
+/// are visited, the checksum is returned.
+struct SnapshotVisitor<'i, 'a>(&'i mut SnapshotImporter<'a>);
+
+impl<'de> Visitor<'de> for SnapshotVisitor<'_, '_> {
+    type Value = Option<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a serialized snapshot")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut checksum = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "kvs" => map.next_value_seed(SnapshotKvsSeed(&mut *self.0))?,
+                "checksum" => checksum = map.next_value::<Option<String>>()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(checksum)
+    }
+}
+
+struct SnapshotKvsSeed<'i, 'a>(&'i mut SnapshotImporter<'a>);
+
+impl<'de> DeserializeSeed<'de> for SnapshotKvsSeed<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SnapshotKvsSeed<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of kv pairs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(kv) = seq.next_element::<SnapshotKeyValue>()? {
+            if let Err(cause) = self.0.add(kv) {
+                let message = cause.message();
+                self.0.error = Some(cause);
+                return Err(de::Error::custom(message));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl StateMachine {
     pub fn initializer() -> StateMachineInitializer {
         StateMachineInitializer {
@@ -297,26 +567,30 @@ impl StateMachine {
             let (k, v) = rkv.map_err_to_code(ErrorCode::MetaStoreDamaged, || "taking snapshot")?;
             kvs.push(vec![k.to_vec(), v.to_vec()]);
         }
-        let snap = SerializableSnapshot { kvs };
+        let snap = SerializableSnapshot::new(kvs);
         let snap = serde_json::to_vec(&snap)?;
         Ok(snap)
     }
 
-    /// Internal func to get an auto-incr seq number.
-    /// It is just what Cmd::IncrSeq does and is also used by Cmd that requires
-    /// a unique id such as Cmd::AddDatabase which needs make a new database id.
+    /// Internal func to reserve `delta` seq numbers at once, i.e., what Cmd::IncrSeq and Cmd::IncrSeqBy do.
+    /// It is also used by Cmd that requires a unique id such as Cmd::CreateDatabase which needs make a new database id.
+    /// It returns the last reserved one, or an error without reserving any if the seq would overflow.
     ///
     /// Note: this can only be called inside apply().
-    async fn incr_seq(&self, key: &str) -> common_exception::Result<u64> {
+    async fn incr_seq_by(&self, key: &str, delta: u64) -> common_exception::Result<u64> {
+        self.peek_seq_by(key, delta)?;
+
         let sequences = self.sequences();
 
         let curr = sequences
-            .update_and_fetch(&key.to_string(), |old| Some(old.unwrap_or_default() + 1))
+            .update_and_fetch(&key.to_string(), |old| {
+                Some(old.unwrap_or_default() + delta)
+            })
             .await?;
 
         let curr = curr.unwrap();
 
-        tracing::debug!("applied IncrSeq: {}={}", key, curr);
+        tracing::debug!("applied IncrSeqBy: {}={}, delta: {}", key, curr, delta);
 
         Ok(curr.0)
     }
@@ -382,82 +656,112 @@ impl StateMachine {
     /// The `cmd` is always committed by raft before applying.
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn apply_cmd(&mut self, cmd: &Cmd) -> common_exception::Result<AppliedState> {
+        // TODO(xp): all the changes need to be done in a tx.
+        let (resp, changes) = self.compute_cmd(cmd)?;
+        for change in changes {
+            self.apply_change(change).await?;
+        }
+
+        tracing::debug!("applied {}: {:?}", cmd, resp);
+        Ok(resp)
+    }
+
+    /// Computes what applying `cmd` would result in, without applying it.
+    ///
+    /// It reads the current state only, neither sled nor the in-memory maps are written,
+    /// e.g., a `CreateTable` of an existent table reports the existent one as `prev` and
+    /// `result`, by which the caller tells a conflict before committing the cmd through raft.
+    /// The ids and seqs in the result are the ones would be assigned if `cmd` is the next to apply.
+    pub fn try_apply_cmd(&self, cmd: &Cmd) -> common_exception::Result<AppliedState> {
+        let (resp, _changes) = self.compute_cmd(cmd)?;
+        Ok(resp)
+    }
+
+    /// Computes the response to `cmd` and the changes applying it makes, against the current state.
+    /// It is shared by `apply_cmd()` and `try_apply_cmd()`, thus a dry-run reports just what applying does.
+    fn compute_cmd(&self, cmd: &Cmd) -> common_exception::Result<(AppliedState, Vec<Change>)> {
         match cmd {
             Cmd::AddFile { ref key, ref value } => {
-                // TODO(xp): put it in a transaction
-                let files = self.files();
-
-                let prev = files.get(key)?;
+                let prev = self.files().get(key)?;
                 if prev.is_none() {
-                    files.insert(key, value).await?;
-                    tracing::info!("applied AddFile: {}={}", key, value);
-                    Ok((prev, Some(value.clone())).into())
+                    let change = Change::SetFile {
+                        key: key.clone(),
+                        value: value.clone(),
+                    };
+                    Ok(((prev, Some(value.clone())).into(), vec![change]))
                 } else {
                     // TODO(xp): failure to add should returns `prev` as `result`
-                    Ok((prev, None).into())
+                    Ok(((prev, None).into(), vec![]))
                 }
             }
 
             Cmd::SetFile { ref key, ref value } => {
-                let files = self.files();
-
-                let prev = files.insert(key, value).await?;
-                tracing::info!("applied SetFile: {}={}", key, value);
-                Ok((prev, Some(value.clone())).into())
+                let prev = self.files().get(key)?;
+                let change = Change::SetFile {
+                    key: key.clone(),
+                    value: value.clone(),
+                };
+                Ok(((prev, Some(value.clone())).into(), vec![change]))
             }
 
-            Cmd::IncrSeq { ref key } => Ok(self.incr_seq(key).await?.into()),
+            Cmd::IncrSeq { ref key } => self.compute_incr_seq(key, 1),
+
+            Cmd::IncrSeqBy { ref key, ref delta } => self.compute_incr_seq(key, *delta),
 
             Cmd::AddNode {
                 ref node_id,
                 ref node,
             } => {
-                let sm_nodes = self.nodes();
-
-                let prev = sm_nodes.get(node_id)?;
-
+                let prev = self.nodes().get(node_id)?;
                 if prev.is_some() {
-                    Ok((prev, None).into())
+                    Ok(((prev, None).into(), vec![]))
                 } else {
-                    sm_nodes.insert(node_id, node).await?;
-                    tracing::info!("applied AddNode: {}={:?}", node_id, node);
-                    Ok((prev, Some(node.clone())).into())
+                    let change = Change::AddNode {
+                        node_id: *node_id,
+                        node: node.clone(),
+                    };
+                    Ok(((prev, Some(node.clone())).into(), vec![change]))
                 }
             }
 
+            Cmd::Rebalance => Ok((AppliedState::None, vec![Change::Rebalance])),
+
             Cmd::CreateDatabase {
                 ref name, ref db, ..
             } => {
                 // - If the db present, return it.
                 // - Otherwise, create a new one with next seq number as database id, and add it in to store.
-                if self.databases.contains_key(name) {
-                    let prev = self.databases.get(name);
-                    Ok((prev.cloned(), prev.cloned()).into())
+                if let Some(prev) = self.databases.get(name) {
+                    Ok(((Some(prev.clone()), Some(prev.clone())).into(), vec![]))
                 } else {
                     let db = Database {
-                        database_id: self.incr_seq(SEQ_DATABASE_ID).await?,
+                        database_id: self.peek_seq_by(SEQ_DATABASE_ID, 1)?,
                         database_engine: db.database_engine.clone(),
+                        database_options: db.database_options.clone(),
                         tables: Default::default(),
                     };
-                    self.incr_seq(SEQ_DATABASE_META_ID).await?;
-
-                    self.databases.insert(name.clone(), db.clone());
-                    tracing::debug!("applied CreateDatabase: {}={:?}", name, db);
-
-                    Ok((None, Some(db)).into())
+                    let changes = vec![
+                        Change::reserve_seq(SEQ_DATABASE_ID),
+                        Change::reserve_seq(SEQ_DATABASE_META_ID),
+                        Change::SetDatabase {
+                            name: name.clone(),
+                            db: db.clone(),
+                        },
+                    ];
+                    Ok(((None, Some(db)).into(), changes))
                 }
             }
 
             Cmd::DropDatabase { ref name } => {
                 let prev = self.databases.get(name).cloned();
-                if prev.is_some() {
-                    self.databases.remove(name);
-                    self.incr_seq(SEQ_DATABASE_META_ID).await?;
-                    tracing::debug!("applied DropDatabase: {}", name);
-                    Ok((prev, None).into())
-                } else {
-                    Ok((None::<Database>, None::<Database>).into())
-                }
+                let changes = match prev {
+                    Some(_) => vec![
+                        Change::RemoveDatabase { name: name.clone() },
+                        Change::reserve_seq(SEQ_DATABASE_META_ID),
+                    ],
+                    None => vec![],
+                };
+                Ok(((prev, None::<Database>).into(), changes))
             }
 
             Cmd::CreateTable {
@@ -466,28 +770,29 @@ impl StateMachine {
                 if_not_exists: _,
                 ref table,
             } => {
-                let db = self.databases.get(db_name);
-                let mut db = db.unwrap().to_owned();
-
-                if db.tables.contains_key(table_name) {
-                    let table_id = db.tables.get(table_name).unwrap();
+                let db = self.try_get_database(db_name)?;
+                if let Some(table_id) = db.tables.get(table_name) {
                     let prev = self.tables.get(table_id);
-                    Ok((prev.cloned(), prev.cloned()).into())
+                    Ok(((prev.cloned(), prev.cloned()).into(), vec![]))
                 } else {
                     let table = Table {
-                        table_id: self.incr_seq(SEQ_TABLE_ID).await?,
-                        schema: table.schema.clone(),
-                        table_engine: table.table_engine.clone(),
-                        table_options: table.table_options.clone(),
-                        parts: table.parts.clone(),
+                        table_id: self.peek_seq_by(SEQ_TABLE_ID, 1)?,
+                        ..table.clone()
                     };
-                    self.incr_seq(SEQ_DATABASE_META_ID).await?;
+                    let mut db = db.clone();
                     db.tables.insert(table_name.clone(), table.table_id);
-                    self.databases.insert(db_name.clone(), db);
-                    self.tables.insert(table.table_id, table.clone());
-                    tracing::debug!("applied CreateTable: {}={:?}", table_name, table);
-
-                    Ok((None, Some(table)).into())
+                    let changes = vec![
+                        Change::reserve_seq(SEQ_TABLE_ID),
+                        Change::reserve_seq(SEQ_DATABASE_META_ID),
+                        Change::SetDatabase {
+                            name: db_name.clone(),
+                            db,
+                        },
+                        Change::SetTable {
+                            table: table.clone(),
+                        },
+                    ];
+                    Ok(((None, Some(table)).into(), changes))
                 }
             }
 
@@ -496,17 +801,54 @@ impl StateMachine {
                 ref table_name,
                 if_exists: _,
             } => {
-                let db = self.databases.get_mut(db_name).unwrap();
-                let tbl_id = db.tables.get(table_name);
-                if let Some(tbl_id) = tbl_id {
-                    let tbl_id = tbl_id.to_owned();
-                    db.tables.remove(table_name);
-                    let prev = self.tables.remove(&tbl_id);
-                    self.incr_seq(SEQ_DATABASE_META_ID).await?;
-
-                    Ok((prev, None).into())
-                } else {
-                    Ok((None::<Table>, None::<Table>).into())
+                let db = self.try_get_database(db_name)?;
+                match db.tables.get(table_name) {
+                    Some(table_id) => {
+                        let prev = self.tables.get(table_id).cloned();
+                        let table_id = *table_id;
+                        let mut db = db.clone();
+                        db.tables.remove(table_name);
+                        let changes = vec![
+                            Change::SetDatabase {
+                                name: db_name.clone(),
+                                db,
+                            },
+                            Change::RemoveTable { table_id },
+                            Change::reserve_seq(SEQ_DATABASE_META_ID),
+                        ];
+                        Ok(((prev, None).into(), changes))
+                    }
+                    None => Ok(((None::<Table>, None::<Table>).into(), vec![])),
+                }
+            }
+
+            Cmd::UpdateTableSchema {
+                ref db_name,
+                ref table_name,
+                ref schema,
+                ref updated_on,
+            } => {
+                let prev = self
+                    .databases
+                    .get(db_name)
+                    .and_then(|db| db.tables.get(table_name))
+                    .and_then(|id| self.tables.get(id).cloned());
+                match prev {
+                    Some(prev) => {
+                        let table = Table {
+                            schema: schema.clone(),
+                            updated_on: *updated_on,
+                            ..prev.clone()
+                        };
+                        let changes = vec![
+                            Change::SetTable {
+                                table: table.clone(),
+                            },
+                            Change::reserve_seq(SEQ_DATABASE_META_ID),
+                        ];
+                        Ok(((Some(prev), Some(table)).into(), changes))
+                    }
+                    None => Ok(((None::<Table>, None::<Table>).into(), vec![])),
                 }
             }
 
@@ -515,16 +857,15 @@ impl StateMachine {
                 ref seq,
                 value: ref value_op,
                 ref value_meta,
+                ref content_type,
             } => {
-                // TODO(xp): need to be done all in a tx
                 // TODO(xp): now must be a timestamp extracted from raft log.
                 let now = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
                     .unwrap()
                     .as_secs();
 
-                let kvs = self.kvs();
-                let prev = kvs.get(key)?;
+                let prev = self.kvs().get(key)?;
 
                 // If prev is timed out, treat it as a None.
                 let prev = match prev {
@@ -539,56 +880,116 @@ impl StateMachine {
                 };
 
                 if seq.match_seq(&prev).is_err() {
-                    return Ok((prev.clone(), prev).into());
+                    return Ok(((prev.clone(), prev).into(), vec![]));
                 }
 
-                // result is the state after applying an operation.
-                let result;
+                // the value and content type to store, None to delete
+                let value = match value_op {
+                    Operation::Update(v) => Some((v.clone(), content_type.clone())),
+                    Operation::Delete => None,
+                    Operation::AsIs => match prev {
+                        // nothing to update
+                        None => return Ok(((prev, None).into(), vec![])),
+                        Some((_, ref kv_value)) => {
+                            let content_type = content_type
+                                .as_ref()
+                                .or_else(|| kv_value.content_type.as_ref())
+                                .cloned();
+                            Some((kv_value.value.clone(), content_type))
+                        }
+                    },
+                };
 
-                match value_op {
-                    Operation::Update(v) => {
-                        result = self.kv_update(key, value_meta, v).await?;
-                    }
-                    Operation::Delete => {
-                        kvs.remove(key, true).await?;
-                        result = None;
+                // result is the state after applying an operation.
+                match value {
+                    None => {
+                        let change = Change::RemoveKV { key: key.clone() };
+                        Ok(((prev, None).into(), vec![change]))
                     }
-                    Operation::AsIs => {
-                        result = match prev {
-                            None => None,
-                            Some((_, ref curr_kv_value)) => {
-                                self.kv_update(key, value_meta, &curr_kv_value.value)
-                                    .await?
-                            }
-                        };
+                    Some((value, content_type)) => {
+                        let result = (self.peek_seq_by(SEQ_GENERIC_KV, 1)?, KVValue {
+                            meta: value_meta.clone(),
+                            value,
+                            content_type,
+                        });
+                        let changes = vec![Change::reserve_seq(SEQ_GENERIC_KV), Change::SetKV {
+                            key: key.clone(),
+                            value: result.clone(),
+                        }];
+                        Ok(((prev, Some(result)).into(), changes))
                     }
                 }
-
-                tracing::debug!("applied UpsertKV: {} {:?}", key, result);
-                Ok((prev, result).into())
             }
         }
     }
 
-    /// Update a generic-kv record, without seq checking
-    async fn kv_update(
+    fn compute_incr_seq(
         &self,
         key: &str,
-        value_meta: &Option<KVMeta>,
-        v: &[u8],
-    ) -> common_exception::Result<Option<SeqValue<KVValue>>> {
-        let new_seq = self.incr_seq(SEQ_GENERIC_KV).await?;
-
-        let kv_value = KVValue {
-            meta: value_meta.clone(),
-            value: v.to_vec(),
+        delta: u64,
+    ) -> common_exception::Result<(AppliedState, Vec<Change>)> {
+        let seq = self.peek_seq_by(key, delta)?;
+        let change = Change::ReserveSeq {
+            key: key.to_string(),
+            delta,
         };
-        let seq_kv_value = (new_seq, kv_value);
+        Ok((seq.into(), vec![change]))
+    }
 
-        let kvs = self.kvs();
-        kvs.insert(&key.to_string(), &seq_kv_value).await?;
+    /// Writes a change computed by `compute_cmd()`.
+    async fn apply_change(&mut self, change: Change) -> common_exception::Result<()> {
+        match change {
+            Change::SetFile { key, value } => {
+                self.files().insert(&key, &value).await?;
+            }
+            Change::ReserveSeq { key, delta } => {
+                self.incr_seq_by(&key, delta).await?;
+            }
+            Change::AddNode { node_id, node } => {
+                self.nodes().insert(&node_id, &node).await?;
+            }
+            Change::Rebalance => {
+                let moved = self.rebalance_slots()?;
+                tracing::info!("applied Rebalance: {} replicas moved", moved);
+            }
+            Change::SetDatabase { name, db } => {
+                self.databases.insert(name, db);
+            }
+            Change::RemoveDatabase { name } => {
+                self.databases.remove(&name);
+            }
+            Change::SetTable { table } => {
+                self.tables.insert(table.table_id, table);
+            }
+            Change::RemoveTable { table_id } => {
+                self.tables.remove(&table_id);
+            }
+            Change::SetKV { key, value } => {
+                self.kvs().insert(&key, &value).await?;
+            }
+            Change::RemoveKV { key } => {
+                self.kvs().remove(&key, true).await?;
+            }
+        }
+        Ok(())
+    }
 
-        Ok(Some(seq_kv_value))
+    /// The seq `incr_seq_by()` would return, without reserving it.
+    fn peek_seq_by(&self, key: &str, delta: u64) -> common_exception::Result<u64> {
+        let curr = self.sequences().get(&key.to_string())?;
+        let curr = curr.map(|x| x.0).unwrap_or_default();
+        curr.checked_add(delta).ok_or_else(|| {
+            ErrorCode::Overflow(format!(
+                "Sequence {} overflows, it is {}, can not be increased by {}",
+                key, curr, delta
+            ))
+        })
+    }
+
+    fn try_get_database(&self, name: &str) -> common_exception::Result<&Database> {
+        self.databases
+            .get(name)
+            .ok_or_else(|| ErrorCode::UnknownDatabase(format!("Unknown database: {}", name)))
     }
 
     pub fn get_membership(&self) -> common_exception::Result<Option<MembershipConfig>> {
@@ -611,9 +1012,10 @@ impl StateMachine {
     }
 
     /// Initialize slots by assign nodes to everyone of them randomly, according to replicationn config.
+    /// A node is assigned slots in proportion to its weight.
     pub fn init_slots(&mut self) -> common_exception::Result<()> {
         for i in 0..self.slots.len() {
-            self.assign_rand_nodes_to_slot(i)?;
+            self.assign_weighted_nodes_to_slot(i)?;
         }
 
         Ok(())
@@ -642,6 +1044,135 @@ impl StateMachine {
         Ok(())
     }
 
+    /// Assign `n` random nodes to a slot, as `assign_rand_nodes_to_slot` does,
+    /// except that a node is chosen with a probability in proportion to its weight,
+    /// thus a node with a larger capacity stores more slots.
+    pub fn assign_weighted_nodes_to_slot(
+        &mut self,
+        slot_index: usize,
+    ) -> common_exception::Result<()> {
+        let n = match self.replication {
+            Replication::Mirror(x) => x,
+        } as usize;
+
+        // sorted by node id
+        let nodes = self.nodes().range_kvs(..)?;
+        let weights = nodes
+            .iter()
+            .map(|(_, node)| node.placement_weight())
+            .collect::<Vec<_>>();
+        let node_indexes = weighted_rand_n_from_m(&weights, n)?;
+
+        let mut slot = self
+            .slots
+            .get_mut(slot_index)
+            .ok_or_else(|| ErrorCode::InvalidConfig(format!("slot not found: {}", slot_index)))?;
+
+        slot.node_ids = node_indexes.iter().map(|i| nodes[*i].0).collect();
+
+        Ok(())
+    }
+
+    /// Reassign slots to the current nodes to restore balanced replication, e.g., after a node joins or leaves.
+    ///
+    /// The load of a node is the number of replicas it stores in proportion to its weight.
+    /// It moves as few replicas as possible: replicas on the nodes gone are reassigned,
+    /// then replicas are moved from the most loaded nodes to the least loaded ones,
+    /// until no move makes the receiving node less loaded than the giving node was.
+    /// With equal weights, the replica counts of any two nodes differ by at most one.
+    ///
+    /// Returns the number of replicas assigned to a new node.
+    pub fn rebalance_slots(&mut self) -> common_exception::Result<usize> {
+        // sorted by node id
+        let nodes = self.nodes().range_kvs(..)?;
+
+        let n = match self.replication {
+            Replication::Mirror(x) => x,
+        } as usize;
+        // a slot can not have more replicas than nodes
+        let n = n.min(nodes.len());
+
+        let mut loads: BTreeMap<NodeId, NodeLoad> = nodes
+            .iter()
+            .map(|(id, node)| {
+                (*id, NodeLoad {
+                    replicas: 0,
+                    weight: node.placement_weight(),
+                })
+            })
+            .collect();
+        for slot in self.slots.iter_mut() {
+            slot.node_ids.retain(|id| loads.contains_key(id));
+            for id in slot.node_ids.iter() {
+                loads.get_mut(id).unwrap().replicas += 1;
+            }
+        }
+
+        let mut moved = 0;
+        for slot in self.slots.iter_mut() {
+            // more replicas than required, drop the ones on the most loaded nodes
+            while slot.node_ids.len() > n {
+                let (i, id) = slot
+                    .node_ids
+                    .iter()
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| loads[*a].cmp_load(&loads[*b]))
+                    .map(|(i, id)| (i, *id))
+                    .unwrap();
+                slot.node_ids.remove(i);
+                loads.get_mut(&id).unwrap().replicas -= 1;
+            }
+
+            // less replicas than required, add the least loaded nodes
+            while slot.node_ids.len() < n {
+                let id = least_loaded(&loads, &slot.node_ids).unwrap();
+                slot.node_ids.push(id);
+                loads.get_mut(&id).unwrap().replicas += 1;
+                moved += 1;
+            }
+        }
+
+        // relieve the overloaded nodes, one replica at a time
+        while let Some((from, to, slot_index)) = self.find_replica_to_move(&loads) {
+            for id in self.slots[slot_index].node_ids.iter_mut() {
+                if *id == from {
+                    *id = to;
+                }
+            }
+            loads.get_mut(&from).unwrap().replicas -= 1;
+            loads.get_mut(&to).unwrap().replicas += 1;
+            moved += 1;
+        }
+
+        Ok(moved)
+    }
+
+    /// Find a replica on a node that can be moved to another node,
+    /// which is still less loaded than the giving node after receiving it.
+    /// The replicas on the most loaded nodes are tried first.
+    fn find_replica_to_move(
+        &self,
+        loads: &BTreeMap<NodeId, NodeLoad>,
+    ) -> Option<(NodeId, NodeId, usize)> {
+        let mut by_load = loads.iter().collect::<Vec<_>>();
+        by_load.sort_by(|(a_id, a), (b_id, b)| b.cmp_load(a).then(a_id.cmp(b_id)));
+
+        for (from, from_load) in by_load {
+            for (i, slot) in self.slots.iter().enumerate() {
+                if !slot.node_ids.contains(from) {
+                    continue;
+                }
+                match least_loaded(loads, &slot.node_ids) {
+                    Some(to) if loads[&to].added().cmp_load(from_load) == Ordering::Less => {
+                        return Some((*from, to, i))
+                    }
+                    _ => continue,
+                }
+            }
+        }
+        None
+    }
+
     fn list_node_ids(&self) -> Vec<NodeId> {
         let sm_nodes = self.nodes();
         sm_nodes.range_keys(..).expect("fail to list nodes")
@@ -717,6 +1248,23 @@ impl StateMachine {
         Ok(res)
     }
 
+    /// Reads the keys like `mget_kv` does, with the seq of each of them checked against the
+    /// `MatchSeq` given, a key of a mismatching seq gets a `SeqMismatch` instead of the value,
+    /// so that the caller can retry the stale ones.
+    pub fn mget_kv_checked(
+        &self,
+        keys: &[(String, MatchSeq)],
+    ) -> common_exception::Result<Vec<Result<Option<SeqValue<KVValue>>, SeqMismatch>>> {
+        let kvs = self.kvs();
+        let mut res = Vec::with_capacity(keys.len());
+        for (key, seq) in keys.iter() {
+            let v = Self::unexpired_opt(kvs.get(key)?);
+            res.push(SeqMismatch::check(key, *seq, v));
+        }
+
+        Ok(res)
+    }
+
     pub fn prefix_list_kv(
         &self,
         prefix: &str,
@@ -767,7 +1315,11 @@ impl StateMachine {
 
         tracing::debug!("seq_value: {:?} now: {}", seq_value, now);
 
-        if seq_value.1 < now {
+        let expired = match seq_value.1.meta {
+            None => false,
+            Some(ref meta) => meta.is_expired(now),
+        };
+        if expired {
             None
         } else {
             Some(seq_value)
@@ -812,3 +1364,84 @@ impl Placement for StateMachine {
         self.get_node(node_id)
     }
 }
+
+/// The node with the least load that does not hold a replica in `exclude`, the smaller id first if tie.
+/// A write to the state machine, applying a cmd makes a list of them.
+#[derive(Debug)]
+enum Change {
+    SetFile {
+        key: String,
+        value: String,
+    },
+    ReserveSeq {
+        key: String,
+        delta: u64,
+    },
+    AddNode {
+        node_id: NodeId,
+        node: Node,
+    },
+    Rebalance,
+    SetDatabase {
+        name: String,
+        db: Database,
+    },
+    RemoveDatabase {
+        name: String,
+    },
+    SetTable {
+        table: Table,
+    },
+    RemoveTable {
+        table_id: u64,
+    },
+    SetKV {
+        key: String,
+        value: SeqValue<KVValue>,
+    },
+    RemoveKV {
+        key: String,
+    },
+}
+
+impl Change {
+    fn reserve_seq(key: &str) -> Change {
+        Change::ReserveSeq {
+            key: key.to_string(),
+            delta: 1,
+        }
+    }
+}
+
+/// The replicas a node stores and its placement weight, for rebalancing slots.
+#[derive(Debug, Clone, Copy)]
+struct NodeLoad {
+    replicas: u64,
+    weight: u64,
+}
+
+impl NodeLoad {
+    /// The load after receiving one more replica.
+    fn added(&self) -> NodeLoad {
+        NodeLoad {
+            replicas: self.replicas + 1,
+            ..*self
+        }
+    }
+
+    /// Compares `replicas / weight` without rounding.
+    fn cmp_load(&self, other: &NodeLoad) -> Ordering {
+        let a = self.replicas as u128 * other.weight as u128;
+        let b = other.replicas as u128 * self.weight as u128;
+        a.cmp(&b)
+    }
+}
+
+/// Returns the node, not in `exclude`, that is the least loaded after receiving one more replica.
+fn least_loaded(loads: &BTreeMap<NodeId, NodeLoad>, exclude: &[NodeId]) -> Option<NodeId> {
+    loads
+        .iter()
+        .filter(|(id, _)| !exclude.contains(id))
+        .min_by(|(a_id, a), (b_id, b)| a.added().cmp_load(&b.added()).then(a_id.cmp(b_id)))
+        .map(|(id, _)| *id)
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
@@ -20,7 +23,12 @@ use async_raft::raft::EntryNormal;
 use async_raft::raft::EntryPayload;
 use async_raft::raft::MembershipConfig;
 use async_raft::LogId;
+use chrono::TimeZone;
+use chrono::Utc;
 use common_base::tokio;
+use common_base::ProgressCallback;
+use common_base::ProgressValues;
+use common_exception::ErrorCode;
 use common_metatypes::Cmd;
 use common_metatypes::Database;
 use common_metatypes::KVMeta;
@@ -31,8 +39,11 @@ use common_metatypes::Node;
 use common_metatypes::Operation;
 use common_metatypes::SeqValue;
 use common_metatypes::Slot;
+use common_metatypes::Table;
 use common_tracing::tracing;
+use maplit::btreemap;
 use maplit::btreeset;
+use maplit::hashmap;
 use pretty_assertions::assert_eq;
 
 use crate::init_raft_store_ut;
@@ -118,6 +129,220 @@ async fn test_state_machine_init_slots() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_assign_weighted_nodes_to_slot() -> anyhow::Result<()> {
+    // - Create a state machine with 3 node 1,3,5 of weight 1,1,2.
+    // - Initialize a lot of slots.
+    // - Assert node 5 is assigned about double the slots of the others.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let weighted = |weight| Node {
+        weight,
+        ..Default::default()
+    };
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+    sm.nodes()
+        .append(&[(1, weighted(1)), (3, weighted(1)), (5, weighted(2))])
+        .await?;
+
+    sm.slots = vec![Slot::default(); 4000];
+    sm.replication = Replication::Mirror(1);
+
+    sm.init_slots()?;
+    let mut counts = BTreeMap::new();
+    for slot in sm.slots.iter() {
+        assert_eq!(1, slot.node_ids.len());
+        *counts.entry(slot.node_ids[0]).or_insert(0) += 1;
+    }
+
+    // expected: 1000, 1000, 2000
+    for (id, want) in [(1, 1000), (3, 1000), (5, 2000)] {
+        let got = counts.get(&id).cloned().unwrap_or(0);
+        assert!(
+            got > want * 85 / 100 && got < want * 115 / 100,
+            "node {}: {:?}",
+            id,
+            counts
+        );
+    }
+
+    // the copy count is honored, all the nodes are chosen
+    sm.replication = Replication::Mirror(3);
+    sm.assign_weighted_nodes_to_slot(0)?;
+    assert_eq!(vec![1, 3, 5], sm.slots[0].node_ids);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_rebalance_slots() -> anyhow::Result<()> {
+    // - Create a state machine with 3 node 1,2,3 and 12 slots, 2 replicas each, evenly assigned.
+    // - Add node 4 and rebalance, the new node takes its share.
+    // - Remove node 2 and rebalance, its replicas are reassigned.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    fn loads(sm: &StateMachine) -> BTreeMap<u64, usize> {
+        let mut loads = BTreeMap::new();
+        for slot in sm.slots.iter() {
+            for id in slot.node_ids.iter() {
+                *loads.entry(*id).or_insert(0) += 1;
+            }
+        }
+        loads
+    }
+
+    fn changed_replicas(prev: &[Slot], curr: &[Slot]) -> usize {
+        prev.iter()
+            .zip(curr.iter())
+            .map(|(p, c)| {
+                c.node_ids
+                    .iter()
+                    .filter(|id| !p.node_ids.contains(id))
+                    .count()
+            })
+            .sum()
+    }
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+    sm.nodes()
+        .append(&[
+            (1, Node::default()),
+            (2, Node::default()),
+            (3, Node::default()),
+        ])
+        .await?;
+
+    sm.slots = [[1, 2], [2, 3], [3, 1]]
+        .iter()
+        .cycle()
+        .take(12)
+        .map(|ids| Slot {
+            node_ids: ids.to_vec(),
+        })
+        .collect();
+    sm.replication = Replication::Mirror(2);
+
+    // balanced already, nothing to move
+    assert_eq!(btreemap! {1 => 8, 2 => 8, 3 => 8}, loads(&sm));
+    let prev = sm.slots.clone();
+    assert_eq!(0, sm.rebalance_slots()?);
+    assert_eq!(prev, sm.slots);
+
+    tracing::info!("--- add node 4");
+    {
+        sm.apply_cmd(&Cmd::AddNode {
+            node_id: 4,
+            node: Node::default(),
+        })
+        .await?;
+        let prev = sm.slots.clone();
+        let resp = sm.apply_cmd(&Cmd::Rebalance).await?;
+        assert_eq!(AppliedState::None, resp);
+
+        assert_eq!(btreemap! {1 => 6, 2 => 6, 3 => 6, 4 => 6}, loads(&sm));
+        // only the share of the new node is moved
+        assert_eq!(6, changed_replicas(&prev, &sm.slots));
+        for slot in sm.slots.iter() {
+            assert_eq!(2, slot.node_ids.len());
+            assert_ne!(slot.node_ids[0], slot.node_ids[1]);
+        }
+    }
+
+    tracing::info!("--- remove node 2");
+    {
+        sm.nodes().remove(&2, true).await?;
+        let prev = sm.slots.clone();
+        let moved = sm.rebalance_slots()?;
+
+        assert_eq!(btreemap! {1 => 8, 3 => 8, 4 => 8}, loads(&sm));
+        // only the replicas on node 2 are moved
+        assert_eq!(6, moved);
+        assert_eq!(6, changed_replicas(&prev, &sm.slots));
+        for slot in sm.slots.iter() {
+            assert_eq!(2, slot.node_ids.len());
+            assert_ne!(slot.node_ids[0], slot.node_ids[1]);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_rebalance_slots_weighted() -> anyhow::Result<()> {
+    // - Create a state machine with 3 node 1,2,3 of weight 1,1,2 and 12 slots, 2 replicas each, evenly assigned.
+    // - Rebalance, node 3 takes double the replicas of the others.
+    // - Add node 4 of weight 2 and rebalance, the new node takes its share by weight.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    fn loads(sm: &StateMachine) -> BTreeMap<u64, usize> {
+        let mut loads = BTreeMap::new();
+        for slot in sm.slots.iter() {
+            for id in slot.node_ids.iter() {
+                *loads.entry(*id).or_insert(0) += 1;
+            }
+        }
+        loads
+    }
+
+    let weighted = |weight| Node {
+        weight,
+        ..Default::default()
+    };
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+    sm.nodes()
+        .append(&[(1, weighted(1)), (2, weighted(1)), (3, weighted(2))])
+        .await?;
+
+    sm.slots = [[1, 2], [2, 3], [3, 1]]
+        .iter()
+        .cycle()
+        .take(12)
+        .map(|ids| Slot {
+            node_ids: ids.to_vec(),
+        })
+        .collect();
+    sm.replication = Replication::Mirror(2);
+
+    assert_eq!(btreemap! {1 => 8, 2 => 8, 3 => 8}, loads(&sm));
+    // node 3 takes 4 more replicas from node 1 and 2
+    assert_eq!(4, sm.rebalance_slots()?);
+    assert_eq!(btreemap! {1 => 6, 2 => 6, 3 => 12}, loads(&sm));
+
+    // balanced by weight already, nothing to move
+    assert_eq!(0, sm.rebalance_slots()?);
+
+    tracing::info!("--- add node 4 of weight 2");
+    {
+        sm.apply_cmd(&Cmd::AddNode {
+            node_id: 4,
+            node: weighted(2),
+        })
+        .await?;
+        let moved = sm.rebalance_slots()?;
+
+        assert_eq!(btreemap! {1 => 4, 2 => 4, 3 => 8, 4 => 8}, loads(&sm));
+        // only the share of the new node is moved
+        assert_eq!(8, moved);
+        for slot in sm.slots.iter() {
+            assert_eq!(2, slot.node_ids.len());
+            assert_ne!(slot.node_ids[0], slot.node_ids[1]);
+        }
+    }
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_builder() -> anyhow::Result<()> {
     // - Assert default state machine builder
@@ -183,6 +408,89 @@ async fn test_state_machine_apply_non_dup_incr_seq() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_incr_seq_by() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    // reserve 10 ids: [1, 10]
+    let resp = sm
+        .apply_cmd(&Cmd::IncrSeqBy {
+            key: "foo".to_string(),
+            delta: 10,
+        })
+        .await?;
+    assert_eq!(AppliedState::Seq { seq: 10 }, resp);
+
+    // the next allocation does not overlap the reserved range
+    let resp = sm
+        .apply_cmd(&Cmd::IncrSeq {
+            key: "foo".to_string(),
+        })
+        .await?;
+    assert_eq!(AppliedState::Seq { seq: 11 }, resp);
+
+    // reserve 5 more: [12, 16]
+    let resp = sm
+        .apply_cmd(&Cmd::IncrSeqBy {
+            key: "foo".to_string(),
+            delta: 5,
+        })
+        .await?;
+    assert_eq!(AppliedState::Seq { seq: 16 }, resp);
+
+    // other keys are not affected
+    let resp = sm
+        .apply_cmd(&Cmd::IncrSeqBy {
+            key: "bar".to_string(),
+            delta: 3,
+        })
+        .await?;
+    assert_eq!(AppliedState::Seq { seq: 3 }, resp);
+
+    // reserve up to u64::MAX
+    let resp = sm
+        .apply_cmd(&Cmd::IncrSeqBy {
+            key: "foo".to_string(),
+            delta: u64::MAX - 16,
+        })
+        .await?;
+    assert_eq!(AppliedState::Seq { seq: u64::MAX }, resp);
+
+    // overflow is an error and reserves nothing
+    let res = sm
+        .apply_cmd(&Cmd::IncrSeqBy {
+            key: "bar".to_string(),
+            delta: u64::MAX - 2,
+        })
+        .await;
+    let err = res.unwrap_err();
+    assert_eq!(ErrorCode::Overflow("").code(), err.code());
+    assert_eq!(
+        "Sequence bar overflows, it is 3, can not be increased by 18446744073709551613",
+        err.message()
+    );
+
+    let res = sm
+        .apply_cmd(&Cmd::IncrSeq {
+            key: "foo".to_string(),
+        })
+        .await;
+    assert_eq!(ErrorCode::Overflow("").code(), res.unwrap_err().code());
+
+    let resp = sm
+        .apply_cmd(&Cmd::IncrSeq {
+            key: "bar".to_string(),
+        })
+        .await?;
+    assert_eq!(AppliedState::Seq { seq: 4 }, resp);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_apply_incr_seq() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_raft_store_ut!();
@@ -284,6 +592,382 @@ async fn test_state_machine_apply_add_database() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_add_database_with_options() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut m = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let options = hashmap! {"k1".to_string() => "v1".to_string()};
+    let resp = m
+        .apply_cmd(&Cmd::CreateDatabase {
+            name: "foo".to_string(),
+            if_not_exists: true,
+            db: Database {
+                database_engine: "default".to_string(),
+                database_options: options.clone(),
+                ..Default::default()
+            },
+        })
+        .await?;
+    let want = Database {
+        database_id: 1,
+        database_engine: "default".to_string(),
+        database_options: options,
+        ..Default::default()
+    };
+    assert_eq!(
+        AppliedState::DataBase {
+            prev: None,
+            result: Some(want.clone()),
+        },
+        resp
+    );
+    assert_eq!(Some(want), m.get_database("foo"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_create_table_timestamps() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    // The timestamps are carried by the command and applied verbatim,
+    // thus every replica applying the same log entry ends up with the same table.
+
+    let now = Utc.timestamp(1_600_000_000, 0);
+    let create = Cmd::CreateTable {
+        db_name: "db".to_string(),
+        table_name: "t".to_string(),
+        if_not_exists: true,
+        table: Table {
+            created_on: now,
+            updated_on: now,
+            ..Default::default()
+        },
+    };
+
+    let mut tables = vec![];
+    for id in 1..=2 {
+        let tc = new_raft_test_context();
+        let mut m = StateMachine::open(&tc.raft_config, id).await?;
+
+        m.apply_cmd(&Cmd::CreateDatabase {
+            name: "db".to_string(),
+            if_not_exists: true,
+            db: Default::default(),
+        })
+        .await?;
+        let resp = m.apply_cmd(&create).await?;
+        match resp {
+            AppliedState::Table {
+                prev: None,
+                result: Some(result),
+            } => {
+                assert_eq!(result.created_on, now);
+                assert_eq!(result.updated_on, now);
+                assert_eq!(m.get_table(&result.table_id), Some(result.clone()));
+                tables.push(result);
+            }
+            _ => panic!("expect result, got: {:?}", resp),
+        }
+    }
+    assert_eq!(tables[0], tables[1]);
+
+    // the tables persisted without timestamps are loaded with the unix epoch
+    let old = r#"{"table_id":1,"schema":[],"table_engine":"FUSE","table_options":{},"parts":[]}"#;
+    let table: Table = serde_json::from_str(old)?;
+    assert_eq!(table.table_engine, "FUSE");
+    assert_eq!(table.created_on, Utc.timestamp(0, 0));
+    assert_eq!(table.updated_on, Utc.timestamp(0, 0));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_try_apply_create_table() -> anyhow::Result<()> {
+    // - Dry-run of a create of an existent table reports the existent one.
+    // - Dry-run of a create of a new table reports the table to create.
+    // - Neither of them changes the state.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut m = StateMachine::open(&tc.raft_config, 1).await?;
+
+    m.apply_cmd(&Cmd::CreateDatabase {
+        name: "db".to_string(),
+        if_not_exists: true,
+        db: Default::default(),
+    })
+    .await?;
+    let create = |name: &str| Cmd::CreateTable {
+        db_name: "db".to_string(),
+        table_name: name.to_string(),
+        if_not_exists: false,
+        table: Default::default(),
+    };
+    let resp = m.apply_cmd(&create("t")).await?;
+    let table = match resp {
+        AppliedState::Table {
+            prev: None,
+            result: Some(result),
+        } => result,
+        _ => panic!("expect result, got: {:?}", resp),
+    };
+    let meta_ver = m.get_database_meta_ver()?;
+
+    tracing::info!("--- dry-run of a conflicting create");
+
+    let resp = m.try_apply_cmd(&create("t"))?;
+    assert_eq!(
+        AppliedState::Table {
+            prev: Some(table.clone()),
+            result: Some(table.clone()),
+        },
+        resp
+    );
+
+    tracing::info!("--- dry-run of a create");
+
+    let resp = m.try_apply_cmd(&create("t2"))?;
+    match resp {
+        AppliedState::Table {
+            prev: None,
+            result: Some(ref result),
+        } => assert_eq!(table.table_id + 1, result.table_id),
+        _ => panic!("expect result, got: {:?}", resp),
+    }
+
+    tracing::info!("--- state unchanged");
+
+    assert_eq!(meta_ver, m.get_database_meta_ver()?);
+    let db = m.get_database("db").unwrap();
+    assert_eq!(1, db.tables.len());
+    assert!(db.tables.contains_key("t"));
+    assert_eq!(m.get_table(&(table.table_id + 1)), None);
+
+    // the table id is not taken by the dry-run
+    let resp = m.apply_cmd(&create("t2")).await?;
+    match resp {
+        AppliedState::Table {
+            prev: None,
+            result: Some(result),
+        } => assert_eq!(table.table_id + 1, result.table_id),
+        _ => panic!("expect result, got: {:?}", resp),
+    }
+
+    tracing::info!("--- dry-run in an unknown database");
+
+    let res = m.try_apply_cmd(&Cmd::CreateTable {
+        db_name: "unknown".to_string(),
+        table_name: "t".to_string(),
+        if_not_exists: false,
+        table: Default::default(),
+    });
+    assert_eq!(
+        ErrorCode::UnknownDatabase("").code(),
+        res.unwrap_err().code()
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_try_apply_cmd_as_apply_cmd() -> anyhow::Result<()> {
+    // - For every cmd, the dry-run reports just what applying it returns.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut m = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let cmds = vec![
+        Cmd::AddFile {
+            key: "f".to_string(),
+            value: "v1".to_string(),
+        },
+        Cmd::AddFile {
+            key: "f".to_string(),
+            value: "v2".to_string(),
+        },
+        Cmd::SetFile {
+            key: "f".to_string(),
+            value: "v3".to_string(),
+        },
+        Cmd::IncrSeq {
+            key: "s".to_string(),
+        },
+        Cmd::IncrSeqBy {
+            key: "s".to_string(),
+            delta: 5,
+        },
+        Cmd::AddNode {
+            node_id: 1,
+            node: Node::default(),
+        },
+        Cmd::AddNode {
+            node_id: 1,
+            node: Node::default(),
+        },
+        Cmd::CreateDatabase {
+            name: "db".to_string(),
+            if_not_exists: true,
+            db: Default::default(),
+        },
+        Cmd::CreateDatabase {
+            name: "db".to_string(),
+            if_not_exists: true,
+            db: Default::default(),
+        },
+        Cmd::CreateTable {
+            db_name: "db".to_string(),
+            table_name: "t".to_string(),
+            if_not_exists: true,
+            table: Default::default(),
+        },
+        Cmd::UpdateTableSchema {
+            db_name: "db".to_string(),
+            table_name: "t".to_string(),
+            schema: vec![1],
+            updated_on: Utc.timestamp(1, 0),
+        },
+        Cmd::DropTable {
+            db_name: "db".to_string(),
+            table_name: "t".to_string(),
+            if_exists: true,
+        },
+        Cmd::DropTable {
+            db_name: "db".to_string(),
+            table_name: "t".to_string(),
+            if_exists: true,
+        },
+        Cmd::DropDatabase {
+            name: "db".to_string(),
+        },
+        Cmd::UpsertKV {
+            key: "k".to_string(),
+            seq: MatchSeq::Any,
+            value: Operation::Update(b"v".to_vec()),
+            value_meta: None,
+            content_type: Some("json".to_string()),
+        },
+        Cmd::UpsertKV {
+            key: "k".to_string(),
+            seq: MatchSeq::Exact(100),
+            value: Operation::Update(b"v2".to_vec()),
+            value_meta: None,
+            content_type: None,
+        },
+        Cmd::UpsertKV {
+            key: "k".to_string(),
+            seq: MatchSeq::Any,
+            value: Operation::AsIs,
+            value_meta: Some(KVMeta {
+                expire_at: Some(u64::MAX),
+            }),
+            content_type: None,
+        },
+        Cmd::UpsertKV {
+            key: "k".to_string(),
+            seq: MatchSeq::Any,
+            value: Operation::Delete,
+            value_meta: None,
+            content_type: None,
+        },
+        Cmd::UpsertKV {
+            key: "k".to_string(),
+            seq: MatchSeq::Any,
+            value: Operation::AsIs,
+            value_meta: None,
+            content_type: None,
+        },
+    ];
+
+    for cmd in cmds.iter() {
+        let dry_run = m.try_apply_cmd(cmd)?;
+        let resp = m.apply_cmd(cmd).await?;
+        assert_eq!(resp, dry_run, "cmd: {}", cmd);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_update_table_schema() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut m = StateMachine::open(&tc.raft_config, 1).await?;
+
+    m.apply_cmd(&Cmd::CreateDatabase {
+        name: "db".to_string(),
+        if_not_exists: true,
+        db: Default::default(),
+    })
+    .await?;
+    m.apply_cmd(&Cmd::CreateTable {
+        db_name: "db".to_string(),
+        table_name: "t".to_string(),
+        if_not_exists: true,
+        table: Table {
+            schema: vec![1],
+            table_engine: "FUSE".to_string(),
+            ..Default::default()
+        },
+    })
+    .await?;
+
+    let resp = m
+        .apply_cmd(&Cmd::UpdateTableSchema {
+            db_name: "db".to_string(),
+            table_name: "t".to_string(),
+            schema: vec![1, 2],
+            updated_on: Utc.timestamp(1_600_000_000, 0),
+        })
+        .await?;
+    match resp {
+        AppliedState::Table {
+            prev: Some(prev),
+            result: Some(result),
+        } => {
+            assert_eq!(prev.schema, vec![1]);
+            assert_eq!(result.schema, vec![1, 2]);
+            assert_eq!(result.table_id, prev.table_id);
+            assert_eq!(result.table_engine, "FUSE");
+            assert_eq!(result.created_on, prev.created_on);
+            assert_eq!(result.updated_on, Utc.timestamp(1_600_000_000, 0));
+            assert_eq!(m.get_table(&result.table_id), Some(result));
+        }
+        _ => panic!("expect prev and result, got: {:?}", resp),
+    }
+
+    // unknown table
+    let resp = m
+        .apply_cmd(&Cmd::UpdateTableSchema {
+            db_name: "db".to_string(),
+            table_name: "unknown".to_string(),
+            schema: vec![1, 2],
+            updated_on: Utc.timestamp(1_600_000_000, 0),
+        })
+        .await?;
+    assert_eq!(
+        AppliedState::Table {
+            prev: None,
+            result: None
+        },
+        resp
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_apply_non_dup_generic_kv_upsert_get() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_raft_store_ut!();
@@ -321,12 +1005,14 @@ async fn test_state_machine_apply_non_dup_generic_kv_upsert_get() -> anyhow::Res
                 (a, KVValue {
                     meta: None,
                     value: b.as_bytes().to_vec(),
+                    content_type: None,
                 })
             }),
             result: result.map(|(a, b)| {
                 (a, KVValue {
                     meta: m,
                     value: b.as_bytes().to_vec(),
+                    content_type: None,
                 })
             }),
         }
@@ -397,6 +1083,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_upsert_get() -> anyhow::Res
                 seq: c.seq,
                 value: Some(c.value.clone()).into(),
                 value_meta: c.value_meta.clone(),
+                content_type: None,
             })
             .await?;
         assert_eq!(
@@ -463,6 +1150,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
             value_meta: Some(KVMeta {
                 expire_at: Some(now + 10),
             }),
+            content_type: None,
         })
         .await?;
 
@@ -486,6 +1174,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
             value_meta: Some(KVMeta {
                 expire_at: Some(now + 10),
             }),
+            content_type: None,
         })
         .await?;
 
@@ -498,6 +1187,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
             value_meta: Some(KVMeta {
                 expire_at: Some(now + 20),
             }),
+            content_type: None,
         })
         .await?;
 
@@ -511,7 +1201,8 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
             meta: Some(KVMeta {
                 expire_at: Some(now + 20)
             }),
-            value: b"value_meta_bar".to_vec()
+            value: b"value_meta_bar".to_vec(),
+            content_type: None,
         },
         got.1,
         "update meta of None does nothing",
@@ -547,12 +1238,14 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
                 (a, KVValue {
                     meta: None,
                     value: b.as_bytes().to_vec(),
+                    content_type: None,
                 })
             }),
             result: result.map(|(a, b)| {
                 (a, KVValue {
                     meta: None,
                     value: b.as_bytes().to_vec(),
+                    content_type: None,
                 })
             }),
         }
@@ -580,6 +1273,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
             seq: MatchSeq::Any,
             value: Some(b"x".to_vec()).into(),
             value_meta: None,
+            content_type: None,
         })
         .await?;
 
@@ -590,6 +1284,7 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
                 seq: c.seq,
                 value: Operation::Delete,
                 value_meta: None,
+                content_type: None,
             })
             .await?;
         assert_eq!(
@@ -611,6 +1306,82 @@ async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_upsert_kv_content_type() -> anyhow::Result<()> {
+    // - Upsert a value with a content type, it is returned by get_kv.
+    // - Updating the meta keeps the content type.
+    // - Updating the value replaces the content type.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let mut sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let resp = sm
+        .apply_cmd(&Cmd::UpsertKV {
+            key: "foo".to_string(),
+            seq: MatchSeq::Any,
+            value: Operation::Update(b"{}".to_vec()),
+            value_meta: None,
+            content_type: Some("json".to_string()),
+        })
+        .await?;
+    let want = Some((1, KVValue {
+        meta: None,
+        value: b"{}".to_vec(),
+        content_type: Some("json".to_string()),
+    }));
+    assert_eq!(
+        AppliedState::KV {
+            prev: None,
+            result: want.clone(),
+        },
+        resp
+    );
+    assert_eq!(want, sm.get_kv("foo")?);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let meta = KVMeta {
+        expire_at: Some(now + 1000),
+    };
+    sm.apply_cmd(&Cmd::UpsertKV {
+        key: "foo".to_string(),
+        seq: MatchSeq::Exact(1),
+        value: Operation::AsIs,
+        value_meta: Some(meta.clone()),
+        content_type: None,
+    })
+    .await?;
+    assert_eq!(
+        Some((2, KVValue {
+            meta: Some(meta),
+            value: b"{}".to_vec(),
+            content_type: Some("json".to_string()),
+        })),
+        sm.get_kv("foo")?
+    );
+
+    sm.apply_cmd(&Cmd::UpsertKV {
+        key: "foo".to_string(),
+        seq: MatchSeq::Any,
+        value: Operation::Update(b"x".to_vec()),
+        value_meta: None,
+        content_type: None,
+    })
+    .await?;
+    assert_eq!(
+        Some((3, KVValue {
+            meta: None,
+            value: b"x".to_vec(),
+            content_type: None,
+        })),
+        sm.get_kv("foo")?
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_apply_add_file() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_raft_store_ut!();
@@ -737,6 +1508,97 @@ async fn test_state_machine_snapshot() -> anyhow::Result<()> {
         let res = pretty_snapshot(&d.kvs);
 
         assert_eq!(want, res);
+        assert!(d.checksum.is_some());
+        d.verify()?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_serializable_snapshot_checksum() -> anyhow::Result<()> {
+    let kvs = vec![vec![vec![1, 2], vec![3]], vec![vec![4], vec![5, 6]]];
+
+    let snap = SerializableSnapshot::new(kvs.clone());
+    snap.verify()?;
+
+    // a snapshot built by the older versions has no checksum
+    let old: SerializableSnapshot = serde_json::from_str(r#"{"kvs":[[[1,2],[3]]]}"#)?;
+    assert_eq!(None, old.checksum);
+    old.verify()?;
+
+    // a flipped byte
+    let mut damaged = snap.clone();
+    damaged.kvs[1][1][0] ^= 0x01;
+    let res = damaged.verify();
+    assert_eq!(
+        ErrorCode::MetaStoreDamaged("").code(),
+        res.unwrap_err().code()
+    );
+
+    // moving a byte from the value to the key
+    let moved = SerializableSnapshot {
+        kvs: vec![vec![vec![1, 2, 3], vec![]], vec![vec![4], vec![5, 6]]],
+        checksum: snap.checksum.clone(),
+    };
+    assert!(moved.verify().is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_serializable_snapshot_import() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let kvs = (0..5u8)
+        .map(|i| vec![vec![i], vec![i, i]])
+        .collect::<Vec<_>>();
+    let data = serde_json::to_vec(&SerializableSnapshot::new(kvs))?;
+
+    let tc = new_raft_test_context();
+    {
+        let sm = StateMachine::open(&tc.raft_config, 1).await?;
+        let tree = &sm.sm_tree.tree;
+
+        let reported = Arc::new(Mutex::new(vec![]));
+        let progress: ProgressCallback = {
+            let reported = reported.clone();
+            Box::new(move |values: &ProgressValues| {
+                reported
+                    .lock()
+                    .unwrap()
+                    .push((values.read_rows, values.total_rows_to_read));
+            })
+        };
+        let n = SerializableSnapshot::import(&data, tree, 2, Some(progress))?;
+        assert_eq!(5, n);
+        // by batches of 2, the total is reported with the first one
+        assert_eq!(vec![(2, 5), (2, 0), (1, 0)], *reported.lock().unwrap());
+        for i in 0..5u8 {
+            assert_eq!(Some(vec![i, i]), tree.get([i])?.map(|v| v.to_vec()));
+        }
+    }
+
+    // the checksum is verified once the kv pairs are imported
+    {
+        let sm = StateMachine::open(&tc.raft_config, 2).await?;
+        let mut damaged: SerializableSnapshot = serde_json::from_slice(&data)?;
+        damaged.kvs[4][1][0] ^= 0x01;
+        let damaged = serde_json::to_vec(&damaged)?;
+        let res = SerializableSnapshot::import(&damaged, &sm.sm_tree.tree, 2, None);
+        assert_eq!(
+            ErrorCode::MetaStoreDamaged("").code(),
+            res.unwrap_err().code()
+        );
+    }
+
+    // a snapshot built by the older versions has no checksum
+    {
+        let sm = StateMachine::open(&tc.raft_config, 3).await?;
+        let n =
+            SerializableSnapshot::import(br#"{"kvs":[[[1,2],[3]]]}"#, &sm.sm_tree.tree, 2, None)?;
+        assert_eq!(1, n);
     }
 
     Ok(())
@@ -51,6 +51,7 @@ pub fn snapshot_logs() -> (Vec<Entry<LogEntry>>, Vec<String>) {
                         seq: MatchSeq::Any,
                         value: Operation::Update(b"A".to_vec()),
                         value_meta: None,
+                        content_type: None,
                     },
                 },
             }),
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -20,15 +23,46 @@ use serde::Serialize;
 /// The meta data of a record in kv
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 pub struct KVMeta {
-    /// expiration time in second since 1970
+    /// Absolute expiration time in seconds since 1970, not a duration.
+    ///
+    /// It is stored in the raft log as is and every node compares it with its own clock,
+    /// thus it must be computed once, by the node proposing the log (the leader),
+    /// e.g. with `KVMeta::with_ttl`, never when applying the log.
     pub expire_at: Option<u64>,
 }
 
+impl KVMeta {
+    /// Build a meta that expires `ttl` after `now`.
+    ///
+    /// A `now` before 1970 is taken as 1970, and a ttl beyond the representable time
+    /// never expires in practice, instead of panicking.
+    pub fn with_ttl(ttl: Duration, now: SystemTime) -> Self {
+        let since_epoch = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let expire_at = since_epoch.saturating_add(ttl);
+        KVMeta {
+            expire_at: Some(expire_at.as_secs()),
+        }
+    }
+
+    /// Whether it is expired at `now`, in seconds since 1970.
+    pub fn is_expired(&self, now: u64) -> bool {
+        match self.expire_at {
+            None => false,
+            Some(expire_at) => expire_at < now,
+        }
+    }
+}
+
 /// Value of StateMachine generic-kv
 #[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
 pub struct KVValue<T = Vec<u8>> {
     pub meta: Option<KVMeta>,
     pub value: T,
+
+    /// The encoding of `value`, e.g. "json" or "protobuf", as the writer declared.
+    /// It does not take part in the seq or expiration checking.
+    #[serde(default)]
+    pub content_type: Option<String>,
 }
 
 /// Compare with a timestamp to check if it is expired.
@@ -0,0 +1,69 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use crate::KVMeta;
+use crate::KVValue;
+
+#[test]
+fn test_kv_meta_with_ttl() -> anyhow::Result<()> {
+    let now = UNIX_EPOCH + Duration::from_secs(1000);
+
+    // the relative ttl is stored as an absolute time
+    let meta = KVMeta::with_ttl(Duration::from_secs(20), now);
+    assert_eq!(Some(1020), meta.expire_at);
+
+    // sub-second part is truncated
+    let meta = KVMeta::with_ttl(Duration::from_millis(20_500), now);
+    assert_eq!(Some(1020), meta.expire_at);
+
+    // a huge ttl saturates
+    let meta = KVMeta::with_ttl(Duration::MAX, now);
+    assert_eq!(Some(u64::MAX), meta.expire_at);
+
+    // a clock before 1970 counts from 1970
+    let meta = KVMeta::with_ttl(Duration::from_secs(20), UNIX_EPOCH - Duration::from_secs(1));
+    assert_eq!(Some(20), meta.expire_at);
+
+    Ok(())
+}
+
+#[test]
+fn test_kv_meta_is_expired() -> anyhow::Result<()> {
+    let meta = KVMeta::with_ttl(
+        Duration::from_secs(20),
+        UNIX_EPOCH + Duration::from_secs(1000),
+    );
+
+    assert!(!meta.is_expired(1000));
+    assert!(!meta.is_expired(1020));
+    assert!(meta.is_expired(1021));
+
+    // never expires without expire_at
+    assert!(!KVMeta::default().is_expired(u64::MAX));
+
+    // KVValue compares its expiration time with a timestamp
+    let value = KVValue {
+        meta: Some(meta),
+        value: vec![],
+        content_type: None,
+    };
+    assert!(value < 1021);
+    assert!(value == 1020);
+    assert!(value > 1000);
+
+    Ok(())
+}
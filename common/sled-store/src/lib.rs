@@ -39,6 +39,8 @@ mod sled_key_space;
 mod sled_serde;
 mod sled_tree;
 
+#[cfg(test)]
+mod kv_test;
 #[cfg(test)]
 mod sled_tree_test;
 #[cfg(test)]
@@ -36,12 +36,14 @@ impl KVApi for StoreClient {
         seq: MatchSeq,
         value: Option<Vec<u8>>,
         value_meta: Option<KVMeta>,
+        content_type: Option<String>,
     ) -> Result<UpsertKVActionResult> {
         self.do_action(UpsertKVAction {
             key: key.to_string(),
             seq,
             value,
             value_meta,
+            content_type,
         })
         .await
     }
@@ -136,6 +138,9 @@ pub struct UpsertKVAction {
     pub seq: MatchSeq,
     pub value: Option<Vec<u8>>,
     pub value_meta: Option<KVMeta>,
+    /// The content type of `value`, e.g., "json", it is kept unchanged if it is None.
+    #[serde(default)]
+    pub content_type: Option<String>,
 }
 
 action_declare!(
@@ -17,6 +17,7 @@ use common_meta_api::MetaApi;
 use common_meta_api_vo::*;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
@@ -60,6 +61,14 @@ impl MetaApi for StoreClient {
         self.do_action(DropTableAction { plan }).await
     }
 
+    /// Add a column to a table.
+    async fn add_table_column(
+        &self,
+        plan: AlterTableAddColumnPlan,
+    ) -> common_exception::Result<()> {
+        self.do_action(AddTableColumnAction { plan }).await
+    }
+
     /// Get table.
     async fn get_table(&self, db: &str, table: &str) -> common_exception::Result<TableInfo> {
         self.do_action(GetTableAction {
@@ -131,6 +140,13 @@ pub struct DropTableAction {
 }
 action_declare!(DropTableAction, (), StoreDoAction::DropTable);
 
+// - add table column
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct AddTableColumnAction {
+    pub plan: AlterTableAddColumnPlan,
+}
+action_declare!(AddTableColumnAction, (), StoreDoAction::AddTableColumn);
+
 // - get table
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct GetTableAction {
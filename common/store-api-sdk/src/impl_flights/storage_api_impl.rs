@@ -60,7 +60,7 @@ impl StorageApi for StoreClient {
         // replace table schema with projected schema
         // TODO tweak method signature, only ReadDataSourcePlan are supposed to be passed in
         if let PlanNode::ReadSource(plan) = &read_action.push_down {
-            arrow_schema = Arc::new(plan.schema.to_arrow())
+            arrow_schema = Arc::new(plan.schema().to_arrow())
         }
 
         let res_stream = res.map(move |item| {
@@ -23,6 +23,8 @@ pub use impl_flights::storage_api_impl;
 pub use store_client::StoreClient;
 pub use store_client_conf::ClientConf;
 pub use store_client_conf::StoreClientConf;
+pub use store_client_pool::ClientPool;
+pub use store_client_pool::PooledClient;
 pub use store_do_action::RequestFor;
 pub use store_do_action::StoreDoAction;
 pub use store_do_get::StoreDoGet;
@@ -35,6 +37,7 @@ mod store_client;
 #[macro_use]
 mod store_do_action;
 mod store_client_conf;
+mod store_client_pool;
 mod store_do_get;
 
 // ProtoBuf generated files.
@@ -46,3 +49,5 @@ pub mod protobuf {
 
 #[cfg(test)]
 mod dns_resolver_test;
+#[cfg(test)]
+mod store_client_pool_test;
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::convert::TryInto;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
@@ -27,19 +30,25 @@ use common_kv_api_util::STORE_SYNC_CALL_TIMEOUT;
 use common_tracing::tracing;
 use futures::stream;
 use futures::StreamExt;
+use lazy_static::lazy_static;
 use prost::Message;
 use serde::de::DeserializeOwned;
 use tonic::codegen::InterceptedService;
 use tonic::metadata::MetadataValue;
 use tonic::service::Interceptor;
 use tonic::transport::Channel;
+use tonic::Code;
 use tonic::Request;
+use tonic::Status;
 
 use crate::common::flight_result_to_str;
 use crate::store_client_conf::StoreClientConf;
 use crate::store_do_action::RequestFor;
 use crate::store_do_action::StoreDoAction;
+use crate::ClientConf;
+use crate::ClientPool;
 use crate::ConnectionFactory;
+use crate::PooledClient;
 use crate::RpcClientTlsConfig;
 
 #[derive(Clone)]
@@ -48,19 +57,31 @@ pub struct StoreClient {
     token: Vec<u8>,
     pub(crate) timeout: Duration,
     pub(crate) client: FlightServiceClient<InterceptedService<Channel, AuthInterceptor>>,
+    // shared by the clones, set once the server is found unavailable
+    broken: Arc<AtomicBool>,
 }
 
 const AUTH_TOKEN_KEY: &str = "auth-token-bin";
 
+lazy_static! {
+    static ref STORE_CLIENT_POOL: ClientPool<StoreClient> = ClientPool::create();
+}
+
 impl StoreClient {
+    /// Connect to the first available endpoint of the meta service.
     pub async fn try_new(conf: &StoreClientConf) -> Result<StoreClient> {
-        Self::with_tls_conf(
-            &conf.meta_service_config.address,
-            &conf.meta_service_config.username,
-            &conf.meta_service_config.password,
-            conf.meta_service_config.tls_conf.clone(),
-        )
-        .await
+        let conf = &conf.meta_service_config;
+        let mut last_error = None;
+        for address in &conf.addresses {
+            match <StoreClient as PooledClient>::connect(address, conf).await {
+                Ok(client) => return Ok(client),
+                Err(cause) => {
+                    tracing::warn!("failed to connect to {}: {}", address, cause);
+                    last_error = Some(cause);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| ErrorCode::BadArguments("No endpoint is configured")))
     }
 
     pub fn sync_try_new(conf: &StoreClientConf) -> Result<StoreClient> {
@@ -71,6 +92,20 @@ impl StoreClient {
         )?
     }
 
+    /// Get a client of the meta service from the process wide pool, the connection is shared
+    /// with the other callers of the same endpoint.
+    pub async fn try_get_pooled(conf: &StoreClientConf) -> Result<StoreClient> {
+        STORE_CLIENT_POOL.get(&conf.meta_service_config).await
+    }
+
+    pub fn sync_try_get_pooled(conf: &StoreClientConf) -> Result<StoreClient> {
+        let cfg = conf.clone();
+        STORE_RUNTIME.block_on(
+            async move { StoreClient::try_get_pooled(&cfg).await },
+            STORE_SYNC_CALL_TIMEOUT.as_ref().cloned(),
+        )?
+    }
+
     #[tracing::instrument(level = "debug", skip(password))]
     pub async fn try_create(addr: &str, username: &str, password: &str) -> Result<Self> {
         Self::with_tls_conf(addr, username, password, None).await
@@ -104,6 +139,7 @@ impl StoreClient {
             token,
             timeout,
             client,
+            broken: Arc::new(AtomicBool::new(false)),
         };
         Ok(rx)
     }
@@ -156,8 +192,18 @@ impl StoreClient {
 
         req.set_timeout(self.timeout);
 
-        let mut stream = self.client.clone().do_action(req).await?.into_inner();
-        match stream.message().await? {
+        let mut stream = self
+            .client
+            .clone()
+            .do_action(req)
+            .await
+            .map_err(|status| self.check_status(status))?
+            .into_inner();
+        let message = stream
+            .message()
+            .await
+            .map_err(|status| self.check_status(status))?;
+        match message {
             None => Err(ErrorCode::EmptyData(format!(
                 "Can not receive data from dfs flight server, action: {:?}",
                 act
@@ -169,6 +215,30 @@ impl StoreClient {
             }
         }
     }
+
+    fn check_status(&self, status: Status) -> Status {
+        if status.code() == Code::Unavailable {
+            self.broken.store(true, Ordering::Relaxed);
+        }
+        status
+    }
+}
+
+#[async_trait::async_trait]
+impl PooledClient for StoreClient {
+    async fn connect(endpoint: &str, conf: &ClientConf) -> Result<Self> {
+        Self::with_tls_conf(
+            endpoint,
+            &conf.username,
+            &conf.password,
+            conf.tls_conf.clone(),
+        )
+        .await
+    }
+
+    fn is_broken(&self) -> bool {
+        self.broken.load(Ordering::Relaxed)
+    }
 }
 
 #[derive(Clone)]
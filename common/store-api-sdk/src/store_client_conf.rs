@@ -23,7 +23,8 @@ pub struct StoreClientConf {
 
 #[derive(Clone, Debug, Default)]
 pub struct ClientConf {
-    pub address: String,
+    /// Endpoints of the service, they are tried in order until one is connected.
+    pub addresses: Vec<String>,
     pub username: String,
     pub password: String,
     pub tls_conf: Option<RpcClientTlsConfig>,
@@ -31,6 +32,6 @@ pub struct ClientConf {
 
 impl ClientConf {
     pub fn local_mode(&self) -> bool {
-        self.address.is_empty()
+        self.addresses.is_empty()
     }
 }
@@ -0,0 +1,118 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_tracing::tracing;
+
+use crate::ClientConf;
+
+/// A client which can be shared by the callers of a `ClientPool`.
+#[async_trait::async_trait]
+pub trait PooledClient: Clone + Send + Sync + Sized + 'static {
+    async fn connect(endpoint: &str, conf: &ClientConf) -> Result<Self>;
+
+    /// A broken client is evicted from the pool, the next caller gets a new connection.
+    fn is_broken(&self) -> bool;
+}
+
+/// Keeps one client per endpoint, the healthy ones are reused by all the callers.
+///
+/// The endpoints of a `ClientConf` are tried in order, except the ones failed recently are
+/// tried last, so that the callers fail over to the healthy nodes.
+pub struct ClientPool<C: PooledClient> {
+    inner: Mutex<PoolInner<C>>,
+}
+
+struct PoolInner<C> {
+    clients: HashMap<String, C>,
+    // the last time connecting to an endpoint failed, or a client of it was found broken
+    failed_at: HashMap<String, Instant>,
+}
+
+impl<C: PooledClient> ClientPool<C> {
+    pub fn create() -> Self {
+        ClientPool {
+            inner: Mutex::new(PoolInner {
+                clients: HashMap::new(),
+                failed_at: HashMap::new(),
+            }),
+        }
+    }
+
+    pub async fn get(&self, conf: &ClientConf) -> Result<C> {
+        let endpoints = self.ordered_endpoints(&conf.addresses);
+        for endpoint in &endpoints {
+            if let Some(client) = self.get_healthy(endpoint) {
+                return Ok(client);
+            }
+        }
+
+        // the endpoints of the broken clients evicted above go to the last
+        let endpoints = self.ordered_endpoints(&conf.addresses);
+        let mut last_error = None;
+        for endpoint in &endpoints {
+            match C::connect(endpoint, conf).await {
+                Ok(client) => return Ok(self.put(endpoint, client)),
+                Err(cause) => {
+                    tracing::warn!("failed to connect to {}: {}", endpoint, cause);
+                    self.inner
+                        .lock()
+                        .failed_at
+                        .insert(endpoint.clone(), Instant::now());
+                    last_error = Some(cause);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| ErrorCode::BadArguments("No endpoint is configured")))
+    }
+
+    fn ordered_endpoints(&self, endpoints: &[String]) -> Vec<String> {
+        let inner = self.inner.lock();
+        let mut endpoints = endpoints.to_vec();
+        // stable, the endpoints never failed keep the configured order
+        endpoints.sort_by_key(|endpoint| inner.failed_at.get(endpoint).cloned());
+        endpoints
+    }
+
+    fn get_healthy(&self, endpoint: &str) -> Option<C> {
+        let mut inner = self.inner.lock();
+        let client = inner.clients.get(endpoint).cloned()?;
+        if !client.is_broken() {
+            return Some(client);
+        }
+
+        inner.clients.remove(endpoint);
+        inner.failed_at.insert(endpoint.to_string(), Instant::now());
+        None
+    }
+
+    // the client connected without holding the lock, the one got in first wins if there is a race
+    fn put(&self, endpoint: &str, client: C) -> C {
+        let mut inner = self.inner.lock();
+        if let Some(pooled) = inner.clients.get(endpoint) {
+            if !pooled.is_broken() {
+                return pooled.clone();
+            }
+        }
+
+        inner.clients.insert(endpoint.to_string(), client.clone());
+        client
+    }
+}
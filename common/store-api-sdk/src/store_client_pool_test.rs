@@ -0,0 +1,117 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::ClientConf;
+use crate::ClientPool;
+use crate::PooledClient;
+
+static CONNECTION_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone)]
+struct MockClient {
+    endpoint: String,
+    connection_id: u64,
+    broken: Arc<AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl PooledClient for MockClient {
+    async fn connect(endpoint: &str, _conf: &ClientConf) -> Result<Self> {
+        if endpoint.starts_with("refused") {
+            return Err(ErrorCode::CannotConnectNode(format!(
+                "connection refused: {}",
+                endpoint
+            )));
+        }
+        Ok(MockClient {
+            endpoint: endpoint.to_string(),
+            connection_id: CONNECTION_ID.fetch_add(1, Ordering::SeqCst),
+            broken: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    fn is_broken(&self) -> bool {
+        self.broken.load(Ordering::SeqCst)
+    }
+}
+
+fn client_conf(addresses: &[&str]) -> ClientConf {
+    ClientConf {
+        addresses: addresses.iter().map(|a| a.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_client_pool_reuse_and_evict() -> Result<()> {
+    let pool = ClientPool::<MockClient>::create();
+
+    // the connection is reused by the same endpoint
+    let c1 = pool.get(&client_conf(&["127.0.0.1:9191"])).await?;
+    let c2 = pool.get(&client_conf(&["127.0.0.1:9191"])).await?;
+    assert_eq!(c1.connection_id, c2.connection_id);
+
+    // another endpoint gets its own connection
+    let c3 = pool.get(&client_conf(&["127.0.0.1:9192"])).await?;
+    assert_ne!(c1.connection_id, c3.connection_id);
+
+    // the broken one is replaced
+    c1.broken.store(true, Ordering::SeqCst);
+    let c4 = pool.get(&client_conf(&["127.0.0.1:9191"])).await?;
+    assert_ne!(c1.connection_id, c4.connection_id);
+    assert!(!c4.is_broken());
+    let c5 = pool.get(&client_conf(&["127.0.0.1:9191"])).await?;
+    assert_eq!(c4.connection_id, c5.connection_id);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_client_pool_failover() -> Result<()> {
+    let pool = ClientPool::<MockClient>::create();
+
+    // the first endpoint refuses connections
+    let conf = client_conf(&["refused:9191", "127.0.0.1:9191"]);
+    let c1 = pool.get(&conf).await?;
+    assert_eq!(c1.endpoint, "127.0.0.1:9191");
+
+    // the healthy one is reused
+    let c2 = pool.get(&conf).await?;
+    assert_eq!(c1.connection_id, c2.connection_id);
+
+    // fails over to the other node once the client is broken
+    let conf = client_conf(&["127.0.0.1:9191", "127.0.0.1:9192"]);
+    c1.broken.store(true, Ordering::SeqCst);
+    let c3 = pool.get(&conf).await?;
+    assert_eq!(c3.endpoint, "127.0.0.1:9192");
+
+    // all the endpoints are down
+    let conf = client_conf(&["refused:9191", "refused:9192"]);
+    let res = pool.get(&conf).await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::CannotConnectNode("").code())
+    );
+
+    Ok(())
+}
@@ -25,6 +25,7 @@ use crate::impl_flights::kv_api_impl::KVMetaAction;
 use crate::impl_flights::kv_api_impl::MGetKVAction;
 use crate::impl_flights::kv_api_impl::PrefixListReq;
 use crate::impl_flights::kv_api_impl::UpsertKVAction;
+use crate::impl_flights::meta_api_impl::AddTableColumnAction;
 use crate::impl_flights::meta_api_impl::CreateDatabaseAction;
 use crate::impl_flights::meta_api_impl::CreateTableAction;
 use crate::impl_flights::meta_api_impl::DropDatabaseAction;
@@ -64,6 +65,7 @@ pub enum StoreDoAction {
     DropDatabase(DropDatabaseAction),
     CreateTable(CreateTableAction),
     DropTable(DropTableAction),
+    AddTableColumn(AddTableColumnAction),
     GetTable(GetTableAction),
     GetTableExt(GetTableExtReq),
     GetTables(GetTablesAction),
@@ -51,6 +51,34 @@ fn test_parse_values() {
     assert!(block.is_none());
 }
 
+#[test]
+fn test_parse_values_escaped_quote() {
+    // a doubled quote is a quote in the string, e.g. of a bound prepared statement parameter
+    let buffer = "(1, 'x'' OR 1=1'), (2, ''''), (3, 'a''''b')";
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int8, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let mut values_source = ValueSource::new(buffer.as_bytes(), schema, 10);
+    let block = values_source.read().unwrap().unwrap();
+    assert_blocks_eq(
+        vec![
+            "+---+-----------+",
+            "| a | b         |",
+            "+---+-----------+",
+            "| 1 | x' OR 1=1 |",
+            "| 2 | '         |",
+            "| 3 | a''b      |",
+            "+---+-----------+",
+        ],
+        &[block],
+    );
+
+    let block = values_source.read().unwrap();
+    assert!(block.is_none());
+}
+
 #[test]
 fn test_parse_csvs() {
     let buffer = "1,\"1\",1.11\n2,\"2\",2\n3,\"3-'3'-3\",3\n";
@@ -78,7 +78,11 @@ where R: io::Read + Send + Sync
 
                 let bs: Result<&[u8]> = {
                     if reader.ignore_byte(b'\'')? {
+                        // a doubled quote is an escaped quote
                         reader.until(b'\'', &mut buf)?;
+                        while reader.ignore_byte(b'\'')? {
+                            reader.until(b'\'', &mut buf)?;
+                        }
 
                         let res = &buf.as_slice()[0..buf.len() - 1];
                         if col != col_size - 1 {
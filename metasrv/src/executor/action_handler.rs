@@ -64,6 +64,7 @@ impl ActionHandler {
             // table
             StoreDoAction::CreateTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::DropTable(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::AddTableColumn(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTables(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTableExt(a) => s.serialize(self.handle(a).await?),
@@ -41,6 +41,7 @@ impl RequestHandler<UpsertKVAction> for ActionHandler {
                 seq: act.seq,
                 value: act.value.into(),
                 value_meta: act.value_meta,
+                content_type: act.content_type,
             },
         };
         let rst = self
@@ -66,6 +67,7 @@ impl RequestHandler<KVMetaAction> for ActionHandler {
                 seq: act.seq,
                 value: Operation::AsIs,
                 value_meta: act.value_meta,
+                content_type: None,
             },
         };
         let rst = self
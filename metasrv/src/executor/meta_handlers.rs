@@ -17,20 +17,24 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+use chrono::Utc;
 use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::ipc::write::common::IpcWriteOptions;
 use common_arrow::arrow_flight::utils::flight_data_from_arrow_schema;
 use common_arrow::arrow_flight::FlightData;
+use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_meta_api_vo::*;
 use common_metatypes::Cmd::CreateDatabase;
 use common_metatypes::Cmd::CreateTable;
 use common_metatypes::Cmd::DropDatabase;
 use common_metatypes::Cmd::DropTable;
+use common_metatypes::Cmd::UpdateTableSchema;
 use common_metatypes::Database;
 use common_metatypes::LogEntry;
 use common_metatypes::Table;
 use common_raft_store::state_machine::AppliedState;
+use common_store_api_sdk::meta_api_impl::AddTableColumnAction;
 use common_store_api_sdk::meta_api_impl::CreateDatabaseAction;
 use common_store_api_sdk::meta_api_impl::CreateTableAction;
 use common_store_api_sdk::meta_api_impl::DropDatabaseAction;
@@ -64,6 +68,7 @@ impl RequestHandler<CreateDatabaseAction> for ActionHandler {
                 db: Database {
                     database_id: 0,
                     database_engine: plan.engine.clone(),
+                    database_options: plan.options.clone(),
                     tables: HashMap::new(),
                 },
             },
@@ -112,6 +117,7 @@ impl RequestHandler<GetDatabaseAction> for ActionHandler {
                     database_id: db.database_id,
                     db: db_name,
                     engine: db.database_engine,
+                    options: db.database_options,
                 };
                 Ok(rst)
             }
@@ -168,12 +174,16 @@ impl RequestHandler<CreateTableAction> for ActionHandler {
         let options = IpcWriteOptions::default();
         let flight_data = flight_data_from_arrow_schema(&plan.schema.to_arrow(), &options);
 
+        // stamped here rather than by the state machine, every replica applies the same log entry
+        let now = Utc::now();
         let table = Table {
             table_id: 0,
             schema: flight_data.data_header,
             table_engine: plan.engine.clone(),
             table_options: plan.options.clone(),
             parts: Default::default(),
+            created_on: now,
+            updated_on: now,
         };
 
         let cr = LogEntry {
@@ -254,6 +264,70 @@ impl RequestHandler<DropTableAction> for ActionHandler {
     }
 }
 
+#[async_trait::async_trait]
+impl RequestHandler<AddTableColumnAction> for ActionHandler {
+    async fn handle(&self, act: AddTableColumnAction) -> common_exception::Result<()> {
+        let plan = act.plan;
+        let db_name = &plan.db;
+        let table_name = &plan.table;
+
+        let table_info = self
+            .handle(GetTableAction {
+                db: db_name.clone(),
+                table: table_name.clone(),
+            })
+            .await?;
+
+        let schema = table_info.schema;
+        if schema.column_with_name(plan.field.name()).is_some() {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "column {} already exists in table {}",
+                plan.field.name(),
+                table_name
+            )));
+        }
+
+        let mut fields = schema.fields().clone();
+        fields.push(plan.field.clone());
+        let new_schema = DataSchema::new_from(fields, schema.meta().clone());
+
+        info!(
+            "add column to table: {:}.{:}: {:?}",
+            db_name, table_name, plan.field
+        );
+
+        let options = IpcWriteOptions::default();
+        let flight_data = flight_data_from_arrow_schema(&new_schema.to_arrow(), &options);
+
+        let cr = LogEntry {
+            txid: None,
+            cmd: UpdateTableSchema {
+                db_name: db_name.clone(),
+                table_name: table_name.clone(),
+                schema: flight_data.data_header,
+                updated_on: Utc::now(),
+            },
+        };
+
+        let rst = self
+            .meta_node
+            .write(cr)
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        match rst {
+            AppliedState::Table { result, .. } => match result {
+                Some(_) => Ok(()),
+                None => Err(ErrorCode::UnknownTable(format!(
+                    "table not found: {:}",
+                    table_name
+                ))),
+            },
+            _ => Err(ErrorCode::MetaNodeInternalError("not a Table result")),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl RequestHandler<GetTableAction> for ActionHandler {
     async fn handle(&self, act: GetTableAction) -> common_exception::Result<TableInfo> {
@@ -287,6 +361,8 @@ impl RequestHandler<GetTableAction> for ActionHandler {
                     schema: Arc::new(arrow_schema.into()),
                     engine: table.table_engine.clone(),
                     options: table.table_options,
+                    created_on: table.created_on,
+                    updated_on: table.updated_on,
                 };
                 Ok(rst)
             }
@@ -318,6 +394,8 @@ impl RequestHandler<GetTableExtReq> for ActionHandler {
                     schema: Arc::new(arrow_schema.into()),
                     engine: table.table_engine.clone(),
                     options: table.table_options,
+                    created_on: table.created_on,
+                    updated_on: table.updated_on,
                 };
                 Ok(rst)
             }
@@ -342,6 +420,7 @@ impl RequestHandler<GetDatabasesAction> for ActionHandler {
                 database_id: db.database_id,
                 db: name.to_string(),
                 engine: db.database_engine.to_string(),
+                options: db.database_options.clone(),
             })
             .collect::<Vec<_>>())
     }
@@ -373,6 +452,8 @@ impl RequestHandler<GetTablesAction> for ActionHandler {
                     schema: Arc::new(arrow_schema.into()),
                     engine: tbl.table_engine.to_string(),
                     options: tbl.table_options.clone(),
+                    created_on: tbl.created_on,
+                    updated_on: tbl.updated_on,
                 };
 
                 acc.push(tbl_info);
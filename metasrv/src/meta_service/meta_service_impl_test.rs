@@ -267,7 +267,9 @@ async fn test_meta_cluster_write_on_non_leader() -> anyhow::Result<()> {
         let mn1 = MetaNode::boot_non_voter(1, &tc1.config.raft_config).await?;
         assert_meta_connection(&addr0).await?;
 
-        let resp = mn0.add_node(1, addr1.clone()).await?;
+        let resp = mn0
+            .add_node(1, addr1.clone(), tc1.config.raft_config.node_weight)
+            .await?;
         match resp {
             AppliedState::Node { prev: _, result } => {
                 assert_eq!(addr1.clone(), result.unwrap().address);
@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+use std::sync::Mutex;
+
 use async_raft::raft::Entry;
 use async_raft::raft::EntryConfigChange;
+use async_raft::raft::EntryNormal;
 use async_raft::raft::EntryPayload;
 use async_raft::raft::EntrySnapshotPointer;
 use async_raft::raft::MembershipConfig;
@@ -21,11 +25,18 @@ use async_raft::storage::HardState;
 use async_raft::LogId;
 use async_raft::RaftStorage;
 use common_base::tokio;
+use common_base::ProgressCallback;
+use common_base::ProgressValues;
+use common_metatypes::Cmd;
+use common_metatypes::LogEntry;
+use common_metatypes::MatchSeq;
+use common_metatypes::Operation;
 use common_raft_store::state_machine::testing::pretty_snapshot;
 use common_raft_store::state_machine::testing::snapshot_logs;
 use common_raft_store::state_machine::SerializableSnapshot;
 use common_raft_store::state_machine::StateMachineMetaKey::LastMembership;
 use common_raft_store::state_machine::StateMachineMetaValue;
+use common_raft_store::state_machine::SNAPSHOT_IMPORT_CHUNK_SIZE;
 use common_tracing::tracing;
 use maplit::btreeset;
 
@@ -461,6 +472,23 @@ async fn test_metasrv_install_snapshot() -> anyhow::Result<()> {
             ));
         }
 
+        tracing::info!("--- rejected because the snapshot is damaged");
+        {
+            ms.raft_state.write_state_machine_id(&(0, 0)).await?;
+            let mut damaged: SerializableSnapshot = serde_json::from_slice(&data)?;
+            let last = damaged.kvs.len() - 1;
+            damaged.kvs[last][1][0] ^= 0x01;
+            let damaged = serde_json::to_vec(&damaged)?;
+
+            let res = ms.install_snapshot(&damaged).await;
+            assert!(res.is_err(), "damaged snapshot disallows installing");
+            assert!(res
+                .unwrap_err()
+                .to_string()
+                .starts_with("Code: 2401, displayText = snapshot checksum mismatch"));
+            assert_eq!((0, 0), ms.raft_state.read_state_machine_id()?);
+        }
+
         tracing::info!("--- install snapshot");
         {
             ms.raft_state.write_state_machine_id(&(0, 0)).await?;
@@ -500,4 +528,106 @@ async fn test_metasrv_install_snapshot() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_metasrv_install_snapshot_with_progress() -> anyhow::Result<()> {
+    // - Create a metasrv with more kv pairs than a chunk of import
+    // - Create a snapshot
+    // - Install it into a new metasrv and check the progress reported
+
+    let (_log_guards, ut_span) = init_meta_ut!();
+    let _ent = ut_span.enter();
+
+    let (mut logs, _want) = snapshot_logs();
+    let n_extra = SNAPSHOT_IMPORT_CHUNK_SIZE * 2 + 100;
+    for i in 0..n_extra {
+        logs.push(Entry {
+            log_id: LogId {
+                term: 1,
+                index: 10 + i as u64,
+            },
+            payload: EntryPayload::Normal(EntryNormal {
+                data: LogEntry {
+                    txid: None,
+                    cmd: Cmd::UpsertKV {
+                        key: format!("k-{}", i),
+                        seq: MatchSeq::Any,
+                        value: Operation::Update(b"v".to_vec()),
+                        value_meta: None,
+                        content_type: None,
+                    },
+                },
+            }),
+        });
+    }
+
+    let id = 3;
+    let snap;
+    {
+        let mut tc = new_test_context();
+        tc.config.raft_config.id = id;
+
+        let ms = MetaRaftStore::open_create(&tc.config.raft_config, None, Some(())).await?;
+
+        for l in logs.iter() {
+            ms.log.insert(l).await?;
+            ms.state_machine.write().await.apply(l).await?;
+        }
+        snap = ms.do_log_compaction().await?;
+    }
+
+    let data = snap.snapshot.into_inner();
+    let ser_snap: SerializableSnapshot = serde_json::from_slice(&data)?;
+    let total = ser_snap.kvs.len();
+    assert!(total > n_extra);
+
+    tracing::info!("--- install snapshot with progress");
+    {
+        let mut tc = new_test_context();
+        tc.config.raft_config.id = id;
+
+        let ms = MetaRaftStore::open_create(&tc.config.raft_config, None, Some(())).await?;
+
+        let imported = Arc::new(Mutex::new(vec![]));
+        let totals = Arc::new(Mutex::new(vec![]));
+        let progress: ProgressCallback = {
+            let imported = imported.clone();
+            let totals = totals.clone();
+            let mut n = 0;
+            Box::new(move |values: &ProgressValues| {
+                n += values.read_rows;
+                imported.lock().unwrap().push(n);
+                totals.lock().unwrap().push(values.total_rows_to_read);
+            })
+        };
+
+        ms.raft_state.write_state_machine_id(&(0, 0)).await?;
+        ms.install_snapshot_with_progress(&data, Some(progress))
+            .await?;
+
+        let imported = imported.lock().unwrap().clone();
+        assert_eq!(
+            (total + SNAPSHOT_IMPORT_CHUNK_SIZE - 1) / SNAPSHOT_IMPORT_CHUNK_SIZE,
+            imported.len()
+        );
+        assert!(imported.windows(2).all(|w| w[0] < w[1]), "{:?}", imported);
+        assert_eq!(Some(&total), imported.last());
+
+        // the total is reported once, with the first chunk
+        let totals = totals.lock().unwrap().clone();
+        assert_eq!(total, totals.iter().sum::<usize>());
+        assert_eq!(total, totals[0]);
+
+        let last_applied = ms.state_machine.write().await.get_last_applied()?;
+        assert_eq!(
+            LogId {
+                term: 1,
+                index: 9 + n_extra as u64
+            },
+            last_applied
+        );
+    }
+
+    Ok(())
+}
+
 // TODO(xp): test finalize_snapshot_installation
@@ -39,6 +39,7 @@ use common_base::tokio::sync::Mutex;
 use common_base::tokio::sync::RwLock;
 use common_base::tokio::sync::RwLockWriteGuard;
 use common_base::tokio::task::JoinHandle;
+use common_base::ProgressCallback;
 use common_exception::prelude::ErrorCode;
 use common_exception::prelude::ToErrorCode;
 use common_metatypes::Cmd;
@@ -56,6 +57,7 @@ use common_raft_store::state_machine::AppliedState;
 use common_raft_store::state_machine::SerializableSnapshot;
 use common_raft_store::state_machine::Snapshot;
 use common_raft_store::state_machine::StateMachine;
+use common_raft_store::state_machine::SNAPSHOT_IMPORT_CHUNK_SIZE;
 use common_sled_store::get_sled_db;
 use common_tracing::tracing;
 use common_tracing::tracing::Instrument;
@@ -197,8 +199,18 @@ impl MetaRaftStore {
     }
 
     /// Install a snapshot to build a state machine from it and replace the old state machine with the new one.
-    #[tracing::instrument(level = "debug", skip(self, data))]
     pub async fn install_snapshot(&self, data: &[u8]) -> common_exception::Result<()> {
+        self.install_snapshot_with_progress(data, None).await
+    }
+
+    /// Same as `install_snapshot`, and reports the number of the kv pairs imported to `progress`,
+    /// the kv pairs are imported by chunks of `SNAPSHOT_IMPORT_CHUNK_SIZE`.
+    #[tracing::instrument(level = "debug", skip(self, data, progress))]
+    pub async fn install_snapshot_with_progress(
+        &self,
+        data: &[u8],
+        progress: Option<ProgressCallback>,
+    ) -> common_exception::Result<()> {
         let mut sm = self.state_machine.write().await;
 
         let (sm_id, prev_sm_id) = self.raft_state.read_state_machine_id()?;
@@ -213,27 +225,28 @@ impl MetaRaftStore {
 
         tracing::debug!("snapshot data len: {}", data.len());
 
-        let snap: SerializableSnapshot = serde_json::from_slice(data)?;
-
         // If not finished, clean up the new tree.
         self.raft_state
             .write_state_machine_id(&(sm_id, new_sm_id))
             .await?;
 
         let new_sm = StateMachine::open(&self.config, new_sm_id).await?;
-        tracing::info!(
-            "insert all key-value into new state machine, n={}",
-            snap.kvs.len()
-        );
+        tracing::info!("insert all key-value into new state machine");
 
         let tree = &new_sm.sm_tree.tree;
-        let nkvs = snap.kvs.len();
-        for x in snap.kvs.into_iter() {
-            let k = &x[0];
-            let v = &x[1];
-            tree.insert(k, v.clone())
-                .map_err_to_code(ErrorCode::MetaStoreDamaged, || "fail to insert snapshot")?;
-        }
+        let nkvs =
+            match SerializableSnapshot::import(data, tree, SNAPSHOT_IMPORT_CHUNK_SIZE, progress) {
+                Ok(nkvs) => nkvs,
+                Err(cause) => {
+                    // a damaged snapshot is found as it is imported, the new tree is dropped
+                    drop(new_sm);
+                    StateMachine::clean(&self.config, new_sm_id)?;
+                    self.raft_state
+                        .write_state_machine_id(&(sm_id, sm_id))
+                        .await?;
+                    return Err(cause);
+                }
+            };
 
         tracing::info!(
             "installed state machine from snapshot, no_kvs: {} last_applied: {}",
@@ -904,7 +917,8 @@ impl MetaNode {
 
         tracing::info!("initialized cluster, rst: {:?}", rst);
 
-        self.add_node(node_id, addr).await?;
+        self.add_node(node_id, addr, self.sto.config.node_weight)
+            .await?;
 
         Ok(())
     }
@@ -974,13 +988,14 @@ impl MetaNode {
         sm.get_node(node_id)
     }
 
-    /// Add a new node into this cluster.
+    /// Add a new node into this cluster, with the weight the node is configured with.
     /// The node info is committed with raft, thus it must be called on an initialized node.
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn add_node(
         &self,
         node_id: NodeId,
         addr: String,
+        weight: u64,
     ) -> common_exception::Result<AppliedState> {
         // TODO: use txid?
         let _resp = self
@@ -991,6 +1006,7 @@ impl MetaNode {
                     node: Node {
                         name: "".to_string(),
                         address: addr,
+                        weight,
                     },
                 },
             })
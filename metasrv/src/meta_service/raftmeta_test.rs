@@ -302,6 +302,7 @@ async fn test_meta_node_snapshot_replication() -> anyhow::Result<()> {
                 seq: MatchSeq::Any,
                 value: Some(b"v".to_vec()).into(),
                 value_meta: None,
+                content_type: None,
             },
         })
         .await?;
@@ -626,10 +627,13 @@ async fn setup_non_voter(
 
     {
         // add node to cluster as a non-voter
-        let resp = leader.add_node(id, addr.clone()).await?;
+        let weight = tc.config.raft_config.node_weight;
+        let resp = leader.add_node(id, addr.clone(), weight).await?;
         match resp {
             AppliedState::Node { prev: _, result } => {
-                assert_eq!(addr.clone(), result.unwrap().address);
+                let node = result.unwrap();
+                assert_eq!(addr.clone(), node.address);
+                assert_eq!(weight, node.weight);
             }
             _ => {
                 panic!("expect node")
@@ -46,7 +46,7 @@ async fn test_restart() -> anyhow::Result<()> {
     tracing::info!("--- upsert kv");
     {
         let res = client
-            .upsert_kv("foo", MatchSeq::Any, Some(b"bar".to_vec()), None)
+            .upsert_kv("foo", MatchSeq::Any, Some(b"bar".to_vec()), None, None)
             .await;
 
         tracing::debug!("set kv res: {:?}", res);
@@ -56,7 +56,8 @@ async fn test_restart() -> anyhow::Result<()> {
                 prev: None,
                 result: Some((1, KVValue {
                     meta: None,
-                    value: b"bar".to_vec()
+                    value: b"bar".to_vec(),
+                    content_type: None,
                 }))
             },
             res,
@@ -72,7 +73,8 @@ async fn test_restart() -> anyhow::Result<()> {
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"bar".to_vec()
+                value: b"bar".to_vec(),
+                content_type: None,
             })),
             res.result,
             "get kv"
@@ -110,7 +112,8 @@ async fn test_restart() -> anyhow::Result<()> {
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"bar".to_vec()
+                value: b"bar".to_vec(),
+                content_type: None,
             })),
             res.result,
             "get kv"
@@ -133,10 +136,10 @@ async fn test_generic_kv_mget() -> anyhow::Result<()> {
         let client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
 
         client
-            .upsert_kv("k1", MatchSeq::Any, Some(b"v1".to_vec()), None)
+            .upsert_kv("k1", MatchSeq::Any, Some(b"v1".to_vec()), None, None)
             .await?;
         client
-            .upsert_kv("k2", MatchSeq::Any, Some(b"v2".to_vec()), None)
+            .upsert_kv("k2", MatchSeq::Any, Some(b"v2".to_vec()), None, None)
             .await?;
 
         let res = client
@@ -145,12 +148,14 @@ async fn test_generic_kv_mget() -> anyhow::Result<()> {
         assert_eq!(res.result, vec![
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             // NOTE, the sequence number is increased globally (inside the namespace of generic kv)
             Some((2, KVValue {
                 meta: None,
-                value: b"v2".to_vec()
+                value: b"v2".to_vec(),
+                content_type: None,
             })),
         ]);
 
@@ -160,7 +165,8 @@ async fn test_generic_kv_mget() -> anyhow::Result<()> {
         assert_eq!(res.result, vec![
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             None
         ]);
@@ -183,7 +189,7 @@ async fn test_generic_kv_list() -> anyhow::Result<()> {
         let mut values = vec![];
         {
             client
-                .upsert_kv("t", MatchSeq::Any, Some("".as_bytes().to_vec()), None)
+                .upsert_kv("t", MatchSeq::Any, Some("".as_bytes().to_vec()), None, None)
                 .await?;
 
             for i in 0..9 {
@@ -191,11 +197,17 @@ async fn test_generic_kv_list() -> anyhow::Result<()> {
                 let val = format!("val_{}", i);
                 values.push(val.clone());
                 client
-                    .upsert_kv(&key, MatchSeq::Any, Some(val.as_bytes().to_vec()), None)
+                    .upsert_kv(
+                        &key,
+                        MatchSeq::Any,
+                        Some(val.as_bytes().to_vec()),
+                        None,
+                        None,
+                    )
                     .await?;
             }
             client
-                .upsert_kv("v", MatchSeq::Any, Some(b"".to_vec()), None)
+                .upsert_kv("v", MatchSeq::Any, Some(b"".to_vec()), None, None)
                 .await?;
         }
 
@@ -208,7 +220,8 @@ async fn test_generic_kv_list() -> anyhow::Result<()> {
                 .iter()
                 .map(|v| KVValue {
                     meta: None,
-                    value: v.as_bytes().to_vec()
+                    value: v.as_bytes().to_vec(),
+                    content_type: None,
                 })
                 .collect::<Vec<_>>()
         );
@@ -230,7 +243,7 @@ async fn test_generic_kv_delete() -> anyhow::Result<()> {
 
         let test_key = "test_key";
         client
-            .upsert_kv(test_key, MatchSeq::Any, Some(b"v1".to_vec()), None)
+            .upsert_kv(test_key, MatchSeq::Any, Some(b"v1".to_vec()), None, None)
             .await?;
 
         let current = client.get_kv(test_key).await?;
@@ -238,13 +251,13 @@ async fn test_generic_kv_delete() -> anyhow::Result<()> {
             // seq mismatch
             let wrong_seq = Some(seq + 1);
             let res = client
-                .upsert_kv(test_key, wrong_seq.into(), None, None)
+                .upsert_kv(test_key, wrong_seq.into(), None, None, None)
                 .await?;
             assert_eq!(res.prev, res.result);
 
             // seq match
             let res = client
-                .upsert_kv(test_key, MatchSeq::Exact(seq), None, None)
+                .upsert_kv(test_key, MatchSeq::Exact(seq), None, None, None)
                 .await?;
             assert!(res.result.is_none());
 
@@ -257,24 +270,25 @@ async fn test_generic_kv_delete() -> anyhow::Result<()> {
 
         // key not exist
         let res = client
-            .upsert_kv("not exists", MatchSeq::Any, None, None)
+            .upsert_kv("not exists", MatchSeq::Any, None, None, None)
             .await?;
         assert_eq!(None, res.prev);
         assert_eq!(None, res.result);
 
         // do not care seq
         client
-            .upsert_kv(test_key, MatchSeq::Any, Some(b"v2".to_vec()), None)
+            .upsert_kv(test_key, MatchSeq::Any, Some(b"v2".to_vec()), None, None)
             .await?;
 
         let res = client
-            .upsert_kv(test_key, MatchSeq::Any, None, None)
+            .upsert_kv(test_key, MatchSeq::Any, None, None, None)
             .await?;
         assert_eq!(
             (
                 Some((2, KVValue {
                     meta: None,
-                    value: b"v2".to_vec()
+                    value: b"v2".to_vec(),
+                    content_type: None,
                 })),
                 None
             ),
@@ -299,17 +313,18 @@ async fn test_generic_kv_update() -> anyhow::Result<()> {
         let test_key = "test_key_for_update";
 
         let r = client
-            .upsert_kv(test_key, MatchSeq::GE(1), Some(b"v1".to_vec()), None)
+            .upsert_kv(test_key, MatchSeq::GE(1), Some(b"v1".to_vec()), None, None)
             .await?;
         assert_eq!((None, None), (r.prev, r.result), "not changed");
 
         let r = client
-            .upsert_kv(test_key, MatchSeq::Any, Some(b"v1".to_vec()), None)
+            .upsert_kv(test_key, MatchSeq::Any, Some(b"v1".to_vec()), None, None)
             .await?;
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.result
         );
@@ -322,57 +337,70 @@ async fn test_generic_kv_update() -> anyhow::Result<()> {
                 MatchSeq::Exact(seq + 1),
                 Some(b"v2".to_vec()),
                 None,
+                None,
             )
             .await?;
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.prev
         );
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.result
         );
 
         // matched seq
         let r = client
-            .upsert_kv(test_key, MatchSeq::Exact(seq), Some(b"v2".to_vec()), None)
+            .upsert_kv(
+                test_key,
+                MatchSeq::Exact(seq),
+                Some(b"v2".to_vec()),
+                None,
+                None,
+            )
             .await?;
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.prev
         );
         assert_eq!(
             Some((2, KVValue {
                 meta: None,
-                value: b"v2".to_vec()
+                value: b"v2".to_vec(),
+                content_type: None,
             })),
             r.result
         );
 
         // blind update
         let r = client
-            .upsert_kv(test_key, MatchSeq::GE(1), Some(b"v3".to_vec()), None)
+            .upsert_kv(test_key, MatchSeq::GE(1), Some(b"v3".to_vec()), None, None)
             .await?;
         assert_eq!(
             Some((2, KVValue {
                 meta: None,
-                value: b"v2".to_vec()
+                value: b"v2".to_vec(),
+                content_type: None,
             })),
             r.prev
         );
         assert_eq!(
             Some((3, KVValue {
                 meta: None,
-                value: b"v3".to_vec()
+                value: b"v3".to_vec(),
+                content_type: None,
             })),
             r.result
         );
@@ -382,7 +410,8 @@ async fn test_generic_kv_update() -> anyhow::Result<()> {
         assert!(kv.result.is_some());
         assert_eq!(kv.result.unwrap().1, KVValue {
             meta: None,
-            value: b"v3".to_vec()
+            value: b"v3".to_vec(),
+            content_type: None,
         });
     }
     Ok(())
@@ -410,12 +439,13 @@ async fn test_generic_kv_update_meta() -> anyhow::Result<()> {
             .as_secs();
 
         let r = client
-            .upsert_kv(test_key, MatchSeq::Any, Some(b"v1".to_vec()), None)
+            .upsert_kv(test_key, MatchSeq::Any, Some(b"v1".to_vec()), None, None)
             .await?;
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.result
         );
@@ -435,14 +465,16 @@ async fn test_generic_kv_update_meta() -> anyhow::Result<()> {
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.prev
         );
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.result
         );
@@ -461,7 +493,8 @@ async fn test_generic_kv_update_meta() -> anyhow::Result<()> {
         assert_eq!(
             Some((1, KVValue {
                 meta: None,
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.prev
         );
@@ -470,7 +503,8 @@ async fn test_generic_kv_update_meta() -> anyhow::Result<()> {
                 meta: Some(KVMeta {
                     expire_at: Some(now + 20)
                 }),
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             })),
             r.result
         );
@@ -483,7 +517,8 @@ async fn test_generic_kv_update_meta() -> anyhow::Result<()> {
                 meta: Some(KVMeta {
                     expire_at: Some(now + 20)
                 }),
-                value: b"v1".to_vec()
+                value: b"v1".to_vec(),
+                content_type: None,
             }),
             kv.result.unwrap(),
         );
@@ -491,6 +526,73 @@ async fn test_generic_kv_update_meta() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_generic_kv_content_type() -> anyhow::Result<()> {
+    // The content type is stored along with the value, and kept when only meta is updated.
+
+    let (_log_guards, ut_span) = init_meta_ut!();
+    let _ent = ut_span.enter();
+    {
+        let span = tracing::span!(tracing::Level::INFO, "test_generic_kv_content_type");
+        let _ent = span.enter();
+
+        let (_tc, addr) = metasrv::tests::start_metasrv().await?;
+
+        let client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+
+        let test_key = "test_key_for_content_type";
+
+        tracing::info!("--- upsert with a content type");
+        let r = client
+            .upsert_kv(
+                test_key,
+                MatchSeq::Any,
+                Some(b"{}".to_vec()),
+                None,
+                Some("json".to_string()),
+            )
+            .await?;
+        assert_eq!(
+            Some((1, KVValue {
+                meta: None,
+                value: b"{}".to_vec(),
+                content_type: Some("json".to_string()),
+            })),
+            r.result
+        );
+
+        let kv = client.get_kv(test_key).await?;
+        assert_eq!(Some("json".to_string()), kv.result.unwrap().1.content_type);
+
+        tracing::info!("--- updating meta keeps the content type");
+        let r = client
+            .update_kv_meta(test_key, MatchSeq::Exact(1), None)
+            .await?;
+        assert_eq!(
+            Some((2, KVValue {
+                meta: None,
+                value: b"{}".to_vec(),
+                content_type: Some("json".to_string()),
+            })),
+            r.result
+        );
+
+        tracing::info!("--- upserting a value without a content type clears it");
+        let r = client
+            .upsert_kv(test_key, MatchSeq::Any, Some(b"v".to_vec()), None, None)
+            .await?;
+        assert_eq!(
+            Some((3, KVValue {
+                meta: None,
+                value: b"v".to_vec(),
+                content_type: None,
+            })),
+            r.result
+        );
+    }
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_generic_kv_timeout() -> anyhow::Result<()> {
     // - Test get  expired and non-expired.
@@ -521,6 +623,7 @@ async fn test_generic_kv_timeout() -> anyhow::Result<()> {
                 Some(KVMeta {
                     expire_at: Some(now + 1),
                 }),
+                None,
             )
             .await?;
 
@@ -553,6 +656,7 @@ async fn test_generic_kv_timeout() -> anyhow::Result<()> {
                     Some(KVMeta {
                         expire_at: Some(now - 1),
                     }),
+                    None,
                 )
                 .await?;
             client
@@ -563,6 +667,7 @@ async fn test_generic_kv_timeout() -> anyhow::Result<()> {
                     Some(KVMeta {
                         expire_at: Some(now + 2),
                     }),
+                    None,
                 )
                 .await?;
 
@@ -576,7 +681,8 @@ async fn test_generic_kv_timeout() -> anyhow::Result<()> {
                     meta: Some(KVMeta {
                         expire_at: Some(now + 2)
                     }),
-                    value: b"v2".to_vec()
+                    value: b"v2".to_vec(),
+                    content_type: None,
                 })),
             ]);
         }
@@ -599,6 +705,7 @@ async fn test_generic_kv_timeout() -> anyhow::Result<()> {
                     Some(KVMeta {
                         expire_at: Some(now - 1),
                     }),
+                    None,
                 )
                 .await?;
 
@@ -625,13 +732,14 @@ async fn test_generic_kv() -> anyhow::Result<()> {
         {
             // write
             let res = client
-                .upsert_kv("foo", MatchSeq::Any, Some(b"bar".to_vec()), None)
+                .upsert_kv("foo", MatchSeq::Any, Some(b"bar".to_vec()), None, None)
                 .await?;
             assert_eq!(None, res.prev);
             assert_eq!(
                 Some((1, KVValue {
                     meta: None,
-                    value: b"bar".to_vec()
+                    value: b"bar".to_vec(),
+                    content_type: None,
                 })),
                 res.result
             );
@@ -640,17 +748,19 @@ async fn test_generic_kv() -> anyhow::Result<()> {
         {
             // write fails with unmatched seq
             let res = client
-                .upsert_kv("foo", MatchSeq::Exact(2), Some(b"bar".to_vec()), None)
+                .upsert_kv("foo", MatchSeq::Exact(2), Some(b"bar".to_vec()), None, None)
                 .await?;
             assert_eq!(
                 (
                     Some((1, KVValue {
                         meta: None,
-                        value: b"bar".to_vec()
+                        value: b"bar".to_vec(),
+                        content_type: None,
                     })),
                     Some((1, KVValue {
                         meta: None,
                         value: b"bar".to_vec(),
+                        content_type: None,
                     })),
                 ),
                 (res.prev, res.result),
@@ -661,12 +771,13 @@ async fn test_generic_kv() -> anyhow::Result<()> {
         {
             // write done with matching seq
             let res = client
-                .upsert_kv("foo", MatchSeq::Exact(1), Some(b"wow".to_vec()), None)
+                .upsert_kv("foo", MatchSeq::Exact(1), Some(b"wow".to_vec()), None, None)
                 .await?;
             assert_eq!(
                 Some((1, KVValue {
                     meta: None,
-                    value: b"bar".to_vec()
+                    value: b"bar".to_vec(),
+                    content_type: None,
                 })),
                 res.prev,
                 "old value"
@@ -674,7 +785,8 @@ async fn test_generic_kv() -> anyhow::Result<()> {
             assert_eq!(
                 Some((2, KVValue {
                     meta: None,
-                    value: b"wow".to_vec()
+                    value: b"wow".to_vec(),
+                    content_type: None,
                 })),
                 res.result,
                 "new value"
@@ -14,6 +14,8 @@
 
 use axum::body::Body;
 use axum::extract::Query;
+use axum::http::Response;
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use common_base::tokio::time::Duration;
 use common_base::Profiling;
@@ -45,8 +47,17 @@ pub async fn debug_pprof_handler(req: Option<Query<PProfRequest>>) -> impl IntoR
             Profiling::create(duration, i32::from(PProfRequest::default_frequency()))
         }
     };
-    let body = profile.dump_flamegraph().await.unwrap();
-
-    tracing::info!("finished pprof request");
-    Body::from(body).into_response()
+    match profile.dump_flamegraph().await {
+        Ok(body) => {
+            tracing::info!("finished pprof request");
+            Body::from(body).into_response()
+        }
+        Err(cause) => {
+            tracing::error!("failed to run pprof request: {}", cause);
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(format!("Failed to profile. Error: {}", cause)))
+                .unwrap()
+        }
+    }
 }
@@ -24,6 +24,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_streams::SendableDataBlockStream;
 use tonic::transport::channel::Channel;
+use tonic::Code;
 use tonic::Request;
 use tonic::Streaming;
 
@@ -73,7 +74,15 @@ impl FlightClient {
         let mut request = Request::new(action);
         request.set_timeout(Duration::from_secs(timeout));
 
-        let response = self.inner.do_action(request).await?;
+        // The node is not reachable, the action never arrives at it.
+        let response =
+            self.inner
+                .do_action(request)
+                .await
+                .map_err(|status| match status.code() {
+                    Code::Unavailable => ErrorCode::CannotConnectNode(status.message().to_string()),
+                    _ => ErrorCode::from(status),
+                })?;
 
         match response.into_inner().message().await? {
             Some(response) => Ok(response.body),
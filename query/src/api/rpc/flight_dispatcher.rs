@@ -88,7 +88,11 @@ impl DatabendQueryFlightDispatcher {
         let stage_id = action.get_stage_id();
         let action_sinks = action.get_sinks();
         let data_schema = action.get_plan().schema();
-        self.create_stage_streams(&query_id, &stage_id, &data_schema, &action_sinks);
+
+        // The action is re-issued if its response is lost, the stage runs only once.
+        if !self.create_stage_streams(&query_id, &stage_id, &data_schema, &action_sinks) {
+            return Ok(());
+        }
 
         match action.get_sinks().len() {
             0 => Err(ErrorCode::LogicalError("")),
@@ -105,7 +109,11 @@ impl DatabendQueryFlightDispatcher {
         let stage_id = action.get_stage_id();
         let action_sinks = action.get_sinks();
         let data_schema = action.get_plan().schema();
-        self.create_stage_streams(&query_id, &stage_id, &data_schema, &action_sinks);
+
+        // The action is re-issued if its response is lost, the stage runs only once.
+        if !self.create_stage_streams(&query_id, &stage_id, &data_schema, &action_sinks) {
+            return Ok(());
+        }
 
         match action.get_sinks().len() {
             0 => Err(ErrorCode::LogicalError("")),
@@ -250,17 +258,20 @@ impl DatabendQueryFlightDispatcher {
         Ok(())
     }
 
+    /// Returns false if the streams of the stage are created already.
     fn create_stage_streams(
         &self,
         query_id: &str,
         stage_id: &str,
         schema: &DataSchemaRef,
         streams_name: &[String],
-    ) {
+    ) -> bool {
         let stage_name = format!("{}/{}", query_id, stage_id);
-        self.stages_notify
-            .write()
-            .insert(stage_name.clone(), Arc::new(Notify::new()));
+        let mut stages_notify = self.stages_notify.write();
+        if stages_notify.contains_key(&stage_name) {
+            return false;
+        }
+        stages_notify.insert(stage_name.clone(), Arc::new(Notify::new()));
 
         let mut streams = self.streams.write();
 
@@ -274,6 +285,8 @@ impl DatabendQueryFlightDispatcher {
                 rx,
             });
         }
+
+        true
     }
 }
 
@@ -150,6 +150,50 @@ async fn test_run_shuffle_action_with_scatter() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_run_shuffle_action_twice() -> Result<()> {
+    if let (Some(query_id), Some(stage_id), Some(stream_id)) = generate_uuids(3) {
+        let flight_dispatcher = DatabendQueryFlightDispatcher::create();
+        let sessions = SessionManagerBuilder::create().build()?;
+
+        // A re-issued action of the same stage is ignored.
+        for _ in 0..2 {
+            let rpc_session = sessions.create_rpc_session(query_id.clone(), false)?;
+            flight_dispatcher
+                .shuffle_action(
+                    rpc_session,
+                    FlightAction::PrepareShuffleAction(ShuffleAction {
+                        query_id: query_id.clone(),
+                        stage_id: stage_id.clone(),
+                        plan: parse_query("SELECT number FROM numbers(3)")?,
+                        sinks: vec![stream_id.clone()],
+                        scatters_expression: Expression::create_literal(DataValue::UInt64(Some(1))),
+                    }),
+                )
+                .await?;
+        }
+
+        let stream = stream_ticket(&query_id, &stage_id, &stream_id);
+        let receiver = flight_dispatcher.get_stream(&stream)?;
+        let receiver_stream = ReceiverStream::new(receiver);
+        let collect_data_blocks = receiver_stream.collect::<Result<Vec<_>>>();
+
+        let expect = vec![
+            "+--------+",
+            "| number |",
+            "+--------+",
+            "| 0      |",
+            "| 1      |",
+            "| 2      |",
+            "+--------+",
+        ];
+
+        assert_blocks_eq(expect, &collect_data_blocks.await?);
+    }
+
+    Ok(())
+}
+
 fn stream_ticket(query_id: &str, stage_id: &str, stream: &str) -> StreamTicket {
     StreamTicket {
         query_id: query_id.to_string(),
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api_vo::CreateDatabaseReply;
 use common_metatypes::MetaId;
@@ -21,6 +22,7 @@ use common_metatypes::MetaVersion;
 use common_planners::CreateDatabasePlan;
 use common_planners::DropDatabasePlan;
 
+use crate::catalogs::impls::util::like_pattern::filter_like;
 use crate::catalogs::Database;
 use crate::catalogs::TableFunctionMeta;
 use crate::catalogs::TableMeta;
@@ -43,12 +45,42 @@ pub trait Catalog {
     // Get all the databases.
     fn get_databases(&self) -> Result<Vec<Arc<dyn Database>>>;
 
+    // Get the databases whose name matches the `LIKE` pattern, or all of them if no pattern given.
+    fn get_databases_like(&self, pattern: Option<&str>) -> Result<Vec<Arc<dyn Database>>> {
+        let databases = self.get_databases()?;
+        match pattern {
+            None => Ok(databases),
+            Some(pattern) => filter_like(databases, pattern, |db| db.name()),
+        }
+    }
+
     // Get the database by name.
     fn get_database(&self, db_name: &str) -> Result<Arc<dyn Database>>;
 
+    // Check if the database exists, errors other than UnknownDatabase are returned as is.
+    fn database_exists(&self, db_name: &str) -> Result<bool> {
+        match self.get_database(db_name) {
+            Ok(_) => Ok(true),
+            Err(e) if e.code() == ErrorCode::UnknownDatabase("").code() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     // Get one table by db and table name.
     fn get_table(&self, db_name: &str, table_name: &str) -> Result<Arc<TableMeta>>;
 
+    // Check if the table exists, errors other than UnknownTable are returned as is,
+    // e.g., UnknownDatabase if the database does not exist.
+    //
+    // Catalogs should override it if there is a cheaper way than getting the table.
+    fn table_exists(&self, db_name: &str, table_name: &str) -> Result<bool> {
+        match self.get_table(db_name, table_name) {
+            Ok(_) => Ok(true),
+            Err(e) if e.code() == ErrorCode::UnknownTable("").code() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     fn get_table_by_id(
         &self,
         db_name: &str,
@@ -0,0 +1,125 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api_vo::CreateDatabaseReply;
+use common_metatypes::MetaId;
+use common_metatypes::MetaVersion;
+use common_planners::CreateDatabasePlan;
+use common_planners::DropDatabasePlan;
+
+use crate::catalogs::Catalog;
+use crate::catalogs::Database;
+use crate::catalogs::TableMeta;
+use crate::datasources::database_engine::DatabaseEngine;
+use crate::datasources::database_engine_registry::EngineDescription;
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+#[tokio::test]
+async fn test_catalog_exists() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    for sql in [
+        "create database db1",
+        "create table db1.t1(a int) Engine = Null",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    let catalog = ctx.get_catalog();
+    assert!(catalog.database_exists("db1")?);
+    assert!(catalog.database_exists("system")?);
+    assert!(!catalog.database_exists("db2")?);
+
+    assert!(catalog.table_exists("db1", "t1")?);
+    assert!(catalog.table_exists("system", "tables")?);
+    assert!(!catalog.table_exists("db1", "t2")?);
+
+    // the table is not in a database that does not exist, but the error tells why
+    let res = catalog.table_exists("db2", "t1");
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::UnknownDatabase("").code())
+    );
+
+    Ok(())
+}
+
+/// A catalog whose meta store is unreachable.
+struct UnreachableCatalog;
+
+impl Catalog for UnreachableCatalog {
+    fn register_db_engine(&self, _: &str, _: Arc<dyn DatabaseEngine>) -> Result<()> {
+        unimplemented!()
+    }
+
+    fn get_databases(&self) -> Result<Vec<Arc<dyn Database>>> {
+        unimplemented!()
+    }
+
+    fn get_database(&self, _db_name: &str) -> Result<Arc<dyn Database>> {
+        Err(ErrorCode::CannotConnectNode("connection refused"))
+    }
+
+    fn get_table(&self, _db_name: &str, _table_name: &str) -> Result<Arc<TableMeta>> {
+        Err(ErrorCode::CannotConnectNode("connection refused"))
+    }
+
+    fn get_table_by_id(
+        &self,
+        _db_name: &str,
+        _table_id: MetaId,
+        _table_version: Option<MetaVersion>,
+    ) -> Result<Arc<TableMeta>> {
+        unimplemented!()
+    }
+
+    fn create_database(&self, _plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
+        unimplemented!()
+    }
+
+    fn drop_database(&self, _plan: DropDatabasePlan) -> Result<()> {
+        unimplemented!()
+    }
+
+    fn get_db_engines(&self) -> Result<Vec<EngineDescription>> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_catalog_exists_propagates_errors() -> Result<()> {
+    let catalog = UnreachableCatalog;
+
+    let res = catalog.database_exists("db1");
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::CannotConnectNode("").code())
+    );
+
+    let res = catalog.table_exists("db1", "t1");
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::CannotConnectNode("").code())
+    );
+
+    Ok(())
+}
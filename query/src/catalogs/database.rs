@@ -17,9 +17,11 @@ use std::sync::Arc;
 use common_exception::Result;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
 
+use crate::catalogs::impls::util::like_pattern::filter_like;
 use crate::catalogs::TableMeta;
 
 pub trait Database: Sync + Send {
@@ -41,7 +43,25 @@ pub trait Database: Sync + Send {
     /// Get all tables.
     fn get_tables(&self) -> Result<Vec<Arc<TableMeta>>>;
 
+    /// Get the tables whose name matches the `LIKE` pattern, or all of them if no pattern given.
+    fn get_tables_like(&self, pattern: Option<&str>) -> Result<Vec<Arc<TableMeta>>> {
+        let tables = self.get_tables()?;
+        match pattern {
+            None => Ok(tables),
+            Some(pattern) => filter_like(tables, pattern, |tbl| tbl.raw().name()),
+        }
+    }
+
     /// DDL
     fn create_table(&self, plan: CreateTablePlan) -> Result<()>;
     fn drop_table(&self, plan: DropTablePlan) -> Result<()>;
+    fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> Result<()>;
+
+    /// Points the table to a new snapshot, see `MetaBackend::commit_table_snapshot`.
+    fn commit_table_snapshot(
+        &self,
+        table_name: &str,
+        prev_snapshot_loc: Option<&str>,
+        new_snapshot_loc: &str,
+    ) -> Result<()>;
 }
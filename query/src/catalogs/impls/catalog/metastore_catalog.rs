@@ -25,6 +25,8 @@ use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
 use common_planners::CreateDatabasePlan;
 use common_planners::DropDatabasePlan;
+use common_planners::DropTablePlan;
+use common_tracing::tracing;
 
 use crate::catalogs::catalog::Catalog;
 use crate::catalogs::impls::meta_backends::EmbeddedMetaBackend;
@@ -84,7 +86,7 @@ impl MetaStoreCatalog {
         let db_engine_registry = Arc::new(DatabaseEngineRegistry::new());
         let table_engine_registry = Arc::new(TableEngineRegistry::new());
 
-        register_prelude_tbl_engines(&table_engine_registry)?;
+        register_prelude_tbl_engines(&table_engine_registry, &conf)?;
         register_prelude_db_engines(
             &db_engine_registry,
             meta_backend.clone(),
@@ -189,8 +191,51 @@ impl Catalog for MetaStoreCatalog {
 
     fn drop_database(&self, plan: DropDatabasePlan) -> Result<()> {
         let name = plan.db.clone();
+
+        let db = match self.get_database(&name) {
+            Ok(db) => Some(db),
+            // the database does not exist, let the backend deal with it (e.g. IF EXISTS)
+            Err(e) if e.code() == ErrorCode::UnknownDatabase("").code() => None,
+            Err(e) => return Err(e),
+        };
+
+        let mut dropped_tables = vec![];
+        if let Some(db) = db {
+            let tables = db.get_tables()?;
+            if !tables.is_empty() && !plan.cascade {
+                return Err(ErrorCode::DatabaseNotEmpty(format!(
+                    "Database {} is not empty, it has {} table(s), use DROP DATABASE ... CASCADE to drop it with its tables",
+                    name,
+                    tables.len()
+                )));
+            }
+
+            for table in tables {
+                db.drop_table(DropTablePlan {
+                    if_exists: true,
+                    db: name.clone(),
+                    table: table.raw().name().to_string(),
+                })?;
+                dropped_tables.push(table.raw().clone());
+            }
+        }
+
         self.meta_backend.drop_database(plan)?;
         self.db_instances.write().remove(&name);
+
+        // the data of the dropped tables is removed in the background, a failure of it
+        // leaves files behind, but does not fail the (committed) drop
+        for table in dropped_tables {
+            common_base::tokio::spawn(async move {
+                if let Err(cause) = table.purge().await {
+                    tracing::warn!(
+                        "failed to purge the data of dropped table {}: {}",
+                        table.name(),
+                        cause
+                    );
+                }
+            });
+        }
         Ok(())
     }
 
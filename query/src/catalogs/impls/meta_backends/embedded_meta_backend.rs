@@ -16,6 +16,8 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono::Utc;
+use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_infallible::RwLock;
 use common_meta_api_vo::CreateDatabaseReply;
@@ -24,13 +26,18 @@ use common_meta_api_vo::DatabaseInfo;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
 
 use crate::catalogs::meta_backend::MetaBackend;
+use crate::catalogs::meta_backend::TABLE_META_KEY_SNAPSHOT_LOCATION;
+use crate::catalogs::meta_id_ranges::LOCAL_DB_ID_BEGIN;
+use crate::catalogs::meta_id_ranges::LOCAL_DB_ID_END;
 use crate::catalogs::meta_id_ranges::LOCAL_TBL_ID_BEGIN;
+use crate::catalogs::meta_id_ranges::LOCAL_TBL_ID_END;
 
 struct InMemoryTableInfo {
     pub(crate) name2meta: HashMap<String, Arc<TableInfo>>,
@@ -55,25 +62,60 @@ impl InMemoryTableInfo {
 
 type Databases = Arc<RwLock<HashMap<String, (Arc<DatabaseInfo>, InMemoryTableInfo)>>>;
 
+/// A monotonic id sequence in range `[begin, end)`, it never wraps around.
+struct IdSequence {
+    next: RwLock<u64>,
+    end: u64,
+    name: &'static str,
+}
+
+impl IdSequence {
+    fn create(begin: u64, end: u64, name: &'static str) -> Self {
+        Self {
+            next: RwLock::new(begin),
+            end,
+            name,
+        }
+    }
+
+    fn next_id(&self) -> common_exception::Result<u64> {
+        let mut next = self.next.write();
+        if *next >= self.end {
+            return Err(ErrorCode::Overflow(format!(
+                "Local {} ids are exhausted, the max is {}",
+                self.name,
+                self.end - 1
+            )));
+        }
+        let id = *next;
+        *next += 1;
+        Ok(id)
+    }
+}
+
 pub struct EmbeddedMetaBackend {
     databases: Databases,
-    tbl_id_seq: Arc<RwLock<u64>>,
+    db_id_seq: IdSequence,
+    tbl_id_seq: IdSequence,
 }
 
 impl EmbeddedMetaBackend {
     pub fn new() -> Self {
-        let tbl_id_seq = Arc::new(RwLock::new(LOCAL_TBL_ID_BEGIN));
+        Self::with_id_ranges(
+            (LOCAL_DB_ID_BEGIN, LOCAL_DB_ID_END),
+            (LOCAL_TBL_ID_BEGIN, LOCAL_TBL_ID_END),
+        )
+    }
+
+    /// Creates a backend whose database ids and table ids are allocated from the given
+    /// ranges `[begin, end)`.
+    pub fn with_id_ranges(db_ids: (u64, u64), tbl_ids: (u64, u64)) -> Self {
         Self {
             databases: Default::default(),
-            tbl_id_seq,
+            db_id_seq: IdSequence::create(db_ids.0, db_ids.1, "database"),
+            tbl_id_seq: IdSequence::create(tbl_ids.0, tbl_ids.1, "table"),
         }
     }
-
-    fn next_db_id(&self) -> u64 {
-        *self.tbl_id_seq.write() += 1;
-        let r = self.tbl_id_seq.read();
-        *r
-    }
 }
 
 impl MetaBackend for EmbeddedMetaBackend {
@@ -170,42 +212,45 @@ impl MetaBackend for EmbeddedMetaBackend {
         let db_name = clone.db.as_str();
         let table_name = clone.table.as_str();
 
-        let table_info = TableInfo {
-            db: plan.db,
-            table_id: self.next_db_id(),
-            name: plan.table,
-            schema: plan.schema,
-            options: plan.options,
-            engine: plan.engine,
-        };
-
         let mut lock = self.databases.write();
-        let v = lock.get_mut(db_name);
-        match v {
+        let metas = match lock.get_mut(db_name) {
             None => {
                 return Err(ErrorCode::UnknownDatabase(format!(
                     "Unknown database: {}",
                     db_name
                 )));
             }
-            Some((_db_info, metas)) => {
-                if metas.name2meta.get(table_name).is_some() {
-                    if plan.if_not_exists {
-                        // TODO(xp): just let it passed, gonna be removed
-                        return Ok(CreateTableReply { table_id: 0 });
-                    } else {
-                        return Err(ErrorCode::TableAlreadyExists(format!(
-                            "Table: '{}.{}' already exists.",
-                            db_name, table_name,
-                        )));
-                    };
-                }
-                metas.insert(table_info);
-            }
+            Some((_db_info, metas)) => metas,
+        };
+
+        if let Some(existing) = metas.name2meta.get(table_name) {
+            return if plan.if_not_exists {
+                Ok(CreateTableReply {
+                    table_id: existing.table_id,
+                })
+            } else {
+                Err(ErrorCode::TableAlreadyExists(format!(
+                    "Table: '{}.{}' already exists.",
+                    db_name, table_name,
+                )))
+            };
         }
 
-        // TODO(xp): just let it passed, gonna be removed
-        Ok(CreateTableReply { table_id: 0 })
+        let table_id = self.tbl_id_seq.next_id()?;
+        let now = Utc::now();
+        let table_info = TableInfo {
+            db: plan.db,
+            table_id,
+            name: plan.table,
+            schema: plan.schema,
+            options: plan.options,
+            engine: plan.engine,
+            created_on: now,
+            updated_on: now,
+        };
+        metas.insert(table_info);
+
+        Ok(CreateTableReply { table_id })
     }
 
     fn drop_table(&self, plan: DropTablePlan) -> common_exception::Result<()> {
@@ -256,6 +301,93 @@ impl MetaBackend for EmbeddedMetaBackend {
         Ok(())
     }
 
+    fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> common_exception::Result<()> {
+        let db_name = plan.db.as_str();
+        let table_name = plan.table.as_str();
+
+        let mut lock = self.databases.write();
+        let metas = match lock.get_mut(db_name) {
+            None => {
+                return Err(ErrorCode::UnknownDatabase(format!(
+                    "Unknown database: {}",
+                    db_name
+                )))
+            }
+            Some((_, metas)) => metas,
+        };
+
+        let table = metas.name2meta.get(table_name).ok_or_else(|| {
+            ErrorCode::UnknownTable(format!("Unknown table: '{}.{}'", db_name, table_name))
+        })?;
+
+        if table.schema.column_with_name(plan.field.name()).is_some() {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "column {} already exists in table {}",
+                plan.field.name(),
+                table_name
+            )));
+        }
+
+        let mut fields = table.schema.fields().clone();
+        fields.push(plan.field);
+        let schema = DataSchema::new_from(fields, table.schema.meta().clone());
+
+        let table_info = TableInfo {
+            schema: Arc::new(schema),
+            updated_on: Utc::now(),
+            ..table.as_ref().clone()
+        };
+        metas.insert(table_info);
+        Ok(())
+    }
+
+    fn commit_table_snapshot(
+        &self,
+        db_name: &str,
+        table_name: &str,
+        prev_snapshot_loc: Option<&str>,
+        new_snapshot_loc: &str,
+    ) -> common_exception::Result<()> {
+        let mut lock = self.databases.write();
+        let metas = match lock.get_mut(db_name) {
+            None => {
+                return Err(ErrorCode::UnknownDatabase(format!(
+                    "Unknown database: {}",
+                    db_name
+                )))
+            }
+            Some((_, metas)) => metas,
+        };
+
+        let table = metas.name2meta.get(table_name).ok_or_else(|| {
+            ErrorCode::UnknownTable(format!("Unknown table: '{}.{}'", db_name, table_name))
+        })?;
+
+        let mut meta = table.schema.meta().clone();
+        let current = meta
+            .get(TABLE_META_KEY_SNAPSHOT_LOCATION)
+            .map(|v| v.as_str());
+        if current != prev_snapshot_loc {
+            return Err(ErrorCode::TableVersionMismatched(format!(
+                "Table '{}.{}' has been changed by another commit, expects snapshot {:?}, but it is {:?}",
+                db_name, table_name, prev_snapshot_loc, current
+            )));
+        }
+
+        meta.insert(
+            TABLE_META_KEY_SNAPSHOT_LOCATION.to_string(),
+            new_snapshot_loc.to_string(),
+        );
+        let schema = DataSchema::new_from(table.schema.fields().clone(), meta);
+        let table_info = TableInfo {
+            schema: Arc::new(schema),
+            updated_on: Utc::now(),
+            ..table.as_ref().clone()
+        };
+        metas.insert(table_info);
+        Ok(())
+    }
+
     fn create_database(
         &self,
         plan: CreateDatabasePlan,
@@ -264,10 +396,11 @@ impl MetaBackend for EmbeddedMetaBackend {
 
         let mut db = self.databases.write();
 
-        if db.get(db_name).is_some() {
+        if let Some((existing, _)) = db.get(db_name) {
             return if plan.if_not_exists {
-                // TODO(xp): just let it pass. This file will be removed as soon as common/kv provides full meta-APIs.
-                Ok(CreateDatabaseReply { database_id: 0 })
+                Ok(CreateDatabaseReply {
+                    database_id: existing.database_id,
+                })
             } else {
                 Err(ErrorCode::DatabaseAlreadyExists(format!(
                     "Database: '{}' already exists.",
@@ -276,11 +409,12 @@ impl MetaBackend for EmbeddedMetaBackend {
             };
         }
 
+        let database_id = self.db_id_seq.next_id()?;
         let database_info = DatabaseInfo {
-            // TODO(xp): just let it pass. This file will be removed as soon as common/kv provides full meta-APIs.
-            database_id: 0,
+            database_id,
             db: db_name.to_string(),
             engine: plan.engine.clone(),
+            options: plan.options.clone(),
         };
 
         db.insert(
@@ -288,8 +422,7 @@ impl MetaBackend for EmbeddedMetaBackend {
             (Arc::new(database_info), InMemoryTableInfo::create()),
         );
 
-        // TODO(xp): just let it pass. This file will be removed as soon as common/kv provides full meta-APIs.
-        Ok(CreateDatabaseReply { database_id: 0 })
+        Ok(CreateDatabaseReply { database_id })
     }
 
     fn drop_database(&self, plan: DropDatabasePlan) -> common_exception::Result<()> {
@@ -0,0 +1,164 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CreateDatabasePlan;
+use common_planners::CreateTablePlan;
+
+use crate::catalogs::impls::meta_backends::EmbeddedMetaBackend;
+use crate::catalogs::meta_backend::MetaBackend;
+use crate::catalogs::meta_backend::TABLE_META_KEY_SNAPSHOT_LOCATION;
+use crate::catalogs::LOCAL_DB_ID_BEGIN;
+use crate::catalogs::LOCAL_DB_ID_END;
+use crate::catalogs::LOCAL_TBL_ID_BEGIN;
+use crate::catalogs::LOCAL_TBL_ID_END;
+
+fn create_db_plan(db: &str) -> CreateDatabasePlan {
+    CreateDatabasePlan {
+        if_not_exists: false,
+        db: db.to_string(),
+        engine: "Default".to_string(),
+        options: Default::default(),
+    }
+}
+
+fn create_table_plan(db: &str, table: &str) -> CreateTablePlan {
+    CreateTablePlan {
+        if_not_exists: false,
+        db: db.to_string(),
+        table: table.to_string(),
+        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]),
+        engine: "Memory".to_string(),
+        options: Default::default(),
+    }
+}
+
+#[test]
+fn test_embedded_meta_backend_ids() -> Result<()> {
+    let backend = EmbeddedMetaBackend::new();
+
+    let db1 = backend.create_database(create_db_plan("db1"))?.database_id;
+    let db2 = backend.create_database(create_db_plan("db2"))?.database_id;
+    let t1 = backend
+        .create_table(create_table_plan("db1", "t1"))?
+        .table_id;
+    let t2 = backend
+        .create_table(create_table_plan("db2", "t2"))?
+        .table_id;
+
+    // ids of databases and tables are from disjoint ranges
+    for id in [db1, db2] {
+        assert!((LOCAL_DB_ID_BEGIN..LOCAL_DB_ID_END).contains(&id));
+    }
+    for id in [t1, t2] {
+        assert!((LOCAL_TBL_ID_BEGIN..LOCAL_TBL_ID_END).contains(&id));
+    }
+    assert!(LOCAL_DB_ID_END <= LOCAL_TBL_ID_BEGIN);
+    assert_eq!(db1 + 1, db2);
+    assert_eq!(t1 + 1, t2);
+
+    // the created ones carry the ids
+    assert_eq!(backend.get_database("db1")?.database_id, db1);
+    assert_ne!(db1, 0);
+    assert_eq!(backend.get_table("db2", "t2")?.table_id, t2);
+    assert_eq!(backend.get_table_by_id("db1", t1, None)?.name, "t1");
+    assert_ne!(t1, 0);
+
+    // creating an existing one with if_not_exists returns its id
+    let mut plan = create_db_plan("db1");
+    plan.if_not_exists = true;
+    assert_eq!(backend.create_database(plan)?.database_id, db1);
+    let mut plan = create_table_plan("db1", "t1");
+    plan.if_not_exists = true;
+    assert_eq!(backend.create_table(plan)?.table_id, t1);
+
+    Ok(())
+}
+
+#[test]
+fn test_embedded_meta_backend_ids_exhausted() -> Result<()> {
+    let backend = EmbeddedMetaBackend::with_id_ranges((1, 2), (10, 12));
+
+    assert_eq!(
+        backend.create_database(create_db_plan("db1"))?.database_id,
+        1
+    );
+    let res = backend.create_database(create_db_plan("db2"));
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::Overflow("").code())
+    );
+    assert!(backend.get_database("db2").is_err());
+
+    backend.create_table(create_table_plan("db1", "t1"))?;
+    backend.create_table(create_table_plan("db1", "t2"))?;
+    let res = backend.create_table(create_table_plan("db1", "t3"));
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::Overflow("").code())
+    );
+    assert!(backend.get_table("db1", "t3").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_embedded_meta_backend_commit_table_snapshot() -> Result<()> {
+    let backend = EmbeddedMetaBackend::new();
+    backend.create_database(create_db_plan("db1"))?;
+    backend.create_table(create_table_plan("db1", "t1"))?;
+    let snapshot_loc = |db: &str, table: &str| -> Result<Option<String>> {
+        let table = backend.get_table(db, table)?;
+        Ok(table
+            .schema
+            .meta()
+            .get(TABLE_META_KEY_SNAPSHOT_LOCATION)
+            .cloned())
+    };
+    assert_eq!(snapshot_loc("db1", "t1")?, None);
+
+    backend.commit_table_snapshot("db1", "t1", None, "_ss/1")?;
+    assert_eq!(snapshot_loc("db1", "t1")?, Some("_ss/1".to_string()));
+    backend.commit_table_snapshot("db1", "t1", Some("_ss/1"), "_ss/2")?;
+    assert_eq!(snapshot_loc("db1", "t1")?, Some("_ss/2".to_string()));
+
+    // based on a snapshot which is not the latest one any more
+    for prev in [None, Some("_ss/1")] {
+        let res = backend.commit_table_snapshot("db1", "t1", prev, "_ss/3");
+        assert_eq!(
+            res.err().map(|e| e.code()),
+            Some(ErrorCode::TableVersionMismatched("").code())
+        );
+    }
+    assert_eq!(snapshot_loc("db1", "t1")?, Some("_ss/2".to_string()));
+
+    // the table is looked up by id as well
+    let table_id = backend.get_table("db1", "t1")?.table_id;
+    let table = backend.get_table_by_id("db1", table_id, None)?;
+    assert_eq!(
+        table.schema.meta().get(TABLE_META_KEY_SNAPSHOT_LOCATION),
+        Some(&"_ss/2".to_string())
+    );
+
+    let res = backend.commit_table_snapshot("db1", "t2", None, "_ss/1");
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::UnknownTable("").code())
+    );
+
+    Ok(())
+}
@@ -22,5 +22,8 @@ pub use remote_meta_backend::RemoteMeteStoreClient;
 //pub use crate::catalogs::metastore_client::MetaBackend;
 //pub use crate::catalogs::metastore_client::TableInfo;
 
+#[cfg(test)]
+mod embedded_meta_backend_test;
+
 mod embedded_meta_backend;
 mod remote_meta_backend;
@@ -20,6 +20,7 @@ use common_base::Runtime;
 use common_base::TrySpawn;
 use common_cache::Cache;
 use common_cache::LruCache;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use common_meta_api_vo::CreateDatabaseReply;
@@ -28,6 +29,7 @@ use common_meta_api_vo::DatabaseInfo;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
@@ -55,7 +57,8 @@ impl RemoteMeteStoreClient {
         apis_provider: Arc<StoreApiProvider>,
         timeout: Option<Duration>,
     ) -> RemoteMeteStoreClient {
-        let rt = Runtime::with_worker_threads(1).expect("remote catalogs initialization failure");
+        let rt = Runtime::with_worker_threads_named(1, "meta-client")
+            .expect("remote catalogs initialization failure");
         RemoteMeteStoreClient {
             rt: Arc::new(rt),
             // TODO configuration
@@ -88,6 +91,8 @@ impl MetaBackend for RemoteMeteStoreClient {
             schema: reply.schema,
             engine: reply.engine,
             options: reply.options,
+            created_on: reply.created_on,
+            updated_on: reply.updated_on,
         };
         Ok(Arc::new(table_info))
     }
@@ -121,6 +126,8 @@ impl MetaBackend for RemoteMeteStoreClient {
             schema: reply.schema.clone(),
             engine: reply.engine.clone(),
             options: reply.options.clone(),
+            created_on: reply.created_on,
+            updated_on: reply.updated_on,
         };
 
         let mut cache = self.table_meta_cache.lock();
@@ -147,6 +154,7 @@ impl MetaBackend for RemoteMeteStoreClient {
             database_id: db.database_id,
             db: db_name.to_owned(),
             engine: db.engine,
+            options: db.options,
         };
 
         Ok(Arc::new(database_info))
@@ -202,6 +210,31 @@ impl MetaBackend for RemoteMeteStoreClient {
         Ok(())
     }
 
+    fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> Result<()> {
+        let cli = self.store_api_provider.clone();
+        self.rt.block_on(
+            async move {
+                let client = cli.try_get_meta_client().await?;
+                client.add_table_column(plan).await
+            },
+            self.rpc_time_out,
+        )??;
+        Ok(())
+    }
+
+    fn commit_table_snapshot(
+        &self,
+        db_name: &str,
+        table_name: &str,
+        _prev_snapshot_loc: Option<&str>,
+        _new_snapshot_loc: &str,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "Cannot commit table '{}.{}', the remote meta store does not support it yet",
+            db_name, table_name
+        )))
+    }
+
     fn create_database(&self, plan: CreateDatabasePlan) -> Result<CreateDatabaseReply> {
         let cli_provider = self.store_api_provider.clone();
         let r = self.rt.block_on(
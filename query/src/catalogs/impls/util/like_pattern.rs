@@ -0,0 +1,49 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::ComparisonLikeFunction;
+use common_functions::scalars::Function;
+
+/// Keeps the items whose name matches a SQL `LIKE` pattern,
+/// by the same function as the `LIKE` of a query, e.g. `%` matches any sequence of characters.
+pub fn filter_like<T>(items: Vec<T>, pattern: &str, name: impl Fn(&T) -> &str) -> Result<Vec<T>> {
+    if items.is_empty() {
+        return Ok(items);
+    }
+
+    let rows = items.len();
+    let names = items.iter().map(|item| name(item)).collect::<Vec<_>>();
+    let columns = vec![
+        DataColumnWithField::new(
+            DataColumn::Array(Series::new(names)),
+            DataField::new("name", DataType::String, false),
+        ),
+        DataColumnWithField::new(
+            DataColumn::Constant(DataValue::String(Some(pattern.as_bytes().to_vec())), rows),
+            DataField::new("pattern", DataType::String, false),
+        ),
+    ];
+    let matched = ComparisonLikeFunction::try_create_func("like")?
+        .eval(&columns, rows)?
+        .to_array()?;
+
+    Ok(items
+        .into_iter()
+        .zip(matched.bool()?.into_no_null_iter())
+        .filter_map(|(item, matched)| matched.then(|| item))
+        .collect())
+}
@@ -0,0 +1,46 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_exception::Result;
+
+use crate::catalogs::impls::util::like_pattern::filter_like;
+
+#[test]
+fn test_filter_like() -> Result<()> {
+    let cases = vec![
+        ("db%", "db1", true),
+        ("db%", "db", true),
+        ("db%", "xdb", false),
+        ("%db", "mydb", true),
+        ("d_", "db", true),
+        ("d_", "dbb", false),
+        ("%b%", "abc", true),
+        ("a%c%e", "abcde", true),
+        ("a%c%e", "abcdf", false),
+        ("%", "", true),
+        ("abc", "abc", true),
+        ("abc", "abd", false),
+    ];
+
+    for (pattern, s, expect) in cases {
+        let matched = filter_like(vec![s], pattern, |s| *s)?;
+        assert_eq!(expect, !matched.is_empty(), "{} LIKE {}", s, pattern);
+    }
+
+    // the order of the items is kept
+    let names = vec!["t1", "x", "t2", "t3"];
+    assert_eq!(filter_like(names, "t%", |s| *s)?, vec!["t1", "t2", "t3"]);
+    Ok(())
+}
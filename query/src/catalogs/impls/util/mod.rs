@@ -13,4 +13,8 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod like_pattern_test;
+
 pub(crate) mod in_memory_metas;
+pub(crate) mod like_pattern;
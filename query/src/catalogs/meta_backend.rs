@@ -22,11 +22,15 @@ use common_meta_api_vo::DatabaseInfo;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
 
+/// Key of the table schema meta, which points to the latest snapshot of a table.
+pub const TABLE_META_KEY_SNAPSHOT_LOCATION: &str = "META_SNAPSHOT_LOCATION";
+
 pub trait MetaBackend: Send + Sync {
     // database
 
@@ -44,6 +48,18 @@ pub trait MetaBackend: Send + Sync {
 
     fn drop_table(&self, plan: DropTablePlan) -> Result<()>;
 
+    fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> Result<()>;
+
+    /// Points the table to the snapshot at `new_snapshot_loc`, provided that it still points to
+    /// `prev_snapshot_loc`, the snapshot the new one is based on; fails otherwise.
+    fn commit_table_snapshot(
+        &self,
+        db_name: &str,
+        table_name: &str,
+        prev_snapshot_loc: Option<&str>,
+        new_snapshot_loc: &str,
+    ) -> Result<()>;
+
     fn get_table(&self, db_name: &str, table_name: &str) -> Result<Arc<TableInfo>>;
 
     fn get_tables(&self, db_name: &str) -> Result<Vec<Arc<TableInfo>>>;
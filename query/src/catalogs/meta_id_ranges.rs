@@ -23,6 +23,12 @@ pub const SYS_TBL_FUNC_ID_BEGIN: u64 = SYS_TBL_ID_END;
 // max id for table tables (exclusive)
 pub const SYS_TBL_FUC_ID_END: u64 = SYS_TBL_FUNC_ID_BEGIN + 10000;
 
-// min id for system tables (inclusive)
-// max id for local tables is u64:MAX
-pub const LOCAL_TBL_ID_BEGIN: u64 = SYS_TBL_ID_END;
+// min id for local tables (inclusive)
+pub const LOCAL_TBL_ID_BEGIN: u64 = SYS_TBL_FUC_ID_END;
+// max id for local tables (exclusive)
+pub const LOCAL_TBL_ID_END: u64 = u64::MAX;
+
+// min id for local databases (inclusive)
+pub const LOCAL_DB_ID_BEGIN: u64 = 1 << 61;
+// max id for local databases (exclusive)
+pub const LOCAL_DB_ID_END: u64 = SYS_TBL_ID_BEGIN;
@@ -26,6 +26,9 @@ pub use table_meta::TableMeta;
 
 pub use crate::datasources::database_engine::DatabaseEngine;
 
+#[cfg(test)]
+mod catalog_test;
+
 mod catalog;
 mod database;
 mod meta_id_ranges;
@@ -21,6 +21,7 @@ use common_exception::Result;
 use common_metatypes::MetaId;
 use common_planners::Extras;
 use common_planners::InsertIntoPlan;
+use common_planners::OptimizeTablePlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
@@ -58,27 +59,47 @@ pub trait Table: Sync + Send {
     ) -> Result<SendableDataBlockStream>;
 
     // temporary added, pls feel free to rm it
+    // Returns the number of rows appended.
     async fn append_data(
         &self,
         _ctx: DatabendQueryContextRef,
         _insert_plan: InsertIntoPlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         Err(ErrorCode::UnImplement(format!(
             "append data for local table {} is not implemented",
             self.name()
         )))
     }
 
+    // Remove all the data of the table, returns the number of rows removed.
     async fn truncate(
         &self,
         _ctx: DatabendQueryContextRef,
         _truncate_plan: TruncateTablePlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         Err(ErrorCode::UnImplement(format!(
             "truncate for local table {} is not implemented",
             self.name()
         )))
     }
+
+    // Remove the data of the table from the storage, once the table is dropped.
+    // Tables that keep no data of their own have nothing to remove.
+    async fn purge(&self) -> Result<()> {
+        Ok(())
+    }
+
+    // Reorganize the data of the table for faster reads, e.g. merge the small blocks.
+    async fn optimize(
+        &self,
+        _ctx: DatabendQueryContextRef,
+        _optimize_plan: OptimizeTablePlan,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "optimize for table {} is not implemented",
+            self.name()
+        )))
+    }
 }
 
 pub type TablePtr = Arc<dyn Table>;
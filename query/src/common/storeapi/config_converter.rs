@@ -33,7 +33,13 @@ impl From<&Config> for StoreClientConf {
         };
 
         let meta_config = ClientConf {
-            address: conf.meta.meta_address.clone(),
+            addresses: conf
+                .meta
+                .meta_address
+                .split(',')
+                .map(|address| address.trim().to_string())
+                .filter(|address| !address.is_empty())
+                .collect(),
             username: conf.meta.meta_username.clone(),
             password: conf.meta.meta_password.clone(),
             tls_conf: meta_tls_conf,
@@ -40,37 +40,38 @@ impl StoreApiProvider {
     }
 
     /// Get meta async client, trait is defined in MetaApi.
+    /// The connections to the meta service are pooled and shared by all the providers.
     pub async fn try_get_meta_client(&self) -> Result<Arc<dyn MetaApi>> {
-        let client = StoreClient::try_new(&self.conf).await?;
+        let client = StoreClient::try_get_pooled(&self.conf).await?;
         Ok(Arc::new(client))
     }
 
     /// Get meta client, operations trait is defined in MetaApi.
     pub fn sync_try_get_meta_client(&self) -> Result<Arc<dyn MetaApi>> {
-        let client = StoreClient::sync_try_new(&self.conf)?;
+        let client = StoreClient::sync_try_get_pooled(&self.conf)?;
         Ok(Arc::new(client))
     }
 
     /// Get kv async client, operations trait defined in KVApi.
     pub async fn try_get_kv_client(&self) -> Result<Arc<dyn KVApi>> {
-        let local = self.conf.kv_service_config.address.is_empty();
+        let local = self.conf.kv_service_config.local_mode();
         if local {
             let client = common_kv::KV::new_temp().await?;
             Ok(Arc::new(client))
         } else {
-            let client = StoreClient::try_new(&self.conf).await?;
+            let client = StoreClient::try_get_pooled(&self.conf).await?;
             Ok(Arc::new(client))
         }
     }
 
     /// Get kv client, operations trait defined in KVApi.
     pub fn sync_try_get_kv_client(&self) -> Result<Arc<dyn KVApi>> {
-        let local = self.conf.kv_service_config.address.is_empty();
+        let local = self.conf.kv_service_config.local_mode();
         if local {
             let client = common_kv::KV::sync_new_temp()?;
             Ok(Arc::new(client))
         } else {
-            let client = StoreClient::sync_try_new(&self.conf)?;
+            let client = StoreClient::sync_try_get_pooled(&self.conf)?;
             Ok(Arc::new(client))
         }
     }
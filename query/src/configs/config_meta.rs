@@ -30,7 +30,7 @@ const META_RPC_TLS_SERVICE_DOMAIN_NAME: &str = "META_RPC_TLS_SERVICE_DOMAIN_NAME
 /// serde(default) make the toml de to default working.
 #[derive(Clone, serde::Serialize, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
 pub struct MetaConfig {
-    #[structopt(long, env = META_ADDRESS, default_value = "", help = "MetaStore backend address")]
+    #[structopt(long, env = META_ADDRESS, default_value = "", help = "MetaStore backend address, comma separated for multiple nodes")]
     #[serde(default)]
     pub meta_address: String,
 
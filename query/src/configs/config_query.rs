@@ -23,6 +23,7 @@ const QUERY_NAMESPACE: &str = "QUERY_NAMESPACE";
 const QUERY_NUM_CPUS: &str = "QUERY_NUM_CPUS";
 const QUERY_MYSQL_HANDLER_HOST: &str = "QUERY_MYSQL_HANDLER_HOST";
 const QUERY_MYSQL_HANDLER_PORT: &str = "QUERY_MYSQL_HANDLER_PORT";
+const QUERY_MYSQL_HANDLER_IDLE_TIMEOUT: &str = "QUERY_MYSQL_HANDLER_IDLE_TIMEOUT";
 const QUERY_MAX_ACTIVE_SESSIONS: &str = "QUERY_MAX_ACTIVE_SESSIONS";
 const QUERY_CLICKHOUSE_HANDLER_HOST: &str = "QUERY_CLICKHOUSE_HANDLER_HOST";
 const QUERY_CLICKHOUSE_HANDLER_PORT: &str = "QUERY_CLICKHOUSE_HANDLER_PORT";
@@ -68,6 +69,15 @@ pub struct QueryConfig {
     #[serde(default)]
     pub mysql_handler_port: u16,
 
+    #[structopt(
+    long,
+    env = QUERY_MYSQL_HANDLER_IDLE_TIMEOUT,
+    default_value = "28800",
+    help = "Seconds a MySQL connection may be idle before it is closed, 0 means never"
+    )]
+    #[serde(default)]
+    pub mysql_handler_idle_timeout: u64,
+
     #[structopt(
     long,
     env = QUERY_MAX_ACTIVE_SESSIONS,
@@ -171,6 +181,7 @@ impl QueryConfig {
             num_cpus: 8,
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
+            mysql_handler_idle_timeout: 28800,
             max_active_sessions: 256,
             clickhouse_handler_host: "127.0.0.1".to_string(),
             clickhouse_handler_port: 9000,
@@ -205,6 +216,13 @@ impl QueryConfig {
             u16,
             QUERY_MYSQL_HANDLER_PORT
         );
+        env_helper!(
+            mut_config,
+            query,
+            mysql_handler_idle_timeout,
+            u64,
+            QUERY_MYSQL_HANDLER_IDLE_TIMEOUT
+        );
         env_helper!(
             mut_config,
             query,
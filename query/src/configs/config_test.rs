@@ -42,6 +42,7 @@ namespace = \"\"
 num_cpus = 8
 mysql_handler_host = \"127.0.0.1\"
 mysql_handler_port = 3307
+mysql_handler_idle_timeout = 28800
 max_active_sessions = 256
 clickhouse_handler_host = \"127.0.0.1\"
 clickhouse_handler_port = 9000
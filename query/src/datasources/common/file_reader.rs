@@ -0,0 +1,107 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::io;
+use std::io::Read;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_base::tokio::runtime::Handle;
+use common_base::tokio::sync::mpsc::Sender;
+use common_dal::DataAccessor;
+use common_dal::InputStream;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::AsyncReadExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::sessions::DatabendQueryContextRef;
+
+/// Reads a file of a data accessor as a blocking `Read`, for the readers of the file formats
+/// which take a `Read`. It blocks on the runtime of `handle`, thus must be used on a blocking
+/// thread, e.g. of `spawn_blocking`.
+pub struct BlockingReader {
+    handle: Handle,
+    input: InputStream,
+}
+
+impl BlockingReader {
+    pub fn try_create(
+        handle: Handle,
+        data_accessor: &dyn DataAccessor,
+        path: &str,
+    ) -> Result<BlockingReader> {
+        let input = handle.block_on(data_accessor.get_input_stream(path, None))?;
+        Ok(BlockingReader { handle, input })
+    }
+}
+
+impl Read for BlockingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let input = &mut self.input;
+        self.handle.block_on(input.read(buf))
+    }
+}
+
+/// Reads the files of the partitions of `ctx`, one partition per file, until there are no
+/// partitions left. The blocks of a file, read by `read_file` on a blocking thread, are streamed
+/// as they are read, the reading waits while the previous block is not consumed yet.
+pub fn read_files<F, I>(
+    ctx: DatabendQueryContextRef,
+    data_accessor: Arc<dyn DataAccessor>,
+    read_file: F,
+) -> SendableDataBlockStream
+where
+    F: Fn(BlockingReader, &str) -> I + Send + 'static,
+    I: Iterator<Item = Result<DataBlock>>,
+{
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    let handle = Handle::current();
+    tokio::task::spawn_blocking(move || {
+        let res = send_file_blocks(&ctx, data_accessor.as_ref(), &handle, &read_file, &tx);
+        if let Err(cause) = res {
+            let _ = tx.blocking_send(Err(cause));
+        }
+    });
+    Box::pin(ReceiverStream::new(rx))
+}
+
+fn send_file_blocks<F, I>(
+    ctx: &DatabendQueryContextRef,
+    data_accessor: &dyn DataAccessor,
+    handle: &Handle,
+    read_file: &F,
+    tx: &Sender<Result<DataBlock>>,
+) -> Result<()>
+where
+    F: Fn(BlockingReader, &str) -> I,
+    I: Iterator<Item = Result<DataBlock>>,
+{
+    loop {
+        let parts = ctx.try_get_partitions(1)?;
+        let file = match parts.first() {
+            None => return Ok(()),
+            Some(part) => &part.name,
+        };
+        let reader = BlockingReader::try_create(handle.clone(), data_accessor, file)?;
+        for block in read_file(reader, file) {
+            // the stream is dropped, no more blocks are wanted
+            if tx.blocking_send(Ok(block?)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
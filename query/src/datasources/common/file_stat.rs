@@ -0,0 +1,89 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::common::list_files;
+
+/// The size of the head of a file sampled to estimate the records of it,
+/// the records of a file no larger than that are counted exactly.
+const SAMPLE_SIZE: u64 = 64 * 1024;
+
+/// A file of a table, along with its size and the estimated number of its records.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileStat {
+    pub path: String,
+    pub size: u64,
+    pub records: u64,
+}
+
+/// Lists the files of `location`, see `list_files`, and estimates the records of each of them
+/// without downloading it: the records counted by `count_records` in the head of the file are
+/// scaled by the size of the file.
+pub async fn stat_files<F>(
+    data_accessor: Arc<dyn DataAccessor>,
+    location: &str,
+    count_records: F,
+) -> Result<Vec<FileStat>>
+where
+    F: Fn(&[u8]) -> Result<u64> + Send,
+{
+    let mut stats = vec![];
+    for (path, size) in list_files(data_accessor.clone(), location).await? {
+        let size = match size {
+            Some(size) => size,
+            None => file_size(data_accessor.as_ref(), &path).await?,
+        };
+        let records = match size {
+            0 => 0,
+            _ => {
+                let sample = data_accessor
+                    .read_range(&path, 0, SAMPLE_SIZE.min(size))
+                    .await?;
+                let records = count_records(&sample)?;
+                match sample.len() as u64 {
+                    0 => 0,
+                    len if len >= size => records,
+                    len => records * size / len,
+                }
+            }
+        };
+        stats.push(FileStat {
+            path,
+            size,
+            records,
+        });
+    }
+    Ok(stats)
+}
+
+// The size of a file specified by its path, which is listed along with the others in its directory.
+async fn file_size(data_accessor: &dyn DataAccessor, path: &str) -> Result<u64> {
+    let (dir, name) = match path.rfind('/') {
+        Some(pos) => (&path[..pos + 1], &path[pos + 1..]),
+        None => ("./", path),
+    };
+    data_accessor
+        .list(dir)
+        .await?
+        .into_iter()
+        .find(|meta| meta.path.rsplit('/').next() == Some(name))
+        .map(|meta| meta.size)
+        .ok_or_else(|| ErrorCode::CannotReadFile(format!("No file found at location {}", path)))
+}
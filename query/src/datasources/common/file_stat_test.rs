@@ -0,0 +1,72 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_dal::DataAccessor;
+use common_dal::InMemory;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::common::count_lines;
+use crate::datasources::common::stat_files;
+use crate::datasources::common::FileStat;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_stat_files() -> Result<()> {
+    let data_accessor = Arc::new(InMemory::new());
+    // 100 bytes per line
+    let line = format!("{}\n", "x".repeat(99));
+    data_accessor
+        .put("t/small.csv", line.repeat(10).into_bytes())
+        .await?;
+    data_accessor
+        .put("t/large.csv", line.repeat(10_000).into_bytes())
+        .await?;
+    data_accessor.put("t/empty.csv", vec![]).await?;
+
+    let count_records = |sample: &[u8]| -> Result<u64> { Ok(count_lines(sample)? as u64) };
+    let stats = stat_files(data_accessor.clone(), "t/", count_records).await?;
+    assert_eq!(stats, vec![
+        FileStat {
+            path: "t/empty.csv".to_string(),
+            size: 0,
+            records: 0,
+        },
+        // the records of the head are scaled by the size
+        FileStat {
+            path: "t/large.csv".to_string(),
+            size: 1_000_000,
+            records: 9_994,
+        },
+        FileStat {
+            path: "t/small.csv".to_string(),
+            size: 1_000,
+            records: 10,
+        },
+    ]);
+
+    // a file specified by its path is listed for its size
+    let stats = stat_files(data_accessor.clone(), "t/small.csv", count_records).await?;
+    assert_eq!(stats[0].size, 1_000);
+
+    let res = stat_files(data_accessor, "t/unknown.csv", count_records).await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::CannotReadFile("").code())
+    );
+    Ok(())
+}
@@ -34,3 +34,47 @@ pub fn count_lines<R: io::Read>(handle: R) -> Result<usize, io::Error> {
     }
     Ok(count)
 }
+
+/// Counts the records of the CSV source `handle`, the line terminators inside the fields
+/// quoted by `quote` are not counted. Inside a quoted field, the byte following `escape` is
+/// taken as is, a quote can also be escaped by doubling it.
+///
+/// As `count_lines`, only the records terminated by a newline are counted, and without a
+/// `quote` it is just `count_lines`.
+pub fn count_csv_records<R: io::Read>(
+    handle: R,
+    quote: Option<u8>,
+    escape: Option<u8>,
+) -> Result<usize, io::Error> {
+    let quote = match quote {
+        None => return count_lines(handle),
+        Some(quote) => quote,
+    };
+
+    let sep = b'\n';
+    let mut reader = BufReader::new(handle);
+    let mut count = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        for &b in buf {
+            if escaped {
+                escaped = false;
+            } else if quoted && Some(b) == escape && b != quote {
+                escaped = true;
+            } else if b == quote {
+                // a doubled quote closes and reopens the field
+                quoted = !quoted;
+            } else if b == sep && !quoted {
+                count += 1;
+            }
+        }
+        let len = buf.len();
+        reader.consume(len);
+    }
+    Ok(count)
+}
@@ -17,6 +17,7 @@ use std::env;
 use common_exception::Result;
 use pretty_assertions::assert_eq;
 
+use crate::datasources::common::count_csv_records;
 use crate::datasources::common::count_lines;
 
 #[test]
@@ -30,3 +31,31 @@ fn test_lines_count() -> Result<()> {
     assert_eq!(6, lines);
     Ok(())
 }
+
+#[test]
+fn test_csv_records_count() -> Result<()> {
+    let count = |data: &str, quote, escape| count_csv_records(data.as_bytes(), quote, escape);
+
+    // newlines in the quoted fields
+    let data = "1,\"a\nb\"\n2,\"c\r\nd\"\n3,e\n";
+    assert_eq!(3, count(data, Some(b'"'), None)?);
+    assert_eq!(5, count_lines(data.as_bytes())?);
+
+    // quotes escaped by doubling them
+    let data = "1,\"a\"\"\nb\"\n2,\"\"\"\"\n";
+    assert_eq!(2, count(data, Some(b'"'), None)?);
+
+    // quotes escaped by an escape char
+    let data = "1,\"a\\\"\nb\"\n2,\"c\\\\\"\n";
+    assert_eq!(2, count(data, Some(b'"'), Some(b'\\'))?);
+    // the escape char is not special without quoting
+    assert_eq!(3, count("1,a\\\n2,b\n3,c\n", Some(b'"'), Some(b'\\'))?);
+
+    // the last record is not terminated
+    assert_eq!(1, count("1,\"a\nb\"\n2,c", Some(b'"'), None)?);
+
+    // no quoting, as count_lines
+    assert_eq!(4, count("1,\"a\nb\"\n2,c\n3,d\n", None, None)?);
+
+    Ok(())
+}
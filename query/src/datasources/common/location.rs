@@ -0,0 +1,122 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_dal::DataAccessor;
+use common_dal::DataAccessorBuilder;
+use common_dal::DefaultDataAccessorBuilder;
+use common_dal::Local;
+use common_dal::StorageScheme;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::TableOptions;
+
+use crate::datasources::table::fuse::parse_storage_scheme;
+
+/// Trims the quotes around the value of a table option.
+pub fn trim_quotes(value: &str) -> &str {
+    value.trim_matches(|s| s == '\'' || s == '"')
+}
+
+/// Builds the data accessor of the files of a table, by the table option `storage_scheme`.
+pub fn location_data_accessor(options: &TableOptions) -> Result<Arc<dyn DataAccessor>> {
+    match options.get("storage_scheme") {
+        Some(scheme) => {
+            let scheme = trim_quotes(scheme).to_string();
+            match parse_storage_scheme(Some(&scheme))? {
+                // locations are paths of the local file system, relative or absolute
+                StorageScheme::LocalFs => Ok(Arc::new(Local::new(""))),
+                scheme => DefaultDataAccessorBuilder::build(&scheme),
+            }
+        }
+        None => Ok(Arc::new(Local::new(""))),
+    }
+}
+
+/// Lists the files of `location` along with their sizes, in the order of their paths.
+///
+/// The location is a file, a directory (ends with `/`), or a glob pattern of the file names in
+/// a directory. The size of a file specified by its path is unknown, as it is not listed.
+pub async fn list_files(
+    data_accessor: Arc<dyn DataAccessor>,
+    location: &str,
+) -> Result<Vec<(String, Option<u64>)>> {
+    let (dir, pattern) = match location.rfind('/') {
+        Some(pos) => location.split_at(pos + 1),
+        None => ("./", location),
+    };
+    if !pattern.is_empty() && !pattern.contains(|c| c == '*' || c == '?') {
+        return Ok(vec![(location.to_string(), None)]);
+    }
+
+    let mut files = data_accessor
+        .list(dir)
+        .await?
+        .into_iter()
+        .map(|meta| (meta.path, Some(meta.size)))
+        .filter(|(path, _)| {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            pattern.is_empty() || glob_match(pattern, name)
+        })
+        .collect::<Vec<_>>();
+    if files.is_empty() {
+        return Err(ErrorCode::CannotReadFile(format!(
+            "No file found at location {}",
+            location
+        )));
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Matches a file name against a glob pattern,
+/// `*` matches any sequence of characters and `?` matches any single character.
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let p = pattern.chars().collect::<Vec<_>>();
+    let s = name.chars().collect::<Vec<_>>();
+
+    let (mut pi, mut si) = (0, 0);
+    // the position of the last `*` in pattern, and where it started to match in `name`
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while si < s.len() {
+        match p.get(pi) {
+            Some('*') => {
+                backtrack = Some((pi, si));
+                pi += 1;
+                continue;
+            }
+            Some(c) if *c == '?' || *c == s[si] => {
+                pi += 1;
+                si += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // mismatch, let the last `*` swallow one more character
+        match backtrack {
+            Some((bp, bs)) => {
+                pi = bp + 1;
+                si = bs + 1;
+                backtrack = Some((bp, bs + 1));
+            }
+            None => return false,
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,38 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use crate::datasources::common::location::glob_match;
+
+#[test]
+fn test_glob_match() {
+    let cases = vec![
+        ("*.csv", "a.csv", true),
+        ("*.csv", "a.csv.gz", false),
+        ("data_?.csv", "data_1.csv", true),
+        ("data_?.csv", "dataX1.csv", false),
+        ("data_?.csv", "data_10.csv", false),
+        ("*_*", "a_b", true),
+        ("a*c*e", "abcde", true),
+        ("a*c*e", "abcdf", false),
+        ("100%.csv", "100%.csv", true),
+        ("100%.csv", "1000.csv", false),
+        ("", "", true),
+        ("*", "", true),
+    ];
+
+    for (pattern, name, expect) in cases {
+        assert_eq!(expect, glob_match(pattern, name), "{} ~ {}", name, pattern);
+    }
+}
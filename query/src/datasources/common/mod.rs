@@ -13,13 +13,33 @@
 // limitations under the License.
 //
 
+#[cfg(test)]
+mod file_stat_test;
 #[cfg(test)]
 mod line_test;
 #[cfg(test)]
+mod location_test;
+#[cfg(test)]
 mod part_test;
+#[cfg(test)]
+mod schema_test;
 
+mod file_reader;
+mod file_stat;
 mod line;
+mod location;
 mod part;
+mod schema;
 
+pub use file_reader::read_files;
+pub use file_reader::BlockingReader;
+pub use file_stat::stat_files;
+pub use file_stat::FileStat;
+pub use line::count_csv_records;
 pub use line::count_lines;
+pub use location::list_files;
+pub use location::location_data_accessor;
+pub use location::trim_quotes;
 pub use part::generate_parts;
+pub use part::generate_parts_by_size;
+pub use schema::check_insert_schema;
@@ -24,6 +24,7 @@ pub fn generate_parts(start: u64, workers: u64, total: u64) -> Partitions {
         partitions.push(Part {
             name: format!("{}-{}-{}", total, start, total,),
             version: 0,
+            coalesced: vec![],
         })
     } else {
         for part in 0..workers {
@@ -38,8 +39,37 @@ pub fn generate_parts(start: u64, workers: u64, total: u64) -> Partitions {
             partitions.push(Part {
                 name: format!("{}-{}-{}", total, part_begin, part_end,),
                 version: 0,
+                coalesced: vec![],
             })
         }
     }
     partitions
 }
+
+/// Splits `total_bytes` of data into partitions of `part_bytes` each, so that the workers read
+/// roughly the same amount of data. The last partition holds the remainder.
+///
+/// The partitions are named `{total}-{begin}-{end}` as the ones of `generate_parts`, with byte
+/// offsets, `end` exclusive, to be read by range.
+pub fn generate_parts_by_size(total_bytes: u64, part_bytes: u64) -> Partitions {
+    if part_bytes == 0 || total_bytes <= part_bytes {
+        return vec![Part {
+            name: format!("{}-{}-{}", total_bytes, 0, total_bytes),
+            version: 0,
+            coalesced: vec![],
+        }];
+    }
+
+    let num_parts = (total_bytes + part_bytes - 1) / part_bytes;
+    let mut partitions = Vec::with_capacity(num_parts as usize);
+    for part in 0..num_parts {
+        let part_begin = part * part_bytes;
+        let part_end = std::cmp::min(part_begin + part_bytes, total_bytes);
+        partitions.push(Part {
+            name: format!("{}-{}-{}", total_bytes, part_begin, part_end),
+            version: 0,
+            coalesced: vec![],
+        })
+    }
+    partitions
+}
@@ -17,6 +17,7 @@ use common_planners::Part;
 use pretty_assertions::assert_eq;
 
 use crate::datasources::common::generate_parts;
+use crate::datasources::common::generate_parts_by_size;
 
 #[test]
 fn test_util_generate_parts() -> Result<()> {
@@ -28,21 +29,24 @@ fn test_util_generate_parts() -> Result<()> {
         assert_eq!(
             Part {
                 name: "11-0-3".into(),
-                version: 0
+                version: 0,
+                coalesced: vec![]
             },
             ps[0]
         );
         assert_eq!(
             Part {
                 name: "11-3-6".into(),
-                version: 0
+                version: 0,
+                coalesced: vec![]
             },
             ps[1]
         );
         assert_eq!(
             Part {
                 name: "11-6-11".into(),
-                version: 0
+                version: 0,
+                coalesced: vec![]
             },
             ps[2]
         );
@@ -56,7 +60,8 @@ fn test_util_generate_parts() -> Result<()> {
         assert_eq!(
             Part {
                 name: "0-0-0".into(),
-                version: 0
+                version: 0,
+                coalesced: vec![]
             },
             ps[0]
         );
@@ -69,7 +74,8 @@ fn test_util_generate_parts() -> Result<()> {
         assert_eq!(
             Part {
                 name: "2-0-2".into(),
-                version: 0
+                version: 0,
+                coalesced: vec![]
             },
             ps[0]
         );
@@ -77,3 +83,36 @@ fn test_util_generate_parts() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_util_generate_parts_by_size() -> Result<()> {
+    let names = |total_bytes, part_bytes| {
+        generate_parts_by_size(total_bytes, part_bytes)
+            .into_iter()
+            .map(|p| p.name)
+            .collect::<Vec<_>>()
+    };
+
+    // exact multiple
+    assert_eq!(
+        vec!["300-0-100", "300-100-200", "300-200-300"],
+        names(300, 100)
+    );
+
+    // the last part is short
+    assert_eq!(
+        vec!["250-0-100", "250-100-200", "250-200-250"],
+        names(250, 100)
+    );
+
+    // total is smaller than one part
+    assert_eq!(vec!["50-0-50"], names(50, 100));
+
+    // total is exactly one part
+    assert_eq!(vec!["100-0-100"], names(100, 100));
+
+    // total is zero
+    assert_eq!(vec!["0-0-0"], names(0, 100));
+
+    Ok(())
+}
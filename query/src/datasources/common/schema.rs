@@ -0,0 +1,78 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Checks the schema of the data to insert is exactly the schema of the table `table_name`,
+/// the error names the first field that does not match.
+pub fn check_insert_schema(
+    table_name: &str,
+    table_schema: &DataSchema,
+    insert_schema: &DataSchema,
+) -> Result<()> {
+    let table_fields = table_schema.fields();
+    let insert_fields = insert_schema.fields();
+
+    for (idx, (expected, actual)) in table_fields.iter().zip(insert_fields.iter()).enumerate() {
+        if expected.name() != actual.name() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Schema mismatch on table {}, column {} is expected to be `{}`, got `{}`",
+                table_name,
+                idx,
+                expected.name(),
+                actual.name()
+            )));
+        }
+        if expected.data_type() != actual.data_type()
+            || expected.is_nullable() != actual.is_nullable()
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "Schema mismatch on table {}, column `{}` is expected to be {}{}, got {}{}",
+                table_name,
+                expected.name(),
+                expected.data_type(),
+                nullable_suffix(expected.is_nullable()),
+                actual.data_type(),
+                nullable_suffix(actual.is_nullable()),
+            )));
+        }
+    }
+
+    if let Some(missing) = table_fields.get(insert_fields.len()) {
+        return Err(ErrorCode::BadArguments(format!(
+            "Schema mismatch on table {}, column `{}` is missing",
+            table_name,
+            missing.name()
+        )));
+    }
+    if let Some(extra) = insert_fields.get(table_fields.len()) {
+        return Err(ErrorCode::BadArguments(format!(
+            "Schema mismatch on table {}, column `{}` does not exist",
+            table_name,
+            extra.name()
+        )));
+    }
+    Ok(())
+}
+
+fn nullable_suffix(nullable: bool) -> &'static str {
+    if nullable {
+        " NULL"
+    } else {
+        ""
+    }
+}
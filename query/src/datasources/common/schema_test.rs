@@ -0,0 +1,92 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::datasources::common::check_insert_schema;
+
+#[test]
+fn test_check_insert_schema() -> Result<()> {
+    let table_schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::String, true),
+    ]);
+
+    struct Test {
+        name: &'static str,
+        fields: Vec<DataField>,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "renamed column",
+            fields: vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("c", DataType::String, true),
+            ],
+            expect: "Schema mismatch on table default.t, column 1 is expected to be `b`, got `c`",
+        },
+        Test {
+            name: "different type",
+            fields: vec![
+                DataField::new("a", DataType::Int32, false),
+                DataField::new("b", DataType::String, true),
+            ],
+            expect: "Schema mismatch on table default.t, column `a` is expected to be UInt64, got Int32",
+        },
+        Test {
+            name: "different nullability",
+            fields: vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::String, false),
+            ],
+            expect: "Schema mismatch on table default.t, column `b` is expected to be String NULL, got String",
+        },
+        Test {
+            name: "missing column",
+            fields: vec![DataField::new("a", DataType::UInt64, false)],
+            expect: "Schema mismatch on table default.t, column `b` is missing",
+        },
+        Test {
+            name: "extra column",
+            fields: vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::String, true),
+                DataField::new("c", DataType::UInt8, false),
+            ],
+            expect: "Schema mismatch on table default.t, column `c` does not exist",
+        },
+    ];
+
+    for t in tests {
+        let insert_schema = DataSchemaRefExt::create(t.fields);
+        let result = check_insert_schema("default.t", &table_schema, &insert_schema);
+        match result {
+            Ok(_) => panic!("{}: expect an error", t.name),
+            Err(e) => {
+                assert_eq!(e.code(), ErrorCode::BadArguments("").code(), "{}", t.name);
+                assert_eq!(e.message(), t.expect, "{}", t.name);
+            }
+        }
+    }
+
+    // the same schema
+    check_insert_schema("default.t", &table_schema, &table_schema)?;
+
+    Ok(())
+}
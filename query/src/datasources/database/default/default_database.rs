@@ -20,6 +20,7 @@ use common_infallible::RwLock;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
 
@@ -131,7 +132,9 @@ impl Database for DefaultDatabase {
     }
 
     fn create_table(&self, plan: CreateTablePlan) -> common_exception::Result<()> {
-        // TODO validate table parameters by using TableFactory
+        if let Some(provider) = self.table_factory_registry.engine_provider(&plan.engine) {
+            provider.validate_options(&plan.options)?;
+        }
         self.meta_store_client.create_table(plan)?;
         Ok(())
     }
@@ -139,4 +142,34 @@ impl Database for DefaultDatabase {
     fn drop_table(&self, plan: DropTablePlan) -> common_exception::Result<()> {
         self.meta_store_client.drop_table(plan)
     }
+
+    fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> common_exception::Result<()> {
+        // Stateful tables keep their data in memory, laid out by the schema they were created with
+        if self
+            .stateful_table_cache
+            .read()
+            .get_by_name(&plan.table)
+            .is_some()
+        {
+            return Err(ErrorCode::UnImplement(format!(
+                "Cannot add column to table {}.{}, the table engine does not support it",
+                plan.db, plan.table
+            )));
+        }
+        self.meta_store_client.add_table_column(plan)
+    }
+
+    fn commit_table_snapshot(
+        &self,
+        table_name: &str,
+        prev_snapshot_loc: Option<&str>,
+        new_snapshot_loc: &str,
+    ) -> common_exception::Result<()> {
+        self.meta_store_client.commit_table_snapshot(
+            self.name(),
+            table_name,
+            prev_snapshot_loc,
+            new_snapshot_loc,
+        )
+    }
 }
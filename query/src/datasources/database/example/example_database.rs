@@ -19,6 +19,7 @@ use common_exception::Result;
 use common_meta_api_vo::TableInfo;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
 
@@ -115,4 +116,22 @@ impl Database for ExampleDatabase {
     fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
         self.meta_store_client.drop_table(plan)
     }
+
+    fn add_table_column(&self, plan: AlterTableAddColumnPlan) -> Result<()> {
+        self.meta_store_client.add_table_column(plan)
+    }
+
+    fn commit_table_snapshot(
+        &self,
+        table_name: &str,
+        prev_snapshot_loc: Option<&str>,
+        new_snapshot_loc: &str,
+    ) -> Result<()> {
+        self.meta_store_client.commit_table_snapshot(
+            self.name(),
+            table_name,
+            prev_snapshot_loc,
+            new_snapshot_loc,
+        )
+    }
 }
@@ -17,6 +17,7 @@ use std::any::Any;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::Extras;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
@@ -88,14 +89,15 @@ impl Table for ExampleTable {
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
         Ok(ReadDataSourcePlan {
-            db: self.db.clone(),
-            table: self.name().to_string(),
-            table_id: self.table_id,
-            table_version: None,
-            schema: self.schema.clone(),
+            table_info: TableInfo {
+                table_id: self.table_id,
+                engine: self.engine().to_string(),
+                ..TableInfo::simple(&self.db, self.name(), self.schema.clone())
+            },
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                coalesced: vec![],
             }],
             statistics: Statistics::new_exact(0, 0),
             description: format!(
@@ -127,15 +129,15 @@ impl Table for ExampleTable {
         &self,
         _ctx: DatabendQueryContextRef,
         _insert_plan: common_planners::InsertIntoPlan,
-    ) -> Result<()> {
-        Ok(())
+    ) -> Result<usize> {
+        Ok(0)
     }
 
     async fn truncate(
         &self,
         _ctx: DatabendQueryContextRef,
         _truncate_plan: TruncateTablePlan,
-    ) -> Result<()> {
-        Ok(())
+    ) -> Result<usize> {
+        Ok(0)
     }
 }
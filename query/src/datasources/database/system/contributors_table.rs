@@ -17,6 +17,7 @@ use std::any::Any;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::Extras;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
@@ -74,14 +75,15 @@ impl Table for ContributorsTable {
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
         Ok(ReadDataSourcePlan {
-            db: "system".to_string(),
-            table: self.name().to_string(),
-            table_id: self.table_id,
-            table_version: None,
-            schema: self.schema.clone(),
+            table_info: TableInfo {
+                table_id: self.table_id,
+                engine: self.engine().to_string(),
+                ..TableInfo::simple("system", self.name(), self.schema.clone())
+            },
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                coalesced: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.contributors table)".to_string(),
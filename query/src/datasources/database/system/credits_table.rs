@@ -17,6 +17,7 @@ use std::any::Any;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::Extras;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
@@ -78,14 +79,15 @@ impl Table for CreditsTable {
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
         Ok(ReadDataSourcePlan {
-            db: "system".to_string(),
-            table: self.name().to_string(),
-            table_id: self.table_id,
-            table_version: None,
-            schema: self.schema.clone(),
+            table_info: TableInfo {
+                table_id: self.table_id,
+                engine: self.engine().to_string(),
+                ..TableInfo::simple("system", self.name(), self.schema.clone())
+            },
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                coalesced: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.credits table)".to_string(),
@@ -98,19 +100,26 @@ impl Table for CreditsTable {
 
     async fn read(
         &self,
-        _ctx: DatabendQueryContextRef,
+        ctx: DatabendQueryContextRef,
         _source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
-        let metadata_command = cargo_metadata::MetadataCommand::new();
-
-        let deps =
-            match cargo_license::get_dependencies_from_cargo_lock(metadata_command, false, false) {
-                Ok(v) => v,
-                Err(err) => {
-                    log::error!("{:?}", err);
-                    vec![]
+        // `cargo metadata` runs a sub process and parses its output, keep it off the async workers
+        let deps = ctx
+            .spawn_blocking(|| {
+                let metadata_command = cargo_metadata::MetadataCommand::new();
+                match cargo_license::get_dependencies_from_cargo_lock(
+                    metadata_command,
+                    false,
+                    false,
+                ) {
+                    Ok(v) => v,
+                    Err(err) => {
+                        log::error!("{:?}", err);
+                        vec![]
+                    }
                 }
-            };
+            })
+            .await?;
 
         let names: Vec<&[u8]> = deps.iter().map(|x| x.name.as_bytes()).collect();
         let version_strings: Vec<String> = deps.iter().map(|x| x.version.to_string()).collect();
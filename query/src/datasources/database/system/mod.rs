@@ -21,6 +21,7 @@ pub use engines_table::EnginesTable;
 pub use functions_table::FunctionsTable;
 pub use one_table::OneTable;
 pub use processes_table::ProcessesTable;
+pub use query_log_table::QueryLogTable;
 pub use settings_table::SettingsTable;
 pub use system_database::SystemDatabase;
 pub use tables_table::TablesTable;
@@ -42,6 +43,10 @@ mod engines_table_test;
 #[cfg(test)]
 mod functions_table_test;
 #[cfg(test)]
+mod processes_table_test;
+#[cfg(test)]
+mod query_log_table_test;
+#[cfg(test)]
 mod settings_table_test;
 #[cfg(test)]
 mod tables_table_test;
@@ -57,6 +62,7 @@ mod engines_table;
 mod functions_table;
 mod one_table;
 mod processes_table;
+mod query_log_table;
 mod settings_table;
 mod system_database;
 mod tables_table;
@@ -22,6 +22,7 @@ use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::Extras;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
@@ -33,6 +34,11 @@ use crate::catalogs::Table;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sessions::ProcessInfo;
 
+/// The sessions of the server, along with the queries running in them.
+///
+/// A running query can be cancelled by `KILL QUERY <session_id>`, or by its query_id, which
+/// aborts the sources of it, the stages scheduled to the other nodes are then cancelled as its
+/// `ScheduledStream` is dropped without reaching the end.
 pub struct ProcessesTable {
     table_id: u64,
     schema: DataSchemaRef,
@@ -43,11 +49,14 @@ impl ProcessesTable {
         ProcessesTable {
             table_id,
             schema: DataSchemaRefExt::create(vec![
-                DataField::new("id", DataType::String, false),
+                DataField::new("session_id", DataType::String, false),
                 DataField::new("type", DataType::String, false),
+                DataField::new("query_id", DataType::String, true),
+                DataField::new("sql", DataType::String, true),
                 DataField::new("host", DataType::String, true),
-                DataField::new("state", DataType::String, false),
                 DataField::new("database", DataType::String, false),
+                DataField::new("elapsed_ms", DataType::UInt64, true),
+                DataField::new("state", DataType::String, false),
                 DataField::new("extra_info", DataType::String, true),
             ]),
         }
@@ -99,14 +108,15 @@ impl Table for ProcessesTable {
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
         Ok(ReadDataSourcePlan {
-            db: "system".to_string(),
-            table: self.name().to_string(),
-            table_id: self.table_id,
-            table_version: None,
-            schema: self.schema.clone(),
+            table_info: TableInfo {
+                table_id: self.table_id,
+                engine: self.engine().to_string(),
+                ..TableInfo::simple("system", self.name(), self.schema.clone())
+            },
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                coalesced: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.processes table)".to_string(),
@@ -127,16 +137,22 @@ impl Table for ProcessesTable {
 
         let mut processes_id = Vec::with_capacity(processes_info.len());
         let mut processes_type = Vec::with_capacity(processes_info.len());
+        let mut processes_query_id = Vec::with_capacity(processes_info.len());
+        let mut processes_sql = Vec::with_capacity(processes_info.len());
         let mut processes_host = Vec::with_capacity(processes_info.len());
         let mut processes_state = Vec::with_capacity(processes_info.len());
         let mut processes_database = Vec::with_capacity(processes_info.len());
+        let mut processes_elapsed_ms = Vec::with_capacity(processes_info.len());
         let mut processes_extra_info = Vec::with_capacity(processes_info.len());
 
         for process_info in &processes_info {
             processes_id.push(process_info.id.clone().into_bytes());
             processes_type.push(process_info.typ.clone().into_bytes());
+            processes_query_id.push(process_info.query_id.clone().map(|s| s.into_bytes()));
+            processes_sql.push(process_info.query.clone().map(|s| s.into_bytes()));
             processes_state.push(process_info.state.clone().into_bytes());
             processes_database.push(process_info.database.clone().into_bytes());
+            processes_elapsed_ms.push(process_info.query_elapsed.map(|d| d.as_millis() as u64));
             processes_host.push(ProcessesTable::process_host(process_info));
             processes_extra_info.push(ProcessesTable::process_extra_info(process_info));
         }
@@ -145,9 +161,12 @@ impl Table for ProcessesTable {
         let block = DataBlock::create_by_array(schema.clone(), vec![
             Series::new(processes_id),
             Series::new(processes_type),
+            Series::new(processes_query_id),
+            Series::new(processes_sql),
             Series::new(processes_host),
-            Series::new(processes_state),
             Series::new(processes_database),
+            Series::new(processes_elapsed_ms),
+            Series::new(processes_state),
             Series::new(processes_extra_info),
         ]);
 
@@ -0,0 +1,104 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::PlanParser;
+use crate::tests::SessionManagerBuilder;
+
+async fn run_query(ctx: DatabendQueryContextRef, sql: &str) -> Result<Vec<DataBlock>> {
+    ctx.attach_query_str(sql);
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let interpreter = InterpreterFactory::get(ctx, plan)?;
+    let stream = interpreter.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processes_table() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let query_session = sessions.create_session("TestSession")?;
+    let monitor_session = sessions.create_session("TestSession")?;
+
+    // a slow query, in flight until its stream is consumed or dropped
+    let slow_sql = "select sum(number) from numbers_mt(10000000000)";
+    let query_ctx = query_session.create_context().await?;
+    query_ctx.attach_query_str(slow_sql);
+    let plan = PlanParser::create(query_ctx.clone()).build_from_sql(slow_sql)?;
+    let interpreter = InterpreterFactory::get(query_ctx.clone(), plan)?;
+    let slow_stream = interpreter.execute().await?;
+
+    let processes_sql = format!(
+        "select query_id, sql, state from system.processes where session_id = '{}'",
+        query_session.get_id()
+    );
+    let result = run_query(monitor_session.create_context().await?, &processes_sql).await?;
+    // query ids are `q-` followed by a base62 counter, so the column width follows the id
+    let query_id = query_ctx.get_id();
+    let width = query_id.len().max("query_id".len());
+    let border = format!(
+        "+-{}-+-{}-+-------+",
+        "-".repeat(width),
+        "-".repeat(slow_sql.len())
+    );
+    let expected = vec![
+        border.clone(),
+        format!(
+            "| {:<width$} | {:<sql_width$} | state |",
+            "query_id",
+            "sql",
+            width = width,
+            sql_width = slow_sql.len()
+        ),
+        border.clone(),
+        format!(
+            "| {:<width$} | {} | Query |",
+            query_id,
+            slow_sql,
+            width = width
+        ),
+        border,
+    ];
+    common_datablocks::assert_blocks_eq(
+        expected.iter().map(|s| s.as_str()).collect(),
+        result.as_slice(),
+    );
+
+    // the killed query is no longer listed
+    query_session.force_kill_query();
+    drop(slow_stream);
+    drop(interpreter);
+    drop(query_ctx);
+
+    let processes_sql = format!(
+        "select query_id, state from system.processes where session_id = '{}'",
+        query_session.get_id()
+    );
+    let result = run_query(monitor_session.create_context().await?, &processes_sql).await?;
+    let expected = vec![
+        "+----------+-------+",
+        "| query_id | state |",
+        "+----------+-------+",
+        "| NULL     | Idle  |",
+        "+----------+-------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::time::UNIX_EPOCH;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_meta_api_vo::TableInfo;
+use common_planners::Extras;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::sessions::DatabendQueryContextRef;
+
+/// The latest completed queries of the server, kept in memory.
+pub struct QueryLogTable {
+    table_id: u64,
+    schema: DataSchemaRef,
+}
+
+impl QueryLogTable {
+    pub fn create(table_id: u64) -> Self {
+        QueryLogTable {
+            table_id,
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("query_id", DataType::String, false),
+                DataField::new("kind", DataType::String, false),
+                DataField::new("sql", DataType::String, false),
+                DataField::new("start_time", DataType::DateTime32(None), false),
+                DataField::new("duration_ms", DataType::UInt64, false),
+                DataField::new("read_rows", DataType::UInt64, false),
+                DataField::new("read_bytes", DataType::UInt64, false),
+                DataField::new("error", DataType::String, true),
+            ]),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryLogTable {
+    fn name(&self) -> &str {
+        "query_log"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemQueryLog"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn get_id(&self) -> u64 {
+        self.table_id
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: DatabendQueryContextRef,
+        _push_downs: Option<Extras>,
+        _partition_num_hint: Option<usize>,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            table_info: TableInfo {
+                table_id: self.table_id,
+                engine: self.engine().to_string(),
+                ..TableInfo::simple("system", self.name(), self.schema.clone())
+            },
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+                coalesced: vec![],
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.query_log table)".to_string(),
+            scan_plan: Default::default(), // scan_plan will be removed form ReadSourcePlan soon
+            remote: false,
+            tbl_args: None,
+            push_downs: None,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: DatabendQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let records = ctx.get_sessions_manager().get_query_log().snapshot();
+
+        let mut query_ids = Vec::with_capacity(records.len());
+        let mut kinds = Vec::with_capacity(records.len());
+        let mut sqls = Vec::with_capacity(records.len());
+        let mut start_times = Vec::with_capacity(records.len());
+        let mut durations = Vec::with_capacity(records.len());
+        let mut read_rows = Vec::with_capacity(records.len());
+        let mut read_bytes = Vec::with_capacity(records.len());
+        let mut errors = Vec::with_capacity(records.len());
+
+        for record in &records {
+            query_ids.push(record.query_id.clone().into_bytes());
+            kinds.push(record.kind.clone().into_bytes());
+            sqls.push(record.sql.clone().into_bytes());
+            let start_time = record
+                .start_time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            start_times.push(start_time.as_secs() as u32);
+            durations.push(record.duration.as_millis() as u64);
+            read_rows.push(record.read_rows as u64);
+            read_bytes.push(record.read_bytes as u64);
+            errors.push(record.error.clone().map(|e| e.into_bytes()));
+        }
+
+        let schema = self.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(query_ids),
+            Series::new(kinds),
+            Series::new(sqls),
+            Series::new(start_times).cast_with_type(&DataType::DateTime32(None))?,
+            Series::new(durations),
+            Series::new(read_rows),
+            Series::new(read_bytes),
+            Series::new(errors),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::SessionManagerRef;
+use crate::sql::PlanParser;
+use crate::tests::SessionManagerBuilder;
+
+// Runs the query in a new session, so that it has its own query id and progress.
+async fn run_query(sessions: &SessionManagerRef, sql: &str) -> Result<Vec<DataBlock>> {
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context().await?;
+    ctx.attach_query_str(sql);
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let interpreter = InterpreterFactory::get(ctx, plan)?;
+    let stream = interpreter.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_log_table() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    run_query(&sessions, "select * from numbers(10)").await?;
+    run_query(
+        &sessions,
+        "select number from numbers_mt(100) where number > 90",
+    )
+    .await?;
+    let result = run_query(&sessions, "drop table default.not_exists").await;
+    assert!(result.is_err());
+
+    let records = sessions.get_query_log().snapshot();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].read_rows, 10);
+    assert_eq!(records[1].read_rows, 100);
+    assert!(records[1].read_bytes > 0);
+    assert_ne!(records[0].query_id, records[1].query_id);
+    assert!(records[2].error.is_some());
+
+    let result = run_query(
+        &sessions,
+        "select kind, sql, read_rows from system.query_log",
+    )
+    .await?;
+    let expected = vec![
+        "+-----------+------------------------------------------------------+-----------+",
+        "| kind      | sql                                                  | read_rows |",
+        "+-----------+------------------------------------------------------+-----------+",
+        "| Select    | select * from numbers(10)                            | 10        |",
+        "| Select    | select number from numbers_mt(100) where number > 90 | 100       |",
+        "| DropTable | drop table default.not_exists                        | 0         |",
+        "+-----------+------------------------------------------------------+-----------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
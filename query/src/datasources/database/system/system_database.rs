@@ -18,6 +18,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_metatypes::MetaId;
 use common_metatypes::MetaVersion;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
 
@@ -65,6 +66,7 @@ impl SystemDatabase {
             Arc::new(system::TracingTable::create(next_id())),
             Arc::new(system::ProcessesTable::create(next_id())),
             Arc::new(system::ConfigsTable::create(next_id())),
+            Arc::new(system::QueryLogTable::create(next_id())),
         ];
 
         let tbl_meta_list = table_list.into_iter().map(|t| {
@@ -128,4 +130,21 @@ impl Database for SystemDatabase {
             "Cannot drop table for system database",
         ))
     }
+
+    fn add_table_column(&self, _plan: AlterTableAddColumnPlan) -> Result<()> {
+        Result::Err(ErrorCode::UnImplement(
+            "Cannot alter table for system database",
+        ))
+    }
+
+    fn commit_table_snapshot(
+        &self,
+        _table_name: &str,
+        _prev_snapshot_loc: Option<&str>,
+        _new_snapshot_loc: &str,
+    ) -> Result<()> {
+        Result::Err(ErrorCode::UnImplement(
+            "Cannot commit table for system database",
+        ))
+    }
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 //
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use common_datablocks::DataBlock;
@@ -33,7 +34,7 @@ pub struct MinMaxIndex {
 
 #[allow(dead_code)]
 impl MinMaxIndex {
-    fn create(col: String, min: DataValue, max: DataValue) -> Self {
+    pub fn create(col: String, min: DataValue, max: DataValue) -> Self {
         MinMaxIndex {
             col,
             min,
@@ -69,8 +70,99 @@ impl MinMaxIndex {
     /// Apply the expr against the idx_map, and get the result:
     /// true: need
     /// false: skip
-    pub fn apply_index(_idx_map: HashMap<String, MinMaxIndex>, _expr: &Expression) -> Result<bool> {
-        // TODO(bohu): expression apply.
-        Ok(true)
+    pub fn apply_index(idx_map: HashMap<String, MinMaxIndex>, expr: &Expression) -> Result<bool> {
+        Ok(Self::may_match(&idx_map, expr))
+    }
+
+    /// Returns false only if it is certain that no row within the [min, max] ranges satisfies the expr.
+    /// Expressions that can not be evaluated against the ranges are treated as matched.
+    fn may_match(idx_map: &HashMap<String, MinMaxIndex>, expr: &Expression) -> bool {
+        match expr {
+            Expression::BinaryExpression { left, op, right } => {
+                match op.to_lowercase().as_str() {
+                    "and" => Self::may_match(idx_map, left) && Self::may_match(idx_map, right),
+                    "or" => Self::may_match(idx_map, left) || Self::may_match(idx_map, right),
+                    op => match (left.as_ref(), right.as_ref()) {
+                        (Expression::Column(col), Expression::Literal { value, .. }) => {
+                            Self::may_match_cmp(idx_map.get(col), op, value)
+                        }
+                        // `10 < a` is the same as `a > 10`
+                        (Expression::Literal { value, .. }, Expression::Column(col)) => {
+                            let flipped = match op {
+                                "<" => ">",
+                                "<=" => ">=",
+                                ">" => "<",
+                                ">=" => "<=",
+                                op => op,
+                            };
+                            Self::may_match_cmp(idx_map.get(col), flipped, value)
+                        }
+                        _ => true,
+                    },
+                }
+            }
+            _ => true,
+        }
+    }
+
+    fn may_match_cmp(idx: Option<&MinMaxIndex>, op: &str, value: &DataValue) -> bool {
+        let idx = match idx {
+            Some(idx) => idx,
+            None => return true,
+        };
+
+        // how the min/max of the column compare to the value
+        let (min, max) = match (
+            Self::compare_value(&idx.min, value),
+            Self::compare_value(&idx.max, value),
+        ) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return true,
+        };
+
+        match op {
+            "=" => min != Ordering::Greater && max != Ordering::Less,
+            "!=" | "<>" => !(min == Ordering::Equal && max == Ordering::Equal),
+            "<" => min == Ordering::Less,
+            "<=" => min != Ordering::Greater,
+            ">" => max == Ordering::Greater,
+            ">=" => max != Ordering::Less,
+            _ => true,
+        }
+    }
+
+    /// Compares numbers (of any numeric type) with numbers, and strings with strings.
+    /// Integers are compared exactly, only a float on either side makes it a float comparison.
+    fn compare_value(l: &DataValue, r: &DataValue) -> Option<Ordering> {
+        // i128 holds every i64 and u64 value.
+        fn as_i128(v: &DataValue) -> Option<i128> {
+            match v {
+                DataValue::Int8(Some(v)) => Some(*v as i128),
+                DataValue::Int16(Some(v)) => Some(*v as i128),
+                DataValue::Int32(Some(v)) => Some(*v as i128),
+                DataValue::Int64(Some(v)) => Some(*v as i128),
+                DataValue::UInt8(Some(v)) => Some(*v as i128),
+                DataValue::UInt16(Some(v)) => Some(*v as i128),
+                DataValue::UInt32(Some(v)) => Some(*v as i128),
+                DataValue::UInt64(Some(v)) => Some(*v as i128),
+                _ => None,
+            }
+        }
+
+        fn as_f64(v: &DataValue) -> Option<f64> {
+            match v {
+                DataValue::Float32(Some(v)) => Some(*v as f64),
+                DataValue::Float64(Some(v)) => Some(*v),
+                v => as_i128(v).map(|v| v as f64),
+            }
+        }
+
+        match (l, r) {
+            (DataValue::String(Some(l)), DataValue::String(Some(r))) => Some(l.cmp(r)),
+            _ => match (as_i128(l), as_i128(r)) {
+                (Some(l), Some(r)) => Some(l.cmp(&r)),
+                _ => as_f64(l)?.partial_cmp(&as_f64(r)?),
+            },
+        }
     }
 }
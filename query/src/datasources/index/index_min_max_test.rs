@@ -79,5 +79,79 @@ fn test_min_max_index() -> Result<()> {
         assert_eq!(actual, expected);
     }
 
+    // Apply index with ranges.
+    {
+        let mut idx_map = HashMap::new();
+        idx_map.insert("name".to_string(), idx_slice[0].clone());
+        idx_map.insert("age".to_string(), idx_slice[1].clone());
+
+        let tests = vec![
+            (col("age").eq(lit(11)), true),
+            (col("age").eq(lit(25)), false),
+            (col("age").gt(lit(24)), false),
+            (col("age").gt_eq(lit(24)), true),
+            (col("age").lt(lit(11)), false),
+            (col("age").lt(lit(12i64)), true),
+            (lit(30).lt(col("age")), false),
+            (lit(20).lt(col("age")), true),
+            (col("name").eq(lit("ace".as_bytes())), false),
+            (col("name").eq(lit("kate".as_bytes())), true),
+            (col("age").gt(lit(24)).or(col("age").lt(lit(20))), true),
+            (
+                col("age")
+                    .gt(lit(20))
+                    .and(col("name").gt(lit("y".as_bytes()))),
+                false,
+            ),
+            (col("unknown").eq(lit(1)), true),
+        ];
+
+        for (expr, expected) in tests {
+            let actual = MinMaxIndex::apply_index(idx_map.clone(), &expr)?;
+            assert_eq!(actual, expected, "{:?}", expr);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_min_max_index_large_integers() -> Result<()> {
+    // i64::MAX - 1 and i64::MAX are the same f64.
+    let mut idx_map = HashMap::new();
+    idx_map.insert(
+        "id".to_string(),
+        MinMaxIndex::create(
+            "id".to_string(),
+            DataValue::Int64(Some(i64::MAX - 2)),
+            DataValue::Int64(Some(i64::MAX - 1)),
+        ),
+    );
+    idx_map.insert(
+        "uid".to_string(),
+        MinMaxIndex::create(
+            "uid".to_string(),
+            DataValue::UInt64(Some(u64::MAX - 1)),
+            DataValue::UInt64(Some(u64::MAX - 1)),
+        ),
+    );
+
+    let tests = vec![
+        (col("id").eq(lit(i64::MAX)), false),
+        (col("id").gt_eq(lit(i64::MAX)), false),
+        (col("id").eq(lit(i64::MAX - 1)), true),
+        (col("id").lt(lit(i64::MAX - 2)), false),
+        (col("id").lt(lit(i64::MAX - 1)), true),
+        (col("uid").eq(lit(u64::MAX)), false),
+        (col("uid").gt(lit(u64::MAX - 1)), false),
+        (col("uid").gt(lit(i64::MAX)), true),
+        (col("id").lt(lit(1.0e19f64)), true),
+    ];
+
+    for (expr, expected) in tests {
+        let actual = MinMaxIndex::apply_index(idx_map.clone(), &expr)?;
+        assert_eq!(actual, expected, "{:?}", expr);
+    }
+
     Ok(())
 }
@@ -0,0 +1,182 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::convert::TryFrom;
+use std::io::Read;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::io::csv::read;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::TableOptions;
+
+use crate::datasources::common::trim_quotes;
+
+/// How the CSV files of a table are formatted, by the table options
+/// `delimiter`, `quote` and `has_header`.
+#[derive(Clone, Debug)]
+pub struct CsvFormat {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub has_header: bool,
+}
+
+impl CsvFormat {
+    pub fn try_create(options: &TableOptions) -> Result<Self> {
+        let has_header = match options.get("has_header") {
+            None => false,
+            Some(value) => !matches!(trim_quotes(value).to_lowercase().as_str(), "false" | "0"),
+        };
+        Ok(CsvFormat {
+            delimiter: char_option(options, "delimiter", b',')?,
+            quote: char_option(options, "quote", b'"')?,
+            has_header,
+        })
+    }
+
+    fn reader_builder(&self) -> read::ReaderBuilder {
+        let mut builder = read::ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .has_headers(self.has_header)
+            // the number of the fields is checked against the schema, with the line number
+            .flexible(true);
+        builder
+    }
+}
+
+// The value of an option of one char, may be quoted, e.g., `'|'`, `"'"` or `|`.
+fn char_option(options: &TableOptions, key: &str, default: u8) -> Result<u8> {
+    let value = match options.get(key) {
+        None => return Ok(default),
+        Some(value) => value.as_bytes(),
+    };
+    let unquoted = match value {
+        [q, c, end] if (*q == b'\'' || *q == b'"') && q == end => vec![*c],
+        _ => value.to_vec(),
+    };
+    match unquoted.as_slice() {
+        [c] => Ok(*c),
+        _ => Err(ErrorCode::BadOption(format!(
+            "Table option {} must be a single char, got {}",
+            key,
+            String::from_utf8_lossy(value)
+        ))),
+    }
+}
+
+/// Parses a CSV `file` read from `reader` into blocks of at most `block_size` rows, the blocks
+/// are parsed one at a time as they are iterated.
+///
+/// The fields of a row are mapped to the columns of the `schema` by position, the extra ones are
+/// ignored. A row with less fields than the schema, or a field can not be parsed as the type of
+/// its column, is an error telling the line of it, which ends the iteration.
+pub struct CsvBlockReader<R: Read> {
+    reader: read::Reader<R>,
+    file: String,
+    fields: Vec<Field>,
+    block_size: usize,
+    finished: bool,
+}
+
+impl<R: Read> CsvBlockReader<R> {
+    pub fn create(
+        reader: R,
+        file: &str,
+        schema: &DataSchemaRef,
+        format: &CsvFormat,
+        block_size: usize,
+    ) -> Self {
+        CsvBlockReader {
+            reader: format.reader_builder().from_reader(reader),
+            file: file.to_string(),
+            fields: schema.to_arrow().fields().to_vec(),
+            block_size: block_size.max(1),
+            finished: false,
+        }
+    }
+
+    fn read_block(&mut self) -> Result<Option<DataBlock>> {
+        let mut rows = Vec::with_capacity(self.block_size);
+        let mut record = read::ByteRecord::new();
+        while rows.len() < self.block_size {
+            let has_more = self.reader.read_byte_record(&mut record).map_err(|e| {
+                ErrorCode::BadBytes(format!("Failed to parse CSV file {}: {}", self.file, e))
+            })?;
+            if !has_more {
+                break;
+            }
+            if record.len() < self.fields.len() {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Failed to parse CSV file {} at line {}: expects {} fields, got {}",
+                    self.file,
+                    line_of(&record),
+                    self.fields.len(),
+                    record.len()
+                )));
+            }
+            rows.push(record.clone());
+        }
+
+        match rows.is_empty() {
+            true => Ok(None),
+            false => deserialize_rows(&rows, &self.file, &self.fields).map(Some),
+        }
+    }
+}
+
+impl<R: Read> Iterator for CsvBlockReader<R> {
+    type Item = Result<DataBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let res = self.read_block().transpose();
+        self.finished = !matches!(res, Some(Ok(_)));
+        res
+    }
+}
+
+fn deserialize_rows(rows: &[read::ByteRecord], file: &str, fields: &[Field]) -> Result<DataBlock> {
+    let batch = read::deserialize_batch(rows, fields, None, 0, read::deserialize_column)?;
+
+    // the values can not be parsed are deserialized as nulls
+    for (col_idx, column) in batch.columns().iter().enumerate() {
+        for (row_idx, row) in rows.iter().enumerate() {
+            let value = &row[col_idx];
+            if column.is_null(row_idx) && !value.is_empty() {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Failed to parse CSV file {} at line {}: can not parse {} as {:?} for column {}",
+                    file,
+                    line_of(row),
+                    String::from_utf8_lossy(value),
+                    fields[col_idx].data_type(),
+                    fields[col_idx].name()
+                )));
+            }
+        }
+    }
+
+    DataBlock::try_from(batch)
+}
+
+fn line_of(record: &read::ByteRecord) -> u64 {
+    record.position().map(|pos| pos.line()).unwrap_or_default()
+}
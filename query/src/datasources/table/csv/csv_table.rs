@@ -14,50 +14,89 @@
 //
 
 use std::any::Any;
-use std::fs::File;
+use std::sync::mpsc::channel;
 use std::sync::Arc;
 
+use common_base::TrySpawn;
+use common_dal::DataAccessor;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api_vo::TableInfo;
 use common_planners::Extras;
+use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
-use common_planners::ScanPlan;
 use common_planners::Statistics;
 use common_streams::SendableDataBlockStream;
 
 use crate::catalogs::Table;
-use crate::datasources::common::count_lines;
-use crate::datasources::common::generate_parts;
-use crate::datasources::table::csv::csv_table_stream::CsvTableStream;
+use crate::datasources::common::count_csv_records;
+use crate::datasources::common::location_data_accessor;
+use crate::datasources::common::read_files;
+use crate::datasources::common::stat_files;
+use crate::datasources::common::trim_quotes;
+use crate::datasources::common::FileStat;
+use crate::datasources::table::csv::csv_reader::CsvBlockReader;
+use crate::datasources::table::csv::csv_reader::CsvFormat;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct CsvTable {
     tbl_info: TableInfo,
-    file: String,
-    has_header: bool,
+    // a file, a directory (ends with `/`), or a glob pattern of the file names in a directory
+    location: String,
+    format: CsvFormat,
+    data_accessor: Arc<dyn DataAccessor>,
 }
 
 impl CsvTable {
     pub fn try_create(tbl_info: TableInfo) -> Result<Box<dyn Table>> {
-        let options = &tbl_info.options;
-        let has_header = options.get("has_header").is_some();
-        let file = match options.get("location") {
+        let data_accessor = location_data_accessor(&tbl_info.options)?;
+        CsvTable::with_data_accessor(tbl_info, data_accessor)
+    }
+
+    /// Creates a table whose files are read by the given `data_accessor`.
+    pub fn with_data_accessor(
+        tbl_info: TableInfo,
+        data_accessor: Arc<dyn DataAccessor>,
+    ) -> Result<Box<dyn Table>> {
+        let location = match tbl_info.options.get("location") {
             None => {
                 return Result::Err(ErrorCode::BadOption(
                     "CSV Engine must contains file location options",
                 ));
             }
-            Some(v) => v.clone(),
+            Some(v) => trim_quotes(v).to_string(),
         };
+        let format = CsvFormat::try_create(&tbl_info.options)?;
 
         Ok(Box::new(Self {
             tbl_info,
-            file,
-            has_header,
+            location,
+            format,
+            data_accessor,
         }))
     }
+
+    // Lists the files, along with the size and the estimated number of the records of each of them.
+    fn blocking_stat_files(&self, ctx: &DatabendQueryContextRef) -> Result<Vec<FileStat>> {
+        let (tx, rx) = channel();
+        let data_accessor = self.data_accessor.clone();
+        let location = self.location.clone();
+        let format = self.format.clone();
+        ctx.try_spawn(async move {
+            let count_records = |sample: &[u8]| -> Result<u64> {
+                let records = count_csv_records(sample, Some(format.quote), None)?;
+                match format.has_header {
+                    true => Ok(records.saturating_sub(1) as u64),
+                    false => Ok(records as u64),
+                }
+            };
+            let res = stat_files(data_accessor, &location, count_records).await;
+            let _ = tx.send(res);
+        })?;
+
+        rx.recv().map_err(ErrorCode::from_std_error)?
+    }
 }
 
 #[async_trait::async_trait]
@@ -89,32 +128,34 @@ impl Table for CsvTable {
     fn read_plan(
         &self,
         ctx: DatabendQueryContextRef,
-        _push_downs: Option<Extras>,
+        push_downs: Option<Extras>,
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
-        let start_line: usize = if self.has_header { 1 } else { 0 };
-        let file = &self.file;
-        let lines_count = count_lines(File::open(file.clone())?)?;
+        let files = self.blocking_stat_files(&ctx)?;
+        let read_rows = files.iter().map(|file| file.records as usize).sum();
+        let read_bytes = files.iter().map(|file| file.size as usize).sum();
+
+        // one partition per file
+        let parts = files
+            .into_iter()
+            .map(|file| Part {
+                name: file.path,
+                version: 0,
+                coalesced: vec![],
+            })
+            .collect();
 
         let db = &self.tbl_info.db;
         let name = &self.tbl_info.name;
         Ok(ReadDataSourcePlan {
-            db: db.to_owned(),
-            table: name.to_owned(),
-            table_id: self.tbl_info.table_id,
-            table_version: None,
-            schema: self.tbl_info.schema.clone(),
-            parts: generate_parts(
-                start_line as u64,
-                ctx.get_settings().get_max_threads()?,
-                lines_count as u64,
-            ),
-            statistics: Statistics::default(),
+            table_info: self.tbl_info.clone(),
+            parts,
+            statistics: Statistics::new_estimated(read_rows, read_bytes),
             description: format!("(Read from CSV Engine table  {}.{})", db, name),
-            scan_plan: Arc::new(ScanPlan::empty()),
+            scan_plan: Default::default(),
             remote: false,
             tbl_args: None,
-            push_downs: None,
+            push_downs,
         })
     }
 
@@ -123,10 +164,13 @@ impl Table for CsvTable {
         ctx: DatabendQueryContextRef,
         _source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
-        Ok(Box::pin(CsvTableStream::try_create(
-            ctx,
-            self.tbl_info.schema.clone(),
-            self.file.clone(),
-        )?))
+        let data_accessor = self.data_accessor.clone();
+        let schema = self.tbl_info.schema.clone();
+        let format = self.format.clone();
+        let block_size = ctx.get_settings().get_max_block_size()? as usize;
+
+        Ok(read_files(ctx, data_accessor, move |reader, file| {
+            CsvBlockReader::create(reader, file, &schema, &format, block_size)
+        }))
     }
 }
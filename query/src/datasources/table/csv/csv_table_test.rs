@@ -13,11 +13,15 @@
 //  limitations under the License.
 //
 
-use std::env;
+use std::path::Path;
+use std::sync::Arc;
 
 use common_base::tokio;
-use common_datablocks::assert_blocks_sorted_eq;
+use common_dal::DataAccessor;
+use common_dal::InMemory;
+use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api_vo::TableInfo;
 use common_planners::*;
@@ -25,18 +29,18 @@ use futures::TryStreamExt;
 
 use crate::datasources::table::csv::csv_table::CsvTable;
 
-#[tokio::test]
+// The data accessors reject the paths of `..`, the path of the sample is canonicalized.
+fn sample_csv_path() -> Result<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/data/sample.csv");
+    Ok(std::fs::canonicalize(path)?.display().to_string())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_csv_table() -> Result<()> {
-    let options: TableOptions = [(
-        "location".to_string(),
-        env::current_dir()?
-            .join("../tests/data/sample.csv")
-            .display()
-            .to_string(),
-    )]
-    .iter()
-    .cloned()
-    .collect();
+    let options: TableOptions = [("location".to_string(), sample_csv_path()?)]
+        .iter()
+        .cloned()
+        .collect();
 
     let ctx = crate::tests::try_create_context()?;
     let table = CsvTable::try_create(TableInfo {
@@ -46,6 +50,7 @@ async fn test_csv_table() -> Result<()> {
         engine: "Csv".to_string(),
         options: options,
         table_id: 0,
+        ..Default::default()
     })?;
 
     let scan_plan = &ScanPlan {
@@ -67,6 +72,9 @@ async fn test_csv_table() -> Result<()> {
         Some(scan_plan.push_downs.clone()),
         Some(partitions),
     )?;
+    // the file is no larger than the sample, its records are counted exactly
+    assert_eq!(source_plan.statistics.read_rows, 6);
+    assert_eq!(source_plan.statistics.read_bytes, 96);
     ctx.try_set_partitions(source_plan.parts.clone())?;
 
     let stream = table.read(ctx, &source_plan).await?;
@@ -91,18 +99,12 @@ async fn test_csv_table() -> Result<()> {
     Ok(())
 }
 
-#[tokio::test]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_csv_table_parse_error() -> Result<()> {
-    let options: TableOptions = [(
-        "location".to_string(),
-        env::current_dir()?
-            .join("../tests/data/sample.csv")
-            .display()
-            .to_string(),
-    )]
-    .iter()
-    .cloned()
-    .collect();
+    let options: TableOptions = [("location".to_string(), sample_csv_path()?)]
+        .iter()
+        .cloned()
+        .collect();
 
     let ctx = crate::tests::try_create_context()?;
 
@@ -113,52 +115,119 @@ async fn test_csv_table_parse_error() -> Result<()> {
             DataField::new("column1", DataType::UInt64, false),
             DataField::new("column2", DataType::UInt64, false),
             DataField::new("column3", DataType::UInt64, false),
-            DataField::new("column4", DataType::UInt64, false),
         ]),
         engine: "Csv".to_string(),
         options: options,
         table_id: 0,
+        ..Default::default()
     })?;
 
-    let scan_plan = &ScanPlan {
-        schema_name: "".to_string(),
-        table_id: 0,
-        table_version: None,
-        table_schema: DataSchemaRefExt::create(vec![]),
-        table_args: None,
-        projected_schema: DataSchemaRefExt::create(vec![DataField::new(
-            "column2",
-            DataType::UInt64,
-            false,
-        )]),
-        push_downs: Extras::default(),
-    };
-    let partitions = ctx.get_settings().get_max_threads()? as usize;
-    let source_plan = table.read_plan(
-        ctx.clone(),
-        Some(scan_plan.push_downs.clone()),
-        Some(partitions),
-    )?;
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
     ctx.try_set_partitions(source_plan.parts.clone())?;
 
     let stream = table.read(ctx, &source_plan).await?;
     let result = stream.try_collect::<Vec<_>>().await;
-    // integer parse error will result to Null value
-    assert_eq!(false, result.is_err());
-    assert_blocks_sorted_eq(
-        vec![
-            "+---------+---------+---------+---------+",
-            "| column1 | column2 | column3 | column4 |",
-            "+---------+---------+---------+---------+",
-            "| 1       | NULL    | 100     | NULL    |",
-            "| 2       | NULL    | 80      | NULL    |",
-            "| 3       | NULL    | 60      | NULL    |",
-            "| 4       | NULL    | 70      | NULL    |",
-            "| 5       | NULL    | 55      | NULL    |",
-            "| 6       | NULL    | 99      | NULL    |",
-            "+---------+---------+---------+---------+",
-        ],
-        &result.unwrap(),
-    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::BadBytes("").code());
+        assert!(
+            e.message()
+                .ends_with("at line 1: can not parse 'Beijing' as UInt64 for column column2"),
+            "{}",
+            e.message()
+        );
+    }
+    Ok(())
+}
+
+async fn read_csv_table(
+    data_accessor: Arc<InMemory>,
+    schema: DataSchemaRef,
+    options: &[(&str, &str)],
+) -> Result<Vec<DataBlock>> {
+    let options: TableOptions = options
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let ctx = crate::tests::try_create_context()?;
+    let table = CsvTable::with_data_accessor(
+        TableInfo {
+            db: "default".into(),
+            name: "test_csv".into(),
+            schema,
+            engine: "Csv".to_string(),
+            options,
+            table_id: 0,
+            ..Default::default()
+        },
+        data_accessor,
+    )?;
+
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(ctx, &source_plan).await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_csv_table_format_options() -> Result<()> {
+    let data_accessor = Arc::new(InMemory::new());
+    data_accessor
+        .put(
+            "bucket/t/0.csv",
+            b"id|name|score\n1|'a|b'|1.5\n2|'c\nd'|-2\n".to_vec(),
+        )
+        .await?;
+    data_accessor
+        .put("bucket/t/1.csv", b"id|name|score\n3|e|0.25\n".to_vec())
+        .await?;
+    data_accessor
+        .put("bucket/t/ignored.txt", b"not,a,csv\n".to_vec())
+        .await?;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", DataType::Int32, false),
+        DataField::new("name", DataType::String, false),
+        DataField::new("score", DataType::Float64, false),
+    ]);
+    let options = [
+        ("location", "bucket/t/*.csv"),
+        ("delimiter", "'|'"),
+        ("quote", "\"'\""),
+        ("has_header", "true"),
+    ];
+    let blocks = read_csv_table(data_accessor.clone(), schema.clone(), &options).await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 3);
+    assert_eq!(blocks[0].column(0).to_values()?, vec![
+        DataValue::Int32(Some(1)),
+        DataValue::Int32(Some(2))
+    ]);
+    assert_eq!(blocks[0].column(1).to_values()?, vec![
+        DataValue::String(Some(b"a|b".to_vec())),
+        DataValue::String(Some(b"c\nd".to_vec()))
+    ]);
+    assert_eq!(blocks[0].column(2).to_values()?, vec![
+        DataValue::Float64(Some(1.5)),
+        DataValue::Float64(Some(-2.0))
+    ]);
+    assert_eq!(blocks[1].column(2).to_values()?, vec![DataValue::Float64(
+        Some(0.25)
+    )]);
+
+    // a malformed row
+    data_accessor
+        .put("bucket/t/2.csv", b"id|name|score\n4|f|1\n5|g\n".to_vec())
+        .await?;
+    let result = read_csv_table(data_accessor, schema, &options).await;
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::BadBytes("").code());
+        assert_eq!(
+            e.message(),
+            "Failed to parse CSV file bucket/t/2.csv at line 3: expects 3 fields, got 2"
+        );
+    }
+
     Ok(())
 }
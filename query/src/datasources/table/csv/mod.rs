@@ -12,7 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 //
+pub mod csv_reader;
 pub mod csv_table;
-pub mod csv_table_stream;
 #[cfg(test)]
 mod csv_table_test;
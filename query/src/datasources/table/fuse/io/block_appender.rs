@@ -13,12 +13,14 @@
 // limitations under the License.
 //
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::parquet::write::WriteOptions;
 use common_arrow::arrow::io::parquet::write::*;
 use common_arrow::arrow::record_batch::RecordBatch;
+use common_base::tokio::task::JoinHandle;
 use common_catalog::BlockLocation;
 use common_catalog::BlockMeta;
 use common_catalog::ColStats;
@@ -29,66 +31,203 @@ use common_dal::DataAccessor;
 use common_datablocks::DataBlock;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::DataType;
-use common_dfs_api_vo::BlockStream;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_streams::SendableDataBlockStream;
 use futures::StreamExt;
 use uuid::Uuid;
 
 use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::column_distinct_sketch;
 use crate::datasources::table::fuse::column_stats_reduce;
 use crate::datasources::table::fuse::FuseTable;
 
+/// Table option: fsync the appended files (and their directories) before `append_data` returns.
+///
+/// Only the local fs storage scheme honors it; object storages are durable once the upload succeeds.
+pub const TBL_OPT_KEY_FSYNC_ON_APPEND: &str = "fsync_on_append";
+
+/// Table option: the compression codec of the block files, one of `zstd`, `snappy`, `lz4` or `none`.
+pub const TBL_OPT_KEY_COMPRESSION: &str = "compression";
+
+pub const DEFAULT_COMPRESSION: Compression = Compression::Lz4;
+
+/// Table option: max number of rows of a block file, larger incoming blocks are split across files.
+pub const TBL_OPT_KEY_BLOCK_MAX_ROWS: &str = "block_max_rows";
+
+pub const DEFAULT_BLOCK_MAX_ROWS: usize = 1000 * 1000;
+
+/// Table option: block files larger than this (in bytes) are uploaded in parts of this size,
+/// smaller ones are uploaded by a single put.
+pub const TBL_OPT_KEY_UPLOAD_PART_SIZE: &str = "upload_part_size";
+
+pub const DEFAULT_UPLOAD_PART_SIZE: usize = 64 * 1024 * 1024;
+
+/// S3 rejects a multipart upload with a part (but the last one) smaller than this.
+pub const MIN_UPLOAD_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Table option: max number of block files being uploaded at the same time, while the next
+/// block is being encoded.
+pub const TBL_OPT_KEY_UPLOAD_CONCURRENCY: &str = "upload_concurrency";
+
+pub const DEFAULT_UPLOAD_CONCURRENCY: usize = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppenderConfig {
+    pub fsync_on_append: bool,
+    pub compression: Compression,
+    pub block_max_rows: usize,
+    pub upload_part_size: usize,
+    pub upload_concurrency: usize,
+}
+
+impl Default for AppenderConfig {
+    fn default() -> Self {
+        AppenderConfig {
+            fsync_on_append: false,
+            compression: DEFAULT_COMPRESSION,
+            block_max_rows: DEFAULT_BLOCK_MAX_ROWS,
+            upload_part_size: DEFAULT_UPLOAD_PART_SIZE,
+            upload_concurrency: DEFAULT_UPLOAD_CONCURRENCY,
+        }
+    }
+}
+
+pub fn parse_compression(v: &str) -> Result<Compression> {
+    match v.to_lowercase().as_str() {
+        "zstd" => Ok(Compression::Zstd),
+        "snappy" => Ok(Compression::Snappy),
+        "lz4" => Ok(Compression::Lz4),
+        "none" | "uncompressed" => Ok(Compression::Uncompressed),
+        _ => Err(ErrorCode::BadOption(format!(
+            "invalid value of table option {}: {}, expects one of zstd, snappy, lz4 or none",
+            TBL_OPT_KEY_COMPRESSION, v
+        ))),
+    }
+}
+
+impl AppenderConfig {
+    pub fn from_options(options: &HashMap<String, String>) -> Result<AppenderConfig> {
+        let fsync_on_append = match options.get(TBL_OPT_KEY_FSYNC_ON_APPEND) {
+            None => false,
+            Some(v) => v.to_lowercase().parse::<bool>().map_err(|_| {
+                ErrorCode::BadOption(format!(
+                    "invalid value of table option {}: {}, expects true or false",
+                    TBL_OPT_KEY_FSYNC_ON_APPEND, v
+                ))
+            })?,
+        };
+        // unknown codecs are rejected at table creation, see `FuseTableFactory::validate_options`,
+        // tables created before that are written with the default one
+        let compression = options
+            .get(TBL_OPT_KEY_COMPRESSION)
+            .and_then(|v| parse_compression(v).ok())
+            .unwrap_or(DEFAULT_COMPRESSION);
+        let block_max_rows = match options.get(TBL_OPT_KEY_BLOCK_MAX_ROWS) {
+            None => DEFAULT_BLOCK_MAX_ROWS,
+            Some(v) => match v.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "invalid value of table option {}: {}, expects a positive number of rows",
+                        TBL_OPT_KEY_BLOCK_MAX_ROWS, v
+                    )))
+                }
+            },
+        };
+        let upload_part_size = match options.get(TBL_OPT_KEY_UPLOAD_PART_SIZE) {
+            None => DEFAULT_UPLOAD_PART_SIZE,
+            Some(v) => match v.parse::<usize>() {
+                Ok(n) if n >= MIN_UPLOAD_PART_SIZE => n,
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "invalid value of table option {}: {}, expects a number of bytes no less than {}",
+                        TBL_OPT_KEY_UPLOAD_PART_SIZE, v, MIN_UPLOAD_PART_SIZE
+                    )))
+                }
+            },
+        };
+        let upload_concurrency = match options.get(TBL_OPT_KEY_UPLOAD_CONCURRENCY) {
+            None => DEFAULT_UPLOAD_CONCURRENCY,
+            Some(v) => match v.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                    "invalid value of table option {}: {}, expects a positive number of uploads",
+                    TBL_OPT_KEY_UPLOAD_CONCURRENCY, v
+                )))
+                }
+            },
+        };
+        Ok(AppenderConfig {
+            fsync_on_append,
+            compression,
+            block_max_rows,
+            upload_part_size,
+            upload_concurrency,
+        })
+    }
+}
+
 impl FuseTable {
-    pub async fn append_blocks(&self, mut stream: BlockStream) -> Result<SegmentInfo> {
+    pub async fn append_blocks(&self, mut stream: SendableDataBlockStream) -> Result<SegmentInfo> {
         let mut block_metas = vec![];
         let mut blocks_stats = vec![];
         let mut summary_row_count = 0u64;
         let mut summary_block_count = 0u64;
         let mut summary_uncompressed_byte_size = 0u64;
         let mut summary_compressed_byte_size = 0u64;
+        let config = self.appender_config()?;
+        let mut uploader = BlockUploader::create(self.data_accessor()?, config.clone());
 
         while let Some(block) = stream.next().await {
-            let schema = block.schema().to_arrow();
-            let blk_stats = block_stats(&block)?;
+            // an error in the stream fails the append, the uploaded blocks are not referenced
+            let block = block?;
+            // oversized blocks are split, each of the pieces goes to a file (and a BlockMeta) of its own
+            for block in DataBlock::split_block_by_size(&block, config.block_max_rows)? {
+                let schema = block.schema().to_arrow();
+                let blk_stats = block_stats(&block)?;
 
-            let row_count = block.num_rows() as u64;
-            let block_in_memory_size = block.memory_size() as u64;
+                let row_count = block.num_rows() as u64;
+                let block_in_memory_size = block.memory_size() as u64;
 
-            let data_accessor = self.data_accessor()?;
+                let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
+                let location = block_location(&part_uuid);
 
-            let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
-            let location = block_location(&part_uuid);
+                let file_size = uploader.save(&schema, block, &location).await?;
 
-            let file_size = save_block(&schema, block, data_accessor, &location)?;
+                // TODO gather parquet meta
+                let meta_size = 0u64;
 
-            // TODO gather parquet meta
-            let meta_size = 0u64;
+                let col_stats = blk_stats
+                    .iter()
+                    .map(|(idx, v)| (*idx, v.1.clone()))
+                    .collect::<HashMap<ColumnId, ColStats>>();
 
-            let col_stats = blk_stats
-                .iter()
-                .map(|(idx, v)| (*idx, v.1.clone()))
-                .collect::<HashMap<ColumnId, ColStats>>();
+                let block_info = BlockMeta {
+                    location: BlockLocation {
+                        location: location.clone(),
+                        meta_size,
+                    },
+                    row_count,
+                    block_size: block_in_memory_size,
+                    file_size,
+                    col_stats,
+                };
 
-            let block_info = BlockMeta {
-                location: BlockLocation {
-                    location: location.clone(),
-                    meta_size,
-                },
-                row_count,
-                block_size: block_in_memory_size,
-                col_stats,
-            };
-
-            block_metas.push(block_info);
-            blocks_stats.push(blk_stats);
+                block_metas.push(block_info);
+                blocks_stats.push(blk_stats);
 
-            summary_block_count += 1;
-            summary_row_count += row_count;
-            summary_compressed_byte_size += file_size;
-            summary_uncompressed_byte_size += block_in_memory_size;
+                summary_block_count += 1;
+                summary_row_count += row_count;
+                summary_compressed_byte_size += file_size;
+                summary_uncompressed_byte_size += block_in_memory_size;
+            }
         }
 
+        // the segment refers to the block files, all of them must be uploaded
+        uploader.finish().await?;
+
         let summary = column_stats_reduce(blocks_stats)?;
         let segment_info = SegmentInfo {
             blocks: block_metas,
@@ -134,11 +273,14 @@ pub fn block_stats(data_block: &DataBlock) -> Result<HashMap<ColumnId, (DataType
                 }
             };
 
+            let distinct_sketch = column_distinct_sketch(col)?;
             let col_stats = ColStats {
                 min,
                 max,
                 null_count,
                 row_count,
+                distinct_of_values: distinct_sketch.count(),
+                distinct_sketch: Some(distinct_sketch),
             };
 
             res.insert(idx, (data_type, col_stats));
@@ -147,16 +289,100 @@ pub fn block_stats(data_block: &DataBlock) -> Result<HashMap<ColumnId, (DataType
     )
 }
 
-pub(crate) fn save_block(
+/// Saves the blocks, each to a file of its own, the parquet encoding of a block overlaps the
+/// uploads of the ones saved before it.
+pub(crate) struct BlockUploader {
+    data_accessor: Arc<dyn DataAccessor>,
+    config: AppenderConfig,
+    // in the order they are started, at most `config.upload_concurrency` of them
+    uploads: VecDeque<JoinHandle<Result<()>>>,
+}
+
+impl BlockUploader {
+    pub fn create(data_accessor: Arc<dyn DataAccessor>, config: AppenderConfig) -> Self {
+        BlockUploader {
+            data_accessor,
+            config,
+            uploads: VecDeque::new(),
+        }
+    }
+
+    /// Encodes `block` and starts uploading it to `location`, returns the size of the file.
+    ///
+    /// The upload goes on in the background, if there are too many of them in flight, the
+    /// oldest one is waited for first.
+    pub async fn save(
+        &mut self,
+        arrow_schema: &ArrowSchema,
+        block: DataBlock,
+        location: &str,
+    ) -> Result<u64> {
+        let buffer = encode_block(arrow_schema, block, &self.config)?;
+        let file_size = buffer.len() as u64;
+
+        while self.uploads.len() >= self.config.upload_concurrency.max(1) {
+            if let Some(upload) = self.uploads.pop_front() {
+                wait_upload(upload).await?;
+            }
+        }
+
+        let data_accessor = self.data_accessor.clone();
+        let location = location.to_string();
+        let config = self.config.clone();
+        self.uploads
+            .push_back(common_base::tokio::spawn(async move {
+                upload_block(buffer, data_accessor, &location, &config).await
+            }));
+        Ok(file_size)
+    }
+
+    /// Waits for all the uploads, the first error (in the order they are started) is returned.
+    pub async fn finish(mut self) -> Result<()> {
+        while let Some(upload) = self.uploads.pop_front() {
+            wait_upload(upload).await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for BlockUploader {
+    // on the error path (the uploader is dropped without `finish`), the uploads left are not
+    // referred to by any segment, they are stopped instead of running on detached
+    fn drop(&mut self) {
+        for upload in self.uploads.drain(..) {
+            upload.abort();
+        }
+    }
+}
+
+async fn wait_upload(upload: JoinHandle<Result<()>>) -> Result<()> {
+    upload
+        .await
+        .map_err(|e| ErrorCode::TokioError(format!("block upload task failed: {}", e)))?
+}
+
+pub(crate) async fn save_block(
     arrow_schema: &ArrowSchema,
     block: DataBlock,
     data_accessor: Arc<dyn DataAccessor>,
     location: &str,
+    config: &AppenderConfig,
 ) -> Result<u64> {
-    // TODO pick proper compression / encoding algos
+    let buffer = encode_block(arrow_schema, block, config)?;
+    let file_size = buffer.len() as u64;
+    upload_block(buffer, data_accessor, location, config).await?;
+    Ok(file_size)
+}
+
+fn encode_block(
+    arrow_schema: &ArrowSchema,
+    block: DataBlock,
+    config: &AppenderConfig,
+) -> Result<Vec<u8>> {
+    // TODO pick proper encoding algos
     let options = WriteOptions {
         write_statistics: true,
-        compression: Compression::Uncompressed,
+        compression: config.compression,
         version: Version::V2,
     };
     use std::iter::repeat;
@@ -166,13 +392,15 @@ pub(crate) fn save_block(
     let iter = vec![Ok(batch)];
     let row_groups = RowGroupIterator::try_new(iter.into_iter(), arrow_schema, options, encodings)?;
     let parquet_schema = row_groups.parquet_schema().clone();
-    let mut writer = data_accessor.get_writer(location)?;
 
     // arrow2 convert schema to metadata, is it required?
     // -- let key_value_metadata = Some(vec![schema_to_metadata_key(schema)]);
 
-    let len = common_arrow::parquet::write::write_file(
-        &mut writer,
+    // the parquet file is buffered and uploaded as a whole, by `put_stream` or `put_multipart`,
+    // which is the point where the data accessor makes it durable (if configured to)
+    let mut buffer = vec![];
+    common_arrow::parquet::write::write_file(
+        &mut buffer,
         row_groups,
         parquet_schema,
         options,
@@ -180,6 +408,25 @@ pub(crate) fn save_block(
         None,
     )
     .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    Ok(buffer)
+}
 
-    Ok(len)
+async fn upload_block(
+    buffer: Vec<u8>,
+    data_accessor: Arc<dyn DataAccessor>,
+    location: &str,
+    config: &AppenderConfig,
+) -> Result<()> {
+    let stream_len = buffer.len();
+    if stream_len > config.upload_part_size {
+        data_accessor
+            .put_multipart(location, buffer, config.upload_part_size)
+            .await?;
+    } else {
+        let input_stream = futures::stream::iter(vec![Ok::<_, std::io::Error>(buffer)]);
+        data_accessor
+            .put_stream(location, Box::new(input_stream), stream_len)
+            .await?;
+    }
+    Ok(())
 }
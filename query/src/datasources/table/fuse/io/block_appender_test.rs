@@ -0,0 +1,457 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_arrow::arrow::io::parquet::write::Compression;
+use common_base::tokio;
+use common_dal::Bytes;
+use common_dal::DataAccessor;
+use common_dal::InMemory;
+use common_dal::InputStream;
+use common_dal::Local;
+use common_dal::ObjectMeta;
+use common_dal::SeekableReader;
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_meta_api_vo::TableInfo;
+use futures::Stream;
+
+use crate::catalogs::Table;
+use crate::common::StoreApiProvider;
+use crate::configs::Config;
+use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::parse_compression;
+use crate::datasources::table::fuse::read_block;
+use crate::datasources::table::fuse::save_block;
+use crate::datasources::table::fuse::AppenderConfig;
+use crate::datasources::table::fuse::BlockUploader;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::FuseTableFactory;
+use crate::datasources::table::fuse::TableStorageScheme;
+use crate::datasources::table::fuse::DEFAULT_BLOCK_MAX_ROWS;
+use crate::datasources::table::fuse::DEFAULT_COMPRESSION;
+use crate::datasources::table::fuse::DEFAULT_LOCAL_DATA_PATH;
+use crate::datasources::table::fuse::DEFAULT_UPLOAD_CONCURRENCY;
+use crate::datasources::table::fuse::DEFAULT_UPLOAD_PART_SIZE;
+use crate::datasources::table::fuse::MIN_UPLOAD_PART_SIZE;
+use crate::datasources::table::fuse::TBL_OPT_KEY_BLOCK_MAX_ROWS;
+use crate::datasources::table::fuse::TBL_OPT_KEY_COMPRESSION;
+use crate::datasources::table::fuse::TBL_OPT_KEY_STORAGE_SCHEME;
+use crate::datasources::table::fuse::TBL_OPT_KEY_UPLOAD_CONCURRENCY;
+use crate::datasources::table::fuse::TBL_OPT_KEY_UPLOAD_PART_SIZE;
+use crate::datasources::table_engine::TableEngine;
+
+#[test]
+fn test_appender_config_compression() -> Result<()> {
+    assert_eq!(parse_compression("zstd")?, Compression::Zstd);
+    assert_eq!(parse_compression("SNAPPY")?, Compression::Snappy);
+    assert_eq!(parse_compression("lz4")?, Compression::Lz4);
+    assert_eq!(parse_compression("none")?, Compression::Uncompressed);
+    assert!(parse_compression("zstdd").is_err());
+
+    let mut options = HashMap::new();
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.compression,
+        DEFAULT_COMPRESSION
+    );
+
+    options.insert(TBL_OPT_KEY_COMPRESSION.to_string(), "zstd".to_string());
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.compression,
+        Compression::Zstd
+    );
+    assert!(FuseTableFactory::create("")
+        .validate_options(&options)
+        .is_ok());
+
+    // unknown codecs fall back to the default one, but are rejected at table creation
+    options.insert(TBL_OPT_KEY_COMPRESSION.to_string(), "zstdd".to_string());
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.compression,
+        DEFAULT_COMPRESSION
+    );
+    assert!(FuseTableFactory::create("")
+        .validate_options(&options)
+        .is_err());
+    Ok(())
+}
+
+async fn save_and_read_block(config: &AppenderConfig) -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1, 2, 3]),
+        Series::new(vec!["x", "y", "z"]),
+    ]);
+
+    let arrow_schema = schema.to_arrow();
+    let location = block_location("block.parquet");
+    save_block(&arrow_schema, block, da.clone(), &location, config).await?;
+
+    let block = read_block(&location, da, &[0, 1], &arrow_schema).await?;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 3 | z |",
+        "+---+---+",
+    ];
+    assert_blocks_eq(expected, &[block]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_save_block_zstd() -> Result<()> {
+    save_and_read_block(&AppenderConfig {
+        compression: Compression::Zstd,
+        ..Default::default()
+    })
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_save_block_multipart() -> Result<()> {
+    // far smaller than the parquet file, which is uploaded in many parts
+    save_and_read_block(&AppenderConfig {
+        upload_part_size: 16,
+        ..Default::default()
+    })
+    .await
+}
+
+#[test]
+fn test_appender_config_block_max_rows() -> Result<()> {
+    let mut options = HashMap::new();
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.block_max_rows,
+        DEFAULT_BLOCK_MAX_ROWS
+    );
+
+    options.insert(TBL_OPT_KEY_BLOCK_MAX_ROWS.to_string(), "100".to_string());
+    assert_eq!(AppenderConfig::from_options(&options)?.block_max_rows, 100);
+
+    options.insert(TBL_OPT_KEY_BLOCK_MAX_ROWS.to_string(), "0".to_string());
+    assert!(AppenderConfig::from_options(&options).is_err());
+
+    options.insert(TBL_OPT_KEY_BLOCK_MAX_ROWS.to_string(), "1m".to_string());
+    assert!(AppenderConfig::from_options(&options).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_appender_config_upload_part_size() -> Result<()> {
+    let mut options = HashMap::new();
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.upload_part_size,
+        DEFAULT_UPLOAD_PART_SIZE
+    );
+
+    options.insert(
+        TBL_OPT_KEY_UPLOAD_PART_SIZE.to_string(),
+        MIN_UPLOAD_PART_SIZE.to_string(),
+    );
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.upload_part_size,
+        MIN_UPLOAD_PART_SIZE
+    );
+
+    // too small a part for S3
+    options.insert(TBL_OPT_KEY_UPLOAD_PART_SIZE.to_string(), "1024".to_string());
+    assert!(AppenderConfig::from_options(&options).is_err());
+
+    options.insert(TBL_OPT_KEY_UPLOAD_PART_SIZE.to_string(), "0".to_string());
+    assert!(AppenderConfig::from_options(&options).is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_append_blocks_split_oversized_block() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let mut options = HashMap::new();
+    options.insert(TBL_OPT_KEY_BLOCK_MAX_ROWS.to_string(), "2".to_string());
+    let table = FuseTable {
+        tbl_info: TableInfo {
+            table_id: 1,
+            db: "default".to_string(),
+            name: "t".to_string(),
+            schema: schema.clone(),
+            engine: "FUSE".to_string(),
+            options,
+            ..Default::default()
+        },
+        storage_scheme: TableStorageScheme::LocalFs,
+        local_data_path: DEFAULT_LOCAL_DATA_PATH.to_string(),
+    };
+
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1, 2, 3, 4, 5])]);
+    let segment = table
+        .append_blocks(Box::pin(futures::stream::iter::<Vec<Result<DataBlock>>>(
+            vec![Ok(block)],
+        )))
+        .await?;
+
+    // one logical block, three files
+    let row_counts = segment
+        .blocks
+        .iter()
+        .map(|b| b.row_count)
+        .collect::<Vec<_>>();
+    assert_eq!(row_counts, vec![2, 2, 1]);
+    assert_eq!(segment.summary.row_count, 5);
+    assert_eq!(segment.summary.block_count, 3);
+
+    let da = table.data_accessor()?;
+    let arrow_schema = schema.to_arrow();
+    let mut blocks = vec![];
+    for meta in &segment.blocks {
+        blocks.push(read_block(&meta.location.location, da.clone(), &[0], &arrow_schema).await?);
+    }
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "| 5 |", "+---+",
+    ];
+    assert_blocks_eq(expected, &blocks);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_append_and_read_through_local_fs() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let mut options = HashMap::new();
+    options.insert(
+        TBL_OPT_KEY_STORAGE_SCHEME.to_string(),
+        "LOCAL_FS".to_string(),
+    );
+    let tbl_info = TableInfo {
+        table_id: 1,
+        db: "default".to_string(),
+        name: "t".to_string(),
+        schema: schema.clone(),
+        engine: "FUSE".to_string(),
+        options,
+        ..Default::default()
+    };
+    let table = FuseTableFactory::create(dir.path().to_str().unwrap())
+        .try_create(tbl_info, StoreApiProvider::new(&Config::default()))?;
+    let table = table.as_any().downcast_ref::<FuseTable>().unwrap();
+
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1, 2, 3])]);
+    let segment = table
+        .append_blocks(Box::pin(futures::stream::iter::<Vec<Result<DataBlock>>>(
+            vec![Ok(block)],
+        )))
+        .await?;
+    assert_eq!(segment.blocks.len(), 1);
+
+    // the block file lands in the directory of the table, under the configured one
+    let location = &segment.blocks[0].location.location;
+    assert!(dir.path().join("1").join(location).is_file());
+
+    let da = table.data_accessor()?;
+    let block = read_block(location, da, &[0], &schema.to_arrow()).await?;
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+",
+    ];
+    assert_blocks_eq(expected, &[block]);
+    Ok(())
+}
+
+#[test]
+fn test_appender_config_upload_concurrency() -> Result<()> {
+    let mut options = HashMap::new();
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.upload_concurrency,
+        DEFAULT_UPLOAD_CONCURRENCY
+    );
+
+    options.insert(TBL_OPT_KEY_UPLOAD_CONCURRENCY.to_string(), "8".to_string());
+    assert_eq!(
+        AppenderConfig::from_options(&options)?.upload_concurrency,
+        8
+    );
+
+    options.insert(TBL_OPT_KEY_UPLOAD_CONCURRENCY.to_string(), "0".to_string());
+    assert!(AppenderConfig::from_options(&options).is_err());
+    Ok(())
+}
+
+// Logs the uploads, each of which takes a while.
+struct SlowUploadAccessor {
+    inner: InMemory,
+    log: Arc<Mutex<Vec<String>>>,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl DataAccessor for SlowUploadAccessor {
+    fn get_reader(&self, path: &str, len: Option<u64>) -> Result<Box<dyn SeekableReader>> {
+        self.inner.get_reader(path, len)
+    }
+
+    fn get_writer(&self, path: &str) -> Result<Box<dyn Write>> {
+        self.inner.get_writer(path)
+    }
+
+    async fn get_input_stream(&self, path: &str, stream_len: Option<u64>) -> Result<InputStream> {
+        self.inner.get_input_stream(path, stream_len).await
+    }
+
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        self.inner.get(path).await
+    }
+
+    async fn read_range(&self, path: &str, offset: u64, len: u64) -> Result<Bytes> {
+        self.inner.read_range(path, offset, len).await
+    }
+
+    async fn put(&self, path: &str, content: Vec<u8>) -> Result<()> {
+        self.inner.put(path, content).await
+    }
+
+    async fn put_stream(
+        &self,
+        path: &str,
+        input_stream: Box<
+            dyn Stream<Item = std::result::Result<Bytes, std::io::Error>> + Send + Unpin + 'static,
+        >,
+        stream_len: usize,
+    ) -> Result<()> {
+        self.log.lock().push(format!("upload start {}", path));
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let res = self.inner.put_stream(path, input_stream, stream_len).await;
+
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.log.lock().push(format!("upload end {}", path));
+        res
+    }
+
+    async fn put_multipart(&self, path: &str, content: Vec<u8>, part_size: usize) -> Result<()> {
+        self.inner.put_multipart(path, content, part_size).await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ObjectMeta>> {
+        self.inner.list(prefix).await
+    }
+
+    async fn remove(&self, path: &str) -> Result<()> {
+        self.inner.remove(path).await
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_uploader_overlaps_encoding_and_uploads() -> Result<()> {
+    let log = Arc::new(Mutex::new(vec![]));
+    let da = Arc::new(SlowUploadAccessor {
+        inner: InMemory::new(),
+        log: log.clone(),
+        in_flight: AtomicUsize::new(0),
+        max_in_flight: AtomicUsize::new(0),
+    });
+    let config = AppenderConfig {
+        upload_concurrency: 2,
+        ..Default::default()
+    };
+    let mut uploader = BlockUploader::create(da.clone(), config);
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let arrow_schema = schema.to_arrow();
+    for i in 0..4 {
+        log.lock().push(format!("encode {}", i));
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![i, i + 1])]);
+        uploader
+            .save(&arrow_schema, block, &format!("block_{}", i))
+            .await?;
+    }
+    uploader.finish().await?;
+
+    // one file per block
+    for i in 0..4 {
+        let location = format!("block_{}", i);
+        let block = read_block(&location, da.clone(), &[0], &arrow_schema).await?;
+        assert_eq!(block.num_rows(), 2);
+    }
+
+    // the next block is encoded while the previous one is being uploaded
+    let log = log.lock().clone();
+    let position = |event: &str| log.iter().position(|e| e == event).unwrap();
+    assert!(
+        position("encode 1") < position("upload end block_0"),
+        "{:?}",
+        log
+    );
+    // and no more than `upload_concurrency` uploads are in flight
+    assert!(position("upload start block_2") > position("upload end block_0"));
+    assert!(da.max_in_flight.load(Ordering::SeqCst) <= 2);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_uploader_aborts_uploads_on_drop() -> Result<()> {
+    let log = Arc::new(Mutex::new(vec![]));
+    let da = Arc::new(SlowUploadAccessor {
+        inner: InMemory::new(),
+        log: log.clone(),
+        in_flight: AtomicUsize::new(0),
+        max_in_flight: AtomicUsize::new(0),
+    });
+    let mut uploader = BlockUploader::create(da.clone(), AppenderConfig::default());
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let arrow_schema = schema.to_arrow();
+    for i in 0..2 {
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![i, i + 1])]);
+        uploader
+            .save(&arrow_schema, block, &format!("block_{}", i))
+            .await?;
+    }
+
+    // e.g. the next block fails to encode, the append gives up without `finish`
+    drop(uploader);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let log = log.lock().clone();
+    assert!(
+        !log.iter().any(|e| e.starts_with("upload end")),
+        "{:?}",
+        log
+    );
+    for i in 0..2 {
+        let location = format!("block_{}", i);
+        assert!(read_block(&location, da.clone(), &[0], &arrow_schema)
+            .await
+            .is_err());
+    }
+    Ok(())
+}
@@ -0,0 +1,194 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+
+use common_catalog::BlockMeta;
+use common_catalog::Stats;
+use common_catalog::TableSnapshot;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use uuid::Uuid;
+
+use crate::catalogs::meta_backend::TABLE_META_KEY_SNAPSHOT_LOCATION;
+use crate::datasources::table::fuse::merge_statistics;
+use crate::datasources::table::fuse::read_block;
+use crate::datasources::table::fuse::read_segment_async;
+use crate::datasources::table::fuse::read_table_snapshot_async;
+use crate::datasources::table::fuse::segment_info_location;
+use crate::datasources::table::fuse::snapshot_location;
+use crate::datasources::table::fuse::FuseTable;
+use crate::sessions::DatabendQueryContextRef;
+
+pub const TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD: &str = "compact_block_size_threshold";
+
+/// Blocks smaller than this (in-memory size, in bytes) are merged by compaction
+pub const DEFAULT_COMPACT_BLOCK_SIZE_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactorConfig {
+    pub block_size_threshold: u64,
+}
+
+impl Default for CompactorConfig {
+    fn default() -> Self {
+        CompactorConfig {
+            block_size_threshold: DEFAULT_COMPACT_BLOCK_SIZE_THRESHOLD,
+        }
+    }
+}
+
+impl CompactorConfig {
+    pub fn from_options(options: &HashMap<String, String>) -> Result<CompactorConfig> {
+        let block_size_threshold = match options.get(TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD) {
+            None => DEFAULT_COMPACT_BLOCK_SIZE_THRESHOLD,
+            Some(v) => v.parse::<u64>().map_err(|_| {
+                ErrorCode::BadOption(format!(
+                    "invalid value of table option {}: {}, expects a number of bytes",
+                    TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD, v
+                ))
+            })?,
+        };
+        Ok(CompactorConfig {
+            block_size_threshold,
+        })
+    }
+}
+
+impl FuseTable {
+    /// Merges the blocks smaller than the configured threshold into larger ones.
+    ///
+    /// The segments that contain small blocks are replaced by a single new segment, which
+    /// holds the merged blocks and the large blocks of the replaced segments. The replaced
+    /// blocks and segments are left untouched, they are reclaimed by GC.
+    ///
+    /// Returns the new snapshot, which is committed as the latest one of the table, or `None` if
+    /// there is nothing to compact.
+    pub async fn compact(&self, ctx: DatabendQueryContextRef) -> Result<Option<TableSnapshot>> {
+        self.check_writable()?;
+        let schema = self.tbl_info.schema.clone();
+        let snapshot_loc = match schema.meta().get(TABLE_META_KEY_SNAPSHOT_LOCATION) {
+            Some(loc) => loc,
+            None => return Ok(None),
+        };
+
+        let da = self.data_accessor()?;
+        let config = self.compactor_config()?;
+        let snapshot = read_table_snapshot_async(da.clone(), snapshot_loc).await?;
+
+        // 1. split the segments into those kept as they are, and those to be rewritten
+        let mut kept_segments = vec![];
+        let mut kept_stats = vec![];
+        let mut small_blocks = vec![];
+        let mut large_blocks = vec![];
+        for seg_loc in &snapshot.segments {
+            let segment = read_segment_async(da.clone(), seg_loc).await?;
+            let is_small = |b: &BlockMeta| b.block_size < config.block_size_threshold;
+            if segment.blocks.iter().any(is_small) {
+                let (small, large): (Vec<_>, Vec<_>) =
+                    segment.blocks.into_iter().partition(is_small);
+                small_blocks.extend(small);
+                large_blocks.extend(large);
+            } else {
+                kept_segments.push(seg_loc.clone());
+                kept_stats.push(segment.summary);
+            }
+        }
+
+        if small_blocks.len() < 2 {
+            return Ok(None);
+        }
+
+        // 2. read the small blocks back, and merge them into blocks of about the threshold size
+        let arrow_schema = schema.to_arrow();
+        let projection = (0..schema.fields().len()).collect::<Vec<usize>>();
+        let mut merged_blocks = vec![];
+        let mut pending = vec![];
+        let mut pending_size = 0;
+        for meta in &small_blocks {
+            let block = read_block(
+                &meta.location.location,
+                da.clone(),
+                &projection,
+                &arrow_schema,
+            )
+            .await?;
+            pending_size += meta.block_size;
+            pending.push(block);
+            if pending_size >= config.block_size_threshold {
+                merged_blocks.push(DataBlock::concat_blocks(&pending)?);
+                pending.clear();
+                pending_size = 0;
+            }
+        }
+        if !pending.is_empty() {
+            merged_blocks.push(DataBlock::concat_blocks(&pending)?);
+        }
+
+        // 3. append the merged blocks, together with the large blocks, as a new segment
+        let mut segment_info = self
+            .append_blocks(Box::pin(futures::stream::iter(
+                merged_blocks.into_iter().map(Ok::<_, ErrorCode>),
+            )))
+            .await?;
+        segment_info.blocks.extend(large_blocks);
+        let block_stats = segment_info
+            .blocks
+            .iter()
+            .map(|b| Stats {
+                row_count: b.row_count,
+                block_count: 1,
+                uncompressed_byte_size: b.block_size,
+                compressed_byte_size: b.file_size,
+                col_stats: b.col_stats.clone(),
+            })
+            .collect::<Vec<_>>();
+        segment_info.summary = merge_statistics(&schema, &block_stats)?;
+
+        let seg_loc = {
+            let uuid = Uuid::new_v4().to_simple().to_string();
+            segment_info_location(&uuid)
+        };
+        {
+            let bytes = serde_json::to_vec(&segment_info)?;
+            da.put(&seg_loc, bytes).await?;
+        }
+
+        // 4. new snapshot
+        kept_segments.push(seg_loc);
+        kept_stats.push(segment_info.summary);
+        let new_snapshot = TableSnapshot {
+            snapshot_id: Uuid::new_v4(),
+            prev_snapshot_id: Some(snapshot.snapshot_id),
+            schema: snapshot.schema,
+            summary: merge_statistics(&schema, &kept_stats)?,
+            segments: kept_segments,
+        };
+        let new_snapshot_loc = snapshot_location(&new_snapshot.snapshot_id.to_simple().to_string());
+        {
+            let bytes = serde_json::to_vec(&new_snapshot)?;
+            da.put(&new_snapshot_loc, bytes).await?;
+        }
+
+        // 5. commit, the same way as `append_data` does
+        self.commit_snapshot(&ctx, &new_snapshot_loc)?;
+        Ok(Some(new_snapshot))
+    }
+
+    pub(crate) fn compactor_config(&self) -> Result<CompactorConfig> {
+        CompactorConfig::from_options(&self.tbl_info.options)
+    }
+}
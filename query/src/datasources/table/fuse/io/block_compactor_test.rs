@@ -0,0 +1,47 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+
+use common_exception::Result;
+
+use crate::datasources::table::fuse::CompactorConfig;
+use crate::datasources::table::fuse::DEFAULT_COMPACT_BLOCK_SIZE_THRESHOLD;
+use crate::datasources::table::fuse::TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD;
+
+#[test]
+fn test_compactor_config() -> Result<()> {
+    let mut options = HashMap::new();
+    assert_eq!(
+        CompactorConfig::from_options(&options)?.block_size_threshold,
+        DEFAULT_COMPACT_BLOCK_SIZE_THRESHOLD
+    );
+
+    options.insert(
+        TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD.to_string(),
+        "1024".to_string(),
+    );
+    assert_eq!(
+        CompactorConfig::from_options(&options)?.block_size_threshold,
+        1024
+    );
+
+    options.insert(
+        TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD.to_string(),
+        "1k".to_string(),
+    );
+    assert!(CompactorConfig::from_options(&options).is_err());
+    Ok(())
+}
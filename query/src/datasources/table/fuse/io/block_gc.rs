@@ -0,0 +1,148 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use common_dal::DataAccessor;
+use common_dal::ObjectMeta;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::catalogs::meta_backend::TABLE_META_KEY_SNAPSHOT_LOCATION;
+use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::read_segment_async;
+use crate::datasources::table::fuse::read_table_snapshot_async;
+use crate::datasources::table::fuse::segment_info_location;
+use crate::datasources::table::fuse::snapshot_location;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TableStorageScheme;
+
+impl FuseTable {
+    /// Removes the snapshots last modified at least `retain` ago, except the current one,
+    /// together with the segments and blocks that none of the retained snapshots refers to.
+    ///
+    /// The files are found by listing the data root of the table, which is not shared with
+    /// the other tables, so that the orphans, e.g. the blocks of a failed insert, are also
+    /// collected. Returns the locations of the removed blocks.
+    pub async fn gc(&self, retain: Duration) -> Result<Vec<String>> {
+        // only the local fs data root is (a directory) of the table itself
+        if !matches!(self.storage_scheme, TableStorageScheme::LocalFs) {
+            return Err(ErrorCode::UnImplement(format!(
+                "GC of fuse table {}.{} is not supported by storage scheme {:?}",
+                self.tbl_info.db, self.tbl_info.name, self.storage_scheme
+            )));
+        }
+
+        let schema = self.tbl_info.schema.clone();
+        let snapshot_loc = match schema.meta().get(TABLE_META_KEY_SNAPSHOT_LOCATION) {
+            Some(loc) => loc,
+            // without a snapshot, we can not tell orphans from blocks being committed
+            None => return Ok(vec![]),
+        };
+
+        let da = self.data_accessor()?;
+        remove_unreferenced_files(da, snapshot_loc, retain).await
+    }
+
+    /// Removes all the snapshots, segments and blocks of the table, which has been dropped.
+    pub async fn remove_all_files(&self) -> Result<()> {
+        // the data root of other storage schemes may be shared with the other tables
+        if !matches!(self.storage_scheme, TableStorageScheme::LocalFs) {
+            return Err(ErrorCode::UnImplement(format!(
+                "Purge of fuse table {}.{} is not supported by storage scheme {:?}",
+                self.tbl_info.db, self.tbl_info.name, self.storage_scheme
+            )));
+        }
+
+        let da = self.data_accessor()?;
+        // the snapshots go first, for the same reason as gc does
+        for prefix in [
+            snapshot_location(""),
+            segment_info_location(""),
+            block_location(""),
+        ] {
+            for object in da.list(&prefix).await? {
+                da.remove(&object.path).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the files under the data root `da` of one table, which are not referred to by
+/// the current snapshot, nor by the snapshots modified within the retention window.
+///
+/// The snapshots are not walked by their `prev_snapshot_id`, the history may have gaps,
+/// e.g. left by an interrupted gc. The files modified within the retention window are kept,
+/// as they may belong to a commit in progress.
+pub(crate) async fn remove_unreferenced_files(
+    da: Arc<dyn DataAccessor>,
+    current_snapshot_loc: &str,
+    retain: Duration,
+) -> Result<Vec<String>> {
+    // (a modification time in the future is treated as "just now")
+    let now = SystemTime::now();
+    let expired = |object: &ObjectMeta| {
+        now.duration_since(object.last_modified).unwrap_or_default() >= retain
+    };
+
+    let snapshots = da.list(&snapshot_location("")).await?;
+    let (expired_snapshots, retained_snapshots): (Vec<_>, Vec<_>) = snapshots
+        .into_iter()
+        .partition(|object| object.path != current_snapshot_loc && expired(object));
+
+    let mut retained_segments = HashSet::new();
+    let mut retained_blocks = HashSet::new();
+    let retained_locs = retained_snapshots
+        .iter()
+        .map(|object| object.path.as_str())
+        .chain(std::iter::once(current_snapshot_loc))
+        .collect::<HashSet<_>>();
+    for snapshot_loc in retained_locs {
+        let snapshot = read_table_snapshot_async(da.clone(), snapshot_loc).await?;
+        for seg_loc in snapshot.segments {
+            if retained_segments.contains(&seg_loc) {
+                continue;
+            }
+            let segment = read_segment_async(da.clone(), &seg_loc).await?;
+            retained_blocks.extend(segment.blocks.into_iter().map(|b| b.location.location));
+            retained_segments.insert(seg_loc);
+        }
+    }
+
+    // the expired snapshots go first, so that an interrupted gc leaves (unreferenced) files
+    // behind, rather than snapshots referring to removed files
+    for object in expired_snapshots {
+        da.remove(&object.path).await?;
+    }
+
+    for object in da.list(&segment_info_location("")).await? {
+        if !retained_segments.contains(&object.path) && expired(&object) {
+            da.remove(&object.path).await?;
+        }
+    }
+
+    let mut removed = vec![];
+    for object in da.list(&block_location("")).await? {
+        if !retained_blocks.contains(&object.path) && expired(&object) {
+            da.remove(&object.path).await?;
+            removed.push(object.path);
+        }
+    }
+    Ok(removed)
+}
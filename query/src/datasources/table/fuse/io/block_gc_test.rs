@@ -0,0 +1,178 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::tokio;
+use common_catalog::BlockLocation;
+use common_catalog::BlockMeta;
+use common_catalog::SegmentInfo;
+use common_catalog::Stats;
+use common_catalog::TableSnapshot;
+use common_dal::DataAccessor;
+use common_dal::Local;
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api_vo::TableInfo;
+use uuid::Uuid;
+
+use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::read_block;
+use crate::datasources::table::fuse::remove_unreferenced_files;
+use crate::datasources::table::fuse::save_block;
+use crate::datasources::table::fuse::segment_info_location;
+use crate::datasources::table::fuse::snapshot_location;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TableStorageScheme;
+use crate::datasources::table::fuse::DEFAULT_LOCAL_DATA_PATH;
+
+fn empty_stats() -> Stats {
+    Stats {
+        row_count: 0,
+        block_count: 0,
+        uncompressed_byte_size: 0,
+        compressed_byte_size: 0,
+        col_stats: HashMap::new(),
+    }
+}
+
+fn block_meta(location: &str, row_count: u64) -> BlockMeta {
+    BlockMeta {
+        row_count,
+        block_size: 0,
+        file_size: 1,
+        col_stats: HashMap::new(),
+        location: BlockLocation {
+            location: location.to_string(),
+            meta_size: 0,
+        },
+    }
+}
+
+// Saves a segment of the blocks, and a snapshot of the segments, returns the snapshot location.
+async fn commit(
+    da: &Arc<dyn DataAccessor>,
+    prev: Option<&TableSnapshot>,
+    segments: Vec<Vec<BlockMeta>>,
+) -> Result<(String, TableSnapshot)> {
+    let mut seg_locs = vec![];
+    for blocks in segments {
+        let segment = SegmentInfo {
+            blocks,
+            summary: empty_stats(),
+        };
+        let seg_loc = segment_info_location(&Uuid::new_v4().to_simple().to_string());
+        da.put(&seg_loc, serde_json::to_vec(&segment)?).await?;
+        seg_locs.push(seg_loc);
+    }
+
+    let snapshot = TableSnapshot {
+        snapshot_id: Uuid::new_v4(),
+        prev_snapshot_id: prev.map(|s| s.snapshot_id),
+        schema: DataSchema::empty(),
+        summary: empty_stats(),
+        segments: seg_locs,
+    };
+    let loc = snapshot_location(&snapshot.snapshot_id.to_simple().to_string());
+    da.put(&loc, serde_json::to_vec(&snapshot)?).await?;
+    Ok((loc, snapshot))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_gc() -> Result<()> {
+    // the data root of one table
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    // the table rewrites its block twice, the old ones are referenced by the previous snapshots
+    let first = block_location("first.parquet");
+    let old = block_location("old.parquet");
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1, 2, 3])]);
+    let new = block_location("new.parquet");
+    da.put(&first, vec![1]).await?;
+    da.put(&old, vec![1]).await?;
+    save_block(
+        &schema.to_arrow(),
+        block,
+        da.clone(),
+        &new,
+        &Default::default(),
+    )
+    .await?;
+    let (s0_loc, s0) = commit(&da, None, vec![vec![block_meta(&first, 1)]]).await?;
+    let (s1_loc, s1) = commit(&da, Some(&s0), vec![vec![block_meta(&old, 1)]]).await?;
+    let (s2_loc, _) = commit(&da, Some(&s1), vec![vec![block_meta(&new, 3)]]).await?;
+
+    // a block not recorded by any snapshot, e.g. of a failed insert
+    let orphan = block_location("orphan.parquet");
+    da.put(&orphan, vec![1]).await?;
+
+    // the previous snapshots are within the retention window, they can be read as of
+    let removed = remove_unreferenced_files(da.clone(), &s2_loc, Duration::from_secs(3600)).await?;
+    assert!(removed.is_empty());
+    assert!(da.get(&old).await.is_ok());
+    assert!(da.get(&orphan).await.is_ok());
+    assert!(da.get(&s1_loc).await.is_ok());
+
+    // a gap in the history does not hide the snapshots before it
+    da.remove(&s1_loc).await?;
+    let mut removed =
+        remove_unreferenced_files(da.clone(), &s2_loc, Duration::from_secs(0)).await?;
+    removed.sort();
+    let mut expected = vec![first.clone(), old.clone(), orphan.clone()];
+    expected.sort();
+    assert_eq!(removed, expected);
+    for loc in [&first, &old, &orphan, &s0_loc] {
+        assert!(da.get(loc).await.is_err(), "{} is not removed", loc);
+    }
+    assert!(da.get(&s2_loc).await.is_ok());
+    assert_eq!(da.list(&segment_info_location("")).await?.len(), 1);
+
+    // the rows of the current snapshot survive
+    let block = read_block(&new, da.clone(), &[0], &schema.to_arrow()).await?;
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+",
+    ];
+    assert_blocks_eq(expected, &[block]);
+
+    // nothing else to collect
+    let removed = remove_unreferenced_files(da.clone(), &s2_loc, Duration::from_secs(0)).await?;
+    assert!(removed.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_gc_storage_scheme() -> Result<()> {
+    // the data root of S3 is shared by the tables
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let table = FuseTable {
+        tbl_info: TableInfo::simple("default", "t", schema),
+        storage_scheme: TableStorageScheme::S3,
+        local_data_path: DEFAULT_LOCAL_DATA_PATH.to_string(),
+    };
+    let res = table.gc(Duration::from_secs(0)).await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::UnImplement("").code())
+    );
+    Ok(())
+}
@@ -27,6 +27,7 @@ use common_datablocks::DataBlock;
 use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::IntoSeries;
 use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
@@ -34,6 +35,7 @@ use common_planners::Part;
 use futures::StreamExt;
 
 use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::part_block_names;
 
 #[derive(PartialEq, Eq, Hash)]
 pub struct BlockMetaCacheKey {
@@ -70,29 +72,91 @@ impl BlockReader {
     }
 }
 
+/// The values the blocks written before a column was added read for it, the default value of
+/// the column as evaluated when it was added, or NULL if it has none.
+pub(crate) fn backfill_values(schema: &DataSchema) -> Result<Vec<DataValue>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            Ok(field
+                .default_value()?
+                .unwrap_or_else(|| DataValue::from(field.data_type())))
+        })
+        .collect()
+}
+
+/// `backfill` holds the values (by column index) of the columns missing in the blocks.
 pub(crate) async fn read_part(
     part: Part,
     data_accessor: Arc<dyn DataAccessor>,
     projection: Vec<usize>,
     sender: Sender<Result<DataBlock>>,
     arrow_schema: &ArrowSchema,
+    backfill: &[DataValue],
+    batch_size: usize,
 ) -> Result<()> {
-    let loc = block_location(&part.name);
+    for name in part_block_names(&part) {
+        let loc = block_location(name);
+        let block = read_block_with_backfill(
+            &loc,
+            data_accessor.clone(),
+            &projection,
+            arrow_schema,
+            backfill,
+        )
+        .await?;
+        // a block file is read as a whole, and sent in batches of (at most) `batch_size` rows
+        for batch in DataBlock::split_block_by_size(&block, batch_size.max(1))? {
+            sender
+                .send(Ok(batch))
+                .await
+                .map_err(|e| ErrorCode::BrokenChannel(e.to_string()))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn read_block(
+    location: &str,
+    data_accessor: Arc<dyn DataAccessor>,
+    projection: &[usize],
+    arrow_schema: &ArrowSchema,
+) -> Result<DataBlock> {
+    let backfill = backfill_values(&DataSchema::from(arrow_schema))?;
+    read_block_with_backfill(location, data_accessor, projection, arrow_schema, &backfill).await
+}
+
+pub(crate) async fn read_block_with_backfill(
+    location: &str,
+    data_accessor: Arc<dyn DataAccessor>,
+    projection: &[usize],
+    arrow_schema: &ArrowSchema,
+    backfill: &[DataValue],
+) -> Result<DataBlock> {
     // TODO pass in parquet file len
-    let mut reader = data_accessor.get_input_stream(&loc, None).await?;
+    let mut reader = data_accessor.get_input_stream(location, None).await?;
     let metadata = read_metadata_async(&mut reader)
         .await
         .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
 
     // only onw page in the the parquet
     let row_group = 0;
-    let cols = projection
-        .iter()
-        .map(|idx| (metadata.row_groups[row_group].column(*idx), *idx));
+    let schema = Arc::new(DataSchema::from(arrow_schema));
+    let num_rows = metadata.row_groups[row_group].num_rows() as usize;
+    let num_cols = metadata.row_groups[row_group].columns().len();
 
     let fields = arrow_schema.fields();
-    let mut arrays: Vec<Arc<dyn common_arrow::arrow::array::Array>> = vec![];
-    for (col_meta, idx) in cols {
+    let mut columns = Vec::with_capacity(projection.len());
+    for idx in projection.iter().cloned() {
+        // columns added (by ALTER TABLE) after the block was written are backfilled
+        if idx >= num_cols {
+            columns.push(DataColumn::Constant(backfill[idx].clone(), num_rows));
+            continue;
+        }
+
+        let col_meta = metadata.row_groups[row_group].column(idx);
         // NOTE: here the page filter is !Send
         let pages = get_page_stream(col_meta, &mut reader, vec![], Arc::new(|_, _| true))
             .await
@@ -105,19 +169,9 @@ pub(crate) async fn read_part(
             fields[idx].data_type.clone(),
         )
         .await?;
-        arrays.push(array.into());
+        let array: Arc<dyn common_arrow::arrow::array::Array> = array.into();
+        columns.push(DataColumn::Array(array.into_series()));
     }
 
-    let ser = arrays
-        .into_iter()
-        .map(|a| DataColumn::Array(a.into_series()))
-        .collect::<Vec<_>>();
-
-    let block = DataBlock::create(Arc::new(DataSchema::from(arrow_schema)), ser);
-    sender
-        .send(Ok(block))
-        .await
-        .map_err(|e| ErrorCode::BrokenChannel(e.to_string()))?;
-
-    Ok(())
+    Ok(DataBlock::create(schema, columns))
 }
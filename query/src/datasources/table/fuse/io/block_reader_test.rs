@@ -0,0 +1,174 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_dal::DataAccessor;
+use common_dal::Local;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Part;
+
+use crate::datasources::table::fuse::backfill_values;
+use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::read_part;
+use crate::datasources::table::fuse::save_block;
+use crate::datasources::table::fuse::AppenderConfig;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_read_part_batch_size() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(
+        (0..10).collect::<Vec<i32>>(),
+    )]);
+    let arrow_schema = schema.to_arrow();
+    let location = block_location("batch.parquet");
+    let config = AppenderConfig::default();
+    save_block(&arrow_schema, block, da.clone(), &location, &config).await?;
+
+    // custom batch size, set by the `read_batch_size` setting
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_read_batch_size(3)?;
+    let batch_size = ctx.get_settings().get_read_batch_size()? as usize;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let part = Part {
+        name: "batch.parquet".to_string(),
+        version: 0,
+        coalesced: vec![],
+    };
+    let backfill = backfill_values(&schema)?;
+    read_part(part, da, vec![0], tx, &arrow_schema, &backfill, batch_size).await?;
+
+    let mut row_counts = vec![];
+    while let Some(block) = rx.recv().await {
+        row_counts.push(block?.num_rows());
+    }
+    assert_eq!(row_counts, vec![3, 3, 3, 1]);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_read_part_with_added_column() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    // the block is written before column `b` is added
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i32, 2, 3])]);
+    let location = block_location("old.parquet");
+    let config = AppenderConfig::default();
+    save_block(&schema.to_arrow(), block, da.clone(), &location, &config).await?;
+
+    let new_schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int32, false),
+        DataField::new("b", DataType::String, true),
+    ]);
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let part = Part {
+        name: "old.parquet".to_string(),
+        version: 0,
+        coalesced: vec![],
+    };
+    let backfill = backfill_values(&new_schema)?;
+    read_part(
+        part,
+        da,
+        vec![0, 1],
+        tx,
+        &new_schema.to_arrow(),
+        &backfill,
+        10,
+    )
+    .await?;
+
+    let mut blocks = vec![];
+    while let Some(block) = rx.recv().await {
+        blocks.push(block?);
+    }
+    let expected = vec![
+        "+---+------+",
+        "| a | b    |",
+        "+---+------+",
+        "| 1 | NULL |",
+        "| 2 | NULL |",
+        "| 3 | NULL |",
+        "+---+------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, blocks.as_slice());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_read_part_with_added_column_default() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    // the block is written before column `b` is added, with a default value
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false)]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i32, 2])]);
+    let location = block_location("old.parquet");
+    let config = AppenderConfig::default();
+    save_block(&schema.to_arrow(), block, da.clone(), &location, &config).await?;
+
+    // the default value is evaluated once as the column is added, and kept in the schema
+    let field =
+        DataField::new("b", DataType::Int64, false).with_default_expr(Some("40 + 2".to_string()));
+    let ctx = crate::tests::try_create_context()?;
+    let default_value = PlanParser::create(ctx).column_default_value(&field)?;
+    let field = field.with_default_value(default_value.as_ref())?;
+    let new_schema =
+        DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, false), field]);
+    let new_schema = DataSchema::from(&new_schema.to_arrow());
+    let backfill = backfill_values(&new_schema)?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let part = Part {
+        name: "old.parquet".to_string(),
+        version: 0,
+        coalesced: vec![],
+    };
+    read_part(
+        part,
+        da,
+        vec![0, 1],
+        tx,
+        &new_schema.to_arrow(),
+        &backfill,
+        10,
+    )
+    .await?;
+
+    let mut blocks = vec![];
+    while let Some(block) = rx.recv().await {
+        blocks.push(block?);
+    }
+    let expected = vec![
+        "+---+----+",
+        "| a | b  |",
+        "+---+----+",
+        "| 1 | 42 |",
+        "| 2 | 42 |",
+        "+---+----+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, blocks.as_slice());
+    Ok(())
+}
@@ -14,14 +14,27 @@
 //
 
 // consider remove these, read_util seems to be enough (type could be inferred)
+#[cfg(test)]
+mod block_appender_test;
+#[cfg(test)]
+mod block_compactor_test;
+#[cfg(test)]
+mod block_gc_test;
+#[cfg(test)]
+mod block_reader_test;
+
 mod segment_reader;
 mod snapshot_reader;
 // end
 
 mod block_appender;
+mod block_compactor;
+mod block_gc;
 mod block_reader;
 
 pub use block_appender::*;
+pub use block_compactor::*;
+pub use block_gc::*;
 pub use block_reader::*;
 pub use segment_reader::*;
 pub use snapshot_reader::*;
@@ -16,11 +16,25 @@
 use std::sync::Arc;
 
 use common_base::TrySpawn;
+use common_catalog::SnapshotId;
 use common_catalog::TableSnapshot;
 use common_dal::DataAccessor;
 use common_dal::ObjectAccessor;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
+/// Table option: the id of a prior snapshot the table is read as of, instead of the latest one.
+pub const TBL_OPT_KEY_SNAPSHOT_ID: &str = "snapshot_id";
+
+pub fn parse_snapshot_id(value: &str) -> Result<SnapshotId> {
+    SnapshotId::parse_str(value).map_err(|_| {
+        ErrorCode::BadOption(format!(
+            "invalid value of table option {}: {}, expects the id of a snapshot",
+            TBL_OPT_KEY_SNAPSHOT_ID, value
+        ))
+    })
+}
+
 pub fn read_table_snapshot<S: TrySpawn>(
     da: Arc<dyn DataAccessor>,
     ctx: &S,
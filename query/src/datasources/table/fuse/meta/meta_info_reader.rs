@@ -25,15 +25,24 @@ use common_exception::Result;
 
 use crate::sessions::DatabendQueryContextRef;
 
-// TODO cache
 pub struct MetaInfoReader {
     da: Arc<dyn DataAccessor>,
     ctx: DatabendQueryContextRef,
+    // the location of the table snapshot, the segments cached are tagged with it
+    table_version: String,
 }
 
 impl MetaInfoReader {
-    pub fn new(da: Arc<dyn DataAccessor>, ctx: DatabendQueryContextRef) -> Self {
-        MetaInfoReader { da, ctx }
+    pub fn new(
+        da: Arc<dyn DataAccessor>,
+        ctx: DatabendQueryContextRef,
+        table_version: &str,
+    ) -> Self {
+        MetaInfoReader {
+            da,
+            ctx,
+            table_version: table_version.to_string(),
+        }
     }
 }
 
@@ -54,8 +63,12 @@ impl MetaInfoReader {
         }
         Ok(res)
     }
-    #[allow(dead_code)]
-    pub fn read_segment_info(&self, location: &str) -> Result<SegmentInfo> {
-        ObjectAccessor::new(self.da.clone()).blocking_read_obj(&self.ctx, location)
+    /// Reads the segment through the segment cache of the node, bounded by the `segment_cache_size` setting.
+    pub fn read_segment_info(&self, location: &str) -> Result<Arc<SegmentInfo>> {
+        let capacity = self.ctx.get_settings().get_segment_cache_size()?;
+        let cache = self.ctx.get_sessions_manager().get_segment_cache();
+        cache.get_or_load(&self.table_version, location, capacity, || {
+            ObjectAccessor::new(self.da.clone()).blocking_read_obj(&self.ctx, location)
+        })
     }
 }
@@ -13,6 +13,12 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod segment_cache_test;
+
 mod meta_info_reader;
+mod segment_cache;
 
 pub use meta_info_reader::MetaInfoReader;
+pub use segment_cache::SegmentCache;
+pub use segment_cache::SegmentCacheRef;
@@ -0,0 +1,86 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_cache::LruCache;
+use common_catalog::SegmentInfo;
+use common_exception::Result;
+use common_infallible::Mutex;
+
+/// The segments read by the fuse tables, shared by all the queries of the node.
+///
+/// The entries are keyed by the segment location and tagged with the version of the table,
+/// i.e. the location of its snapshot, they are read by. A read of another version misses,
+/// the segment is loaded again.
+pub struct SegmentCache {
+    segments: Mutex<LruCache<String, (String, Arc<SegmentInfo>)>>,
+    // number of the segments loaded from the storage, i.e. the misses
+    loads: AtomicU64,
+}
+
+pub type SegmentCacheRef = Arc<SegmentCache>;
+
+impl SegmentCache {
+    pub fn create(capacity: u64) -> SegmentCacheRef {
+        Arc::new(SegmentCache {
+            segments: Mutex::new(LruCache::new(capacity)),
+            loads: AtomicU64::new(0),
+        })
+    }
+
+    /// Gets the segment at `location` of the table `version`, or loads it by `load` on a miss.
+    ///
+    /// The cache is resized to `capacity` (number of segments) first, 0 disables the cache.
+    pub fn get_or_load<F>(
+        &self,
+        version: &str,
+        location: &str,
+        capacity: u64,
+        load: F,
+    ) -> Result<Arc<SegmentInfo>>
+    where
+        F: FnOnce() -> Result<SegmentInfo>,
+    {
+        {
+            let mut segments = self.segments.lock();
+            if segments.capacity() != capacity {
+                segments.set_capacity(capacity);
+            }
+            if let Some((cached_version, segment)) = segments.get(location) {
+                if cached_version == version {
+                    return Ok(segment.clone());
+                }
+            }
+        }
+
+        // loaded without holding the lock, the concurrent misses of a segment may load it twice
+        self.loads.fetch_add(1, Ordering::Relaxed);
+        let segment = Arc::new(load()?);
+        if capacity > 0 {
+            let mut segments = self.segments.lock();
+            segments.put(location.to_string(), (version.to_string(), segment.clone()));
+        }
+        Ok(segment)
+    }
+
+    /// Number of the segments loaded from the storage so far.
+    pub fn loads(&self) -> u64 {
+        self.loads.load(Ordering::Relaxed)
+    }
+}
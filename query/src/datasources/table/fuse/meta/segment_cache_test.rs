@@ -0,0 +1,73 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_catalog::SegmentInfo;
+use common_catalog::Stats;
+use common_dal::DataAccessor;
+use common_dal::Local;
+use common_exception::Result;
+use uuid::Uuid;
+
+use crate::datasources::table::fuse::segment_info_location;
+use crate::datasources::table::fuse::MetaInfoReader;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_segment_cache() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    let segment = SegmentInfo {
+        blocks: vec![],
+        summary: Stats {
+            row_count: 10,
+            block_count: 0,
+            uncompressed_byte_size: 0,
+            compressed_byte_size: 0,
+            col_stats: HashMap::new(),
+        },
+    };
+    let seg_loc = segment_info_location(&Uuid::new_v4().to_simple().to_string());
+    da.put(&seg_loc, serde_json::to_vec(&segment)?).await?;
+
+    let cache = ctx.get_sessions_manager().get_segment_cache();
+    let loads = cache.loads();
+
+    // the second read of the same version hits the cache, even by another reader
+    let seg = MetaInfoReader::new(da.clone(), ctx.clone(), "v1").read_segment_info(&seg_loc)?;
+    assert_eq!(seg.summary.row_count, 10);
+    assert_eq!(cache.loads(), loads + 1);
+    let seg = MetaInfoReader::new(da.clone(), ctx.clone(), "v1").read_segment_info(&seg_loc)?;
+    assert_eq!(seg.summary.row_count, 10);
+    assert_eq!(cache.loads(), loads + 1);
+
+    // the version bumped, reloaded
+    let reader = MetaInfoReader::new(da.clone(), ctx.clone(), "v2");
+    reader.read_segment_info(&seg_loc)?;
+    assert_eq!(cache.loads(), loads + 2);
+    reader.read_segment_info(&seg_loc)?;
+    assert_eq!(cache.loads(), loads + 2);
+
+    // disabled
+    ctx.get_settings().set_segment_cache_size(0)?;
+    reader.read_segment_info(&seg_loc)?;
+    assert_eq!(cache.loads(), loads + 3);
+
+    Ok(())
+}
@@ -13,6 +13,9 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod table_test;
+
 mod io;
 mod meta;
 mod table;
@@ -21,4 +24,6 @@ mod util;
 pub use io::*;
 pub use meta::*;
 pub use table::FuseTable;
+pub use table::FuseTableFactory;
+pub use table::DEFAULT_LOCAL_DATA_PATH;
 pub use util::*;
@@ -14,67 +14,122 @@
 //
 
 use std::any::Any;
+use std::path::Path;
 use std::sync::Arc;
 
-use common_catalog::BlockLocation;
+use common_base::tokio::runtime::Handle;
+use common_base::TrySpawn;
+use common_catalog::BlockMeta;
 use common_catalog::TableSnapshot;
 use common_dal::DataAccessor;
 use common_dal::DataAccessorBuilder;
 use common_dal::DefaultDataAccessorBuilder;
+use common_dal::Local;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api_vo::TableInfo;
 use common_planners::Extras;
 use common_planners::InsertIntoPlan;
+use common_planners::OptimizeTablePlan;
+use common_planners::Part;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
+use common_planners::TableOptions;
 use common_planners::TruncateTablePlan;
 use common_streams::ProgressStream;
 use common_streams::SendableDataBlockStream;
+use common_streams::TakeStream;
 use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
+use crate::catalogs::meta_backend::TABLE_META_KEY_SNAPSHOT_LOCATION;
+use crate::catalogs::Catalog;
 use crate::catalogs::Table;
+use crate::common::StoreApiProvider;
+use crate::datasources::table::fuse::backfill_values;
+use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::coalesce_parts;
+use crate::datasources::table::fuse::merge_statistics;
+use crate::datasources::table::fuse::parse_compression;
+use crate::datasources::table::fuse::parse_snapshot_id;
+use crate::datasources::table::fuse::parse_storage_scheme;
 use crate::datasources::table::fuse::range_filter;
 use crate::datasources::table::fuse::read_part;
 use crate::datasources::table::fuse::read_table_snapshot;
 use crate::datasources::table::fuse::segment_info_location;
 use crate::datasources::table::fuse::snapshot_location;
+use crate::datasources::table::fuse::to_column_statistics;
+use crate::datasources::table::fuse::AppenderConfig;
+use crate::datasources::table::fuse::CompactorConfig;
 use crate::datasources::table::fuse::MetaInfoReader;
 use crate::datasources::table::fuse::TableStorageScheme;
+use crate::datasources::table::fuse::TBL_OPT_KEY_COMPRESSION;
+use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_ID;
+use crate::datasources::table::fuse::TBL_OPT_KEY_STORAGE_SCHEME;
+use crate::datasources::table_engine::TableEngine;
 use crate::sessions::DatabendQueryContextRef;
 
+/// Root directory of the tables stored in the local fs, if the data path of disk storage is not configured.
+pub const DEFAULT_LOCAL_DATA_PATH: &str = "/tmp";
+
 pub struct FuseTable {
     pub(crate) tbl_info: TableInfo,
     pub(crate) storage_scheme: TableStorageScheme,
+    // root directory of the data, if the storage scheme is `LocalFs`
+    pub(crate) local_data_path: String,
 }
 
 impl FuseTable {
-    pub fn try_create(_tbl_info: TableInfo) -> Result<Box<dyn Table>> {
-        todo!()
+    pub fn try_create(tbl_info: TableInfo, local_data_path: &str) -> Result<Box<dyn Table>> {
+        let storage_scheme =
+            parse_storage_scheme(tbl_info.options.get(TBL_OPT_KEY_STORAGE_SCHEME))?;
+        Ok(Box::new(FuseTable {
+            tbl_info,
+            storage_scheme,
+            local_data_path: local_data_path.to_string(),
+        }))
+    }
+}
+
+pub struct FuseTableFactory {
+    local_data_path: String,
+}
+
+impl FuseTableFactory {
+    pub fn create(local_data_path: &str) -> FuseTableFactory {
+        let local_data_path = match local_data_path {
+            "" => DEFAULT_LOCAL_DATA_PATH,
+            path => path,
+        };
+        FuseTableFactory {
+            local_data_path: local_data_path.to_string(),
+        }
     }
+}
 
-    //    pub fn with_meta_client(
-    //        db: String,
-    //        name: String,
-    //        schema: DataSchemaRef,
-    //        options: TableOptions,
-    //        meta_client: T,
-    //    ) -> Result<Box<dyn Table>> {
-    //        let storage_scheme = parse_storage_scheme(options.get("STORAGE_SCHEME"))?;
-    //        let res = FuseTable {
-    //            db,
-    //            name,
-    //            schema,
-    //            storage_scheme,
-    //            meta_client,
-    //            local: true,
-    //        };
-    //
-    //        Ok(Box::new(res))
-    //    }
+impl TableEngine for FuseTableFactory {
+    fn try_create(
+        &self,
+        tbl_info: TableInfo,
+        _store_provider: StoreApiProvider,
+    ) -> Result<Box<dyn Table>> {
+        FuseTable::try_create(tbl_info, &self.local_data_path)
+    }
+
+    fn validate_options(&self, options: &TableOptions) -> Result<()> {
+        AppenderConfig::from_options(options)?;
+        CompactorConfig::from_options(options)?;
+        // `AppenderConfig` falls back to the default codec, reject the unknown ones here
+        if let Some(v) = options.get(TBL_OPT_KEY_COMPRESSION) {
+            parse_compression(v)?;
+        }
+        if let Some(v) = options.get(TBL_OPT_KEY_SNAPSHOT_ID) {
+            parse_snapshot_id(v)?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -111,19 +166,23 @@ impl Table for FuseTable {
     ) -> Result<ReadDataSourcePlan> {
         // primary work to do: partition pruning/elimination
         let tbl_snapshot = self.table_snapshot(&ctx)?;
-        if let Some(snapshot) = tbl_snapshot {
+        if let (Some(snapshot), Some(snapshot_loc)) = (tbl_snapshot, self.snapshot_loc()?) {
             let da = self.data_accessor()?;
-
-            let meta_reader = MetaInfoReader::new(da, ctx);
-            let block_locations = range_filter(&snapshot, &push_downs, meta_reader)?;
-            let (statistics, parts) = self.to_partitions(&block_locations);
+            let max_parts = ctx.get_settings().get_max_parts_per_query()? as usize;
+
+            // the snapshot location identifies the version of the table
+            let meta_reader = MetaInfoReader::new(da, ctx, &snapshot_loc);
+            let block_metas = range_filter(&snapshot, &push_downs, meta_reader)?;
+            let (mut statistics, parts) = self.to_partitions(&block_metas);
+            statistics.column_statistics = to_column_statistics(
+                &snapshot.schema,
+                snapshot.summary.row_count,
+                &snapshot.summary.col_stats,
+            );
+            let parts = coalesce_parts(parts, max_parts);
 
             let plan = ReadDataSourcePlan {
-                db: self.tbl_info.db.to_string(),
-                table: self.name().to_string(),
-                table_id: self.tbl_info.table_id,
-                table_version: None,
-                schema: self.tbl_info.schema.clone(),
+                table_info: self.tbl_info.clone(),
                 parts,
                 statistics,
                 description: "".to_string(),
@@ -167,7 +226,10 @@ impl Table for FuseTable {
 
         let (tx, rx) = common_base::tokio::sync::mpsc::channel(1024);
 
-        let bite_size = 1; // TODO config
+        let settings = ctx.get_settings();
+        // taking no partitions at a time would end the read before any of them is read
+        let bite_size = (settings.get_read_bite_size()? as usize).max(1);
+        let batch_size = settings.get_read_batch_size()? as usize;
         let mut iter = {
             let ctx = ctx.clone();
             std::iter::from_fn(move || match ctx.clone().try_get_partitions(bite_size) {
@@ -179,32 +241,52 @@ impl Table for FuseTable {
         };
         let da = self.data_accessor()?;
         let arrow_schema = self.tbl_info.schema.to_arrow();
-        let _h = common_base::tokio::task::spawn_local(async move {
-            // TODO error handling is buggy
-            for part in &mut iter {
-                read_part(
-                    part,
-                    da.clone(),
-                    projection.clone(),
-                    tx.clone(),
-                    &arrow_schema,
-                )
-                .await?;
+        let backfill = backfill_values(&self.tbl_info.schema)?;
+        ctx.try_spawn_blocking(move || {
+            // the page streams are !Send, thus the parts are read by this thread,
+            // instead of by a spawned task
+            let res = Handle::current().block_on(async {
+                for part in &mut iter {
+                    read_part(
+                        part,
+                        da.clone(),
+                        projection.clone(),
+                        tx.clone(),
+                        &arrow_schema,
+                        &backfill,
+                        batch_size,
+                    )
+                    .await?;
+                }
+                Ok::<(), ErrorCode>(())
+            });
+            // the error ends the stream, unless the stream has been dropped already
+            if let Err(cause) = res {
+                let _ = tx.blocking_send(Err(cause));
             }
-            Ok::<(), ErrorCode>(())
-        });
+        })?;
 
         let progress_callback = ctx.progress_callback()?;
         let receiver = ReceiverStream::new(rx);
         let stream = ProgressStream::try_create(Box::pin(receiver), progress_callback)?;
-        Ok(Box::pin(stream))
+        // the reading task stops once the stream is dropped by the take
+        match source_plan
+            .push_downs
+            .as_ref()
+            .and_then(|extras| extras.limit)
+        {
+            Some(limit) => Ok(Box::pin(TakeStream::new(Box::pin(stream), limit))),
+            None => Ok(Box::pin(stream)),
+        }
     }
 
     async fn append_data(
         &self,
         ctx: DatabendQueryContextRef,
         insert_plan: InsertIntoPlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
+        self.check_writable()?;
+
         // 1. take out input stream from plan
         //    Assumes that, insert_interpreter has already split data into blocks properly
         let block_stream = {
@@ -218,6 +300,7 @@ impl Table for FuseTable {
 
         // 2. Append blocks to storage
         let segment_info = self.append_blocks(block_stream).await?;
+        let appended_rows = segment_info.summary.row_count as usize;
 
         let seg_loc = {
             let uuid = Uuid::new_v4().to_simple().to_string();
@@ -230,61 +313,131 @@ impl Table for FuseTable {
         }
 
         // 3. new snapshot
-        let tbl_snapshot = self
-            .table_snapshot(&ctx)?
-            .unwrap_or_else(TableSnapshot::new);
-        let _snapshot_id = tbl_snapshot.snapshot_id;
-        let new_snapshot = tbl_snapshot.append_segment(seg_loc);
-        let _new_snapshot_id = new_snapshot.snapshot_id;
+        let new_snapshot = match self.table_snapshot(&ctx)? {
+            None => TableSnapshot {
+                snapshot_id: Uuid::new_v4(),
+                prev_snapshot_id: None,
+                schema: self.tbl_info.schema.as_ref().clone(),
+                summary: segment_info.summary.clone(),
+                segments: vec![seg_loc],
+            },
+            Some(prev) => {
+                let summary = merge_statistics(&self.tbl_info.schema, &[
+                    prev.summary.clone(),
+                    segment_info.summary.clone(),
+                ])?;
+                TableSnapshot {
+                    summary,
+                    ..prev.append_segment(seg_loc)
+                }
+            }
+        };
 
+        // named by the id, so that the table can be read as of it later
+        let snapshot_loc = snapshot_location(&new_snapshot.snapshot_id.to_simple().to_string());
         {
-            let uuid = Uuid::new_v4().to_simple().to_string();
-            let snapshot_loc = snapshot_location(&uuid);
-
             let bytes = serde_json::to_vec(&new_snapshot)?;
             da.put(&snapshot_loc, bytes).await?;
         }
 
         // 4. commit
-        let _table_id = insert_plan.tbl_id;
-        // TODO simple retry strategy
-        // self.meta_client
-        //     .commit_table(
-        //         table_id,
-        //         snapshot_id.to_simple().to_string(),
-        //         new_snapshot_id.to_simple().to_string(),
-        //     )
-        //     .await?;
-        Ok(())
+        // TODO simple retry strategy, the files written are left to GC if the commit fails
+        self.commit_snapshot(&ctx, &snapshot_loc)?;
+        Ok(appended_rows)
     }
 
     async fn truncate(
         &self,
         _ctx: DatabendQueryContextRef,
         _truncate_plan: TruncateTablePlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
+        self.check_writable()?;
         todo!()
     }
+
+    async fn optimize(
+        &self,
+        ctx: DatabendQueryContextRef,
+        _optimize_plan: OptimizeTablePlan,
+    ) -> Result<()> {
+        self.compact(ctx).await?;
+        Ok(())
+    }
+
+    async fn purge(&self) -> Result<()> {
+        self.remove_all_files().await
+    }
 }
 
 impl FuseTable {
-    fn table_snapshot(&self, ctx: &DatabendQueryContextRef) -> Result<Option<TableSnapshot>> {
-        let schema = self.schema()?;
-        if let Some(loc) = schema.meta().get("META_SNAPSHOT_LOCATION") {
-            let r = read_table_snapshot(self.data_accessor()?, ctx, loc)?;
-            Ok(Some(r))
-        } else {
-            Ok(None)
+    /// Reads the snapshot the table is read as of, the latest one if the `snapshot_id` option is not set.
+    pub(crate) fn table_snapshot(
+        &self,
+        ctx: &DatabendQueryContextRef,
+    ) -> Result<Option<TableSnapshot>> {
+        let loc = match self.snapshot_loc()? {
+            None => return Ok(None),
+            Some(loc) => loc,
+        };
+
+        let res = read_table_snapshot(self.data_accessor()?, ctx, &loc);
+        match self.tbl_info.options.get(TBL_OPT_KEY_SNAPSHOT_ID) {
+            // the prior snapshots may have been purged
+            Some(id) => res.map(Some).map_err(|cause| {
+                ErrorCode::UnknownTableSnapshot(format!(
+                    "Can not read table {} as of snapshot {}, it does not exist or has been purged: {}",
+                    self.tbl_info.name,
+                    id,
+                    cause.message()
+                ))
+            }),
+            None => res.map(Some),
+        }
+    }
+
+    /// A table read as of a prior snapshot can not be written, the history is not to be rewritten.
+    pub(crate) fn check_writable(&self) -> Result<()> {
+        match self.tbl_info.options.get(TBL_OPT_KEY_SNAPSHOT_ID) {
+            Some(id) => Err(ErrorCode::TableIsReadOnly(format!(
+                "Table {} is read as of snapshot {}, it can not be written",
+                self.tbl_info.name, id
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Commits the snapshot at `snapshot_loc` as the latest one of the table, it is based on the
+    /// snapshot the table instance is read as of, the commit fails if the table has been changed since.
+    pub(crate) fn commit_snapshot(
+        &self,
+        ctx: &DatabendQueryContextRef,
+        snapshot_loc: &str,
+    ) -> Result<()> {
+        let prev_snapshot_loc = self.snapshot_loc()?;
+        let database = ctx.get_catalog().get_database(&self.tbl_info.db)?;
+        database.commit_table_snapshot(
+            &self.tbl_info.name,
+            prev_snapshot_loc.as_deref(),
+            snapshot_loc,
+        )
+    }
+
+    pub(crate) fn snapshot_loc(&self) -> Result<Option<String>> {
+        if let Some(v) = self.tbl_info.options.get(TBL_OPT_KEY_SNAPSHOT_ID) {
+            let id = parse_snapshot_id(v)?;
+            return Ok(Some(snapshot_location(&id.to_simple().to_string())));
         }
+        Ok(self
+            .tbl_info
+            .schema
+            .meta()
+            .get(TABLE_META_KEY_SNAPSHOT_LOCATION)
+            .cloned())
     }
 
     pub(crate) fn empty_read_source_plan(&self) -> Result<ReadDataSourcePlan> {
         Ok(ReadDataSourcePlan {
-            db: self.tbl_info.name.clone(),
-            table: self.name().to_string(),
-            table_id: self.tbl_info.table_id,
-            table_version: None,
-            schema: self.tbl_info.schema.clone(),
+            table_info: self.tbl_info.clone(),
             parts: vec![],
             statistics: Statistics::default(),
             description: "".to_string(),
@@ -295,12 +448,45 @@ impl FuseTable {
         })
     }
 
-    pub(crate) fn to_partitions(&self, _blocs: &[BlockLocation]) -> (Statistics, Partitions) {
-        todo!()
+    /// One part for each of the blocks, named by the block file, see `read_part`.
+    pub(crate) fn to_partitions(&self, blocks: &[BlockMeta]) -> (Statistics, Partitions) {
+        let prefix = block_location("");
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut parts = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            read_rows += block.row_count as usize;
+            read_bytes += block.block_size as usize;
+            let location = &block.location.location;
+            parts.push(Part {
+                name: location
+                    .strip_prefix(&prefix)
+                    .unwrap_or(location)
+                    .to_string(),
+                version: 0,
+                coalesced: vec![],
+            });
+        }
+        (Statistics::new_exact(read_rows, read_bytes), parts)
     }
 
     pub(crate) fn data_accessor(&self) -> Result<Arc<dyn DataAccessor>> {
         // TODO(xp): temp impl, a DataAccessor should be built by the caller that uses `Table`, not `Table` itself
-        DefaultDataAccessorBuilder::build(&self.storage_scheme)
+        match self.storage_scheme {
+            TableStorageScheme::LocalFs => {
+                // each table has a directory of its own, whose files are all of the table
+                let root =
+                    Path::new(&self.local_data_path).join(self.tbl_info.table_id.to_string());
+                let fsync_on_append = self.appender_config()?.fsync_on_append;
+                let local =
+                    Local::new(&root.to_string_lossy()).with_fsync_on_append(fsync_on_append);
+                Ok(Arc::new(local))
+            }
+            _ => DefaultDataAccessorBuilder::build(&self.storage_scheme),
+        }
+    }
+
+    pub(crate) fn appender_config(&self) -> Result<AppenderConfig> {
+        AppenderConfig::from_options(&self.tbl_info.options)
     }
 }
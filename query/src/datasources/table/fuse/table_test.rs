@@ -0,0 +1,240 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_catalog::TableSnapshot;
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_meta_api_vo::TableInfo;
+use common_planners::InsertIntoPlan;
+use common_planners::PlanNode;
+use common_planners::TruncateTablePlan;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::read_block;
+use crate::datasources::table::fuse::read_segment_async;
+use crate::datasources::table::fuse::segment_info_location;
+use crate::datasources::table::fuse::snapshot_location;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TableStorageScheme;
+use crate::datasources::table::fuse::TBL_OPT_KEY_SNAPSHOT_ID;
+use crate::datasources::table::fuse::TBL_OPT_KEY_STORAGE_SCHEME;
+use crate::interpreters::CreateTableInterpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::PlanParser;
+
+fn fuse_table(path: &str, snapshot_loc: &str, options: HashMap<String, String>) -> FuseTable {
+    let mut meta = HashMap::new();
+    meta.insert(
+        "META_SNAPSHOT_LOCATION".to_string(),
+        snapshot_loc.to_string(),
+    );
+    let schema = DataSchema::new_from(vec![DataField::new("a", DataType::Int32, false)], meta);
+    let mut tbl_info = TableInfo::simple("default", "t", Arc::new(schema));
+    tbl_info.options = options;
+    FuseTable {
+        tbl_info,
+        storage_scheme: TableStorageScheme::LocalFs,
+        local_data_path: path.to_string(),
+    }
+}
+
+// the rows of the snapshot the table is read as of
+async fn read_rows(table: &FuseTable, ctx: &DatabendQueryContextRef) -> Result<Vec<DataBlock>> {
+    let da = table.data_accessor()?;
+    let arrow_schema = table.tbl_info.schema.to_arrow();
+    let snapshot = table.table_snapshot(ctx)?.unwrap();
+    let mut blocks = vec![];
+    for seg_loc in &snapshot.segments {
+        let segment = read_segment_async(da.clone(), seg_loc).await?;
+        for block_meta in &segment.blocks {
+            let location = &block_meta.location.location;
+            blocks.push(read_block(location, da.clone(), &[0], &arrow_schema).await?);
+        }
+    }
+    Ok(blocks)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_read_as_of_snapshot() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().to_str().unwrap();
+    let da = fuse_table(path, "", HashMap::new()).data_accessor()?;
+
+    // two writes, each of them adds a segment and a snapshot
+    let writer = fuse_table(path, "", HashMap::new());
+    let schema = writer.tbl_info.schema.clone();
+    let mut snapshot = None;
+    let mut snapshot_locs = vec![];
+    for rows in [vec![1, 2], vec![3, 4, 5]] {
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(rows)]);
+        let segment = writer
+            .append_blocks(Box::pin(futures::stream::iter::<Vec<Result<DataBlock>>>(
+                vec![Ok(block)],
+            )))
+            .await?;
+        let summary = segment.summary.clone();
+        let seg_loc = segment_info_location(&Uuid::new_v4().to_simple().to_string());
+        da.put(&seg_loc, serde_json::to_vec(&segment)?).await?;
+
+        let new_snapshot = match snapshot {
+            None => TableSnapshot {
+                snapshot_id: Uuid::new_v4(),
+                prev_snapshot_id: None,
+                schema: DataSchema::empty(),
+                summary,
+                segments: vec![seg_loc],
+            },
+            Some(prev) => TableSnapshot::append_segment(prev, seg_loc),
+        };
+        let snapshot_loc = snapshot_location(&new_snapshot.snapshot_id.to_simple().to_string());
+        da.put(&snapshot_loc, serde_json::to_vec(&new_snapshot)?)
+            .await?;
+        snapshot_locs.push(snapshot_loc);
+        snapshot = Some(new_snapshot);
+    }
+    let latest = snapshot.unwrap();
+    let first_id = latest.prev_snapshot_id.unwrap();
+
+    // the latest one by default
+    let table = fuse_table(path, &snapshot_locs[1], HashMap::new());
+    let read = table.table_snapshot(&ctx)?.unwrap();
+    assert_eq!(read.snapshot_id, latest.snapshot_id);
+    assert_eq!(read.segments.len(), 2);
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "| 5 |", "+---+",
+    ];
+    assert_blocks_sorted_eq(expected, &read_rows(&table, &ctx).await?);
+
+    // as of the first write, only the segment of the first batch is seen
+    let mut options = HashMap::new();
+    options.insert(
+        TBL_OPT_KEY_SNAPSHOT_ID.to_string(),
+        first_id.to_simple().to_string(),
+    );
+    let table = fuse_table(path, &snapshot_locs[1], options);
+    let read = table.table_snapshot(&ctx)?.unwrap();
+    assert_eq!(read.snapshot_id, first_id);
+    assert_eq!(read.segments, latest.segments[..1].to_vec());
+    assert_eq!(read.summary.row_count, 2);
+    let expected = vec!["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "+---+"];
+    assert_blocks_sorted_eq(expected, &read_rows(&table, &ctx).await?);
+
+    // the history is not to be rewritten
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![6])]);
+    let insert_plan = InsertIntoPlan {
+        db_name: "default".to_string(),
+        tbl_name: "t".to_string(),
+        tbl_id: 0,
+        schema: schema.clone(),
+        select_plan: None,
+        input_stream: Arc::new(Mutex::new(Some(Box::pin(futures::stream::iter::<
+            Vec<Result<DataBlock>>,
+        >(vec![Ok(block)]))))),
+    };
+    let res = table.append_data(ctx.clone(), insert_plan).await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::TableIsReadOnly("").code())
+    );
+    let truncate_plan = TruncateTablePlan {
+        db: "default".to_string(),
+        table: "t".to_string(),
+    };
+    let res = table.truncate(ctx.clone(), truncate_plan).await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::TableIsReadOnly("").code())
+    );
+    assert_eq!(read_rows(&table, &ctx).await?.len(), 1);
+
+    // the first snapshot purged
+    da.remove(&snapshot_locs[0]).await?;
+    let res = table.table_snapshot(&ctx);
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::UnknownTableSnapshot("").code())
+    );
+
+    // not a snapshot id
+    let mut options = HashMap::new();
+    options.insert(TBL_OPT_KEY_SNAPSHOT_ID.to_string(), "latest".to_string());
+    let table = fuse_table(path, &snapshot_locs[1], options);
+    let res = table.table_snapshot(&ctx);
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(ErrorCode::BadOption("").code())
+    );
+
+    Ok(())
+}
+
+async fn execute_query(ctx: &DatabendQueryContextRef, sql: &str) -> Result<Vec<DataBlock>> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    executor.execute().await?.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fuse_table_prune_blocks() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let ctx = crate::tests::try_create_context_with_data_path(dir.path().to_str().unwrap())?;
+
+    if let PlanNode::CreateTable(mut plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("create table default.t(a Int32) Engine = Fuse")?
+    {
+        plan.options.insert(
+            TBL_OPT_KEY_STORAGE_SCHEME.to_string(),
+            "LOCAL_FS".to_string(),
+        );
+        let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // two blocks, of disjoint ranges of a
+    execute_query(&ctx, "insert into default.t values(1),(2),(3)").await?;
+    execute_query(&ctx, "insert into default.t values(11),(12)").await?;
+
+    let result = execute_query(&ctx, "select a from default.t").await?;
+    let expected = vec![
+        "+----+", "| a  |", "+----+", "| 1  |", "| 2  |", "| 3  |", "| 11 |", "| 12 |", "+----+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+    assert_eq!(ctx.get_and_reset_progress_value().read_rows, 5);
+
+    // only the block of a in [11, 12] is read
+    let result = execute_query(&ctx, "select a from default.t where a > 10").await?;
+    let expected = vec!["+----+", "| a  |", "+----+", "| 11 |", "| 12 |", "+----+"];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+    assert_eq!(ctx.get_and_reset_progress_value().read_rows, 2);
+
+    // none of the blocks is read
+    let result = execute_query(&ctx, "select a from default.t where a > 20").await?;
+    assert_eq!(result.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+    assert_eq!(ctx.get_and_reset_progress_value().read_rows, 0);
+
+    Ok(())
+}
@@ -13,58 +13,73 @@
 //  limitations under the License.
 //
 
-use common_catalog::BlockLocation;
+use std::collections::HashMap;
+
+use common_catalog::BlockMeta;
+use common_catalog::ColStats;
+use common_catalog::ColumnId;
 use common_catalog::TableSnapshot;
+use common_datavalues::DataSchema;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
 
+use crate::datasources::index::MinMaxIndex;
 use crate::datasources::table::fuse::MetaInfoReader;
 
-struct TableSparseIndex {}
-struct CacheMgr;
-
-// non-distributed indexing
-impl TableSparseIndex {
-    pub fn load(
-        _table_snapshot: &TableSnapshot,
-        _meta_reader: &MetaInfoReader,
-        _cache_mgr: &CacheMgr,
-    ) -> Result<Self> {
-        // load index, which may be cached (or partially cached)
-        todo!()
-    }
-
-    // Returns an iterator or stream would be better
-    // let's begin with
-    pub fn apply(&self, _expression: &Option<Extras>) -> Result<Vec<BlockLocation>> {
-        // prunes blocks
-        todo!()
-    }
-}
-
+/// Returns the metas of the blocks that may contain rows satisfying the filters of `push_down`,
+/// judging by the min/max statistics of the segments and blocks.
 pub fn range_filter(
     table_snapshot: &TableSnapshot,
     push_down: &Option<Extras>,
     // MetaInfoReader takes care of caching itself
     meta_reader: MetaInfoReader,
-) -> Result<Vec<BlockLocation>> {
-    let cache_mgr = CacheMgr; // TODO passed in from context
-    let range_index = TableSparseIndex::load(table_snapshot, &meta_reader, &cache_mgr)?;
-    range_index.apply(push_down)
+) -> Result<Vec<BlockMeta>> {
+    let filters = match push_down {
+        Some(extras) => extras.filters.as_slice(),
+        None => &[],
+    };
+    let schema = &table_snapshot.schema;
 
-    /*
+    let mut res = vec![];
     for seg_loc in &table_snapshot.segments {
-        // TODO filter by seg.summary
         let seg = meta_reader.read_segment_info(seg_loc)?;
-        let _seg_summary = &seg.summary;
-        for x in seg.blocks {
-            // filter by block_meta's ColStats if necessary
-            let _col_stats = &x.col_stats;
-            // keep the block we are fond of
-            res.push(x);
+        if !may_match(schema, &seg.summary.col_stats, filters)? {
+            continue;
+        }
+        for block in &seg.blocks {
+            if may_match(schema, &block.col_stats, filters)? {
+                res.push(block.clone());
+            }
         }
     }
-
     Ok(res)
-    */
+}
+
+/// Returns false if none of the rows, described by `col_stats`, satisfy all the `filters`.
+pub fn may_match(
+    schema: &DataSchema,
+    col_stats: &HashMap<ColumnId, ColStats>,
+    filters: &[Expression],
+) -> Result<bool> {
+    if filters.is_empty() {
+        return Ok(true);
+    }
+
+    let idx_map = col_stats
+        .iter()
+        .filter(|(id, _)| (**id as usize) < schema.fields().len())
+        .map(|(id, stats)| {
+            let name = schema.field(*id as usize).name().clone();
+            let idx = MinMaxIndex::create(name.clone(), stats.min.clone(), stats.max.clone());
+            (name, idx)
+        })
+        .collect::<HashMap<_, _>>();
+
+    for filter in filters {
+        if !MinMaxIndex::apply_index(idx_map.clone(), filter)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
@@ -0,0 +1,122 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_catalog::BlockLocation;
+use common_catalog::BlockMeta;
+use common_catalog::ColStats;
+use common_catalog::SegmentInfo;
+use common_catalog::Stats;
+use common_catalog::TableSnapshot;
+use common_dal::DataAccessor;
+use common_dal::Local;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::Extras;
+use uuid::Uuid;
+
+use crate::datasources::table::fuse::merge_statistics;
+use crate::datasources::table::fuse::range_filter;
+use crate::datasources::table::fuse::segment_info_location;
+use crate::datasources::table::fuse::MetaInfoReader;
+
+fn block_meta(location: &str, min: i32, max: i32) -> BlockMeta {
+    let mut col_stats = HashMap::new();
+    col_stats.insert(0, ColStats {
+        min: DataValue::Int32(Some(min)),
+        max: DataValue::Int32(Some(max)),
+        null_count: 0,
+        row_count: 10,
+        distinct_of_values: 0,
+        distinct_sketch: None,
+    });
+    BlockMeta {
+        row_count: 10,
+        block_size: 40,
+        file_size: 40,
+        col_stats,
+        location: BlockLocation {
+            location: location.to_string(),
+            meta_size: 0,
+        },
+    }
+}
+
+fn block_summary(meta: &BlockMeta) -> Stats {
+    Stats {
+        row_count: meta.row_count,
+        block_count: 1,
+        uncompressed_byte_size: meta.block_size,
+        compressed_byte_size: meta.file_size,
+        col_stats: meta.col_stats.clone(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_range_filter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = tempfile::tempdir()?;
+    let da: Arc<dyn DataAccessor> = Arc::new(Local::new(dir.path().to_str().unwrap()));
+
+    let schema = DataSchema::new(vec![DataField::new("a", DataType::Int32, false)]);
+
+    // two blocks of disjoint ranges
+    let blocks = vec![block_meta("_b/1", 1, 10), block_meta("_b/2", 11, 20)];
+    let summary = merge_statistics(
+        &schema,
+        &blocks.iter().map(block_summary).collect::<Vec<_>>(),
+    )?;
+    let segment = SegmentInfo { blocks, summary };
+    let seg_loc = segment_info_location(&Uuid::new_v4().to_simple().to_string());
+    da.put(&seg_loc, serde_json::to_vec(&segment)?).await?;
+
+    let snapshot = TableSnapshot {
+        snapshot_id: Uuid::new_v4(),
+        prev_snapshot_id: None,
+        schema,
+        summary: segment.summary,
+        segments: vec![seg_loc],
+    };
+
+    let tests = vec![
+        (None, vec!["_b/1", "_b/2"]),
+        (Some(col("a").gt(lit(15))), vec!["_b/2"]),
+        (Some(col("a").lt_eq(lit(10))), vec!["_b/1"]),
+        (Some(col("a").eq(lit(30))), vec![]),
+        (Some(col("a").lt(lit(5)).or(col("a").gt(lit(15)))), vec![
+            "_b/1", "_b/2",
+        ]),
+    ];
+
+    for (filter, expected) in tests {
+        let push_down = filter.map(|filter| Extras {
+            filters: vec![filter],
+            ..Extras::default()
+        });
+        let meta_reader = MetaInfoReader::new(da.clone(), ctx.clone(), "v1");
+        let locations = range_filter(&snapshot, &push_down, meta_reader)?
+            .into_iter()
+            .map(|meta| meta.location.location)
+            .collect::<Vec<_>>();
+        assert_eq!(locations, expected, "{:?}", push_down);
+    }
+
+    Ok(())
+}
@@ -13,8 +13,10 @@
 //  limitations under the License.
 //
 
+pub const BLOCK_LOCATION_PREFIX: &str = "_b";
+
 pub fn block_location(name: &str) -> String {
-    format!("_b/{}", name)
+    format!("{}/{}", BLOCK_LOCATION_PREFIX, name)
 }
 
 pub fn segment_info_location(name: &str) -> String {
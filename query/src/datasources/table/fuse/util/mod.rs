@@ -13,14 +13,27 @@
 //  limitations under the License.
 //
 
+#[cfg(test)]
+mod index_helpers_test;
+#[cfg(test)]
+mod part_helpers_test;
+#[cfg(test)]
+mod statistic_helper_test;
+
 mod index_helpers;
 mod location_gen;
+mod part_helpers;
 mod projection_helper;
 mod statistic_helper;
 mod storage_scheme_helper;
 
+pub use index_helpers::may_match;
 pub use index_helpers::range_filter;
 pub use location_gen::*;
+pub use part_helpers::*;
 pub use projection_helper::project_col_idx;
+pub use statistic_helper::column_distinct_sketch;
 pub use statistic_helper::column_stats_reduce;
+pub use statistic_helper::merge_statistics;
+pub use statistic_helper::to_column_statistics;
 pub use storage_scheme_helper::*;
@@ -0,0 +1,51 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_planners::Part;
+use common_planners::Partitions;
+
+/// Coalesces adjacent parts, such that there are at most `max_parts` of them (0 means unlimited).
+///
+/// A coalesced part is named after the first of the parts it covers, and lists the names of all
+/// of them, see `part_block_names`.
+pub fn coalesce_parts(parts: Partitions, max_parts: usize) -> Partitions {
+    if max_parts == 0 || parts.len() <= max_parts {
+        return parts;
+    }
+
+    let chunk_size = (parts.len() + max_parts - 1) / max_parts;
+    parts
+        .chunks(chunk_size)
+        .map(|chunk| Part {
+            name: chunk[0].name.clone(),
+            version: chunk[0].version,
+            coalesced: chunk
+                .iter()
+                .flat_map(part_block_names)
+                .map(String::from)
+                .collect(),
+        })
+        .collect()
+}
+
+/// Names of the blocks covered by the part, which may have been coalesced
+pub fn part_block_names(part: &Part) -> impl Iterator<Item = &str> {
+    let names = if part.coalesced.is_empty() {
+        std::slice::from_ref(&part.name)
+    } else {
+        part.coalesced.as_slice()
+    };
+    names.iter().map(String::as_str)
+}
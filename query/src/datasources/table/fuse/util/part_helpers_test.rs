@@ -0,0 +1,71 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_planners::Part;
+
+use crate::datasources::table::fuse::coalesce_parts;
+use crate::datasources::table::fuse::part_block_names;
+
+fn parts(n: usize) -> Vec<Part> {
+    (0..n)
+        .map(|i| Part {
+            name: format!("{}.parquet", i),
+            version: 0,
+            coalesced: vec![],
+        })
+        .collect()
+}
+
+#[test]
+fn test_coalesce_parts() {
+    // within the limit, parts are scheduled as they are
+    assert_eq!(coalesce_parts(parts(10), 10), parts(10));
+    assert_eq!(coalesce_parts(parts(10), 0), parts(10));
+
+    // a huge number of parts is capped, and all the blocks are still covered
+    let total = 1_000_003;
+    let max_parts = 1000;
+    let coalesced = coalesce_parts(parts(total), max_parts);
+    assert!(coalesced.len() <= max_parts);
+
+    let names = coalesced
+        .iter()
+        .flat_map(part_block_names)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    let expected = parts(total).into_iter().map(|p| p.name).collect::<Vec<_>>();
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn test_coalesce_parts_keeps_names() {
+    // the names are carried as they are, whatever characters they consist of
+    let mut names = parts(4);
+    names[1].name = "a,b.parquet".to_string();
+
+    let coalesced = coalesce_parts(names.clone(), 2);
+    assert_eq!(coalesced.len(), 2);
+    assert_eq!(coalesced[0].name, "0.parquet");
+    assert_eq!(coalesced[0].coalesced, vec!["0.parquet", "a,b.parquet"]);
+    assert_eq!(part_block_names(&coalesced[0]).collect::<Vec<_>>(), vec![
+        "0.parquet",
+        "a,b.parquet"
+    ]);
+
+    // a part that is not coalesced is a block by itself
+    assert_eq!(part_block_names(&names[1]).collect::<Vec<_>>(), vec![
+        "a,b.parquet"
+    ]);
+}
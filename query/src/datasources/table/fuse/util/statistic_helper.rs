@@ -18,8 +18,13 @@ use std::collections::HashMap;
 
 use common_catalog::ColStats;
 use common_catalog::ColumnId;
+use common_catalog::HyperLogLog;
+use common_catalog::Stats;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::DataSchema;
 use common_datavalues::DataType;
 use common_exception::Result;
+use common_planners::ColumnStatistics;
 
 pub fn column_stats_reduce(
     stats: Vec<HashMap<ColumnId, (DataType, ColStats)>>,
@@ -51,12 +56,21 @@ pub fn column_stats_reduce(
             let mut max_stats = Vec::with_capacity(stats.len());
             let mut null_count = 0;
             let mut row_count = 0;
+            // the distinct values are unknown if any of the stats is written without a sketch
+            let mut distinct_sketch = Some(HyperLogLog::new());
 
             for col_stats in stats {
                 min_stats.push(col_stats.min.clone());
                 max_stats.push(col_stats.max.clone());
                 null_count += col_stats.null_count;
                 row_count += col_stats.row_count;
+                distinct_sketch = match (distinct_sketch, &col_stats.distinct_sketch) {
+                    (Some(mut merged), Some(sketch)) => {
+                        merged.merge(sketch);
+                        Some(merged)
+                    }
+                    _ => None,
+                };
             }
 
             let min =
@@ -64,7 +78,7 @@ pub fn column_stats_reduce(
                     .min()?;
 
             let max =
-                common_datavalues::DataValue::try_into_data_array(min_stats.as_slice(), data_type)?
+                common_datavalues::DataValue::try_into_data_array(max_stats.as_slice(), data_type)?
                     .max()?;
 
             acc.insert(*id, ColStats {
@@ -72,8 +86,93 @@ pub fn column_stats_reduce(
                 max,
                 null_count,
                 row_count,
+                distinct_of_values: distinct_sketch.as_ref().map_or(0, |s| s.count()),
+                distinct_sketch,
             });
             Ok(acc)
         },
     )
 }
+
+/// Merges the statistics of blocks or segments into a summary.
+///
+/// Column ids are resolved against `schema` (by field index), see `block_stats`.
+pub fn merge_statistics(schema: &DataSchema, stats: &[Stats]) -> Result<Stats> {
+    let mut row_count = 0;
+    let mut block_count = 0;
+    let mut uncompressed_byte_size = 0;
+    let mut compressed_byte_size = 0;
+    let mut col_stats = Vec::with_capacity(stats.len());
+
+    for item in stats {
+        row_count += item.row_count;
+        block_count += item.block_count;
+        uncompressed_byte_size += item.uncompressed_byte_size;
+        compressed_byte_size += item.compressed_byte_size;
+        col_stats.push(
+            item.col_stats
+                .iter()
+                .map(|(id, v)| {
+                    let data_type = schema.field(*id as usize).data_type().clone();
+                    (*id, (data_type, v.clone()))
+                })
+                .collect::<HashMap<ColumnId, (DataType, ColStats)>>(),
+        );
+    }
+
+    Ok(Stats {
+        row_count,
+        block_count,
+        uncompressed_byte_size,
+        compressed_byte_size,
+        col_stats: column_stats_reduce(col_stats)?,
+    })
+}
+
+/// Converts the column statistics of a summary to the (by column name) ones of a read plan,
+/// which are used to estimate the cardinality of the plan, and to answer min/max from.
+/// The min/max are only kept if they are of all the `row_count` rows of the summary, a column
+/// added by ALTER TABLE has no statistics of the rows written before, which read its default.
+pub fn to_column_statistics(
+    schema: &DataSchema,
+    row_count: u64,
+    col_stats: &HashMap<ColumnId, ColStats>,
+) -> HashMap<String, ColumnStatistics> {
+    col_stats
+        .iter()
+        .filter(|(id, _)| (**id as usize) < schema.fields().len())
+        .map(|(id, stats)| {
+            let name = schema.field(*id as usize).name().clone();
+            let complete = stats.row_count as u64 == row_count;
+            (name, ColumnStatistics {
+                null_count: stats.null_count as u64,
+                distinct_count: stats.distinct_of_values,
+                min: complete.then(|| stats.min.clone()),
+                max: complete.then(|| stats.max.clone()),
+            })
+        })
+        .collect()
+}
+
+/// Sketch of the distinct non-null values of a column.
+///
+/// A constant column is sketched as an array of its value, so that a value hashes to the
+/// same key whichever of the two the column of a block is.
+pub fn column_distinct_sketch(column: &DataColumn) -> Result<HyperLogLog> {
+    let series = column.to_minimal_array()?;
+    let mut keys = vec![vec![]; series.len()];
+    if series.serialize(&mut keys).is_err() {
+        // not every type is serializable as group by keys
+        for (row, key) in keys.iter_mut().enumerate() {
+            *key = serde_json::to_vec(&series.try_get(row)?)?;
+        }
+    }
+
+    let mut sketch = HyperLogLog::new();
+    for (row, key) in keys.iter().enumerate() {
+        if !series.is_null(row) {
+            sketch.add(key);
+        }
+    }
+    Ok(sketch)
+}
@@ -0,0 +1,89 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::datasources::table::fuse::block_stats;
+use crate::datasources::table::fuse::column_distinct_sketch;
+use crate::datasources::table::fuse::column_stats_reduce;
+
+fn nullable_block(values: impl Iterator<Item = i32>) -> DataBlock {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, true)]);
+    // every 10th value is a null
+    let values = values
+        .map(|v| if v % 10 == 0 { None } else { Some(v) })
+        .collect::<Vec<_>>();
+    DataBlock::create_by_array(schema, vec![Series::new(values)])
+}
+
+fn assert_estimate(estimate: u64, expected: u64) {
+    let error = (estimate as f64 - expected as f64).abs() / expected as f64;
+    assert!(
+        error < 0.1,
+        "estimate {} of {} distinct values",
+        estimate,
+        expected
+    );
+}
+
+#[test]
+fn test_column_stats_null_and_distinct_count() -> Result<()> {
+    // overlapping ranges: [0, 6000) and [4000, 10000)
+    let left = block_stats(&nullable_block(0..6000))?;
+    let right = block_stats(&nullable_block(4000..10000))?;
+
+    assert_eq!(left[&0].1.null_count, 600);
+    assert_eq!(left[&0].1.row_count, 6000);
+    assert_estimate(left[&0].1.distinct_of_values, 5400);
+
+    let merged = column_stats_reduce(vec![left, right])?;
+    let stats = &merged[&0];
+    // null counts are summed up, distinct values are not
+    assert_eq!(stats.null_count, 1200);
+    assert_eq!(stats.row_count, 12000);
+    assert_estimate(stats.distinct_of_values, 9000);
+    Ok(())
+}
+
+#[test]
+fn test_column_distinct_sketch_of_constant() -> Result<()> {
+    let constant = DataColumn::Constant(DataValue::Int32(Some(7)), 3);
+    let array = DataColumn::Array(Series::new(vec![7i32, 7, 7]));
+    assert_eq!(
+        column_distinct_sketch(&constant)?,
+        column_distinct_sketch(&array)?
+    );
+
+    let null = DataColumn::Constant(DataValue::Int32(None), 3);
+    assert_eq!(column_distinct_sketch(&null)?.count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_column_stats_reduce_without_sketch() -> Result<()> {
+    let left = block_stats(&nullable_block(0..100))?;
+    let mut right = block_stats(&nullable_block(100..200))?;
+    // stats written before the sketches are introduced
+    right.get_mut(&0).unwrap().1.distinct_sketch = None;
+
+    let merged = column_stats_reduce(vec![left, right])?;
+    let stats = &merged[&0];
+    assert_eq!(stats.row_count, 200);
+    assert_eq!(stats.distinct_sketch, None);
+    assert_eq!(stats.distinct_of_values, 0);
+    Ok(())
+}
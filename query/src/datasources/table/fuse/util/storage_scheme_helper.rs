@@ -19,7 +19,9 @@ use common_exception::Result;
 
 pub type TableStorageScheme = StorageScheme;
 
-#[allow(dead_code)]
+/// Table option: where the data of a Fuse table is stored, one of `LOCAL_FS`, `DATABEND_DFS` or `S3`.
+pub const TBL_OPT_KEY_STORAGE_SCHEME: &str = "STORAGE_SCHEME";
+
 pub fn parse_storage_scheme(value: Option<&String>) -> Result<StorageScheme> {
     if let Some(v) = value {
         let v = v.to_uppercase();
@@ -0,0 +1,199 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::convert::TryFrom;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value;
+
+/// Parses a newline delimited JSON `file` read from `reader` into blocks of at most `block_size`
+/// rows, the blocks are parsed one at a time as they are iterated.
+///
+/// Each non-blank line is an object, its fields are mapped to the columns of the `schema` by name,
+/// the extra ones are ignored. A missing field is null, it is an error for a non-nullable column,
+/// as well as a line is not an object or a value can not be coerced to the type of its column.
+/// An error tells the line of it, and ends the iteration.
+pub struct JsonBlockReader<R: Read> {
+    reader: BufReader<R>,
+    file: String,
+    schema: DataSchemaRef,
+    block_size: usize,
+    line_no: usize,
+    finished: bool,
+}
+
+impl<R: Read> JsonBlockReader<R> {
+    pub fn create(reader: R, file: &str, schema: &DataSchemaRef, block_size: usize) -> Self {
+        JsonBlockReader {
+            reader: BufReader::new(reader),
+            file: file.to_string(),
+            schema: schema.clone(),
+            block_size: block_size.max(1),
+            line_no: 0,
+            finished: false,
+        }
+    }
+
+    fn read_block(&mut self) -> Result<Option<DataBlock>> {
+        let fields = self.schema.fields();
+        let mut columns = vec![Vec::with_capacity(self.block_size); fields.len()];
+        let mut rows = 0;
+        let mut line = vec![];
+
+        while rows < self.block_size {
+            line.clear();
+            if self.reader.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            self.line_no += 1;
+            if line.iter().all(|b| b.is_ascii_whitespace()) {
+                continue;
+            }
+
+            let object = match serde_json::from_slice::<Value>(&line) {
+                Ok(Value::Object(object)) => object,
+                Ok(other) => {
+                    return Err(self.parse_error(format!("expects an object, got {}", other)));
+                }
+                Err(e) => return Err(self.parse_error(e.to_string())),
+            };
+
+            for (field, column) in fields.iter().zip(columns.iter_mut()) {
+                let value = match object.get(field.name()) {
+                    Some(value) => value,
+                    None if field.is_nullable() => &Value::Null,
+                    None => {
+                        return Err(self.parse_error(format!(
+                            "missing the value of the non-nullable column {}",
+                            field.name()
+                        )));
+                    }
+                };
+                let coerced = match value.is_null() && !field.is_nullable() {
+                    true => None,
+                    false => coerce_json_value(value, field.data_type()),
+                };
+                let coerced = coerced.ok_or_else(|| {
+                    self.parse_error(format!(
+                        "can not coerce {} to {:?} for column {}",
+                        value,
+                        field.data_type(),
+                        field.name()
+                    ))
+                })?;
+                column.push(coerced);
+            }
+            rows += 1;
+        }
+
+        match rows {
+            0 => Ok(None),
+            _ => build_block(&self.schema, &mut columns).map(Some),
+        }
+    }
+
+    fn parse_error(&self, cause: String) -> ErrorCode {
+        ErrorCode::BadBytes(format!(
+            "Failed to parse JSON file {} at line {}: {}",
+            self.file, self.line_no, cause
+        ))
+    }
+}
+
+impl<R: Read> Iterator for JsonBlockReader<R> {
+    type Item = Result<DataBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let res = self.read_block().transpose();
+        self.finished = !matches!(res, Some(Ok(_)));
+        res
+    }
+}
+
+fn build_block(schema: &DataSchemaRef, columns: &mut [Vec<DataValue>]) -> Result<DataBlock> {
+    let arrays = schema
+        .fields()
+        .iter()
+        .zip(columns.iter_mut())
+        .map(|(field, values)| {
+            let array = DataValue::try_into_data_array(values, field.data_type());
+            values.clear();
+            array
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(DataBlock::create_by_array(schema.clone(), arrays))
+}
+
+/// Coerces a JSON value to a value of `data_type`, `None` if it is not compatible.
+///
+/// Only the lossless coercions are made: a JSON null to a null of any type, a boolean to
+/// `Boolean`, a string to `String`, an integer to any numeric type it fits in, and any number to
+/// a float type.
+fn coerce_json_value(value: &Value, data_type: &DataType) -> Option<DataValue> {
+    if value.is_null() {
+        return match data_type {
+            DataType::Int8 => Some(DataValue::Int8(None)),
+            DataType::Int16 => Some(DataValue::Int16(None)),
+            DataType::Int32 => Some(DataValue::Int32(None)),
+            DataType::Int64 => Some(DataValue::Int64(None)),
+            DataType::UInt8 => Some(DataValue::UInt8(None)),
+            DataType::UInt16 => Some(DataValue::UInt16(None)),
+            DataType::UInt32 => Some(DataValue::UInt32(None)),
+            DataType::UInt64 => Some(DataValue::UInt64(None)),
+            DataType::Float32 => Some(DataValue::Float32(None)),
+            DataType::Float64 => Some(DataValue::Float64(None)),
+            DataType::Boolean => Some(DataValue::Boolean(None)),
+            DataType::String => Some(DataValue::String(None)),
+            _ => None,
+        };
+    }
+
+    match (data_type, value) {
+        (DataType::Boolean, Value::Bool(v)) => Some(DataValue::Boolean(Some(*v))),
+        (DataType::String, Value::String(v)) => {
+            Some(DataValue::String(Some(v.as_bytes().to_vec())))
+        }
+        (DataType::Float32, Value::Number(v)) => {
+            v.as_f64().map(|v| DataValue::Float32(Some(v as f32)))
+        }
+        (DataType::Float64, Value::Number(v)) => v.as_f64().map(|v| DataValue::Float64(Some(v))),
+        (DataType::Int8, Value::Number(v)) => integer(v).map(|v| DataValue::Int8(Some(v))),
+        (DataType::Int16, Value::Number(v)) => integer(v).map(|v| DataValue::Int16(Some(v))),
+        (DataType::Int32, Value::Number(v)) => integer(v).map(|v| DataValue::Int32(Some(v))),
+        (DataType::Int64, Value::Number(v)) => integer(v).map(|v| DataValue::Int64(Some(v))),
+        (DataType::UInt8, Value::Number(v)) => integer(v).map(|v| DataValue::UInt8(Some(v))),
+        (DataType::UInt16, Value::Number(v)) => integer(v).map(|v| DataValue::UInt16(Some(v))),
+        (DataType::UInt32, Value::Number(v)) => integer(v).map(|v| DataValue::UInt32(Some(v))),
+        (DataType::UInt64, Value::Number(v)) => integer(v).map(|v| DataValue::UInt64(Some(v))),
+        _ => None,
+    }
+}
+
+// The value of an integer number if it fits in `T`, a float number is never an integer.
+fn integer<T: TryFrom<i64> + TryFrom<u64>>(number: &serde_json::Number) -> Option<T> {
+    if let Some(v) = number.as_u64() {
+        return T::try_from(v).ok();
+    }
+    number.as_i64().and_then(|v| T::try_from(v).ok())
+}
@@ -0,0 +1,166 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::any::Any;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+
+use common_base::TrySpawn;
+use common_dal::DataAccessor;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api_vo::TableInfo;
+use common_planners::Extras;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::Statistics;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Table;
+use crate::datasources::common::count_lines;
+use crate::datasources::common::location_data_accessor;
+use crate::datasources::common::read_files;
+use crate::datasources::common::stat_files;
+use crate::datasources::common::trim_quotes;
+use crate::datasources::common::FileStat;
+use crate::datasources::table::json::json_reader::JsonBlockReader;
+use crate::sessions::DatabendQueryContextRef;
+
+/// A table of NDJSON files, each line of a file is a JSON object of a row.
+pub struct JsonTable {
+    tbl_info: TableInfo,
+    // a file, a directory (ends with `/`), or a glob pattern of the file names in a directory
+    location: String,
+    data_accessor: Arc<dyn DataAccessor>,
+}
+
+impl JsonTable {
+    pub fn try_create(tbl_info: TableInfo) -> Result<Box<dyn Table>> {
+        let data_accessor = location_data_accessor(&tbl_info.options)?;
+        JsonTable::with_data_accessor(tbl_info, data_accessor)
+    }
+
+    /// Creates a table whose files are read by the given `data_accessor`.
+    pub fn with_data_accessor(
+        tbl_info: TableInfo,
+        data_accessor: Arc<dyn DataAccessor>,
+    ) -> Result<Box<dyn Table>> {
+        let location = match tbl_info.options.get("location") {
+            None => {
+                return Result::Err(ErrorCode::BadOption(
+                    "JSON Engine must contains file location options",
+                ));
+            }
+            Some(v) => trim_quotes(v).to_string(),
+        };
+
+        Ok(Box::new(Self {
+            tbl_info,
+            location,
+            data_accessor,
+        }))
+    }
+
+    // Lists the files, along with the size and the estimated number of the rows of each of them.
+    fn blocking_stat_files(&self, ctx: &DatabendQueryContextRef) -> Result<Vec<FileStat>> {
+        let (tx, rx) = channel();
+        let data_accessor = self.data_accessor.clone();
+        let location = self.location.clone();
+        ctx.try_spawn(async move {
+            // blank lines are counted as well, the rows are estimated
+            let count_records = |sample: &[u8]| -> Result<u64> { Ok(count_lines(sample)? as u64) };
+            let res = stat_files(data_accessor, &location, count_records).await;
+            let _ = tx.send(res);
+        })?;
+
+        rx.recv().map_err(ErrorCode::from_std_error)?
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for JsonTable {
+    fn name(&self) -> &str {
+        &self.tbl_info.name
+    }
+
+    fn engine(&self) -> &str {
+        &self.tbl_info.engine
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.tbl_info.schema.clone())
+    }
+
+    fn get_id(&self) -> u64 {
+        self.tbl_info.table_id
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        ctx: DatabendQueryContextRef,
+        push_downs: Option<Extras>,
+        _partition_num_hint: Option<usize>,
+    ) -> Result<ReadDataSourcePlan> {
+        let files = self.blocking_stat_files(&ctx)?;
+        let read_rows = files.iter().map(|file| file.records as usize).sum();
+        let read_bytes = files.iter().map(|file| file.size as usize).sum();
+
+        // one partition per file
+        let parts = files
+            .into_iter()
+            .map(|file| Part {
+                name: file.path,
+                version: 0,
+                coalesced: vec![],
+            })
+            .collect();
+
+        let db = &self.tbl_info.db;
+        let name = &self.tbl_info.name;
+        Ok(ReadDataSourcePlan {
+            table_info: self.tbl_info.clone(),
+            parts,
+            statistics: Statistics::new_estimated(read_rows, read_bytes),
+            description: format!("(Read from JSON Engine table  {}.{})", db, name),
+            scan_plan: Default::default(),
+            remote: false,
+            tbl_args: None,
+            push_downs,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: DatabendQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let data_accessor = self.data_accessor.clone();
+        let schema = self.tbl_info.schema.clone();
+        let block_size = ctx.get_settings().get_max_block_size()? as usize;
+
+        Ok(read_files(ctx, data_accessor, move |reader, file| {
+            JsonBlockReader::create(reader, file, &schema, block_size)
+        }))
+    }
+}
@@ -0,0 +1,166 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+//
+
+use std::env;
+use std::sync::Arc;
+
+use common_base::tokio;
+use common_dal::DataAccessor;
+use common_dal::InMemory;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api_vo::TableInfo;
+use common_planners::*;
+use futures::TryStreamExt;
+
+use crate::datasources::table::json::json_table::JsonTable;
+
+fn test_schema() -> DataSchemaRef {
+    DataSchemaRefExt::create(vec![
+        DataField::new("id", DataType::UInt64, false),
+        DataField::new("name", DataType::String, true),
+        DataField::new("score", DataType::Float64, true),
+    ])
+}
+
+fn test_table_info(location: &str) -> TableInfo {
+    TableInfo {
+        db: "default".into(),
+        name: "test_json".into(),
+        schema: test_schema(),
+        engine: "JSON".to_string(),
+        options: [("location".to_string(), location.to_string())]
+            .iter()
+            .cloned()
+            .collect(),
+        table_id: 0,
+        ..Default::default()
+    }
+}
+
+async fn read_json_table(table: Box<dyn crate::catalogs::Table>) -> Result<Vec<DataBlock>> {
+    let ctx = crate::tests::try_create_context()?;
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(ctx, &source_plan).await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test]
+async fn test_json_table() -> Result<()> {
+    let location = env::current_dir()?
+        .join("../tests/data/sample.ndjson")
+        .display()
+        .to_string();
+    let table = JsonTable::try_create(test_table_info(&location))?;
+
+    let blocks = read_json_table(table).await?;
+    assert_eq!(blocks.len(), 1);
+    let block = &blocks[0];
+    assert_eq!(block.num_columns(), 3);
+    assert_eq!(block.column(0).to_values()?, vec![
+        DataValue::UInt64(Some(1)),
+        DataValue::UInt64(Some(2)),
+        DataValue::UInt64(Some(3))
+    ]);
+    // the name of the second object is missing, the extra field `tag` is ignored
+    assert_eq!(block.column(1).to_values()?, vec![
+        DataValue::String(Some(b"shanghai".to_vec())),
+        DataValue::String(None),
+        DataValue::String(Some(b"beijing".to_vec()))
+    ]);
+    assert_eq!(block.column(2).to_values()?, vec![
+        DataValue::Float64(Some(1.5)),
+        DataValue::Float64(Some(-2.0)),
+        DataValue::Float64(None)
+    ]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_table_block_size() -> Result<()> {
+    let data_accessor = Arc::new(InMemory::new());
+    let content = (1..=5)
+        .map(|id| format!("{{\"id\": {}}}\n", id))
+        .collect::<String>();
+    data_accessor
+        .put("bucket/t.json", content.into_bytes())
+        .await?;
+    let table = JsonTable::with_data_accessor(test_table_info("bucket/t.json"), data_accessor)?;
+
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_block_size(2)?;
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    assert_eq!(source_plan.statistics.read_rows, 5);
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(ctx, &source_plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+
+    let rows = blocks.iter().map(|b| b.num_rows()).collect::<Vec<_>>();
+    assert_eq!(rows, vec![2, 2, 1]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_table_incompatible_types() -> Result<()> {
+    let cases = vec![
+        (
+            b"{\"id\": 1}\n{\"id\": \"2\"}\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 2: can not coerce \"2\" to UInt64 for column id",
+        ),
+        (
+            b"{\"id\": 1.5}\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 1: can not coerce 1.5 to UInt64 for column id",
+        ),
+        (
+            b"{\"id\": -1}\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 1: can not coerce -1 to UInt64 for column id",
+        ),
+        (
+            b"{\"id\": 1, \"name\": 2}\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 1: can not coerce 2 to String for column name",
+        ),
+        (
+            b"{\"id\": 1}\n{\"name\": \"a\"}\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 2: missing the value of the non-nullable column id",
+        ),
+        (
+            b"{\"id\": null}\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 1: can not coerce null to UInt64 for column id",
+        ),
+        (
+            b"{\"id\": 1}\n\n[1, 2]\n".to_vec(),
+            "Failed to parse JSON file bucket/t.json at line 3: expects an object, got [1,2]",
+        ),
+    ];
+
+    for (content, expect) in cases {
+        let data_accessor = Arc::new(InMemory::new());
+        data_accessor.put("bucket/t.json", content).await?;
+        let table = JsonTable::with_data_accessor(test_table_info("bucket/t.json"), data_accessor)?;
+
+        let result = read_json_table(table).await;
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.code(), ErrorCode::BadBytes("").code());
+            assert_eq!(e.message(), expect);
+        }
+    }
+
+    Ok(())
+}
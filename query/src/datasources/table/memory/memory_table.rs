@@ -17,36 +17,210 @@ use std::any::Any;
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
-use common_datavalues::DataSchemaRef;
+use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
 use common_meta_api_vo::TableInfo;
+use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
+use common_planners::TableOptions;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
 use futures::stream::StreamExt;
 
 use crate::catalogs::Table;
+use crate::datasources::common::check_insert_schema;
 use crate::datasources::common::generate_parts;
 use crate::datasources::table::memory::memory_table_stream::MemoryTableStream;
+use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::DatabendQueryContextRef;
 
+/// Table option that caps the number of rows a memory table holds
+pub const TBL_OPT_KEY_MAX_ROWS: &str = "max_rows";
+/// Table option that caps the in-memory size of the blocks a memory table holds, in bytes
+pub const TBL_OPT_KEY_MAX_BYTES: &str = "max_bytes";
+
+#[derive(Default)]
+struct MemoryTableData {
+    blocks: Vec<DataBlock>,
+    // running totals of `blocks`, kept up to date by appends
+    rows: usize,
+    bytes: usize,
+    // in-memory size of each column, to estimate the bytes of a projection
+    column_bytes: Vec<usize>,
+}
+
+impl MemoryTableData {
+    // replaces the blocks after a mutation, the totals are recomputed
+    fn replace_blocks(&mut self, blocks: Vec<DataBlock>) {
+        let mut column_bytes = vec![0; self.column_bytes.len()];
+        for block in &blocks {
+            for (idx, column) in block.columns().iter().enumerate() {
+                column_bytes[idx] += column.get_array_memory_size();
+            }
+        }
+        self.rows = blocks.iter().map(|block| block.num_rows()).sum();
+        self.bytes = column_bytes.iter().sum();
+        self.column_bytes = column_bytes;
+        self.blocks = blocks;
+    }
+}
+
 pub struct MemoryTable {
     tbl_info: TableInfo,
-    blocks: Arc<RwLock<Vec<DataBlock>>>,
+    max_rows: Option<usize>,
+    max_bytes: Option<usize>,
+    data: Arc<RwLock<MemoryTableData>>,
 }
 
 impl MemoryTable {
     pub fn try_create(tbl_info: TableInfo) -> Result<Box<dyn Table>> {
+        let max_rows = parse_limit_option(&tbl_info.options, TBL_OPT_KEY_MAX_ROWS)?;
+        let max_bytes = parse_limit_option(&tbl_info.options, TBL_OPT_KEY_MAX_BYTES)?;
         let table = Self {
             tbl_info,
-            blocks: Arc::new(RwLock::new(vec![])),
+            max_rows,
+            max_bytes,
+            data: Arc::new(RwLock::new(MemoryTableData::default())),
         };
         Ok(Box::new(table))
     }
+
+    fn check_limits(&self, rows: usize, bytes: usize) -> Result<()> {
+        if let Some(max_rows) = self.max_rows {
+            if rows > max_rows {
+                return Err(ErrorCode::TableIsFull(format!(
+                    "Memory table {}.{} is full, {} rows exceed {} = {}",
+                    self.tbl_info.db, self.tbl_info.name, rows, TBL_OPT_KEY_MAX_ROWS, max_rows
+                )));
+            }
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            if bytes > max_bytes {
+                return Err(ErrorCode::TableIsFull(format!(
+                    "Memory table {}.{} is full, {} bytes exceed {} = {}",
+                    self.tbl_info.db, self.tbl_info.name, bytes, TBL_OPT_KEY_MAX_BYTES, max_bytes
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the rows matching `predicate`, returns the number of the rows removed.
+    pub fn delete(&self, predicate: &Expression) -> Result<usize> {
+        let executor = self.expression_executor(&[predicate.clone()])?;
+
+        let mut data = self.data.write();
+        let mut deleted = 0;
+        let mut blocks = Vec::with_capacity(data.blocks.len());
+        for block in &data.blocks {
+            let evaluated = executor.execute(block)?;
+            let matched = Self::matched_rows(&evaluated, predicate)?;
+            let kept: DFBooleanArray = matched.into_no_null_iter().map(|m| !m).collect();
+            let block = DataBlock::block_filter(block, &kept)?;
+            deleted += matched.len() - block.num_rows();
+            if block.num_rows() > 0 {
+                blocks.push(block);
+            }
+        }
+        data.replace_blocks(blocks);
+        Ok(deleted)
+    }
+
+    /// Sets the columns of the rows matching `predicate` by the `assignments` of column name and
+    /// value, e.g. `UPDATE t SET b = b + 1 WHERE a > 1`, returns the number of the rows updated.
+    pub fn update(
+        &self,
+        assignments: &[(String, Expression)],
+        predicate: &Expression,
+    ) -> Result<usize> {
+        let schema = &self.tbl_info.schema;
+        let indices = assignments
+            .iter()
+            .map(|(name, _)| schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        let mut exprs = vec![predicate.clone()];
+        exprs.extend(assignments.iter().map(|(_, value)| value.clone()));
+        let executor = self.expression_executor(&exprs)?;
+
+        let mut data = self.data.write();
+        let mut updated = 0;
+        let mut blocks = Vec::with_capacity(data.blocks.len());
+        for block in &data.blocks {
+            let evaluated = executor.execute(block)?;
+            let matched = Self::matched_rows(&evaluated, predicate)?;
+            let matched_rows = matched.into_no_null_iter().filter(|m| *m).count();
+            if matched_rows == 0 {
+                blocks.push(block.clone());
+                continue;
+            }
+
+            let cond = DataColumn::Array(matched.into_series());
+            let mut columns = block.columns().to_vec();
+            for (idx, (_, value)) in indices.iter().zip(assignments) {
+                let value = evaluated.try_column_by_name(&value.column_name())?;
+                let column = cond.if_then_else(value, &columns[*idx])?;
+                let column = column
+                    .to_array()?
+                    .cast_with_type(schema.field(*idx).data_type())?;
+                columns[*idx] = DataColumn::Array(column);
+            }
+            updated += matched_rows;
+            blocks.push(DataBlock::create(block.schema().clone(), columns));
+        }
+        data.replace_blocks(blocks);
+        Ok(updated)
+    }
+
+    fn expression_executor(&self, exprs: &[Expression]) -> Result<ExpressionExecutor> {
+        let schema = self.tbl_info.schema.clone();
+        let fields = exprs
+            .iter()
+            .map(|expr| expr.to_data_field(&schema))
+            .collect::<Result<Vec<_>>>()?;
+        ExpressionExecutor::try_create(
+            "memory table mutation executor",
+            schema,
+            DataSchemaRefExt::create(fields),
+            exprs.to_vec(),
+            false,
+        )
+    }
+
+    // the rows `predicate` evaluates to null are not matched
+    fn matched_rows(evaluated: &DataBlock, predicate: &Expression) -> Result<DFBooleanArray> {
+        let predicate = evaluated
+            .try_column_by_name(&predicate.column_name())?
+            .to_array()?
+            .cast_with_type(&DataType::Boolean)?;
+        Ok(predicate
+            .bool()?
+            .into_iter()
+            .map(|v| v == Some(true))
+            .collect())
+    }
+
+    fn projection(push_downs: &Option<Extras>) -> Option<&Vec<usize>> {
+        push_downs
+            .as_ref()
+            .and_then(|extras| extras.projection.as_ref())
+    }
+}
+
+fn parse_limit_option(options: &TableOptions, key: &str) -> Result<Option<usize>> {
+    match options.get(key) {
+        None => Ok(None),
+        Some(v) => match v.parse::<usize>() {
+            Ok(n) => Ok(Some(n)),
+            Err(_) => Err(ErrorCode::BadOption(format!(
+                "invalid value of table option {}: {}, expects a non-negative number",
+                key, v
+            ))),
+        },
+    }
 }
 
 #[async_trait::async_trait]
@@ -85,24 +259,25 @@ impl Table for MemoryTable {
         push_downs: Option<Extras>,
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
-        let blocks = self.blocks.read();
-        let rows = blocks.iter().map(|block| block.num_rows()).sum();
-        let bytes = blocks.iter().map(|block| block.memory_size()).sum();
+        let data = self.data.read();
+        let bytes = match Self::projection(&push_downs) {
+            Some(projection) => projection
+                .iter()
+                .map(|idx| data.column_bytes.get(*idx).cloned().unwrap_or(0))
+                .sum(),
+            None => data.bytes,
+        };
 
         let tbl_info = &self.tbl_info;
         let db = &tbl_info.db;
         Ok(ReadDataSourcePlan {
-            db: db.to_string(),
-            table: self.name().to_string(),
-            table_id: tbl_info.table_id,
-            table_version: None,
-            schema: tbl_info.schema.clone(),
+            table_info: tbl_info.clone(),
             parts: generate_parts(
                 0,
                 ctx.get_settings().get_max_threads()?,
-                blocks.len() as u64,
+                data.blocks.len() as u64,
             ),
-            statistics: Statistics::new_exact(rows, bytes),
+            statistics: Statistics::new_exact(data.rows, bytes),
             description: format!("(Read from Memory Engine table  {}.{})", db, self.name()),
             scan_plan: Default::default(),
             remote: false,
@@ -114,44 +289,92 @@ impl Table for MemoryTable {
     async fn read(
         &self,
         ctx: DatabendQueryContextRef,
-        _source_plan: &ReadDataSourcePlan,
+        source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
-        let blocks = self.blocks.read();
-        Ok(Box::pin(MemoryTableStream::try_create(
-            ctx,
-            blocks.clone(),
-        )?))
+        let data = self.data.read();
+        let blocks = match Self::projection(&source_plan.push_downs) {
+            Some(projection) => {
+                let fields = projection
+                    .iter()
+                    .map(|idx| self.tbl_info.schema.field(*idx).clone())
+                    .collect::<Vec<_>>();
+                let schema = DataSchemaRefExt::create(fields);
+                data.blocks
+                    .iter()
+                    .map(|block| {
+                        let columns = projection
+                            .iter()
+                            .map(|idx| block.column(*idx).clone())
+                            .collect::<Vec<_>>();
+                        DataBlock::create(schema.clone(), columns)
+                    })
+                    .collect::<Vec<_>>()
+            }
+            None => data.blocks.clone(),
+        };
+        Ok(Box::pin(MemoryTableStream::try_create(ctx, blocks)?))
     }
 
     async fn append_data(
         &self,
         _ctx: DatabendQueryContextRef,
         insert_plan: common_planners::InsertIntoPlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         let mut s = {
             let mut inner = insert_plan.input_stream.lock();
             (*inner).take()
         }
         .ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
 
-        if insert_plan.schema().as_ref().fields() != self.tbl_info.schema.as_ref().fields() {
-            return Err(ErrorCode::BadArguments("DataBlock schema mismatch"));
-        }
+        check_insert_schema(
+            &format!("{}.{}", self.tbl_info.db, self.tbl_info.name),
+            &self.tbl_info.schema,
+            &insert_plan.schema(),
+        )?;
 
+        // the whole insert is accepted or rejected, it is buffered until it is stored,
+        // the limits are checked with each block so that an oversized insert fails early
+        let mut appended = vec![];
+        let mut appended_rows = 0;
+        let mut appended_bytes = 0;
+        let mut appended_column_bytes = vec![0; self.tbl_info.schema.fields().len()];
         while let Some(block) = s.next().await {
-            let mut blocks = self.blocks.write();
-            blocks.push(block);
+            let block = block?;
+            appended_rows += block.num_rows();
+            for (idx, column) in block.columns().iter().enumerate() {
+                let bytes = column.get_array_memory_size();
+                appended_column_bytes[idx] += bytes;
+                appended_bytes += bytes;
+            }
+            appended.push(block);
+
+            let data = self.data.read();
+            self.check_limits(data.rows + appended_rows, data.bytes + appended_bytes)?;
         }
-        Ok(())
+
+        let mut data = self.data.write();
+        let rows = data.rows + appended_rows;
+        let bytes = data.bytes + appended_bytes;
+        self.check_limits(rows, bytes)?;
+
+        data.blocks.extend(appended);
+        data.rows = rows;
+        data.bytes = bytes;
+        data.column_bytes.resize(appended_column_bytes.len(), 0);
+        for (total, bytes) in data.column_bytes.iter_mut().zip(appended_column_bytes) {
+            *total += bytes;
+        }
+        Ok(appended_rows)
     }
 
     async fn truncate(
         &self,
         _ctx: DatabendQueryContextRef,
         _truncate_plan: TruncateTablePlan,
-    ) -> Result<()> {
-        let mut blocks = self.blocks.write();
-        blocks.clear();
-        Ok(())
+    ) -> Result<usize> {
+        let mut data = self.data.write();
+        let rows = data.rows;
+        *data = MemoryTableData::default();
+        Ok(rows)
     }
 }
@@ -19,13 +19,17 @@ use common_base::tokio;
 use common_datablocks::assert_blocks_sorted_eq;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use common_meta_api_vo::TableInfo;
 use common_planners::*;
+use futures::StreamExt;
 use futures::TryStreamExt;
 
 use crate::datasources::table::memory::memory_table::MemoryTable;
+use crate::datasources::table::memory::memory_table::TBL_OPT_KEY_MAX_BYTES;
+use crate::datasources::table::memory::memory_table::TBL_OPT_KEY_MAX_ROWS;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_memorytable() -> Result<()> {
@@ -41,6 +45,7 @@ async fn test_memorytable() -> Result<()> {
         engine: "Memory".to_string(),
         options: TableOptions::default(),
         table_id: 0,
+        ..Default::default()
     })?;
 
     // append data.
@@ -55,12 +60,14 @@ async fn test_memorytable() -> Result<()> {
         ]);
         let blocks = vec![block, block2];
 
-        let input_stream = futures::stream::iter::<Vec<DataBlock>>(blocks.clone());
+        let input_stream =
+            futures::stream::iter(blocks.clone().into_iter().map(Ok::<_, ErrorCode>));
         let insert_plan = InsertIntoPlan {
             db_name: "default".to_string(),
             tbl_name: "a".to_string(),
             tbl_id: 0,
             schema,
+            select_plan: None,
             input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
         };
         table.append_data(ctx.clone(), insert_plan).await.unwrap();
@@ -99,7 +106,12 @@ async fn test_memorytable() -> Result<()> {
             db: "default".to_string(),
             table: "a".to_string(),
         };
-        table.truncate(ctx.clone(), truncate_plan).await?;
+        let affected_rows = table.truncate(ctx.clone(), truncate_plan.clone()).await?;
+        assert_eq!(affected_rows, 4);
+
+        // truncate an empty table removes nothing.
+        let affected_rows = table.truncate(ctx.clone(), truncate_plan).await?;
+        assert_eq!(affected_rows, 0);
 
         let source_plan = table.read_plan(
             ctx.clone(),
@@ -113,3 +125,302 @@ async fn test_memorytable() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memorytable_max_rows() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let mut options = TableOptions::default();
+    options.insert(TBL_OPT_KEY_MAX_ROWS.to_string(), "4".to_string());
+    let table = MemoryTable::try_create(TableInfo {
+        db: "default".into(),
+        name: "a".into(),
+        schema: schema.clone(),
+        engine: "Memory".to_string(),
+        options,
+        table_id: 0,
+        ..Default::default()
+    })?;
+
+    let insert_plan = |values: Vec<u64>| {
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(values)]);
+        let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![Ok(block)]);
+        InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            tbl_id: 0,
+            schema: schema.clone(),
+            select_plan: None,
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        }
+    };
+
+    // append up to the cap.
+    table
+        .append_data(ctx.clone(), insert_plan(vec![1, 2]))
+        .await?;
+    table
+        .append_data(ctx.clone(), insert_plan(vec![3, 4]))
+        .await?;
+
+    // append over the cap is rejected as a whole.
+    let result = table.append_data(ctx.clone(), insert_plan(vec![5])).await;
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::TableIsFull("").code());
+        assert_eq!(
+            e.message(),
+            "Memory table default.a is full, 5 rows exceed max_rows = 4"
+        );
+    }
+
+    // append over the cap fails at the block exceeding it, not waiting for the rest of the input.
+    {
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![5u64])]);
+        let input_stream = futures::stream::iter(vec![Ok(block)])
+            .chain(futures::stream::pending::<Result<DataBlock>>());
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            tbl_id: 0,
+            schema: schema.clone(),
+            select_plan: None,
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        };
+        let result = table.append_data(ctx.clone(), insert_plan).await;
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.code(), ErrorCode::TableIsFull("").code());
+        }
+    }
+
+    // append with another schema is rejected.
+    {
+        let other_schema =
+            DataSchemaRefExt::create(vec![DataField::new("b", DataType::UInt64, false)]);
+        let block = DataBlock::create_by_array(other_schema.clone(), vec![Series::new(vec![5u64])]);
+        let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![Ok(block)]);
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            tbl_id: 0,
+            schema: other_schema,
+            select_plan: None,
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        };
+        let result = table.append_data(ctx.clone(), insert_plan).await;
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+            assert_eq!(
+                e.message(),
+                "Schema mismatch on table default.a, column 0 is expected to be `a`, got `b`"
+            );
+        }
+    }
+
+    // existing data is intact.
+    {
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            None,
+            Some(ctx.get_settings().get_max_threads()? as usize),
+        )?;
+        assert_eq!(source_plan.statistics.read_rows, 4);
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+
+        let stream = table.read(ctx.clone(), &source_plan).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_blocks_sorted_eq(
+            vec![
+                "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "+---+",
+            ],
+            &result,
+        );
+    }
+
+    // truncate frees the quota.
+    {
+        let truncate_plan = TruncateTablePlan {
+            db: "default".to_string(),
+            table: "a".to_string(),
+        };
+        table.truncate(ctx.clone(), truncate_plan).await?;
+        table
+            .append_data(ctx.clone(), insert_plan(vec![1, 2, 3, 4]))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_memorytable_bad_limit_option() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let mut options = TableOptions::default();
+    options.insert(TBL_OPT_KEY_MAX_BYTES.to_string(), "1k".to_string());
+    let result = MemoryTable::try_create(TableInfo {
+        db: "default".into(),
+        name: "a".into(),
+        schema,
+        engine: "Memory".to_string(),
+        options,
+        table_id: 0,
+        ..Default::default()
+    });
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::BadOption("").code());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memorytable_projection() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::String, false),
+        DataField::new("c", DataType::UInt64, false),
+    ]);
+    let table = MemoryTable::try_create(TableInfo {
+        db: "default".into(),
+        name: "a".into(),
+        schema: schema.clone(),
+        engine: "Memory".to_string(),
+        options: TableOptions::default(),
+        table_id: 0,
+        ..Default::default()
+    })?;
+
+    // append data.
+    {
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![1u64, 2]),
+            Series::new(vec!["x", "y"]),
+            Series::new(vec![11u64, 22]),
+        ]);
+        let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![Ok(block)]);
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            tbl_id: 0,
+            schema: schema.clone(),
+            select_plan: None,
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        };
+        table.append_data(ctx.clone(), insert_plan).await?;
+    }
+
+    // read columns c and a.
+    {
+        let full_plan = table.read_plan(ctx.clone(), None, None)?;
+
+        let mut push_downs = Extras::default();
+        push_downs.projection = Some(vec![2, 0]);
+        let source_plan = table.read_plan(ctx.clone(), Some(push_downs), None)?;
+        assert_eq!(source_plan.statistics.read_rows, 2);
+        assert!(source_plan.statistics.read_bytes < full_plan.statistics.read_bytes);
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+
+        let stream = table.read(ctx.clone(), &source_plan).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected_schema = DataSchemaRefExt::create(vec![
+            DataField::new("c", DataType::UInt64, false),
+            DataField::new("a", DataType::UInt64, false),
+        ]);
+        assert_eq!(result[0].schema(), &expected_schema);
+        let read_bytes: usize = result.iter().map(|block| block.memory_size()).sum();
+        assert_eq!(source_plan.statistics.read_bytes, read_bytes);
+        assert_blocks_sorted_eq(
+            vec![
+                "+----+---+",
+                "| c  | a |",
+                "+----+---+",
+                "| 11 | 1 |",
+                "| 22 | 2 |",
+                "+----+---+",
+            ],
+            &result,
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_memorytable_delete_and_update() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::UInt64, false),
+    ]);
+    let table = MemoryTable::try_create(TableInfo {
+        db: "default".into(),
+        name: "a".into(),
+        schema: schema.clone(),
+        engine: "Memory".to_string(),
+        options: TableOptions::default(),
+        table_id: 0,
+        ..Default::default()
+    })?;
+
+    let blocks = vec![
+        DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![1u64, 2]),
+            Series::new(vec![11u64, 22]),
+        ]),
+        DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![4u64, 3]),
+            Series::new(vec![33u64, 33]),
+        ]),
+    ];
+    let insert_plan = InsertIntoPlan {
+        db_name: "default".to_string(),
+        tbl_name: "a".to_string(),
+        tbl_id: 0,
+        schema,
+        select_plan: None,
+        input_stream: Arc::new(Mutex::new(Some(Box::pin(futures::stream::iter(
+            blocks.into_iter().map(Ok::<_, ErrorCode>),
+        ))))),
+    };
+    table.append_data(ctx.clone(), insert_plan).await?;
+
+    let memory_table = table.as_any().downcast_ref::<MemoryTable>().unwrap();
+
+    // delete.
+    {
+        let deleted = memory_table.delete(&col("a").gt(lit(2u64)))?;
+        assert_eq!(deleted, 2);
+        let deleted = memory_table.delete(&col("a").gt(lit(2u64)))?;
+        assert_eq!(deleted, 0);
+    }
+
+    // update.
+    {
+        let assignments = vec![("b".to_string(), add(col("b"), lit(100u64)))];
+        let updated = memory_table.update(&assignments, &col("a").eq(lit(1u64)))?;
+        assert_eq!(updated, 1);
+    }
+
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    assert_eq!(source_plan.statistics.read_rows, 2);
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(ctx, &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_blocks_sorted_eq(
+        vec![
+            "+---+-----+",
+            "| a | b   |",
+            "+---+-----+",
+            "| 1 | 111 |",
+            "| 2 | 22  |",
+            "+---+-----+",
+        ],
+        &result,
+    );
+
+    Ok(())
+}
@@ -17,6 +17,7 @@ pub mod fuse;
 mod prelude;
 
 mod csv;
+mod json;
 mod memory;
 mod null;
 mod parquet;
@@ -30,6 +30,7 @@ use common_tracing::tracing::info;
 use futures::stream::StreamExt;
 
 use crate::catalogs::Table;
+use crate::datasources::common::check_insert_schema;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct NullTable {
@@ -77,14 +78,11 @@ impl Table for NullTable {
         let tbl_info = &self.tbl_info;
         let db = &tbl_info.db;
         Ok(ReadDataSourcePlan {
-            db: db.to_string(),
-            table: self.name().to_string(),
-            table_id: tbl_info.table_id,
-            table_version: None,
-            schema: tbl_info.schema.clone(),
+            table_info: tbl_info.clone(),
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                coalesced: vec![],
             }],
             statistics: Statistics::new_exact(0, 0),
             description: format!("(Read from Null Engine table  {}.{})", db, self.name()),
@@ -113,24 +111,34 @@ impl Table for NullTable {
         &self,
         _ctx: DatabendQueryContextRef,
         insert_plan: common_planners::InsertIntoPlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         let mut s = {
             let mut inner = insert_plan.input_stream.lock();
             (*inner).take()
         }
         .ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
 
+        check_insert_schema(
+            &format!("{}.{}", self.tbl_info.db, self.tbl_info.name),
+            &self.tbl_info.schema,
+            &insert_plan.schema(),
+        )?;
+
+        // nothing is stored, but the rows are reported as appended
+        let mut rows = 0;
         while let Some(block) = s.next().await {
-            info!("Ignore one block rows: {}", block.num_rows())
+            let block = block?;
+            info!("Ignore one block rows: {}", block.num_rows());
+            rows += block.num_rows();
         }
-        Ok(())
+        Ok(rows)
     }
 
     async fn truncate(
         &self,
         _ctx: DatabendQueryContextRef,
         _truncate_plan: TruncateTablePlan,
-    ) -> Result<()> {
-        Ok(())
+    ) -> Result<usize> {
+        Ok(0)
     }
 }
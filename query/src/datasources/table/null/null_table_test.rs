@@ -18,6 +18,7 @@ use std::sync::Arc;
 use common_base::tokio;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use common_meta_api_vo::TableInfo;
@@ -36,10 +37,11 @@ async fn test_null_table() -> Result<()> {
     let table = NullTable::try_create(TableInfo {
         db: "default".into(),
         name: "a".into(),
-        schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]),
+        schema: schema.clone(),
         engine: "Null".to_string(),
         options: TableOptions::default(),
         table_id: 0,
+        ..Default::default()
     })?;
 
     // append data.
@@ -48,17 +50,54 @@ async fn test_null_table() -> Result<()> {
             Series::new(vec![1u64, 2]),
             Series::new(vec![11u64, 22]),
         ]);
-        let blocks = vec![block];
+        let block2 = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![3u64, 4, 5]),
+            Series::new(vec![33u64, 44, 55]),
+        ]);
+        let blocks = vec![block, block2];
 
-        let input_stream = futures::stream::iter::<Vec<DataBlock>>(blocks.clone());
+        let input_stream =
+            futures::stream::iter(blocks.clone().into_iter().map(Ok::<_, ErrorCode>));
         let insert_plan = InsertIntoPlan {
             db_name: "default".to_string(),
             tbl_name: "a".to_string(),
             tbl_id: 0,
             schema: schema.clone(),
+            select_plan: None,
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        };
+        let appended_rows = table.append_data(ctx.clone(), insert_plan).await?;
+        assert_eq!(appended_rows, 5);
+    }
+
+    // append data with another schema.
+    {
+        let other_schema = DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::UInt64, false),
+            DataField::new("b", DataType::Int32, false),
+        ]);
+        let block = DataBlock::create_by_array(other_schema.clone(), vec![
+            Series::new(vec![1u64, 2]),
+            Series::new(vec![11i32, 22]),
+        ]);
+        let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![Ok(block)]);
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            tbl_id: 0,
+            schema: other_schema,
+            select_plan: None,
             input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
         };
-        table.append_data(ctx.clone(), insert_plan).await.unwrap();
+        let result = table.append_data(ctx.clone(), insert_plan).await;
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+            assert_eq!(
+                e.message(),
+                "Schema mismatch on table default.a, column `b` is expected to be UInt64, got Int32"
+            );
+        }
     }
 
     // read.
@@ -73,7 +112,7 @@ async fn test_null_table() -> Result<()> {
         let stream = table.read(ctx.clone(), &source_plan).await?;
         let result = stream.try_collect::<Vec<_>>().await?;
         let block = &result[0];
-        assert_eq!(block.num_columns(), 1);
+        assert_eq!(block.num_columns(), 2);
     }
 
     // truncate.
@@ -82,7 +121,8 @@ async fn test_null_table() -> Result<()> {
             db: "default".to_string(),
             table: "a".to_string(),
         };
-        table.truncate(ctx.clone(), truncate_plan).await?;
+        let affected_rows = table.truncate(ctx.clone(), truncate_plan).await?;
+        assert_eq!(affected_rows, 0);
     }
 
     Ok(())
@@ -13,43 +13,90 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::convert::TryInto;
-use std::fs::File;
+use std::collections::HashMap;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
 
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::io::parquet::read;
+use common_arrow::parquet::metadata::RowGroupMetaData;
+use common_arrow::parquet::read::get_page_stream;
+use common_arrow::parquet::statistics::BinaryStatistics;
+use common_arrow::parquet::statistics::PrimitiveStatistics;
+use common_arrow::parquet::statistics::Statistics as ParquetStatistics;
+use common_base::tokio::runtime::Handle;
 use common_base::tokio::task;
+use common_base::TrySpawn;
+use common_dal::DataAccessor;
 use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::IntoSeries;
+use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api_vo::TableInfo;
+use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
 use common_planners::Statistics;
 use common_streams::ParquetStream;
 use common_streams::SendableDataBlockStream;
+use common_streams::TakeStream;
 use crossbeam::channel::bounded;
 use crossbeam::channel::Receiver;
 use crossbeam::channel::Sender;
+use futures::StreamExt;
 
 use crate::catalogs::Table;
+use crate::datasources::common::generate_parts_by_size;
+use crate::datasources::common::list_files;
+use crate::datasources::common::location_data_accessor;
+use crate::datasources::common::trim_quotes;
+use crate::datasources::index::MinMaxIndex;
 use crate::sessions::DatabendQueryContextRef;
 
+/// The files are split into partitions of about this size, to be read by different workers.
+const DEFAULT_PART_BYTES: u64 = 128 * 1024 * 1024;
+
 pub struct ParquetTable {
     tbl_info: TableInfo,
-    file: String,
+    // a file, a directory (ends with `/`), or a glob pattern of the file names in a directory
+    location: String,
+    // size of the byte ranges the files are split into, option `part_bytes`
+    part_bytes: u64,
+    data_accessor: Arc<dyn DataAccessor>,
 }
 
 impl ParquetTable {
     pub fn try_create(tbl_info: TableInfo) -> Result<Box<dyn Table>> {
-        let options = &tbl_info.options;
-        let file = options.get("location").cloned();
-        return match file {
-            Some(file) => {
+        let data_accessor = location_data_accessor(&tbl_info.options)?;
+        ParquetTable::with_data_accessor(tbl_info, data_accessor)
+    }
+
+    /// Creates a table whose files are read by the given `data_accessor`.
+    pub fn with_data_accessor(
+        tbl_info: TableInfo,
+        data_accessor: Arc<dyn DataAccessor>,
+    ) -> Result<Box<dyn Table>> {
+        let part_bytes = match tbl_info.options.get("part_bytes") {
+            None => DEFAULT_PART_BYTES,
+            Some(value) => trim_quotes(value).parse::<u64>().map_err(|e| {
+                ErrorCode::BadOption(format!("Invalid part_bytes {}: {}", value, e))
+            })?,
+        };
+        let location = tbl_info.options.get("location").cloned();
+        return match location {
+            Some(location) => {
                 let table = ParquetTable {
                     tbl_info,
-                    file: file.trim_matches(|s| s == '\'' || s == '"').to_string(),
+                    location: trim_quotes(&location).to_string(),
+                    part_bytes,
+                    data_accessor,
                 };
                 Ok(Box::new(table))
             }
@@ -58,33 +105,268 @@ impl ParquetTable {
             )),
         };
     }
+
+    fn blocking_list_files(
+        &self,
+        ctx: &DatabendQueryContextRef,
+    ) -> Result<Vec<(String, Option<u64>)>> {
+        let (tx, rx) = channel();
+        let data_accessor = self.data_accessor.clone();
+        let location = self.location.clone();
+        ctx.try_spawn(async move {
+            let res = list_files(data_accessor, &location).await;
+            let _ = tx.send(res);
+        })?;
+
+        rx.recv().map_err(ErrorCode::from_std_error)?
+    }
 }
 
-fn read_file(
-    file: &str,
+/// Splits a file into partitions by byte ranges, the partitions are named `{file}:{range}`.
+/// A file of unknown size is read by one partition, named by the file itself.
+fn file_parts(file: &str, size: Option<u64>, part_bytes: u64) -> Vec<Part> {
+    match size {
+        None => vec![Part {
+            name: file.to_string(),
+            version: 0,
+            coalesced: vec![],
+        }],
+        Some(size) => generate_parts_by_size(size, part_bytes)
+            .into_iter()
+            .map(|part| Part {
+                name: format!("{}:{}", file, part.name),
+                version: 0,
+                coalesced: vec![],
+            })
+            .collect(),
+    }
+}
+
+/// Parses the name of a partition into the file and the byte range `[begin, end)` to read.
+fn parse_file_part(name: &str) -> (&str, Option<(u64, u64)>) {
+    let parsed = name.rsplit_once(':').and_then(|(file, range)| {
+        let bounds = range
+            .split('-')
+            .map(|v| v.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        match bounds.as_slice() {
+            [_total, begin, end] => Some((file, Some((*begin, *end)))),
+            _ => None,
+        }
+    });
+    parsed.unwrap_or((name, None))
+}
+
+// Reads the files of the partitions, until there are no partitions left
+async fn read_parts(
+    ctx: DatabendQueryContextRef,
+    data_accessor: Arc<dyn DataAccessor>,
     tx: Sender<Option<Result<DataBlock>>>,
+    schema: &ArrowSchema,
+    projection: &[usize],
+    filters: &[Expression],
+) -> Result<()> {
+    loop {
+        let parts = ctx.try_get_partitions(1)?;
+        let part = match parts.first() {
+            None => return Ok(()),
+            Some(part) => part,
+        };
+        let (file, range) = parse_file_part(&part.name);
+        let res = read_file(
+            &data_accessor,
+            file,
+            range,
+            &tx,
+            schema,
+            projection,
+            filters,
+        )
+        .await;
+        if let Err(e) = res {
+            // the stream ends with the error, instead of ending as if all the data were read
+            let _ = tx.send(Some(Err(e.clone())));
+            return Err(e);
+        }
+    }
+}
+
+// The files of a table may be written by different writers, the columns read must have
+// the types of the table schema
+fn check_file_schema(
+    file: &str,
+    file_schema: &ArrowSchema,
+    schema: &ArrowSchema,
     projection: &[usize],
 ) -> Result<()> {
-    let reader = File::open(file)?;
-    let reader = read::RecordReader::try_new(reader, Some(projection.to_vec()), None, None, None)?;
-
-    for maybe_batch in reader {
-        match maybe_batch {
-            Ok(batch) => {
-                tx.send(Some(Ok(batch.try_into()?)))
-                    .map_err(|e| ErrorCode::UnknownException(e.to_string()))?;
+    for idx in projection.iter().cloned() {
+        let expected = schema.field(idx);
+        let matched = file_schema
+            .fields()
+            .get(idx)
+            .map(|f| f.name() == expected.name() && f.data_type() == expected.data_type())
+            .unwrap_or(false);
+        if !matched {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "Schema of parquet file {} does not match the table, expects column {}:{:?} at {}",
+                file,
+                expected.name(),
+                expected.data_type(),
+                idx
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the offset of the first page of a row group in the file.
+fn row_group_start(row_group: &RowGroupMetaData) -> u64 {
+    let first_column = row_group.column(0);
+    let offset = first_column
+        .dictionary_page_offset()
+        .unwrap_or_else(|| first_column.data_page_offset());
+    offset as u64
+}
+
+/// Returns the min/max of the columns of a row group, by the statistics written along with it.
+fn row_group_min_max(
+    file_schema: &ArrowSchema,
+    row_group: &RowGroupMetaData,
+) -> HashMap<String, MinMaxIndex> {
+    let mut res = HashMap::new();
+    for (field, column) in file_schema.fields().iter().zip(row_group.columns()) {
+        // the statistics of unsigned integers are ordered as signed ones, they are not used
+        let usable = matches!(
+            field.data_type(),
+            ArrowDataType::Int8
+                | ArrowDataType::Int16
+                | ArrowDataType::Int32
+                | ArrowDataType::Int64
+                | ArrowDataType::Float32
+                | ArrowDataType::Float64
+                | ArrowDataType::Utf8
+                | ArrowDataType::LargeUtf8
+        );
+        if !usable {
+            continue;
+        }
+        if let Some(Ok(stats)) = column.statistics() {
+            if let Some((min, max)) = statistics_min_max(stats.as_ref()) {
+                let name = field.name().clone();
+                res.insert(name.clone(), MinMaxIndex::create(name, min, max));
             }
-            Err(e) => {
-                let err_msg = format!("Error reading batch from {:?}: {}", file, e.to_string());
+        }
+    }
+    res
+}
+
+fn statistics_min_max(stats: &dyn ParquetStatistics) -> Option<(DataValue, DataValue)> {
+    let stats = stats.as_any();
+    if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<i32>>() {
+        return Some((
+            DataValue::Int32(Some(s.min_value?)),
+            DataValue::Int32(Some(s.max_value?)),
+        ));
+    }
+    if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<i64>>() {
+        return Some((
+            DataValue::Int64(Some(s.min_value?)),
+            DataValue::Int64(Some(s.max_value?)),
+        ));
+    }
+    if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<f32>>() {
+        return Some((
+            DataValue::Float32(Some(s.min_value?)),
+            DataValue::Float32(Some(s.max_value?)),
+        ));
+    }
+    if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<f64>>() {
+        return Some((
+            DataValue::Float64(Some(s.min_value?)),
+            DataValue::Float64(Some(s.max_value?)),
+        ));
+    }
+    if let Some(s) = stats.downcast_ref::<BinaryStatistics>() {
+        return Some((
+            DataValue::String(Some(s.min_value.clone()?)),
+            DataValue::String(Some(s.max_value.clone()?)),
+        ));
+    }
+    None
+}
 
-                tx.send(Some(Result::Err(ErrorCode::CannotReadFile(
-                    err_msg.clone(),
-                ))))
-                .map_err(|send_error| ErrorCode::UnknownException(send_error.to_string()))?;
+// The footer and the column chunks of the row groups are read by ranges,
+// the file is not downloaded as a whole if it is in an object storage
+// Reads the row groups starting in the byte `range` of the file, or all of them if it is `None`,
+// thus each row group is read by exactly one of the partitions of a file.
+async fn read_file(
+    data_accessor: &Arc<dyn DataAccessor>,
+    file: &str,
+    range: Option<(u64, u64)>,
+    tx: &Sender<Option<Result<DataBlock>>>,
+    schema: &ArrowSchema,
+    projection: &[usize],
+    filters: &[Expression],
+) -> Result<()> {
+    let mut reader = data_accessor.get_input_stream(file, None).await?;
+    let metadata = read::read_metadata_async(&mut reader)
+        .await
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    let file_schema = read::get_schema(&metadata)?;
+    check_file_schema(file, &file_schema, schema, projection)?;
+
+    let fields = projection
+        .iter()
+        .map(|idx| schema.field(*idx).clone())
+        .collect::<Vec<_>>();
+    let block_schema = Arc::new(DataSchema::from(&ArrowSchema::new(fields)));
+
+    for row_group in &metadata.row_groups {
+        if let Some((begin, end)) = range {
+            let start = row_group_start(row_group);
+            if start < begin || start >= end {
+                continue;
+            }
+        }
 
-                return Result::Err(ErrorCode::CannotReadFile(err_msg));
+        // skips the row groups that none of the rows may satisfy all the filters
+        if !filters.is_empty() {
+            let idx_map = row_group_min_max(&file_schema, row_group);
+            let may_match = filters
+                .iter()
+                .all(|filter| MinMaxIndex::apply_index(idx_map.clone(), filter).unwrap_or(true));
+            if !may_match {
+                continue;
             }
         }
+
+        let mut columns = Vec::with_capacity(projection.len());
+        for idx in projection.iter().cloned() {
+            let col_meta = row_group.column(idx);
+            // NOTE: here the page filter is !Send
+            let pages = get_page_stream(col_meta, &mut reader, vec![], Arc::new(|_, _| true))
+                .await
+                .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+            let pages =
+                pages.map(|compressed_page| read::decompress(compressed_page?, &mut vec![]));
+            let array =
+                read::page_stream_to_array(pages, col_meta, schema.field(idx).data_type().clone())
+                    .await
+                    .map_err(|e| {
+                        ErrorCode::CannotReadFile(format!(
+                            "Error reading column {} from {:?}: {}",
+                            schema.field(idx).name(),
+                            file,
+                            e
+                        ))
+                    })?;
+            let array: Arc<dyn Array> = array.into();
+            columns.push(DataColumn::Array(array.into_series()));
+        }
+
+        let block = DataBlock::create(block_schema.clone(), columns);
+        tx.send(Some(Ok(block)))
+            .map_err(|e| ErrorCode::UnknownException(e.to_string()))?;
     }
 
     Ok(())
@@ -118,21 +400,21 @@ impl Table for ParquetTable {
 
     fn read_plan(
         &self,
-        _ctx: DatabendQueryContextRef,
+        ctx: DatabendQueryContextRef,
         push_downs: Option<Extras>,
         _partition_num_hint: Option<usize>,
     ) -> Result<ReadDataSourcePlan> {
+        // the files are split by size, so that the workers read about the same amount of data
+        let parts = self
+            .blocking_list_files(&ctx)?
+            .into_iter()
+            .flat_map(|(file, size)| file_parts(&file, size, self.part_bytes))
+            .collect();
+
         let db = &self.tbl_info.db;
         Ok(ReadDataSourcePlan {
-            db: db.to_string(),
-            table: self.name().to_string(),
-            table_id: self.tbl_info.table_id,
-            table_version: None,
-            schema: self.tbl_info.schema.clone(),
-            parts: vec![Part {
-                name: "".to_string(),
-                version: 0,
-            }],
+            table_info: self.tbl_info.clone(),
+            parts,
             statistics: Statistics::default(),
             description: format!("(Read from Parquet Engine table  {}.{})", db, self.name()),
             scan_plan: Default::default(),
@@ -144,22 +426,48 @@ impl Table for ParquetTable {
 
     async fn read(
         &self,
-        _ctx: DatabendQueryContextRef,
-        _source_plan: &ReadDataSourcePlan,
+        ctx: DatabendQueryContextRef,
+        source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
         type BlockSender = Sender<Option<Result<DataBlock>>>;
         type BlockReceiver = Receiver<Option<Result<DataBlock>>>;
 
         let (response_tx, response_rx): (BlockSender, BlockReceiver) = bounded(2);
 
-        let file = self.file.clone();
-        let projection: Vec<usize> = (0..self.tbl_info.schema.fields().len()).collect();
+        let data_accessor = self.data_accessor.clone();
+        let schema = self.tbl_info.schema.to_arrow();
+        let (projection, filters, limit) = match &source_plan.push_downs {
+            Some(extras) => (
+                extras.projection.clone(),
+                extras.filters.clone(),
+                extras.limit,
+            ),
+            None => (None, vec![], None),
+        };
+        let projection = projection
+            .unwrap_or_else(|| (0..self.tbl_info.schema.fields().len()).collect::<Vec<usize>>());
         task::spawn_blocking(move || {
-            if let Err(e) = read_file(&file, response_tx, &projection) {
+            let tx = response_tx;
+            // the page streams are !Send, thus the parts are read by this thread,
+            // instead of by a spawned task
+            let res = Handle::current().block_on(read_parts(
+                ctx,
+                data_accessor,
+                tx,
+                &schema,
+                &projection,
+                &filters,
+            ));
+            if let Err(e) = res {
                 println!("Parquet reader thread terminated due to error: {:?}", e);
             }
         });
 
-        Ok(Box::pin(ParquetStream::try_create(response_rx)?))
+        let stream = ParquetStream::try_create(response_rx)?;
+        // the reader thread stops once the stream is dropped by the take
+        match limit {
+            Some(limit) => Ok(Box::pin(TakeStream::new(Box::pin(stream), limit))),
+            None => Ok(Box::pin(stream)),
+        }
     }
 }
@@ -14,24 +14,35 @@
 //
 
 use std::env;
+use std::sync::Arc;
 
 use common_base::tokio;
+use common_dal::DataAccessor;
+use common_dal::InMemory;
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_api_vo::TableInfo;
 use common_planners::*;
 use futures::TryStreamExt;
 
 use crate::datasources::table::parquet::parquet_table::ParquetTable;
+use crate::tests::ParquetTestData;
+
+// The locations are read by the local DataAccessor, which rejects the paths containing `..`
+fn test_data_path(path: &str) -> Result<String> {
+    let root = env::current_dir()?;
+    let root = root.parent().unwrap_or(&root);
+    Ok(root.join("tests/data").join(path).display().to_string())
+}
 
 #[tokio::test]
 async fn test_parquet_table() -> Result<()> {
     let options: TableOptions = [(
         "location".to_string(),
-        env::current_dir()?
-            .join("../tests/data/alltypes_plain.parquet")
-            .display()
-            .to_string(),
+        test_data_path("alltypes_plain.parquet")?,
     )]
     .iter()
     .cloned()
@@ -45,6 +56,7 @@ async fn test_parquet_table() -> Result<()> {
         schema: DataSchemaRefExt::create(vec![DataField::new("id", DataType::Int32, false)]),
         engine: "test_parquet".into(),
         options: options,
+        ..Default::default()
     };
     let table = ParquetTable::try_create(tbl_info)?;
 
@@ -53,6 +65,7 @@ async fn test_parquet_table() -> Result<()> {
         None,
         Some(ctx.get_settings().get_max_threads()? as usize),
     )?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
 
     let stream = table.read(ctx, &source_plan).await?;
     let blocks = stream.try_collect::<Vec<_>>().await?;
@@ -61,3 +74,288 @@ async fn test_parquet_table() -> Result<()> {
     assert_eq!(rows, 8);
     Ok(())
 }
+
+async fn read_parquet_table(location: &str, schema: DataSchemaRef) -> Result<(usize, usize)> {
+    let options: TableOptions = [("location".to_string(), test_data_path(location)?)]
+        .iter()
+        .cloned()
+        .collect();
+
+    let ctx = crate::tests::try_create_context()?;
+    let tbl_info = TableInfo {
+        db: "default".to_string(),
+        table_id: 0,
+        name: "test_parquet".to_string(),
+        schema,
+        engine: "test_parquet".into(),
+        options,
+        ..Default::default()
+    };
+    let table = ParquetTable::try_create(tbl_info)?;
+
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    Ok((source_plan.parts.len(), rows))
+}
+
+#[tokio::test]
+async fn test_parquet_table_multiple_files() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("id", DataType::Int32, false)]);
+
+    // glob of the file names
+    let (parts, rows) = read_parquet_table("parquet_parts/part-*.parquet", schema.clone()).await?;
+    assert_eq!(parts, 2);
+    assert_eq!(rows, 16);
+
+    // directory
+    let (parts, rows) = read_parquet_table("parquet_parts/", schema.clone()).await?;
+    assert_eq!(parts, 2);
+    assert_eq!(rows, 16);
+
+    // nothing matches
+    let result = read_parquet_table("parquet_parts/*.csv", schema).await;
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::CannotReadFile("").code());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parquet_table_schema_mismatch() -> Result<()> {
+    // `id` is Int32 in the files
+    let schema = DataSchemaRefExt::create(vec![DataField::new("id", DataType::Int64, false)]);
+    let result = read_parquet_table("parquet_parts/part-*.parquet", schema).await;
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::IllegalSchema("").code());
+    }
+
+    Ok(())
+}
+
+// Writes a parquet file with two row groups, `a` is in [1, 3] in the first one and in [10, 12]
+// in the second one.
+fn write_two_row_groups(path: &str) -> DataSchemaRef {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::String, false),
+    ]);
+    let block1 = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1i64, 2, 3]),
+        Series::new(vec!["x", "y", "z"]),
+    ]);
+    let block2 = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![10i64, 11, 12]),
+        Series::new(vec!["u", "v", "w"]),
+    ]);
+    ParquetTestData::create().write_to_parquet(path, &[block1, block2]);
+    schema
+}
+
+async fn read_with_push_downs(
+    location: &str,
+    schema: DataSchemaRef,
+    push_downs: Extras,
+) -> Result<Vec<DataBlock>> {
+    let options: TableOptions = [("location".to_string(), location.to_string())]
+        .iter()
+        .cloned()
+        .collect();
+    let ctx = crate::tests::try_create_context()?;
+    let table = ParquetTable::try_create(TableInfo {
+        db: "default".to_string(),
+        table_id: 0,
+        name: "test_parquet".to_string(),
+        schema,
+        engine: "test_parquet".into(),
+        options,
+        ..Default::default()
+    })?;
+
+    let source_plan = table.read_plan(ctx.clone(), Some(push_downs), None)?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(ctx, &source_plan).await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test]
+async fn test_parquet_table_projection() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let location = dir.path().join("t.parquet").display().to_string();
+    let schema = write_two_row_groups(&location);
+
+    let mut push_downs = Extras::default();
+    push_downs.projection = Some(vec![1]);
+    let blocks = read_with_push_downs(&location, schema, push_downs).await?;
+    for block in &blocks {
+        assert_eq!(block.num_columns(), 1);
+        assert_eq!(block.schema().field(0).name(), "b");
+    }
+    assert_blocks_sorted_eq(
+        vec![
+            "+---+", "| b |", "+---+", "| u |", "| v |", "| w |", "| x |", "| y |", "| z |",
+            "+---+",
+        ],
+        &blocks,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parquet_table_row_group_pruning() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let location = dir.path().join("t.parquet").display().to_string();
+    let schema = write_two_row_groups(&location);
+
+    // only the second row group may match
+    let mut push_downs = Extras::default();
+    push_downs.filters = vec![col("a").gt(lit(5i64))];
+    let blocks = read_with_push_downs(&location, schema.clone(), push_downs).await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 3);
+
+    // row groups are pruned, rows are not filtered
+    let mut push_downs = Extras::default();
+    push_downs.filters = vec![col("a").lt(lit(2i64))];
+    let blocks = read_with_push_downs(&location, schema.clone(), push_downs).await?;
+    assert_blocks_sorted_eq(
+        vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "| 2 | y |",
+            "| 3 | z |",
+            "+---+---+",
+        ],
+        &blocks,
+    );
+
+    // nothing may match
+    let mut push_downs = Extras::default();
+    push_downs.filters = vec![col("a").gt(lit(100i64))];
+    let blocks = read_with_push_downs(&location, schema, push_downs).await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parquet_table_in_memory_data_accessor() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("t.parquet");
+    let schema = write_two_row_groups(&path.display().to_string());
+
+    // the parquet blob is served by an object storage, instead of the local file system
+    let data_accessor = Arc::new(InMemory::new());
+    data_accessor
+        .put("bucket/t/0.parquet", std::fs::read(&path)?)
+        .await?;
+    data_accessor
+        .put("bucket/t/1.parquet", std::fs::read(&path)?)
+        .await?;
+
+    let options: TableOptions = [("location".to_string(), "bucket/t/".to_string())]
+        .iter()
+        .cloned()
+        .collect();
+    let ctx = crate::tests::try_create_context()?;
+    let table = ParquetTable::with_data_accessor(
+        TableInfo {
+            db: "default".to_string(),
+            table_id: 0,
+            name: "test_parquet".to_string(),
+            schema,
+            engine: "test_parquet".into(),
+            options,
+            ..Default::default()
+        },
+        data_accessor,
+    )?;
+
+    let mut push_downs = Extras::default();
+    push_downs.projection = Some(vec![0]);
+    push_downs.filters = vec![col("a").gt(lit(5i64))];
+    let source_plan = table.read_plan(ctx.clone(), Some(push_downs), None)?;
+    assert_eq!(source_plan.parts.len(), 2);
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    assert_blocks_sorted_eq(
+        vec![
+            "+----+", "| a  |", "+----+", "| 10 |", "| 10 |", "| 11 |", "| 11 |", "| 12 |",
+            "| 12 |", "+----+",
+        ],
+        &blocks,
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parquet_table_split_by_size() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("t.parquet");
+    let schema = write_two_row_groups(&path.display().to_string());
+    let content = std::fs::read(&path)?;
+    let size = content.len() as u64;
+
+    let data_accessor = Arc::new(InMemory::new());
+    data_accessor.put("bucket/t/0.parquet", content).await?;
+
+    // the file is split into 3 byte ranges
+    let options: TableOptions = [
+        ("location".to_string(), "bucket/t/".to_string()),
+        ("part_bytes".to_string(), (size / 3 + 1).to_string()),
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    let ctx = crate::tests::try_create_context()?;
+    let table = ParquetTable::with_data_accessor(
+        TableInfo {
+            db: "default".to_string(),
+            table_id: 0,
+            name: "test_parquet".to_string(),
+            schema,
+            engine: "test_parquet".into(),
+            options,
+            ..Default::default()
+        },
+        data_accessor,
+    )?;
+
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    assert_eq!(source_plan.parts.len(), 3);
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+
+    // each row group is read by exactly one of the partitions
+    let stream = table.read(ctx, &source_plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    assert_blocks_sorted_eq(
+        vec![
+            "+----+---+",
+            "| a  | b |",
+            "+----+---+",
+            "| 1  | x |",
+            "| 2  | y |",
+            "| 3  | z |",
+            "| 10 | u |",
+            "| 11 | v |",
+            "| 12 | w |",
+            "+----+---+",
+        ],
+        &blocks,
+    );
+
+    Ok(())
+}
@@ -15,20 +15,26 @@
 
 use common_exception::Result;
 
+use crate::configs::Config;
 use crate::datasources::table::csv::csv_table::CsvTable;
-use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::FuseTableFactory;
+use crate::datasources::table::json::json_table::JsonTable;
 use crate::datasources::table::memory::memory_table::MemoryTable;
 use crate::datasources::table::null::null_table::NullTable;
 use crate::datasources::table::parquet::parquet_table::ParquetTable;
 use crate::datasources::table::remote::remote_table::RemoteTableFactory;
 use crate::datasources::table_engine_registry::TableEngineRegistry;
 
-pub fn register_prelude_tbl_engines(registry: &TableEngineRegistry) -> Result<()> {
+pub fn register_prelude_tbl_engines(registry: &TableEngineRegistry, conf: &Config) -> Result<()> {
     registry.register("CSV", std::sync::Arc::new(CsvTable::try_create))?;
+    registry.register("JSON", std::sync::Arc::new(JsonTable::try_create))?;
     registry.register("PARQUET", std::sync::Arc::new(ParquetTable::try_create))?;
     registry.register("NULL", std::sync::Arc::new(NullTable::try_create))?;
     registry.register("MEMORY", std::sync::Arc::new(MemoryTable::try_create))?;
-    registry.register("FUSE", std::sync::Arc::new(FuseTable::try_create))?;
+    registry.register(
+        "FUSE",
+        std::sync::Arc::new(FuseTableFactory::create(&conf.storage.disk.data_path)),
+    )?;
     registry.register("REMOTE", std::sync::Arc::new(RemoteTableFactory {}))?;
     Ok(())
 }
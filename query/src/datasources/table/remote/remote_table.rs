@@ -24,6 +24,7 @@ use common_planners::InsertIntoPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
 
 use crate::catalogs::Table;
 use crate::common::StoreApiProvider;
@@ -79,36 +80,41 @@ impl Table for RemoteTable {
         self.do_read(ctx, source_plan).await
     }
 
-    async fn append_data(&self, _ctx: DatabendQueryContextRef, plan: InsertIntoPlan) -> Result<()> {
+    async fn append_data(
+        &self,
+        _ctx: DatabendQueryContextRef,
+        plan: InsertIntoPlan,
+    ) -> Result<usize> {
         let opt_stream = {
             let mut inner = plan.input_stream.lock();
             (*inner).take()
         };
 
-        {
-            let block_stream =
-                opt_stream.ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
+        let block_stream =
+            opt_stream.ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
+        // the store appends the blocks as they arrive, they are gathered first so that
+        // an error in the stream fails the insert before anything is sent
+        let blocks = block_stream.try_collect::<Vec<_>>().await?;
 
-            let client = self.store_api_provider.try_get_storage_client().await?;
+        let client = self.store_api_provider.try_get_storage_client().await?;
 
-            client
-                .append_data(
-                    plan.db_name.clone(),
-                    plan.tbl_name.clone(),
-                    (&plan).schema().clone(),
-                    block_stream,
-                )
-                .await?;
-        }
+        let result = client
+            .append_data(
+                plan.db_name.clone(),
+                plan.tbl_name.clone(),
+                (&plan).schema().clone(),
+                Box::pin(futures::stream::iter(blocks)),
+            )
+            .await?;
 
-        Ok(())
+        Ok(result.summary.rows)
     }
 
     async fn truncate(
         &self,
         _ctx: DatabendQueryContextRef,
         _plan: TruncateTablePlan,
-    ) -> Result<()> {
+    ) -> Result<usize> {
         todo!()
     }
 }
@@ -14,6 +14,7 @@
 //
 
 use common_meta_api_vo::TableInfo;
+use common_planners::TableOptions;
 
 use crate::catalogs::Table;
 use crate::common::StoreApiProvider;
@@ -28,6 +29,11 @@ pub trait TableEngine: Send + Sync {
         tbl_info: TableInfo,
         store_provider: StoreApiProvider,
     ) -> common_exception::Result<Box<dyn Table>>;
+
+    /// Checks the table options of a `CREATE TABLE`, before the table is created.
+    fn validate_options(&self, _options: &TableOptions) -> common_exception::Result<()> {
+        Ok(())
+    }
 }
 
 impl<T> TableEngine for T
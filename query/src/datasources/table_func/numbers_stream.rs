@@ -33,6 +33,11 @@ struct BlockRange {
 pub struct NumbersStream {
     ctx: DatabendQueryContextRef,
     schema: DataSchemaRef,
+    // the partitions are ranges of the indexes, the `i`th number is `start + i * step`
+    start: u64,
+    step: i64,
+    // the indexes from `limit` on are not generated
+    limit: Option<u64>,
     block_index: usize,
     blocks: Vec<BlockRange>,
 }
@@ -41,10 +46,16 @@ impl NumbersStream {
     pub fn try_create(
         ctx: DatabendQueryContextRef,
         schema: DataSchemaRef,
+        start: u64,
+        step: i64,
+        limit: Option<u64>,
     ) -> Result<ProgressStream> {
         let stream = Box::pin(NumbersStream {
             ctx: ctx.clone(),
             schema,
+            start,
+            step,
+            limit,
             block_index: 0,
             blocks: vec![],
         });
@@ -53,7 +64,7 @@ impl NumbersStream {
 
     #[inline]
     fn try_get_one_block(&mut self) -> Result<Option<DataBlock>> {
-        if (self.block_index as usize) == self.blocks.len() {
+        while (self.block_index as usize) == self.blocks.len() {
             let partitions = self.ctx.try_get_partitions(1)?;
             if partitions.is_empty() {
                 return Ok(None);
@@ -67,7 +78,15 @@ impl NumbersStream {
             for part in partitions {
                 let names: Vec<_> = part.name.split('-').collect();
                 let begin: u64 = names[1].parse()?;
-                let end: u64 = names[2].parse()?;
+                let mut end: u64 = names[2].parse()?;
+                if let Some(limit) = self.limit {
+                    // skip the partitions beyond the limit rather than ending the stream,
+                    // the ones before the limit may still be queued
+                    if begin >= limit {
+                        continue;
+                    }
+                    end = end.min(limit);
+                }
 
                 let diff = end - begin;
                 let block_nums = diff / block_size;
@@ -104,11 +123,14 @@ impl NumbersStream {
 
             unsafe { av.set_len(size) };
 
+            let (start, step) = (self.start as i128, self.step as i128);
+
             av.as_mut_slice()
                 .iter_mut()
                 .enumerate()
                 .for_each(|(idx, num)| {
-                    *num = current.begin + idx as u64;
+                    let index = (current.begin + idx as u64) as i128;
+                    *num = (start + index * step) as u64;
                 });
 
             let series = DFUInt64Array::new_from_aligned_vec(av).into_series();
@@ -14,6 +14,7 @@
 //
 
 use std::any::Any;
+use std::convert::TryFrom;
 use std::mem::size_of;
 use std::sync::Arc;
 
@@ -24,6 +25,7 @@ use common_datavalues::DataType;
 use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::ReadDataSourcePlan;
@@ -42,6 +44,9 @@ pub struct NumbersTable {
     table_name: String,
     table_id: u64, // to be removed, if func never renamed
     schema: DataSchemaRef,
+    // the numbers are `start + i * step` for `i` in `0..total`
+    start: u64,
+    step: i64,
     total: u64,
 }
 
@@ -52,22 +57,45 @@ impl NumbersTable {
         table_id: u64,
         table_args: TableArgs,
     ) -> Result<Arc<dyn TableFunction>> {
-        let mut total = None;
-        if let Some(args) = &table_args {
-            if args.len() == 1 {
-                let arg = &args[0];
-                if let Expression::Literal { value, .. } = arg {
-                    total = Some(value.as_u64()?);
-                }
-            }
-        }
-
-        let total = total.ok_or_else(|| {
+        let bad_arguments = || {
             ErrorCode::BadArguments(format!(
-                "Must have exactly one number argument for table function.{}",
+                "Must have one to three number arguments for table function.{}",
                 &table_func_name
             ))
-        })?;
+        };
+
+        let args = match &table_args {
+            Some(args) => args
+                .iter()
+                .map(|arg| literal_as_i128(arg).ok_or_else(bad_arguments))
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![],
+        };
+        // numbers(end), numbers(start, end) or numbers(start, end, step)
+        let (start, end, step) = match args[..] {
+            [end] => (0, end, 1),
+            [start, end] => (start, end, 1),
+            [start, end, step] => (start, end, step),
+            _ => return Err(bad_arguments()),
+        };
+        if step == 0 {
+            return Err(ErrorCode::BadArguments(format!(
+                "The step of table function.{} must not be zero",
+                &table_func_name
+            )));
+        }
+        if start < 0 || end < 0 || start > u64::MAX as i128 || end > u64::MAX as i128 {
+            return Err(ErrorCode::BadArguments(format!(
+                "The range of table function.{} must be within UInt64",
+                &table_func_name
+            )));
+        }
+        let step = i64::try_from(step).map_err(|_| bad_arguments())?;
+
+        // the end is excluded, and the range is empty if it goes against the step
+        let distance = if step > 0 { end - start } else { start - end };
+        let step_len = (step as i128).abs();
+        let total = ((distance.max(0) + step_len - 1) / step_len) as u64;
 
         Ok(Arc::new(NumbersTable {
             db_name: database_name.to_string(),
@@ -78,9 +106,42 @@ impl NumbersTable {
                 DataType::UInt64,
                 false,
             )]),
+            start: start as u64,
+            step,
             total,
         }))
     }
+
+    // The args to create the same table function, e.g. on the other nodes of the cluster
+    fn table_args(&self) -> Vec<Expression> {
+        if self.start == 0 && self.step == 1 {
+            return vec![Expression::create_literal(DataValue::UInt64(Some(
+                self.total,
+            )))];
+        }
+
+        // the end past the last number may be out of UInt64, any end within the range of
+        // the last number and the one past it gives the same numbers
+        let end = self.start as i128 + self.total as i128 * self.step as i128;
+        let end = end.clamp(0, u64::MAX as i128) as u64;
+        vec![
+            Expression::create_literal(DataValue::UInt64(Some(self.start))),
+            Expression::create_literal(DataValue::UInt64(Some(end))),
+            Expression::create_literal(DataValue::Int64(Some(self.step))),
+        ]
+    }
+}
+
+// A literal number, or a negated one (e.g. the step of a descending range)
+fn literal_as_i128(expr: &Expression) -> Option<i128> {
+    match expr {
+        Expression::Literal { value, .. } => match value {
+            DataValue::UInt64(Some(v)) => Some(*v as i128),
+            other => other.as_i64().ok().map(|v| v as i128),
+        },
+        Expression::UnaryExpression { op, expr } if op == "-" => literal_as_i128(expr).map(|v| -v),
+        _ => None,
+    }
 }
 
 #[async_trait::async_trait]
@@ -128,16 +189,14 @@ impl Table for NumbersTable {
         ctx.try_set_statistics(&statistics)?;
         ctx.add_total_rows_approx(statistics.read_rows);
 
-        let tbl_arg = Some(vec![Expression::create_literal(DataValue::UInt64(Some(
-            self.total,
-        )))]);
+        let tbl_arg = Some(self.table_args());
 
         Ok(ReadDataSourcePlan {
-            db: self.db_name.clone(),
-            table: self.table_name.clone(),
-            table_id: self.table_id,
-            table_version: None,
-            schema: self.schema.clone(),
+            table_info: TableInfo {
+                table_id: self.table_id,
+                engine: self.engine().to_string(),
+                ..TableInfo::simple(&self.db_name, &self.table_name, self.schema.clone())
+            },
             parts: generate_parts(0, ctx.get_settings().get_max_threads()?, total),
             statistics: statistics.clone(),
             description: format!(
@@ -154,11 +213,19 @@ impl Table for NumbersTable {
     async fn read(
         &self,
         ctx: DatabendQueryContextRef,
-        _source_plan: &ReadDataSourcePlan,
+        source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
+        let limit = source_plan
+            .push_downs
+            .as_ref()
+            .and_then(|extras| extras.limit)
+            .map(|limit| limit as u64);
         Ok(Box::pin(NumbersStream::try_create(
             ctx,
             self.schema.clone(),
+            self.start,
+            self.step,
+            limit,
         )?))
     }
 }
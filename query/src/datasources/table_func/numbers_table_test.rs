@@ -15,6 +15,7 @@
 
 use common_base::tokio;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::TryStreamExt;
@@ -68,3 +69,110 @@ async fn test_number_table() -> Result<()> {
 
     Ok(())
 }
+
+async fn read_numbers(args: Vec<Expression>) -> Result<Vec<u64>> {
+    let ctx = crate::tests::try_create_context()?;
+    let table = NumbersTable::create("system", "numbers_mt", 1, Some(args))?;
+    let source_plan = table.read_plan(ctx.clone(), None, None)?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let mut numbers = vec![];
+    for block in blocks {
+        let column = block.column(0).to_array()?;
+        numbers.extend(column.u64()?.into_no_null_iter());
+    }
+    numbers.sort_unstable();
+    Ok(numbers)
+}
+
+#[tokio::test]
+async fn test_number_table_range() -> Result<()> {
+    let lit = |v: u64| Expression::create_literal(DataValue::UInt64(Some(v)));
+
+    // numbers(5, 10)
+    let numbers = read_numbers(vec![lit(5), lit(10)]).await?;
+    assert_eq!(numbers, vec![5, 6, 7, 8, 9]);
+
+    // numbers(0, 10, 2)
+    let numbers = read_numbers(vec![lit(0), lit(10), lit(2)]).await?;
+    assert_eq!(numbers, vec![0, 2, 4, 6, 8]);
+
+    // numbers(10, 0, -3), a descending range
+    let step = Expression::UnaryExpression {
+        op: "-".to_string(),
+        expr: Box::new(lit(3)),
+    };
+    let numbers = read_numbers(vec![lit(10), lit(0), step]).await?;
+    assert_eq!(numbers, vec![1, 4, 7, 10]);
+
+    // an empty range, as the end is before the start
+    let numbers = read_numbers(vec![lit(10), lit(5)]).await?;
+    assert!(numbers.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_number_table_args() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let lit = |v: u64| Expression::create_literal(DataValue::UInt64(Some(v)));
+
+    // the end past the last number, u64::MAX + 1, is capped
+    let args = vec![lit(1), lit(u64::MAX), lit(2)];
+    let table = NumbersTable::create("system", "numbers", 1, Some(args.clone()))?;
+    let tbl_args = table.read_plan(ctx.clone(), None, None)?.tbl_args;
+    assert_eq!(tbl_args, Some(args));
+
+    // the args create the same numbers
+    let recreated = NumbersTable::create("system", "numbers", 1, tbl_args.clone())?;
+    assert_eq!(recreated.read_plan(ctx, None, None)?.tbl_args, tbl_args);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_number_table_with_limit() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let tbl_args = vec![Expression::create_literal(DataValue::UInt64(Some(1000000)))];
+    let table = NumbersTable::create("system", "numbers_mt", 1, Some(tbl_args))?;
+
+    // as the limit is pushed down by `numbers(1000000) LIMIT 5`
+    let push_downs = Extras {
+        limit: Some(5),
+        ..Extras::default()
+    };
+    let source_plan = table.read_plan(ctx.clone(), Some(push_downs), None)?;
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+    assert_eq!(rows, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_number_table_bad_arguments() -> Result<()> {
+    let lit = |v: u64| Expression::create_literal(DataValue::UInt64(Some(v)));
+
+    // zero step
+    let result = NumbersTable::create("system", "numbers", 1, Some(vec![lit(0), lit(10), lit(0)]));
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+        assert_eq!(
+            e.message(),
+            "The step of table function.numbers must not be zero"
+        );
+    }
+
+    // too many arguments
+    let args = vec![lit(0), lit(10), lit(1), lit(1)];
+    let result = NumbersTable::create("system", "numbers", 1, Some(args));
+    assert!(result.is_err());
+
+    Ok(())
+}
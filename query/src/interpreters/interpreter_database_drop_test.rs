@@ -13,12 +13,17 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::catalogs::Catalog;
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::TBL_OPT_KEY_STORAGE_SCHEME;
 use crate::interpreters::*;
+use crate::sessions::DatabendQueryContextRef;
 use crate::sql::*;
 
 #[tokio::test]
@@ -40,3 +45,107 @@ async fn test_drop_database_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_drop_database_cascade_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create database db1",
+        "create table db1.t1(a int) Engine = Null",
+        "create table db1.t2(a int) Engine = Null",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // non-empty database, without cascade
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("drop database db1")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let r = executor.execute().await;
+        assert!(r.is_err());
+        assert_eq!(
+            r.err().unwrap().code(),
+            ErrorCode::DatabaseNotEmpty("").code()
+        );
+        assert_eq!(
+            ctx.get_catalog().get_database("db1")?.get_tables()?.len(),
+            2
+        );
+    }
+
+    // with cascade
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("drop database db1 cascade")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec!["++", "++"];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+        assert!(ctx.get_catalog().get_database("db1").is_err());
+        assert!(ctx.get_catalog().get_table("db1", "t1").is_err());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_drop_database_cascade_purge_interpreter() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let ctx = crate::tests::try_create_context_with_data_path(dir.path().to_str().unwrap())?;
+
+    async fn execute_sql(ctx: &DatabendQueryContextRef, sql: &str) -> Result<()> {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+        Ok(())
+    }
+
+    fn count_files(path: &std::path::Path) -> std::io::Result<usize> {
+        let mut count = 0;
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            count += if path.is_dir() {
+                count_files(&path)?
+            } else {
+                1
+            };
+        }
+        Ok(count)
+    }
+
+    execute_sql(&ctx, "create database db1").await?;
+    if let PlanNode::CreateTable(mut plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("create table db1.t(a Int32) Engine = Fuse")?
+    {
+        plan.options.insert(
+            TBL_OPT_KEY_STORAGE_SCHEME.to_string(),
+            "LOCAL_FS".to_string(),
+        );
+        let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+    execute_sql(&ctx, "insert into db1.t values(1),(2)").await?;
+
+    // a snapshot, a segment and a block
+    let table_id = ctx.get_catalog().get_table("db1", "t")?.raw().get_id();
+    let data_root = dir.path().join(table_id.to_string());
+    assert_eq!(count_files(&data_root)?, 3);
+
+    // the files of the table are removed in the background
+    execute_sql(&ctx, "drop database db1 cascade").await?;
+    let mut remaining = count_files(&data_root)?;
+    for _ in 0..100 {
+        if remaining == 0 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        remaining = count_files(&data_root)?;
+    }
+    assert_eq!(remaining, 0);
+
+    Ok(())
+}
@@ -55,6 +55,7 @@ impl Interpreter for DescribeTableInterpreter {
         let mut names: Vec<String> = vec![];
         let mut types: Vec<String> = vec![];
         let mut nulls: Vec<String> = vec![];
+        let mut defaults: Vec<String> = vec![];
         for field in schema.fields().iter() {
             names.push(field.name().to_string());
             types.push(format!("{:?}", field.data_type()));
@@ -63,10 +64,16 @@ impl Interpreter for DescribeTableInterpreter {
             } else {
                 "NO".to_string()
             });
+            // as in MySQL, the columns without default expressions are shown as NULL
+            defaults.push(match field.default_expr() {
+                Some(default_expr) => default_expr.clone(),
+                None => "NULL".to_string(),
+            });
         }
         let names: Vec<&[u8]> = names.iter().map(|x| x.as_bytes()).collect();
         let types: Vec<&[u8]> = types.iter().map(|x| x.as_bytes()).collect();
         let nulls: Vec<&[u8]> = nulls.iter().map(|x| x.as_bytes()).collect();
+        let defaults: Vec<&[u8]> = defaults.iter().map(|x| x.as_bytes()).collect();
 
         let desc_schema = self.plan.schema();
 
@@ -74,6 +81,7 @@ impl Interpreter for DescribeTableInterpreter {
             Series::new(names),
             Series::new(types),
             Series::new(nulls),
+            Series::new(defaults),
         ]);
 
         Ok(Box::pin(DataBlockStream::create(desc_schema, None, vec![
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_base::tokio;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use futures::TryStreamExt;
@@ -46,15 +47,15 @@ async fn interpreter_describe_table_test() -> Result<()> {
             let stream = executor.execute().await?;
             let result = stream.try_collect::<Vec<_>>().await?;
             let expected = vec![
-                "+-------+--------+------+",
-                "| Field | Type   | Null |",
-                "+-------+--------+------+",
-                "| a     | Int64  | NO   |",
-                "| b     | Int32  | NO   |",
-                "| c     | String | NO   |",
-                "| d     | Int16  | NO   |",
-                "| e     | Date16 | NO   |",
-                "+-------+--------+------+",
+                "+-------+--------+------+---------+",
+                "| Field | Type   | Null | Default |",
+                "+-------+--------+------+---------+",
+                "| a     | Int64  | NO   | NULL    |",
+                "| b     | Int32  | NO   | NULL    |",
+                "| c     | String | NO   | NULL    |",
+                "| d     | Int16  | NO   | NULL    |",
+                "| e     | Date16 | NO   | NULL    |",
+                "+-------+--------+------+---------+",
             ];
             common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
         } else {
@@ -62,5 +63,42 @@ async fn interpreter_describe_table_test() -> Result<()> {
         }
     }
 
+    // describe system table.
+    {
+        if let PlanNode::DescribeTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("describe system.one")?
+        {
+            let executor = DescribeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-------+-------+------+---------+",
+                "| Field | Type  | Null | Default |",
+                "+-------+-------+------+---------+",
+                "| dummy | UInt8 | NO   | NULL    |",
+                "+-------+-------+------+---------+",
+            ];
+            common_datablocks::assert_blocks_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // describe unknown table.
+    {
+        if let PlanNode::DescribeTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("describe system.not_exists")?
+        {
+            let executor = DescribeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+            if let Err(e) = result {
+                assert_eq!(e.code(), ErrorCode::UnknownTable("").code());
+            }
+        } else {
+            assert!(false)
+        }
+    }
+
     Ok(())
 }
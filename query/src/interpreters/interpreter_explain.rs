@@ -46,6 +46,7 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::Cost => self.explain_cost(),
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -88,6 +89,18 @@ impl ExplainInterpreter {
         Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
     }
 
+    fn explain_cost(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let plan = Optimizers::create(self.ctx.clone()).optimize(&self.explain.input)?;
+        let formatted_plan = Series::new(
+            format!("{}", plan.display_indent_format_with_cost())
+                .lines()
+                .map(|s| s.as_bytes())
+                .collect::<Vec<_>>(),
+        );
+        Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
+    }
+
     fn explain_pipeline(&self) -> Result<DataBlock> {
         let schema = self.schema();
         let plan = Optimizers::without_scatters(self.ctx.clone()).optimize(&self.explain.input)?;
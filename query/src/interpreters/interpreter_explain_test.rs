@@ -54,3 +54,32 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_cost_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("explain cost select number from numbers(10) where number > 1")?
+    {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+
+        // the scan reports the exact rows of the table, the filter an estimate
+        let expected = vec![
+            "+------------------------------------------------------------------------------------------------------------------------------------------+",
+            "| explain                                                                                                                                  |",
+            "+------------------------------------------------------------------------------------------------------------------------------------------+",
+            "| Projection: number:UInt64 (rows: ~3, cost: 23)                                                                                           |",
+            "|   Filter: (number > 1) (rows: ~3, cost: 20)                                                                                              |",
+            "|     ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80] (rows: 10, cost: 10) |",
+            "+------------------------------------------------------------------------------------------------------------------------------------------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    } else {
+        assert!(false)
+    }
+
+    Ok(())
+}
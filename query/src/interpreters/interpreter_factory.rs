@@ -19,6 +19,7 @@ use common_exception::Result;
 use common_planners::PlanNode;
 
 use crate::interpreters::interpreter_kill::KillInterpreter;
+use crate::interpreters::AlterTableInterpreter;
 use crate::interpreters::CreateDatabaseInterpreter;
 use crate::interpreters::CreateTableInterpreter;
 use crate::interpreters::DescribeTableInterpreter;
@@ -27,9 +28,13 @@ use crate::interpreters::DropTableInterpreter;
 use crate::interpreters::ExplainInterpreter;
 use crate::interpreters::InsertIntoInterpreter;
 use crate::interpreters::Interpreter;
+use crate::interpreters::OptimizeTableInterpreter;
+use crate::interpreters::QueryLogInterpreter;
 use crate::interpreters::SelectInterpreter;
 use crate::interpreters::SettingInterpreter;
 use crate::interpreters::ShowCreateTableInterpreter;
+use crate::interpreters::ShowDatabasesInterpreter;
+use crate::interpreters::ShowTablesInterpreter;
 use crate::interpreters::TruncateTableInterpreter;
 use crate::interpreters::UseDatabaseInterpreter;
 use crate::sessions::DatabendQueryContextRef;
@@ -38,24 +43,33 @@ pub struct InterpreterFactory;
 
 impl InterpreterFactory {
     pub fn get(ctx: DatabendQueryContextRef, plan: PlanNode) -> Result<Arc<dyn Interpreter>> {
-        match plan {
+        // the queries are recorded into the query log, by the kind of their plans
+        let kind = plan.name().trim_end_matches("Plan").to_string();
+        let log_ctx = ctx.clone();
+
+        let interpreter = match plan {
             PlanNode::Select(v) => SelectInterpreter::try_create(ctx, v),
             PlanNode::Explain(v) => ExplainInterpreter::try_create(ctx, v),
             PlanNode::CreateDatabase(v) => CreateDatabaseInterpreter::try_create(ctx, v),
             PlanNode::DropDatabase(v) => DropDatabaseInterpreter::try_create(ctx, v),
             PlanNode::CreateTable(v) => CreateTableInterpreter::try_create(ctx, v),
             PlanNode::DropTable(v) => DropTableInterpreter::try_create(ctx, v),
+            PlanNode::AlterTableAddColumn(v) => AlterTableInterpreter::try_create(ctx, v),
             PlanNode::DescribeTable(v) => DescribeTableInterpreter::try_create(ctx, v),
             PlanNode::TruncateTable(v) => TruncateTableInterpreter::try_create(ctx, v),
+            PlanNode::OptimizeTable(v) => OptimizeTableInterpreter::try_create(ctx, v),
             PlanNode::UseDatabase(v) => UseDatabaseInterpreter::try_create(ctx, v),
             PlanNode::SetVariable(v) => SettingInterpreter::try_create(ctx, v),
             PlanNode::InsertInto(v) => InsertIntoInterpreter::try_create(ctx, v),
             PlanNode::ShowCreateTable(v) => ShowCreateTableInterpreter::try_create(ctx, v),
             PlanNode::Kill(v) => KillInterpreter::try_create(ctx, v),
+            PlanNode::ShowDatabases(v) => ShowDatabasesInterpreter::try_create(ctx, v),
+            PlanNode::ShowTables(v) => ShowTablesInterpreter::try_create(ctx, v),
             _ => Result::Err(ErrorCode::UnknownTypeOfQuery(format!(
                 "Can't get the interpreter by plan:{}",
                 plan.name()
             ))),
-        }
+        }?;
+        Ok(QueryLogInterpreter::create(log_ctx, kind, interpreter))
     }
 }
@@ -12,17 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_base::tokio;
+use common_base::TrySpawn;
+use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::InsertIntoPlan;
+use common_planners::PlanNode;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::catalogs::Catalog;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
+use crate::interpreters::SelectInterpreter;
 use crate::sessions::DatabendQueryContextRef;
+use crate::sql::PlanParser;
 
 pub struct InsertIntoInterpreter {
     ctx: DatabendQueryContextRef,
@@ -48,10 +63,20 @@ impl Interpreter for InsertIntoInterpreter {
         let datasource = self.ctx.get_catalog();
         let database = datasource.get_database(self.plan.db_name.as_str())?;
         let table = database.get_table_by_id(self.plan.tbl_id, None)?;
-        table
-            .raw()
-            .append_data(self.ctx.clone(), self.plan.clone())
-            .await?;
+
+        let plan = match &self.plan.select_plan {
+            None => self.plan.clone(),
+            Some(select_plan) => self.with_select_stream(select_plan).await?,
+        };
+        let plan = self.with_omitted_columns(plan, table.raw().schema()?)?;
+        let appended_rows = table.raw().append_data(self.ctx.clone(), plan).await?;
+        tracing::debug!(
+            "Inserted {} rows into {}.{}",
+            appended_rows,
+            self.plan.db_name,
+            self.plan.tbl_name
+        );
+        self.ctx.set_affected_rows(appended_rows);
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
@@ -59,3 +84,155 @@ impl Interpreter for InsertIntoInterpreter {
         )))
     }
 }
+
+impl InsertIntoInterpreter {
+    // Executes the select, its result is the input stream of the returned plan.
+    async fn with_select_stream(&self, select_plan: &PlanNode) -> Result<InsertIntoPlan> {
+        let select = match select_plan {
+            PlanNode::Select(select) => select.clone(),
+            other => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Insert from {} is not yet implemented",
+                    other.name()
+                )));
+            }
+        };
+        let table_name = format!("{}.{}", self.plan.db_name, self.plan.tbl_name);
+        check_select_schema(&table_name, &self.plan.schema, &select.schema())?;
+
+        let interpreter = SelectInterpreter::try_create(self.ctx.clone(), select)?;
+        let mut stream = interpreter.execute().await?;
+
+        // The select stream is not Sync as the input stream of a plan has to be, its blocks are
+        // passed through a channel as they are produced, the select waits while one is pending.
+        // The columns are matched by position, they are named after the ones of the table.
+        let schema = self.plan.schema();
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.ctx.try_spawn(async move {
+            while let Some(res) = stream.next().await {
+                let res =
+                    res.map(|block| DataBlock::create(schema.clone(), block.columns().to_vec()));
+                let failed = res.is_err();
+                // the receiver is dropped once the insert is done
+                if tx.send(res).await.is_err() || failed {
+                    break;
+                }
+            }
+        })?;
+
+        let plan = InsertIntoPlan {
+            select_plan: None,
+            input_stream: InsertIntoPlan::empty_stream(),
+            ..self.plan.clone()
+        };
+        plan.set_input_stream(Box::pin(ReceiverStream::new(rx)));
+        Ok(plan)
+    }
+
+    // The columns omitted by the insert are filled with their default values, or NULL if they
+    // have none, so that the returned plan inserts all the columns of the table.
+    // The blocks are filled as they are appended.
+    fn with_omitted_columns(
+        &self,
+        plan: InsertIntoPlan,
+        table_schema: DataSchemaRef,
+    ) -> Result<InsertIntoPlan> {
+        let insert_schema = plan.schema();
+        let omitted = table_schema
+            .fields()
+            .iter()
+            .filter(|field| insert_schema.index_of(field.name()).is_err())
+            .collect::<Vec<_>>();
+        if omitted.is_empty() {
+            return Ok(plan);
+        }
+
+        let parser = PlanParser::create(self.ctx.clone());
+        let mut values = HashMap::with_capacity(omitted.len());
+        for field in omitted {
+            let value = match parser.column_default_value(field)? {
+                Some(value) => value,
+                None if field.is_nullable() => DataValue::from(field.data_type()),
+                None => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Column `{}` of table {}.{} has no default value, it can not be omitted",
+                        field.name(),
+                        self.plan.db_name,
+                        self.plan.tbl_name
+                    )));
+                }
+            };
+            values.insert(field.name().clone(), value);
+        }
+
+        let input_stream = match plan.input_stream.lock().take() {
+            Some(stream) => stream,
+            None => Box::pin(futures::stream::empty()),
+        };
+        let schema = table_schema.clone();
+        let blocks = input_stream.map(move |block| {
+            let block = block?;
+            let columns = schema
+                .fields()
+                .iter()
+                .map(|field| match values.get(field.name()) {
+                    // the tables store the value of each row, not a constant column
+                    Some(value) => {
+                        let column = DataColumn::Constant(value.clone(), block.num_rows());
+                        Ok(DataColumn::from(column.to_array()?))
+                    }
+                    None => Ok(block.try_column_by_name(field.name())?.clone()),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(DataBlock::create(schema.clone(), columns))
+        });
+
+        let plan = InsertIntoPlan {
+            schema: table_schema,
+            input_stream: InsertIntoPlan::empty_stream(),
+            ..plan
+        };
+        plan.set_input_stream(Box::pin(blocks));
+        Ok(plan)
+    }
+}
+
+/// Checks the select returns the columns to insert, by position, type and nullability.
+fn check_select_schema(
+    table_name: &str,
+    insert_schema: &DataSchema,
+    select_schema: &DataSchema,
+) -> Result<()> {
+    let insert_fields = insert_schema.fields();
+    let select_fields = select_schema.fields();
+    if insert_fields.len() != select_fields.len() {
+        return Err(ErrorCode::BadArguments(format!(
+            "Schema mismatch on table {}, expects {} columns, the select returns {}",
+            table_name,
+            insert_fields.len(),
+            select_fields.len()
+        )));
+    }
+
+    for (expected, actual) in insert_fields.iter().zip(select_fields.iter()) {
+        if expected.data_type() != actual.data_type() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Schema mismatch on table {}, column `{}` is expected to be {}, the select returns {} for `{}`",
+                table_name,
+                expected.name(),
+                expected.data_type(),
+                actual.data_type(),
+                actual.name()
+            )));
+        }
+        if !expected.is_nullable() && actual.is_nullable() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Schema mismatch on table {}, column `{}` is not nullable, the select returns a nullable `{}`",
+                table_name,
+                expected.name(),
+                actual.name()
+            )));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,306 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+
+use crate::catalogs::Table;
+use crate::datasources::table::fuse::block_location;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TBL_OPT_KEY_STORAGE_SCHEME;
+use crate::interpreters::*;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_insert_into_select_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create table.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.a(a UInt64) Engine = Memory")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Insert into select.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a select number from numbers(5)")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        } else {
+            assert!(false)
+        }
+    }
+
+    // select.
+    {
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select * from default.a")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+", "| a |", "+---+", "| 0 |", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "+---+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // the select returns more columns than the table has.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a select number, number from numbers(5)")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+            if let Err(e) = result {
+                assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+                assert_eq!(
+                    e.message(),
+                    "Schema mismatch on table default.a, expects 1 columns, the select returns 2"
+                );
+            }
+        } else {
+            assert!(false)
+        }
+    }
+
+    // the select returns a column of another type.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a select toString(number) from numbers(5)")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+            if let Err(e) = result {
+                assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+                assert!(
+                    e.message()
+                        .starts_with("Schema mismatch on table default.a, column `a` is expected to be UInt64, the select returns String"),
+                    "{}",
+                    e.message()
+                );
+            }
+        } else {
+            assert!(false)
+        }
+    }
+
+    // the select returns a nullable column, an added column is nullable.
+    {
+        for query in [
+            "create table default.n(a UInt64) Engine = Null",
+            "alter table default.n add column b UInt64",
+        ] {
+            let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+            let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+            let _ = executor.execute().await?;
+        }
+
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a select b from default.n")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+            if let Err(e) = result {
+                assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+                assert_eq!(
+                    e.message(),
+                    "Schema mismatch on table default.a, column `a` is not nullable, the select returns a nullable `b`"
+                );
+            }
+        } else {
+            assert!(false)
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_insert_into_with_default_values_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create table, `b` has a default value.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.d(a UInt64, b UInt64 default 40 + 2) Engine = Memory",
+        )? {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Insert values and insert select, both omit `b`.
+    for query in [
+        "insert into default.d(a) values(1), (2)",
+        "insert into default.d(a) select number from numbers(1)",
+    ] {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone()).build_from_sql(query)? {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        } else {
+            assert!(false)
+        }
+    }
+
+    // select.
+    {
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select * from default.d")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+----+",
+                "| a | b  |",
+                "+---+----+",
+                "| 0 | 42 |",
+                "| 1 | 42 |",
+                "| 2 | 42 |",
+                "+---+----+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // `a` has no default value, it can not be omitted.
+    {
+        if let PlanNode::InsertInto(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("insert into default.d(b) values(3)")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+            if let Err(e) = result {
+                assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+                assert_eq!(
+                    e.message(),
+                    "Column `a` of table default.d has no default value, it can not be omitted"
+                );
+            }
+        } else {
+            assert!(false)
+        }
+    }
+
+    // The default value must be a constant.
+    {
+        let result = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.e(a UInt64, b UInt64 default a + 1) Engine = Memory",
+        );
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.code(), ErrorCode::BadArguments("").code());
+            assert_eq!(
+                e.message(),
+                "Default value of column `b` must be a constant expression, got a + 1"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_insert_into_select_failed_interpreter() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let ctx = crate::tests::try_create_context_with_data_path(dir.path().to_str().unwrap())?;
+    // the blocks of the select are read one after another
+    ctx.get_settings().set_max_threads(1)?;
+
+    async fn execute_sql(ctx: &DatabendQueryContextRef, sql: &str) -> Result<()> {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+        Ok(())
+    }
+
+    async fn create_fuse_table(ctx: &DatabendQueryContextRef, name: &str) -> Result<()> {
+        let sql = format!("create table default.{}(a Int32) Engine = Fuse", name);
+        if let PlanNode::CreateTable(mut plan) =
+            PlanParser::create(ctx.clone()).build_from_sql(&sql)?
+        {
+            plan.options.insert(
+                TBL_OPT_KEY_STORAGE_SCHEME.to_string(),
+                "LOCAL_FS".to_string(),
+            );
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+            let _ = executor.execute().await?;
+        }
+        Ok(())
+    }
+
+    // The source table has three blocks, the file of the last one is lost, the select fails
+    // at it, after the other blocks are read.
+    create_fuse_table(&ctx, "s").await?;
+    for sql in [
+        "insert into default.s values(1),(2)",
+        "insert into default.s values(3)",
+        "insert into default.s values(4),(5),(6)",
+    ] {
+        execute_sql(&ctx, sql).await?;
+    }
+    {
+        let table = ctx.get_table("default", "s")?;
+        let table = table.raw().as_any().downcast_ref::<FuseTable>().unwrap();
+        let plan = table.read_plan(ctx.clone(), None, Some(1))?;
+        assert_eq!(plan.parts.len(), 3);
+        table
+            .data_accessor()?
+            .remove(&block_location(&plan.parts[2].name))
+            .await?;
+    }
+
+    // Nothing of the failed select is inserted into the memory table.
+    execute_sql(&ctx, "create table default.m(a Int32) Engine = Memory").await?;
+    let result = execute_sql(&ctx, "insert into default.m select a from default.s").await;
+    assert!(result.is_err());
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("select * from default.m")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let result = executor.execute().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!(result.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+    }
+
+    // Nor is a snapshot committed into the fuse table.
+    create_fuse_table(&ctx, "f").await?;
+    let result = execute_sql(&ctx, "insert into default.f select a from default.s").await;
+    assert!(result.is_err());
+    {
+        let table = ctx.get_table("default", "f")?;
+        let table = table.raw().as_any().downcast_ref::<FuseTable>().unwrap();
+        assert!(table.table_snapshot(&ctx)?.is_none());
+    }
+
+    Ok(())
+}
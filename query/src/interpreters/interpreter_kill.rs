@@ -44,21 +44,29 @@ impl Interpreter for KillInterpreter {
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let id = &self.plan.id;
-        match self.ctx.get_sessions_manager().get_session(id) {
-            None => Err(ErrorCode::UnknownSession(format!(
-                "Not found session id {}",
-                id
-            ))),
-            Some(kill_session) if self.plan.kill_connection => {
-                kill_session.force_kill_session();
-                let schema = Arc::new(DataSchema::empty());
-                Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+        let sessions = self.ctx.get_sessions_manager();
+        let kill_session = match sessions.get_session(id) {
+            Some(kill_session) => kill_session,
+            // KILL QUERY takes the id of the query too, e.g. the query_id in system.processes
+            None if !self.plan.kill_connection => {
+                sessions.get_session_by_query_id(id).ok_or_else(|| {
+                    ErrorCode::UnknownSession(format!("Not found session or query id {}", id))
+                })?
             }
-            Some(kill_session) => {
-                kill_session.force_kill_query();
-                let schema = Arc::new(DataSchema::empty());
-                Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+            None => {
+                return Err(ErrorCode::UnknownSession(format!(
+                    "Not found session id {}",
+                    id
+                )))
             }
+        };
+
+        match self.plan.kill_connection {
+            true => kill_session.force_kill_session(),
+            false => kill_session.force_kill_query(),
         }
+
+        let schema = Arc::new(DataSchema::empty());
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
     }
 }
@@ -0,0 +1,80 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::interpreters::*;
+use crate::sql::*;
+use crate::tests::SessionManagerBuilder;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_kill_query_interpreter() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().build()?;
+    let query_session = sessions.create_session("TestSession")?;
+    let kill_session = sessions.create_session("TestSession")?;
+
+    // a slow query, in flight until it is killed
+    let slow_sql = "select sum(number) from numbers_mt(10000000000)";
+    let query_ctx = query_session.create_context().await?;
+    query_ctx.attach_query_str(slow_sql);
+    let plan = PlanParser::create(query_ctx.clone()).build_from_sql(slow_sql)?;
+    let interpreter = InterpreterFactory::get(query_ctx.clone(), plan)?;
+    let slow_stream = interpreter.execute().await?;
+    let query_id = query_ctx.get_id();
+    assert_eq!(
+        Some(query_id.clone()),
+        query_session.process_info().query_id
+    );
+
+    // killed by the query id, from another session
+    let kill_sql = format!("KILL QUERY \"{}\"", query_id);
+    let kill_ctx = kill_session.create_context().await?;
+    let plan = PlanParser::create(kill_ctx.clone()).build_from_sql(&kill_sql)?;
+    let executor = InterpreterFactory::get(kill_ctx.clone(), plan)?;
+    assert_eq!(executor.name(), "KillInterpreter");
+    executor.execute().await?.try_collect::<Vec<_>>().await?;
+
+    // the query stops with an error
+    let collected =
+        tokio::time::timeout(Duration::from_secs(30), slow_stream.try_collect::<Vec<_>>())
+            .await
+            .map_err(|_| ErrorCode::Timeout("the killed query is still running"))?;
+    assert_eq!(
+        Some(ErrorCode::AbortedQuery("").code()),
+        collected.err().map(|e| e.code())
+    );
+
+    // the session is freed for the next query
+    drop(interpreter);
+    drop(query_ctx);
+    let info = query_session.process_info();
+    assert_eq!(None, info.query_id);
+    assert_eq!("Idle", info.state);
+
+    // the query is not running any more
+    let plan = PlanParser::create(kill_ctx.clone()).build_from_sql(&kill_sql)?;
+    let executor = InterpreterFactory::get(kill_ctx, plan)?;
+    let res = executor.execute().await;
+    assert_eq!(
+        Some(ErrorCode::UnknownSession("").code()),
+        res.err().map(|e| e.code())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_catalog::TableSnapshot;
+use common_datablocks::assert_blocks_sorted_eq;
+use common_exception::Result;
+use common_planners::*;
+
+use crate::datasources::table::fuse::read_block;
+use crate::datasources::table::fuse::read_segment_async;
+use crate::datasources::table::fuse::FuseTable;
+use crate::datasources::table::fuse::TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD;
+use crate::datasources::table::fuse::TBL_OPT_KEY_STORAGE_SCHEME;
+use crate::interpreters::*;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sql::*;
+
+async fn execute_sql(ctx: &DatabendQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let _ = executor.execute().await?;
+    Ok(())
+}
+
+// the latest snapshot of the fuse table default.a, as committed into the catalog
+fn table_snapshot(ctx: &DatabendQueryContextRef) -> Result<TableSnapshot> {
+    let table = ctx.get_table("default", "a")?;
+    let table = table.raw().as_any().downcast_ref::<FuseTable>().unwrap();
+    Ok(table.table_snapshot(ctx)?.unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_optimize_table_interpreter() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let ctx = crate::tests::try_create_context_with_data_path(dir.path().to_str().unwrap())?;
+
+    // Create table, whose blocks smaller than 1 MiB are merged by compaction.
+    {
+        if let PlanNode::CreateTable(mut plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.a(a Int32) Engine = Fuse")?
+        {
+            plan.options.insert(
+                TBL_OPT_KEY_STORAGE_SCHEME.to_string(),
+                "LOCAL_FS".to_string(),
+            );
+            plan.options.insert(
+                TBL_OPT_KEY_COMPACT_BLOCK_SIZE_THRESHOLD.to_string(),
+                (1024 * 1024).to_string(),
+            );
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Each insert commits a snapshot, which adds a segment of one block.
+    for sql in [
+        "insert into default.a values(1),(2)",
+        "insert into default.a values(3)",
+        "insert into default.a values(4),(5),(6)",
+    ] {
+        execute_sql(&ctx, sql).await?;
+    }
+    let snapshot = table_snapshot(&ctx)?;
+    assert_eq!(snapshot.segments.len(), 3);
+    assert_eq!(snapshot.summary.block_count, 3);
+    assert_eq!(snapshot.summary.row_count, 6);
+
+    // Optimize.
+    {
+        if let PlanNode::OptimizeTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("optimize table default.a")?
+        {
+            let executor = OptimizeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "OptimizeTableInterpreter");
+            let _ = executor.execute().await?;
+        }
+    }
+
+    let compacted = table_snapshot(&ctx)?;
+    assert_eq!(compacted.prev_snapshot_id, Some(snapshot.snapshot_id));
+    assert_eq!(compacted.segments.len(), 1);
+    assert_eq!(compacted.summary.block_count, 1);
+    assert_eq!(compacted.summary.row_count, 6);
+
+    let table = ctx.get_table("default", "a")?;
+    let table = table.raw().as_any().downcast_ref::<FuseTable>().unwrap();
+    let da = table.data_accessor()?;
+    let segment = read_segment_async(da.clone(), &compacted.segments[0]).await?;
+    let arrow_schema = table.schema()?.to_arrow();
+    let block = read_block(
+        &segment.blocks[0].location.location,
+        da,
+        &[0],
+        &arrow_schema,
+    )
+    .await?;
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "| 5 |", "| 6 |", "+---+",
+    ];
+    assert_blocks_sorted_eq(expected, &[block]);
+
+    // Nothing left to compact, the table stays as it is.
+    execute_sql(&ctx, "optimize table default.a").await?;
+    assert_eq!(table_snapshot(&ctx)?.snapshot_id, compacted.snapshot_id);
+
+    // The tables which can not be optimized.
+    execute_sql(&ctx, "create table default.b(a Int32) Engine = Memory").await?;
+    let res = execute_sql(&ctx, "optimize table default.b").await;
+    assert_eq!(
+        res.err().map(|e| e.code()),
+        Some(common_exception::ErrorCode::UnImplement("").code())
+    );
+
+    Ok(())
+}
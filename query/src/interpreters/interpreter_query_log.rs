@@ -0,0 +1,144 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+use crate::sessions::QueryLogRecord;
+
+/// Records the query executed by the `inner` interpreter into the query log, once it completes.
+///
+/// A query completes when its result stream is exhausted, fails, or is dropped before the end.
+pub struct QueryLogInterpreter {
+    ctx: DatabendQueryContextRef,
+    kind: String,
+    inner: InterpreterPtr,
+}
+
+impl QueryLogInterpreter {
+    pub fn create(
+        ctx: DatabendQueryContextRef,
+        kind: String,
+        inner: InterpreterPtr,
+    ) -> InterpreterPtr {
+        Arc::new(QueryLogInterpreter { ctx, kind, inner })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for QueryLogInterpreter {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let logger = QueryLogger {
+            ctx: self.ctx.clone(),
+            kind: self.kind.clone(),
+            start_time: SystemTime::now(),
+            start: Instant::now(),
+        };
+
+        match self.inner.execute().await {
+            Ok(stream) => Ok(Box::pin(QueryLogStream {
+                inner: stream,
+                logger: Some(logger),
+            })),
+            Err(error) => {
+                logger.finish(Some(error.message()));
+                Err(error)
+            }
+        }
+    }
+
+    fn schema(&self) -> DataSchemaRef {
+        self.inner.schema()
+    }
+}
+
+struct QueryLogger {
+    ctx: DatabendQueryContextRef,
+    kind: String,
+    start_time: SystemTime,
+    start: Instant,
+}
+
+impl QueryLogger {
+    fn finish(self, error: Option<String>) {
+        let progress = self.ctx.get_progress_value();
+        let record = QueryLogRecord {
+            query_id: self.ctx.get_id(),
+            kind: self.kind,
+            sql: self.ctx.get_query_str(),
+            start_time: self.start_time,
+            duration: self.start.elapsed(),
+            read_rows: progress.read_rows,
+            read_bytes: progress.read_bytes,
+            error,
+        };
+        self.ctx.get_sessions_manager().get_query_log().push(record);
+    }
+}
+
+struct QueryLogStream {
+    inner: SendableDataBlockStream,
+    // taken once the query is recorded
+    logger: Option<QueryLogger>,
+}
+
+impl Stream for QueryLogStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let next = self.inner.poll_next_unpin(cx);
+        match &next {
+            Poll::Ready(None) => {
+                if let Some(logger) = self.logger.take() {
+                    logger.finish(None);
+                }
+            }
+            Poll::Ready(Some(Err(error))) => {
+                if let Some(logger) = self.logger.take() {
+                    logger.finish(Some(error.message()));
+                }
+            }
+            _ => {}
+        }
+        next
+    }
+}
+
+impl Drop for QueryLogStream {
+    fn drop(&mut self) {
+        if let Some(logger) = self.logger.take() {
+            logger.finish(Some("Query is cancelled before completion".to_string()));
+        }
+    }
+}
@@ -17,11 +17,14 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::Context;
+use std::time::Duration;
 
+use common_base::tokio;
 use common_base::tokio::macros::support::Pin;
 use common_base::tokio::macros::support::Poll;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_management::NodeInfo;
 use common_planners::SelectPlan;
@@ -77,6 +80,93 @@ impl Interpreter for SelectInterpreter {
 
 type Scheduled = HashMap<String, Arc<NodeInfo>>;
 
+/// Issues the action of a stage to the node it is scheduled to.
+#[async_trait::async_trait]
+pub trait StageDispatcher: Send + Sync {
+    async fn dispatch(&self, node: &Arc<NodeInfo>, action: &FlightAction) -> Result<()>;
+}
+
+struct FlightStageDispatcher {
+    ctx: DatabendQueryContextRef,
+}
+
+#[async_trait::async_trait]
+impl StageDispatcher for FlightStageDispatcher {
+    async fn dispatch(&self, node: &Arc<NodeInfo>, action: &FlightAction) -> Result<()> {
+        let config = self.ctx.get_config();
+        let cluster = self.ctx.get_cluster();
+        let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
+
+        // connects for each dispatch, a retry does not reuse the connection failed
+        let mut flight_client = cluster.create_node_conn(&node.id, &config).await?;
+        flight_client.execute_action(action.clone(), timeout).await
+    }
+}
+
+/// The delay before the first re-issue of a stage, it doubles with each retry.
+const STAGE_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+const STAGE_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Dispatches the stages one by one. A stage failed to reach its node (`CannotConnectNode`) is
+/// re-issued up to `max_retries` times with a growing delay, any other error fails at once.
+/// The nodes of the succeeded stages are recorded into `scheduled`, so that they are cancelled
+/// if the query fails, a succeeded stage is never issued again.
+pub async fn dispatch_stages(
+    dispatcher: &dyn StageDispatcher,
+    actions: Vec<(Arc<NodeInfo>, FlightAction)>,
+    max_retries: u64,
+    scheduled: &mut Scheduled,
+) -> Result<()> {
+    for (node, action) in actions {
+        let mut retries = 0;
+        let mut backoff = STAGE_RETRY_BACKOFF;
+        loop {
+            match dispatcher.dispatch(&node, &action).await {
+                Ok(()) => break,
+                Err(cause)
+                    if retries < max_retries
+                        && cause.code() == ErrorCode::CannotConnectNode("").code() =>
+                {
+                    retries += 1;
+                    tracing::warn!(
+                        "Failed to dispatch stage to {}, retry {}/{} in {:?}, cause: {}",
+                        node.id,
+                        retries,
+                        max_retries,
+                        backoff,
+                        cause
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(STAGE_RETRY_MAX_BACKOFF);
+                }
+                Err(cause) => return Err(cause),
+            }
+        }
+        scheduled.insert(node.id.clone(), node.clone());
+    }
+    Ok(())
+}
+
+/// As `dispatch_stages`, but gives up with `ErrorCode::Timeout` once dispatching all the stages
+/// takes longer than `timeout`, e.g. a node never responds. The stages dispatched by then are left
+/// in `scheduled`, to be cancelled.
+pub async fn dispatch_stages_within(
+    dispatcher: &dyn StageDispatcher,
+    actions: Vec<(Arc<NodeInfo>, FlightAction)>,
+    max_retries: u64,
+    timeout: Duration,
+    scheduled: &mut Scheduled,
+) -> Result<()> {
+    let dispatching = dispatch_stages(dispatcher, actions, max_retries, scheduled);
+    match tokio::time::timeout(timeout, dispatching).await {
+        Ok(res) => res,
+        Err(_) => Err(ErrorCode::Timeout(format!(
+            "Query is cancelled, as dispatching its stages exceeds {:?}",
+            timeout
+        ))),
+    }
+}
+
 impl SelectInterpreter {
     async fn schedule_query(&self, scheduled: &mut Scheduled) -> Result<SendableDataBlockStream> {
         let optimized_plan = Optimizers::create(self.ctx.clone()).optimize(&self.select.input)?;
@@ -85,15 +175,24 @@ impl SelectInterpreter {
         let scheduled_tasks = scheduler.reschedule(&optimized_plan)?;
         let remote_stage_actions = scheduled_tasks.get_tasks()?;
 
-        let config = self.ctx.get_config();
-        let cluster = self.ctx.get_cluster();
-        let timeout = self.ctx.get_settings().get_flight_client_timeout()?;
-        for (node, action) in remote_stage_actions {
-            let mut flight_client = cluster.create_node_conn(&node.id, &config).await?;
-            let executing_action = flight_client.execute_action(action.clone(), timeout);
-
-            executing_action.await?;
-            scheduled.insert(node.id.clone(), node.clone());
+        let dispatcher = FlightStageDispatcher {
+            ctx: self.ctx.clone(),
+        };
+        let settings = self.ctx.get_settings();
+        let max_retries = settings.get_max_remote_stage_retries()?;
+        match settings.get_max_stage_dispatch_time()? {
+            0 => dispatch_stages(&dispatcher, remote_stage_actions, max_retries, scheduled).await?,
+            secs => {
+                let timeout = Duration::from_secs(secs);
+                dispatch_stages_within(
+                    &dispatcher,
+                    remote_stage_actions,
+                    max_retries,
+                    timeout,
+                    scheduled,
+                )
+                .await?
+            }
         }
 
         let pipeline_builder = PipelineBuilder::create(self.ctx.clone());
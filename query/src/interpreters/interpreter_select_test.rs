@@ -66,3 +66,171 @@ async fn test_select_interpreter() -> anyhow::Result<()> {
     Ok(())
 }
 */
+
+mod remote_stages {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use common_base::tokio;
+    use common_exception::ErrorCode;
+    use common_exception::Result;
+    use common_infallible::Mutex;
+    use common_management::NodeInfo;
+
+    use crate::api::CancelAction;
+    use crate::api::FlightAction;
+    use crate::interpreters::interpreter_select::dispatch_stages;
+    use crate::interpreters::interpreter_select::dispatch_stages_within;
+    use crate::interpreters::interpreter_select::StageDispatcher;
+
+    // Fails the first `failures` dispatches to each node with `error`, as a node with transient errors.
+    struct FlakyDispatcher {
+        failures: HashMap<String, usize>,
+        error: fn(String) -> ErrorCode,
+        dispatched: Mutex<Vec<String>>,
+    }
+
+    impl FlakyDispatcher {
+        fn create(node: &str, failures: usize) -> FlakyDispatcher {
+            FlakyDispatcher {
+                failures: [(node.to_string(), failures)].iter().cloned().collect(),
+                error: ErrorCode::CannotConnectNode,
+                dispatched: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl StageDispatcher for FlakyDispatcher {
+        async fn dispatch(&self, node: &Arc<NodeInfo>, _action: &FlightAction) -> Result<()> {
+            let mut dispatched = self.dispatched.lock();
+            dispatched.push(node.id.clone());
+            let attempts = dispatched.iter().filter(|id| **id == node.id).count();
+            match self.failures.get(&node.id) {
+                Some(failures) if attempts <= *failures => {
+                    Err((self.error)(format!("Failed on {}", node.id)))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn stage_actions() -> Vec<(Arc<NodeInfo>, FlightAction)> {
+        ["node1", "node2", "node3"]
+            .iter()
+            .map(|id| {
+                let node = Arc::new(NodeInfo::create(id.to_string(), 0, "".to_string()));
+                let action = FlightAction::CancelAction(CancelAction {
+                    query_id: "query".to_string(),
+                });
+                (node, action)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_stages_retry() -> Result<()> {
+        let dispatcher = FlakyDispatcher::create("node2", 1);
+
+        let mut scheduled = HashMap::new();
+        dispatch_stages(&dispatcher, stage_actions(), 2, &mut scheduled).await?;
+
+        // only the failed stage is issued again
+        assert_eq!(*dispatcher.dispatched.lock(), vec![
+            "node1", "node2", "node2", "node3"
+        ]);
+        assert_eq!(scheduled.len(), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_stages_retries_exhausted() -> Result<()> {
+        let dispatcher = FlakyDispatcher::create("node2", 3);
+
+        let mut scheduled = HashMap::new();
+        let result = dispatch_stages(&dispatcher, stage_actions(), 2, &mut scheduled).await;
+        assert_eq!(
+            result.err().map(|e| e.code()),
+            Some(ErrorCode::CannotConnectNode("").code())
+        );
+
+        // gives up after 2 retries, the succeeded stage is left to be cancelled
+        assert_eq!(*dispatcher.dispatched.lock(), vec![
+            "node1", "node2", "node2", "node2"
+        ]);
+        assert_eq!(scheduled.keys().collect::<Vec<_>>(), vec!["node1"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_stages_no_retry_on_remote_error() -> Result<()> {
+        let dispatcher = FlakyDispatcher {
+            error: ErrorCode::LogicalError,
+            ..FlakyDispatcher::create("node2", 1)
+        };
+
+        let mut scheduled = HashMap::new();
+        let result = dispatch_stages(&dispatcher, stage_actions(), 2, &mut scheduled).await;
+        assert_eq!(
+            result.err().map(|e| e.code()),
+            Some(ErrorCode::LogicalError("").code())
+        );
+
+        // the node received the stage and failed it, it is not issued again
+        assert_eq!(*dispatcher.dispatched.lock(), vec!["node1", "node2"]);
+        assert_eq!(scheduled.keys().collect::<Vec<_>>(), vec!["node1"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_stages_retry_backoff() -> Result<()> {
+        let dispatcher = FlakyDispatcher::create("node2", 2);
+
+        let mut scheduled = HashMap::new();
+        let start = Instant::now();
+        dispatch_stages(&dispatcher, stage_actions(), 2, &mut scheduled).await?;
+
+        // waits 100ms before the first retry and 200ms before the second
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+        assert_eq!(scheduled.len(), 3);
+        Ok(())
+    }
+
+    // Never responds to the dispatches to `node2`.
+    struct HungDispatcher;
+
+    #[async_trait::async_trait]
+    impl StageDispatcher for HungDispatcher {
+        async fn dispatch(&self, node: &Arc<NodeInfo>, _action: &FlightAction) -> Result<()> {
+            if node.id == "node2" {
+                futures::future::pending::<()>().await;
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_stages_timeout() -> Result<()> {
+        let mut scheduled = HashMap::new();
+        let start = Instant::now();
+        let timeout = Duration::from_millis(200);
+        let result =
+            dispatch_stages_within(&HungDispatcher, stage_actions(), 2, timeout, &mut scheduled)
+                .await;
+
+        let elapsed = start.elapsed();
+        assert!(elapsed >= timeout, "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+        assert_eq!(
+            result.err().map(|e| e.code()),
+            Some(ErrorCode::Timeout("").code())
+        );
+
+        // the stage dispatched before the hung one is left to be cancelled
+        assert_eq!(scheduled.keys().collect::<Vec<_>>(), vec!["node1"]);
+        Ok(())
+    }
+}
@@ -24,6 +24,7 @@ use common_streams::SendableDataBlockStream;
 
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
+use crate::sessions::parse_timezone;
 use crate::sessions::DatabendQueryContextRef;
 
 pub struct SettingInterpreter {
@@ -53,6 +54,12 @@ impl Interpreter for SettingInterpreter {
                     let threads: u64 = var.value.parse()?;
                     self.ctx.get_settings().set_max_threads(threads)?;
                 }
+                "timezone" => {
+                    let tz = parse_timezone(&var.value)?;
+                    self.ctx
+                        .get_settings()
+                        .set_timezone(tz.name().to_string())?;
+                }
                 _ => {
                     self.ctx
                         .get_settings()
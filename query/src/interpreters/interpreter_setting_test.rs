@@ -40,6 +40,30 @@ async fn test_setting_interpreter() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_setting_interpreter_timezone() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    assert_eq!(ctx.get_settings().get_timezone()?, "UTC");
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("set timezone='Asia/Shanghai'")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let mut stream = executor.execute().await?;
+    while let Some(_block) = stream.next().await {}
+    assert_eq!(ctx.get_settings().get_timezone()?, "Asia/Shanghai");
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("set timezone='Mars/Olympus'")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    if let Err(e) = executor.execute().await {
+        let expect = "Code: 6, displayText = Unknown timezone: \"Mars/Olympus\".";
+        assert_eq!(expect, format!("{}", e));
+    } else {
+        assert!(false);
+    }
+    assert_eq!(ctx.get_settings().get_timezone()?, "Asia/Shanghai");
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_setting_interpreter_error() -> Result<()> {
     let ctx = crate::tests::try_create_context()?;
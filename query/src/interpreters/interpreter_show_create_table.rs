@@ -63,7 +63,15 @@ impl Interpreter for ShowCreateTableInterpreter {
 
         let mut table_info = format!("CREATE TABLE `{}` (\n", name);
         for field in schema.fields().iter() {
-            let column = format!("  `{}` {},\n", field.name(), field.data_type());
+            let column = match field.default_expr() {
+                Some(default_expr) => format!(
+                    "  `{}` {} DEFAULT {},\n",
+                    field.name(),
+                    field.data_type(),
+                    default_expr
+                ),
+                None => format!("  `{}` {},\n", field.name(), field.data_type()),
+            };
             table_info.push_str(column.as_str());
         }
         let table_engine = format!(") ENGINE={}", engine);
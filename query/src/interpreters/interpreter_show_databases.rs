@@ -0,0 +1,68 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_exception::Result;
+use common_planners::ShowDatabasesPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct ShowDatabasesInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: ShowDatabasesPlan,
+}
+
+impl ShowDatabasesInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: ShowDatabasesPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(ShowDatabasesInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for ShowDatabasesInterpreter {
+    fn name(&self) -> &str {
+        "ShowDatabasesInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let mut databases = self
+            .ctx
+            .get_catalog()
+            .get_databases_like(self.plan.like.as_deref())?;
+        databases.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let names: Vec<&[u8]> = databases.iter().map(|x| x.name().as_bytes()).collect();
+        let engines: Vec<&[u8]> = databases.iter().map(|x| x.engine().as_bytes()).collect();
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(names),
+            Series::new(engines),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::stream::StreamExt;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_show_databases_interpreter() -> Result<()> {
+    common_tracing::init_default_ut_tracing();
+
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create database db1 Engine = default",
+        "create database db2 Engine = example",
+        "create database other",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let mut stream = executor.execute().await?;
+        while let Some(_block) = stream.next().await {}
+    }
+
+    // show databases like
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("show databases like 'db%'")?;
+        if let PlanNode::ShowDatabases(plan) = plan {
+            let executor = ShowDatabasesInterpreter::try_create(ctx.clone(), plan)?;
+            assert_eq!(executor.name(), "ShowDatabasesInterpreter");
+
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+------+---------+",
+                "| name | engine  |",
+                "+------+---------+",
+                "| db1  | default |",
+                "| db2  | example |",
+                "+------+---------+",
+            ];
+            common_datablocks::assert_blocks_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // show databases
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("show databases")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+---------+---------+",
+            "| name    | engine  |",
+            "+---------+---------+",
+            "| db1     | default |",
+            "| db2     | example |",
+            "| default | Default |",
+            "| other   | Default |",
+            "| system  | local   |",
+            "+---------+---------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
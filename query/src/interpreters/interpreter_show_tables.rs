@@ -0,0 +1,66 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_exception::Result;
+use common_planners::ShowTablesPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct ShowTablesInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: ShowTablesPlan,
+}
+
+impl ShowTablesInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: ShowTablesPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(ShowTablesInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for ShowTablesInterpreter {
+    fn name(&self) -> &str {
+        "ShowTablesInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let database = self.ctx.get_catalog().get_database(&self.plan.db)?;
+        let tables = database.get_tables_like(self.plan.like.as_deref())?;
+
+        let mut names = tables
+            .iter()
+            .map(|tbl| tbl.raw().name().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        let names: Vec<&[u8]> = names.iter().map(|x| x.as_bytes()).collect();
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(names)]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
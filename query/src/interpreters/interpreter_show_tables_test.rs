@@ -0,0 +1,90 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::stream::StreamExt;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_show_tables_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    for sql in [
+        "create database db1",
+        "create table db1.t1(a int) Engine = Null",
+        "create table db1.t2(a int) Engine = Null",
+        "create table db1.other(a int) Engine = Null",
+        "create table default.a(a int) Engine = Null",
+    ] {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let mut stream = executor.execute().await?;
+        while let Some(_block) = stream.next().await {}
+    }
+
+    // show tables of the current database
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("show tables")?;
+        if let PlanNode::ShowTables(plan) = plan {
+            let executor = ShowTablesInterpreter::try_create(ctx.clone(), plan)?;
+            assert_eq!(executor.name(), "ShowTablesInterpreter");
+
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["+------+", "| name |", "+------+", "| a    |", "+------+"];
+            common_datablocks::assert_blocks_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // show tables from
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("show tables from db1")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+-------+",
+            "| name  |",
+            "+-------+",
+            "| other |",
+            "| t1    |",
+            "| t2    |",
+            "+-------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    // show tables like
+    {
+        ctx.set_current_database("db1".to_string())?;
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("show tables like 't%'")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+------+", "| name |", "+------+", "| t1   |", "| t2   |", "+------+",
+        ];
+        common_datablocks::assert_blocks_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
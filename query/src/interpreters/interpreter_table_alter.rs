@@ -0,0 +1,89 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AlterTableAddColumnPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::catalogs::Catalog;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct AlterTableInterpreter {
+    ctx: DatabendQueryContextRef,
+    plan: AlterTableAddColumnPlan,
+}
+
+impl AlterTableInterpreter {
+    pub fn try_create(
+        ctx: DatabendQueryContextRef,
+        plan: AlterTableAddColumnPlan,
+    ) -> Result<InterpreterPtr> {
+        Ok(Arc::new(AlterTableInterpreter { ctx, plan }))
+    }
+
+    fn validate(&self) -> Result<()> {
+        let table = self.ctx.get_table(&self.plan.db, &self.plan.table)?;
+        let schema = table.raw().schema()?;
+        let field = &self.plan.field;
+
+        if schema.column_with_name(field.name()).is_some() {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "column {} already exists in table {}.{}",
+                field.name(),
+                self.plan.db,
+                self.plan.table
+            )));
+        }
+
+        // The existing rows have no value for the new column, they read it as its default value,
+        // or NULL if it has none.
+        if !field.is_nullable() && field.default_expr().is_none() {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "column {} must be nullable or have a default value, as the existing rows of table {}.{} have no value for it",
+                field.name(),
+                self.plan.db,
+                self.plan.table
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterTableInterpreter {
+    fn name(&self) -> &str {
+        "AlterTableInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.validate()?;
+
+        let datasource = self.ctx.get_catalog();
+        let database = datasource.get_database(self.plan.db.as_str())?;
+        database.add_table_column(self.plan.clone())?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_exception::Result;
+use common_planners::*;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_alter_table_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create table.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.a(a bigint, b int) Engine = Null")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Add column.
+    {
+        if let PlanNode::AlterTableAddColumn(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("alter table a add column c varchar(255)")?
+        {
+            let executor = AlterTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "AlterTableInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // The new column is visible.
+    {
+        if let PlanNode::DescribeTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("describe a")?
+        {
+            let executor = DescribeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-------+--------+------+---------+",
+                "| Field | Type   | Null | Default |",
+                "+-------+--------+------+---------+",
+                "| a     | Int64  | NO   | NULL    |",
+                "| b     | Int32  | NO   | NULL    |",
+                "| c     | String | YES  | NULL    |",
+                "+-------+--------+------+---------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // Duplicate column name.
+    {
+        if let PlanNode::AlterTableAddColumn(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("alter table a add column b int")?
+        {
+            let executor = AlterTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert!(result.is_err());
+            assert_eq!(
+                result.err().unwrap().message(),
+                "column b already exists in table default.a"
+            );
+        } else {
+            assert!(false)
+        }
+    }
+
+    // Not nullable, the existing rows have no value for it.
+    {
+        if let PlanNode::AlterTableAddColumn(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("alter table a add column d int not null")?
+        {
+            let executor = AlterTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert!(executor.execute().await.is_err());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // Not nullable, the existing rows read the default value.
+    {
+        if let PlanNode::AlterTableAddColumn(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("alter table a add column e int not null default 1 + 1")?
+        {
+            let executor = AlterTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        } else {
+            assert!(false)
+        }
+
+        if let PlanNode::DescribeTable(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("describe a")?
+        {
+            let executor = DescribeTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+-------+--------+------+---------+",
+                "| Field | Type   | Null | Default |",
+                "+-------+--------+------+---------+",
+                "| a     | Int64  | NO   | NULL    |",
+                "| b     | Int32  | NO   | NULL    |",
+                "| c     | String | YES  | NULL    |",
+                "| e     | Int32  | NO   | 1 + 1   |",
+                "+-------+--------+------+---------+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    Ok(())
+}
@@ -14,6 +14,8 @@
 
 use std::sync::Arc;
 
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
 use common_exception::Result;
 use common_planners::TruncateTablePlan;
 use common_streams::DataBlockStream;
@@ -47,14 +49,15 @@ impl Interpreter for TruncateTableInterpreter {
         let table = self
             .ctx
             .get_table(self.plan.db.as_str(), self.plan.table.as_str())?;
-        table
+        let affected_rows = table
             .raw()
             .truncate(self.ctx.clone(), self.plan.clone())
             .await?;
-        Ok(Box::pin(DataBlockStream::create(
-            self.plan.schema(),
-            None,
-            vec![],
-        )))
+
+        let schema = self.plan.schema();
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![
+            affected_rows as u64,
+        ])]);
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
     }
 }
@@ -76,7 +76,13 @@ async fn test_truncate_table_interpreter() -> Result<()> {
 
             let stream = executor.execute().await?;
             let result = stream.try_collect::<Vec<_>>().await?;
-            let expected = vec!["++", "++"];
+            let expected = vec![
+                "+---------------+",
+                "| affected_rows |",
+                "+---------------+",
+                "| 1             |",
+                "+---------------+",
+            ];
             common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
         } else {
             assert!(false)
@@ -21,12 +21,24 @@ mod interpreter_describe_table_test;
 #[cfg(test)]
 mod interpreter_explain_test;
 #[cfg(test)]
+mod interpreter_insert_into_test;
+#[cfg(test)]
+mod interpreter_kill_test;
+#[cfg(test)]
+mod interpreter_optimize_table_test;
+#[cfg(test)]
 mod interpreter_select_test;
 #[cfg(test)]
 mod interpreter_setting_test;
 #[cfg(test)]
 mod interpreter_show_create_table_test;
 #[cfg(test)]
+mod interpreter_show_databases_test;
+#[cfg(test)]
+mod interpreter_show_tables_test;
+#[cfg(test)]
+mod interpreter_table_alter_test;
+#[cfg(test)]
 mod interpreter_table_create_test;
 #[cfg(test)]
 mod interpreter_table_drop_test;
@@ -45,9 +57,14 @@ mod interpreter_explain;
 mod interpreter_factory;
 mod interpreter_insert_into;
 mod interpreter_kill;
+mod interpreter_optimize_table;
+mod interpreter_query_log;
 mod interpreter_select;
 mod interpreter_setting;
 mod interpreter_show_create_table;
+mod interpreter_show_databases;
+mod interpreter_show_tables;
+mod interpreter_table_alter;
 mod interpreter_table_create;
 mod interpreter_table_drop;
 mod interpreter_truncate_table;
@@ -63,9 +80,14 @@ pub use interpreter_describe_table::DescribeTableInterpreter;
 pub use interpreter_explain::ExplainInterpreter;
 pub use interpreter_factory::InterpreterFactory;
 pub use interpreter_insert_into::InsertIntoInterpreter;
+pub use interpreter_optimize_table::OptimizeTableInterpreter;
+pub use interpreter_query_log::QueryLogInterpreter;
 pub use interpreter_select::SelectInterpreter;
 pub use interpreter_setting::SettingInterpreter;
 pub use interpreter_show_create_table::ShowCreateTableInterpreter;
+pub use interpreter_show_databases::ShowDatabasesInterpreter;
+pub use interpreter_show_tables::ShowTablesInterpreter;
+pub use interpreter_table_alter::AlterTableInterpreter;
 pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
 pub use interpreter_truncate_table::TruncateTableInterpreter;
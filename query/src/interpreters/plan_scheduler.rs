@@ -27,16 +27,17 @@ use common_planners::EmptyPlan;
 use common_planners::Expression;
 use common_planners::ExpressionPlan;
 use common_planners::Expressions;
+use common_planners::Extras;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
 use common_planners::Partitions;
 use common_planners::PlanNode;
+use common_planners::PlanRewriter;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::RemotePlan;
-use common_planners::ScanPlan;
 use common_planners::SelectPlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
@@ -109,7 +110,10 @@ impl PlanScheduler {
         let mut tasks = Tasks::create(context);
 
         match cluster.is_empty() {
-            true => tasks.finalize(plan),
+            true => {
+                let mut planner = StandaloneSourcePlanner::create(self.query_context.clone());
+                tasks.finalize(&planner.rewrite_plan_node(plan)?)
+            }
             false => {
                 self.visit_plan_node(plan, &mut tasks)?;
                 tasks.finalize(&self.nodes_plan[self.local_pos])
@@ -118,6 +122,44 @@ impl PlanScheduler {
     }
 }
 
+/// Re-plans the reads of the non-local tables with the push downs of the optimizers, by which the
+/// tables prune their parts, the reads are planned before the optimizers run.
+/// The reads of a cluster are re-planned as they are scheduled, see `visit_data_source`.
+struct StandaloneSourcePlanner {
+    ctx: DatabendQueryContextRef,
+}
+
+impl StandaloneSourcePlanner {
+    fn create(ctx: DatabendQueryContextRef) -> StandaloneSourcePlanner {
+        StandaloneSourcePlanner { ctx }
+    }
+}
+
+impl PlanRewriter for StandaloneSourcePlanner {
+    fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
+        if plan.tbl_args.is_some() || plan.push_downs.is_none() {
+            return Ok(PlanNode::ReadSource(plan.clone()));
+        }
+
+        let table_meta = self
+            .ctx
+            .get_table(&plan.table_info.db, &plan.table_info.name)?;
+        let table = table_meta.raw();
+        match table.is_local() {
+            true => Ok(PlanNode::ReadSource(plan.clone())),
+            false => {
+                let max_threads = self.ctx.get_settings().get_max_threads()? as usize;
+                let read_plan = table.read_plan(
+                    self.ctx.clone(),
+                    plan.push_downs.clone(),
+                    Some(max_threads),
+                )?;
+                Ok(PlanNode::ReadSource(read_plan))
+            }
+        }
+    }
+}
+
 impl Tasks {
     pub fn create(context: DatabendQueryContextRef) -> Tasks {
         Tasks {
@@ -782,22 +824,25 @@ impl PlanScheduler {
 
     fn visit_data_source(&mut self, plan: &ReadDataSourcePlan, _: &mut Tasks) -> Result<()> {
         let table = if plan.tbl_args.is_none() {
-            let table_meta = self.query_context.get_table(&plan.db, &plan.table)?;
+            let table_meta = self
+                .query_context
+                .get_table(&plan.table_info.db, &plan.table_info.name)?;
             table_meta.raw().clone()
         } else {
             let meta = self
                 .query_context
-                .get_table_function(&plan.table, plan.tbl_args.clone())?;
+                .get_table_function(&plan.table_info.name, plan.tbl_args.clone())?;
             meta.raw().clone().as_table()
         };
 
-        // let table_meta = self.query_context.get_table(&plan.db, &plan.table)?;
+        // let table_meta = self.query_context.get_table(&plan.table_info.db, &plan.table_info.name)?;
         // let table = table_meta.raw();
 
         match table.is_local() {
             true => self.visit_local_data_source(plan),
             false => {
-                let cluster_source = self.cluster_source(&plan.scan_plan, table.clone())?;
+                // re-planned with the push downs of the optimizers, by which the table prunes its parts
+                let cluster_source = self.cluster_source(&plan.push_downs, table.clone())?;
                 self.visit_cluster_data_source(&cluster_source)
             }
         }
@@ -847,17 +892,17 @@ impl PlanScheduler {
 }
 
 impl PlanScheduler {
-    fn cluster_source(&mut self, node: &ScanPlan, table: TablePtr) -> Result<ReadDataSourcePlan> {
+    fn cluster_source(
+        &mut self,
+        push_downs: &Option<Extras>,
+        table: TablePtr,
+    ) -> Result<ReadDataSourcePlan> {
         let nodes = self.cluster_nodes.clone();
         let ctx = self.query_context.clone();
         let settings = ctx.get_settings();
         let max_threads = settings.get_max_threads()? as usize;
 
-        table.read_plan(
-            ctx,
-            Some(node.push_downs.clone()),
-            Some(max_threads * nodes.len()),
-        )
+        table.read_plan(ctx, push_downs.clone(), Some(max_threads * nodes.len()))
     }
 
     fn repartition(&mut self, cluster_source: &ReadDataSourcePlan) -> Vec<Partitions> {
@@ -17,6 +17,10 @@ mod optimizer_constant_folding_test;
 #[cfg(test)]
 mod optimizer_expression_transform_test;
 #[cfg(test)]
+mod optimizer_limit_push_down_test;
+#[cfg(test)]
+mod optimizer_predicate_push_down_test;
+#[cfg(test)]
 mod optimizer_projection_push_down_test;
 #[cfg(test)]
 mod optimizer_scatters_test;
@@ -29,6 +33,8 @@ mod metrics;
 mod optimizer;
 mod optimizer_constant_folding;
 mod optimizer_expression_transform;
+mod optimizer_limit_push_down;
+mod optimizer_predicate_push_down;
 mod optimizer_projection_push_down;
 mod optimizer_scatters;
 mod optimizer_statistics_exact;
@@ -38,6 +44,8 @@ pub use optimizer::Optimizer;
 pub use optimizer::Optimizers;
 pub use optimizer_constant_folding::ConstantFoldingOptimizer;
 pub use optimizer_expression_transform::ExprTransformOptimizer;
+pub use optimizer_limit_push_down::LimitPushDownOptimizer;
+pub use optimizer_predicate_push_down::PredicatePushDownOptimizer;
 pub use optimizer_projection_push_down::ProjectionPushDownOptimizer;
 pub use optimizer_scatters::ScattersOptimizer;
 pub use optimizer_statistics_exact::StatisticsExactOptimizer;
@@ -21,6 +21,8 @@ use metrics::histogram;
 use crate::optimizers::optimizer_scatters::ScattersOptimizer;
 use crate::optimizers::ConstantFoldingOptimizer;
 use crate::optimizers::ExprTransformOptimizer;
+use crate::optimizers::LimitPushDownOptimizer;
+use crate::optimizers::PredicatePushDownOptimizer;
 use crate::optimizers::ProjectionPushDownOptimizer;
 use crate::optimizers::StatisticsExactOptimizer;
 use crate::sessions::DatabendQueryContextRef;
@@ -48,6 +50,8 @@ impl Optimizers {
             inner: vec![
                 Box::new(ConstantFoldingOptimizer::create(ctx.clone())),
                 Box::new(ExprTransformOptimizer::create(ctx.clone())),
+                Box::new(PredicatePushDownOptimizer::create(ctx.clone())),
+                Box::new(LimitPushDownOptimizer::create(ctx.clone())),
                 Box::new(ProjectionPushDownOptimizer::create(ctx.clone())),
                 Box::new(StatisticsExactOptimizer::create(ctx)),
             ],
@@ -23,6 +23,8 @@ use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::Expression;
 use common_planners::Expressions;
+use common_planners::FilterPlan;
+use common_planners::HavingPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
@@ -31,10 +33,14 @@ use crate::optimizers::Optimizer;
 use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::DatabendQueryContextRef;
 
-pub struct ConstantFoldingOptimizer {}
+pub struct ConstantFoldingOptimizer {
+    ctx: DatabendQueryContextRef,
+}
 
 struct ConstantFoldingImpl {
     before_group_by_schema: Option<DataSchemaRef>,
+    // the timezone of the session, the constants are evaluated in it
+    timezone: String,
 }
 
 impl ConstantFoldingImpl {
@@ -48,40 +54,54 @@ impl ConstantFoldingImpl {
             .any(|expr| !matches!(expr, Expression::Literal { .. }))
     }
 
-    fn rewrite_function<F>(op: &str, args: Expressions, name: String, f: F) -> Result<Expression>
-    where F: Fn(&str, Expressions) -> Expression {
+    fn rewrite_function<F>(
+        &self,
+        op: &str,
+        args: Expressions,
+        name: String,
+        f: F,
+    ) -> Result<Expression>
+    where
+        F: Fn(&str, Expressions) -> Expression,
+    {
         let factory = FunctionFactory::instance();
         let function_features = factory.get_features(op)?;
 
         if function_features.is_deterministic && Self::constants_arguments(&args) {
             let op = op.to_string();
-            return ConstantFoldingImpl::execute_expression(
-                Expression::ScalarFunction { op, args },
-                name,
-            );
+            return self.execute_expression(Expression::ScalarFunction { op, args }, name);
         }
 
         Ok(f(op, args))
     }
 
-    fn expr_executor(schema: &DataSchemaRef, expr: Expression) -> Result<ExpressionExecutor> {
+    fn expr_executor(
+        &self,
+        schema: &DataSchemaRef,
+        expr: Expression,
+    ) -> Result<ExpressionExecutor> {
         let output_fields = vec![expr.to_data_field(schema)?];
         let output_schema = DataSchemaRefExt::create(output_fields);
-        ExpressionExecutor::try_create(
+        let executor = ExpressionExecutor::try_create(
             "Constant folding optimizer.",
             schema.clone(),
             output_schema,
             vec![expr],
             false,
-        )
+        )?;
+        Ok(executor.with_timezone(self.timezone.clone()))
     }
 
-    fn execute_expression(expression: Expression, origin_name: String) -> Result<Expression> {
+    fn execute_expression(
+        &self,
+        expression: Expression,
+        origin_name: String,
+    ) -> Result<Expression> {
         let input_fields = vec![DataField::new("_dummy", DataType::UInt8, false)];
         let input_schema = Arc::new(DataSchema::new(input_fields));
 
         let data_type = expression.to_data_type(&input_schema)?;
-        let expression_executor = Self::expr_executor(&input_schema, expression)?;
+        let expression_executor = self.expr_executor(&input_schema, expression)?;
         let dummy_columns = vec![DataColumn::Constant(DataValue::UInt8(Some(1)), 1)];
         let data_block = DataBlock::create(input_schema, dummy_columns);
         let executed_data_block = expression_executor.execute(&data_block)?;
@@ -105,6 +125,76 @@ impl ConstantFoldingImpl {
             data_type,
         })
     }
+
+    fn is_boolean_literal(expr: &Expression, expected: bool) -> bool {
+        matches!(expr, Expression::Literal { value: DataValue::Boolean(Some(v)), .. } if *v == expected)
+    }
+
+    fn is_number_literal(expr: &Expression, expected: i64) -> bool {
+        match expr {
+            Expression::Literal { value, .. } if is_integer(&value.data_type()) => {
+                matches!(value.as_i64(), Ok(v) if v == expected)
+            }
+            _ => false,
+        }
+    }
+
+    // Simplifies the trivial identities, e.g. `true AND x` to `x` and `x * 1` to `x`.
+    // The result keeps the name of the origin expression, and it is only used if it has the
+    // same type as the origin one, e.g. `x + 0` is not simplified if `x` is promoted by `+`.
+    fn simplify_binary(
+        schema: &DataSchemaRef,
+        op: &str,
+        left: &Expression,
+        right: &Expression,
+        origin_name: &str,
+    ) -> Result<Option<Expression>> {
+        let simplified = match op.to_lowercase().as_str() {
+            "and" if Self::is_boolean_literal(left, true) => right,
+            "and" if Self::is_boolean_literal(right, true) => left,
+            "and" if Self::is_boolean_literal(left, false) => left,
+            "and" if Self::is_boolean_literal(right, false) => right,
+            "or" if Self::is_boolean_literal(left, false) => right,
+            "or" if Self::is_boolean_literal(right, false) => left,
+            "or" if Self::is_boolean_literal(left, true) => left,
+            "or" if Self::is_boolean_literal(right, true) => right,
+            "+" if Self::is_number_literal(left, 0) => right,
+            "+" | "-" if Self::is_number_literal(right, 0) => left,
+            "*" if Self::is_number_literal(left, 1) => right,
+            "*" if Self::is_number_literal(right, 1) => left,
+            _ => return Ok(None),
+        };
+
+        let origin = Expression::create_binary_expression(op, vec![left.clone(), right.clone()]);
+        let origin_type = origin.to_data_type(schema)?;
+        if simplified.to_data_type(schema)? != origin_type {
+            return Ok(None);
+        }
+
+        Ok(Some(match simplified {
+            Expression::Literal {
+                value, data_type, ..
+            } => Expression::Literal {
+                value: value.clone(),
+                column_name: Some(origin_name.to_string()),
+                data_type: data_type.clone(),
+            },
+            expr if expr.column_name() == origin_name => expr.clone(),
+            expr => Expression::Alias(origin_name.to_string(), Box::new(expr.clone())),
+        }))
+    }
+
+    // The name of a predicate does not matter
+    fn rewrite_predicate(
+        &mut self,
+        schema: &DataSchemaRef,
+        expr: &Expression,
+    ) -> Result<Expression> {
+        match self.rewrite_expr(schema, expr)? {
+            Expression::Alias(_, expr) => Ok(*expr),
+            expr => Ok(expr),
+        }
+    }
 }
 
 impl PlanRewriter for ConstantFoldingImpl {
@@ -125,7 +215,7 @@ impl PlanRewriter for ConstantFoldingImpl {
                     .collect::<Result<Vec<_>>>()?;
 
                 let origin_name = origin.column_name();
-                Self::rewrite_function(
+                self.rewrite_function(
                     op,
                     new_args,
                     origin_name,
@@ -135,7 +225,7 @@ impl PlanRewriter for ConstantFoldingImpl {
             Expression::UnaryExpression { op, expr } => {
                 let origin_name = origin.column_name();
                 let new_expr = vec![self.rewrite_expr(schema, expr)?];
-                Self::rewrite_function(
+                self.rewrite_function(
                     op,
                     new_expr,
                     origin_name,
@@ -147,8 +237,16 @@ impl PlanRewriter for ConstantFoldingImpl {
                 let new_right = self.rewrite_expr(schema, right)?;
 
                 let origin_name = origin.column_name();
+                if !Self::constants_arguments(&[new_left.clone(), new_right.clone()]) {
+                    let simplified =
+                        Self::simplify_binary(schema, op, &new_left, &new_right, &origin_name)?;
+                    if let Some(simplified) = simplified {
+                        return Ok(simplified);
+                    }
+                }
+
                 let new_exprs = vec![new_left, new_right];
-                Self::rewrite_function(
+                self.rewrite_function(
                     op,
                     new_exprs,
                     origin_name,
@@ -164,7 +262,7 @@ impl PlanRewriter for ConstantFoldingImpl {
                         data_type: data_type.clone(),
                     };
 
-                    return Self::execute_expression(optimize_expr, origin.column_name());
+                    return self.execute_expression(optimize_expr, origin.column_name());
                 }
 
                 Ok(Expression::Cast {
@@ -205,6 +303,18 @@ impl PlanRewriter for ConstantFoldingImpl {
         }
     }
 
+    fn rewrite_filter(&mut self, plan: &FilterPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_predicate = self.rewrite_predicate(&new_input.schema(), &plan.predicate)?;
+        PlanBuilder::from(&new_input).filter(new_predicate)?.build()
+    }
+
+    fn rewrite_having(&mut self, plan: &HavingPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_predicate = self.rewrite_predicate(&new_input.schema(), &plan.predicate)?;
+        PlanBuilder::from(&new_input).having(new_predicate)?.build()
+    }
+
     fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
         let new_input = self.rewrite_plan_node(&plan.input)?;
         match self.before_group_by_schema {
@@ -241,9 +351,10 @@ impl PlanRewriter for ConstantFoldingImpl {
 }
 
 impl ConstantFoldingImpl {
-    pub fn new() -> ConstantFoldingImpl {
+    pub fn new(timezone: String) -> ConstantFoldingImpl {
         ConstantFoldingImpl {
             before_group_by_schema: None,
+            timezone,
         }
     }
 }
@@ -254,14 +365,15 @@ impl Optimizer for ConstantFoldingOptimizer {
     }
 
     fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
-        let mut visitor = ConstantFoldingImpl::new();
+        let timezone = self.ctx.get_settings().get_timezone()?;
+        let mut visitor = ConstantFoldingImpl::new(timezone);
         visitor.rewrite_plan_node(plan)
     }
 }
 
 impl ConstantFoldingOptimizer {
-    pub fn create(_ctx: DatabendQueryContextRef) -> Self {
-        ConstantFoldingOptimizer {}
+    pub fn create(ctx: DatabendQueryContextRef) -> Self {
+        ConstantFoldingOptimizer { ctx }
     }
 }
 
@@ -108,6 +108,46 @@ mod tests {
                 \n  Expression: String:String (Before Projection)\
                 \n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
             },
+            Test {
+                name: "Projection add const",
+                query: "SELECT 2 + 3",
+                expect: "\
+                Projection: (2 + 3):UInt16\
+                \n  Expression: 5:UInt16 (Before Projection)\
+                \n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
+            },
+            Test {
+                name: "Filter true and non const",
+                query: "SELECT number FROM numbers_mt(10) WHERE true AND number > 1",
+                expect: "\
+                Projection: number:UInt64\
+                \n  Filter: (number > 1)\
+                \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            },
+            Test {
+                name: "Filter non const or false",
+                query: "SELECT number FROM numbers_mt(10) WHERE number > 1 OR false",
+                expect: "\
+                Projection: number:UInt64\
+                \n  Filter: (number > 1)\
+                \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            },
+            Test {
+                name: "Projection multiply by one",
+                query: "SELECT number * 1 FROM numbers_mt(10)",
+                expect: "\
+                Projection: (number * 1):UInt64\
+                \n  Expression: number as (number * 1):UInt64 (Before Projection)\
+                \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            },
+            Test {
+                name: "Projection add zero with type promotion",
+                query: "SELECT dummy + 0",
+                expect: "\
+                Projection: (dummy + 0):UInt16\
+                \n  Expression: (dummy + 0):UInt16 (Before Projection)\
+                \n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
+            },
         ];
 
         for test in tests {
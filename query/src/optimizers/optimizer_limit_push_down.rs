@@ -0,0 +1,112 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Extras;
+use common_planners::LimitPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::ReadDataSourcePlan;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct LimitPushDownOptimizer {}
+
+struct LimitPushDownImpl {}
+
+impl PlanRewriter for LimitPushDownImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .aggregate_final(
+                plan.schema_before_group_by.clone(),
+                &plan.aggr_expr,
+                &plan.group_expr,
+            )?
+            .build()
+    }
+
+    fn rewrite_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
+        let new_input = match plan.n {
+            // the offset rows are read too, they are skipped by the limit itself
+            Some(n) => self.push_down(plan.input.as_ref(), n + plan.offset)?,
+            None => self.rewrite_plan_node(plan.input.as_ref())?,
+        };
+        PlanBuilder::from(&new_input)
+            .limit_offset(plan.n, plan.offset)?
+            .build()
+    }
+}
+
+impl LimitPushDownImpl {
+    // Pushes the limit through the nodes which neither drop nor reorder the rows, the limit
+    // node above is kept, as the tables may return more rows than the limit.
+    fn push_down(&mut self, plan: &PlanNode, limit: usize) -> Result<PlanNode> {
+        match plan {
+            PlanNode::Expression(expression) => {
+                let new_input = self.push_down(expression.input.as_ref(), limit)?;
+                PlanBuilder::from(&new_input)
+                    .expression(&expression.exprs, &expression.desc)?
+                    .build()
+            }
+            PlanNode::Projection(projection) => {
+                let new_input = self.push_down(projection.input.as_ref(), limit)?;
+                PlanBuilder::from(&new_input)
+                    .project(&projection.expr)?
+                    .build()
+            }
+            PlanNode::ReadSource(read_source) => {
+                Ok(PlanNode::ReadSource(push_down_limit(read_source, limit)))
+            }
+            _ => self.rewrite_plan_node(plan),
+        }
+    }
+}
+
+fn push_down_limit(plan: &ReadDataSourcePlan, limit: usize) -> ReadDataSourcePlan {
+    let mut extras = plan.push_downs.clone().unwrap_or_else(Extras::default);
+    extras.limit = Some(extras.limit.map_or(limit, |l| l.min(limit)));
+
+    let mut new_plan = plan.clone();
+    new_plan.push_downs = Some(extras);
+    new_plan
+}
+
+impl Optimizer for LimitPushDownOptimizer {
+    fn name(&self) -> &str {
+        "LimitPushDown"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = LimitPushDownImpl {};
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl LimitPushDownOptimizer {
+    pub fn create(_ctx: DatabendQueryContextRef) -> LimitPushDownOptimizer {
+        LimitPushDownOptimizer {}
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::*;
+use crate::sql::*;
+
+fn find_read_source(plan: &PlanNode) -> Option<&ReadDataSourcePlan> {
+    match plan {
+        PlanNode::ReadSource(read_source) => Some(read_source),
+        PlanNode::Projection(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::Expression(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::Limit(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::Sort(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::AggregatorFinal(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::AggregatorPartial(plan) => find_read_source(plan.input.as_ref()),
+        _ => None,
+    }
+}
+
+fn pushed_limit(query: &str) -> Result<Option<usize>> {
+    let ctx = crate::tests::try_create_context()?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+
+    let mut limit_push_down = LimitPushDownOptimizer::create(ctx);
+    let optimized = limit_push_down.optimize(&plan)?;
+
+    // the limit itself is always kept
+    assert_eq!(format!("{:?}", plan), format!("{:?}", optimized));
+    Ok(find_read_source(&optimized)
+        .and_then(|read_source| read_source.push_downs.as_ref())
+        .and_then(|extras| extras.limit))
+}
+
+#[test]
+fn test_limit_push_down_optimizer() -> Result<()> {
+    let tests = vec![
+        ("select number from numbers_mt(1000000) limit 5", Some(5)),
+        (
+            "select number + 1 from numbers_mt(1000000) limit 5",
+            Some(5),
+        ),
+        // the skipped rows are read as well
+        (
+            "select number from numbers_mt(1000000) limit 5 offset 3",
+            Some(8),
+        ),
+        (
+            "select number from numbers_mt(1000000) order by number limit 5",
+            None,
+        ),
+        (
+            "select count(number) from numbers_mt(1000000) limit 5",
+            None,
+        ),
+        (
+            "select number from numbers_mt(1000000) where number > 1 limit 5",
+            None,
+        ),
+    ];
+
+    for (query, expect) in tests {
+        assert_eq!(pushed_limit(query)?, expect, "{}", query);
+    }
+    Ok(())
+}
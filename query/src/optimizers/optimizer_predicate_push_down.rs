@@ -0,0 +1,201 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::FilterPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::ReadDataSourcePlan;
+use common_planners::RewriteHelper;
+
+use crate::optimizers::Optimizer;
+use crate::optimizers::RequireColumnsVisitor;
+use crate::sessions::DatabendQueryContextRef;
+
+pub struct PredicatePushDownOptimizer {}
+
+struct PredicatePushDownImpl {}
+
+impl PlanRewriter for PredicatePushDownImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        PlanBuilder::from(&new_input)
+            .aggregate_final(
+                plan.schema_before_group_by.clone(),
+                &plan.aggr_expr,
+                &plan.group_expr,
+            )?
+            .build()
+    }
+
+    fn rewrite_filter(&mut self, plan: &FilterPlan) -> Result<PlanNode> {
+        let mut predicates = vec![];
+        split_conjunctions(&plan.predicate, &mut predicates);
+        self.push_down(plan.input.as_ref(), predicates)
+    }
+}
+
+impl PredicatePushDownImpl {
+    // Pushes the predicates (conjuncts of filters) into `plan`, each of them lands as deep as
+    // the columns it references are available, the ones can not go further are kept as a filter
+    // upon the node they stop at.
+    fn push_down(&mut self, plan: &PlanNode, mut predicates: Vec<Expression>) -> Result<PlanNode> {
+        match plan {
+            PlanNode::Filter(filter) => {
+                split_conjunctions(&filter.predicate, &mut predicates);
+                self.push_down(filter.input.as_ref(), predicates)
+            }
+            // the sub queries set is rebuilt by the builder of its parent
+            PlanNode::SubQueryExpression(sub_queries) => {
+                self.push_down(sub_queries.input.as_ref(), predicates)
+            }
+            PlanNode::Sort(sort) => {
+                let new_input = self.push_down(sort.input.as_ref(), predicates)?;
+                PlanBuilder::from(&new_input).sort(&sort.order_by)?.build()
+            }
+            PlanNode::Expression(expression) => {
+                let (pushed, kept) = partition_predicates(
+                    predicates,
+                    &expression.input.schema(),
+                    &expression.exprs,
+                )?;
+                let new_input = self.push_down(expression.input.as_ref(), pushed)?;
+                let new_plan = PlanBuilder::from(&new_input)
+                    .expression(&expression.exprs, &expression.desc)?
+                    .build()?;
+                with_filter(new_plan, kept)
+            }
+            PlanNode::Projection(projection) => {
+                let (pushed, kept) =
+                    partition_predicates(predicates, &projection.input.schema(), &projection.expr)?;
+                let new_input = self.push_down(projection.input.as_ref(), pushed)?;
+                let new_plan = PlanBuilder::from(&new_input)
+                    .project(&projection.expr)?
+                    .build()?;
+                with_filter(new_plan, kept)
+            }
+            PlanNode::ReadSource(read_source) => {
+                let new_plan = PlanNode::ReadSource(push_down_filters(read_source, &predicates)?);
+                // tables use the filters to prune the data, not to filter the rows exactly
+                with_filter(new_plan, predicates)
+            }
+            _ => {
+                let new_plan = self.rewrite_plan_node(plan)?;
+                with_filter(new_plan, predicates)
+            }
+        }
+    }
+}
+
+fn split_conjunctions(predicate: &Expression, predicates: &mut Vec<Expression>) {
+    match predicate {
+        Expression::BinaryExpression { op, left, right } if op.to_lowercase() == "and" => {
+            split_conjunctions(left, predicates);
+            split_conjunctions(right, predicates);
+        }
+        _ => predicates.push(predicate.clone()),
+    }
+}
+
+fn with_filter(plan: PlanNode, predicates: Vec<Expression>) -> Result<PlanNode> {
+    let mut predicates = predicates.into_iter();
+    match predicates.next() {
+        None => Ok(plan),
+        Some(first) => {
+            let predicate = predicates.fold(first, |acc, p| acc.and(p));
+            PlanBuilder::from(&plan).filter(predicate)?.build()
+        }
+    }
+}
+
+fn has_sub_queries(expr: &Expression) -> Result<bool> {
+    let sub_queries = RewriteHelper::collect_exprs_sub_queries(&[expr.clone()])?;
+    Ok(!sub_queries.is_empty())
+}
+
+// Splits the predicates into the ones can be pushed below the node with `exprs`, whose input
+// has `input_schema`, and the ones have to be kept above it.
+fn partition_predicates(
+    predicates: Vec<Expression>,
+    input_schema: &DataSchemaRef,
+    exprs: &[Expression],
+) -> Result<(Vec<Expression>, Vec<Expression>)> {
+    let mut pushed = vec![];
+    let mut kept = vec![];
+    for predicate in predicates {
+        let mut visitor = RequireColumnsVisitor::default();
+        visitor = predicate.accept(visitor)?;
+        // a column is passed through if it is in the input and not redefined by the node,
+        // e.g. by an alias of another expression
+        let passed_through = visitor.required_columns.iter().all(|name| {
+            input_schema.index_of(name).is_ok()
+                && exprs.iter().all(|expr| {
+                    expr.column_name() != *name
+                        || matches!(expr, Expression::Column(c) if c == name)
+                })
+        });
+        if passed_through && !has_sub_queries(&predicate)? {
+            pushed.push(predicate);
+        } else {
+            kept.push(predicate);
+        }
+    }
+    Ok((pushed, kept))
+}
+
+fn push_down_filters(
+    plan: &ReadDataSourcePlan,
+    predicates: &[Expression],
+) -> Result<ReadDataSourcePlan> {
+    let mut extras = plan.push_downs.clone().unwrap_or_else(Extras::default);
+    for predicate in predicates {
+        if !extras.filters.contains(predicate) && !has_sub_queries(predicate)? {
+            extras.filters.push(predicate.clone());
+        }
+    }
+
+    let mut new_plan = plan.clone();
+    new_plan.push_downs = Some(extras);
+    Ok(new_plan)
+}
+
+impl Optimizer for PredicatePushDownOptimizer {
+    fn name(&self) -> &str {
+        "PredicatePushDown"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = PredicatePushDownImpl {};
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl PredicatePushDownOptimizer {
+    pub fn create(_ctx: DatabendQueryContextRef) -> PredicatePushDownOptimizer {
+        PredicatePushDownOptimizer {}
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_api_vo::TableInfo;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::optimizer_test::*;
+use crate::optimizers::*;
+use crate::sql::*;
+
+fn find_read_source(plan: &PlanNode) -> Option<&ReadDataSourcePlan> {
+    match plan {
+        PlanNode::ReadSource(read_source) => Some(read_source),
+        PlanNode::Projection(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::Expression(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::Filter(plan) => find_read_source(plan.input.as_ref()),
+        PlanNode::Sort(plan) => find_read_source(plan.input.as_ref()),
+        _ => None,
+    }
+}
+
+fn pushed_filters(plan: &PlanNode) -> Vec<String> {
+    find_read_source(plan)
+        .and_then(|read_source| read_source.push_downs.as_ref())
+        .map(|extras| extras.filters.iter().map(|f| format!("{:?}", f)).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_predicate_push_down_optimizer_to_read_source() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select number from numbers_mt(10) where number > 1 and number < 8")?;
+
+    let mut predicate_push_down = PredicatePushDownOptimizer::create(ctx);
+    let optimized = predicate_push_down.optimize(&plan)?;
+
+    assert_eq!(pushed_filters(&optimized), vec![
+        "(number > 1)",
+        "(number < 8)"
+    ]);
+
+    // the tables only prune by the pushed down filters, the rows are still filtered
+    let expect = "\
+        Projection: number:UInt64\
+        \n  Filter: ((number > 1) and (number < 8))\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]";
+    let actual = format!("{:?}", optimized);
+    assert_eq!(expect, actual);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_push_down_optimizer_through_expression() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let statistics = Statistics::new_exact(8, 64);
+    let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
+        table_info: TableInfo::simple(
+            "system",
+            "test",
+            DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::UInt64, false),
+            ]),
+        ),
+        parts: generate_partitions(8, 8),
+        statistics: statistics.clone(),
+        description: "(Read from system.test table)".to_string(),
+        scan_plan: Arc::new(ScanPlan::empty()),
+        remote: false,
+        tbl_args: None,
+        push_downs: None,
+    });
+
+    // `(a + b) > 3` depends on the expression, while `a > 1` can be evaluated by the scan
+    let plan = PlanBuilder::from(&source_plan)
+        .expression(&[add(col("a"), col("b"))], "Before OrderBy")?
+        .sort(&[sort("a", true, false)])?
+        .filter(col("(a + b)").gt(lit(3)).and(col("a").gt(lit(1))))?
+        .build()?;
+
+    let mut predicate_push_down = PredicatePushDownOptimizer::create(ctx);
+    let optimized = predicate_push_down.optimize(&plan)?;
+
+    let expect = "\
+        Sort: a:UInt64\
+        \n  Filter: ((a + b) > 3)\
+        \n    Expression: (a + b):UInt64 (Before OrderBy)\
+        \n      Filter: (a > 1)\
+        \n        ReadDataSource: scan partitions: [8], scan schema: [a:UInt64, b:UInt64], statistics: [read_rows: 8, read_bytes: 64]";
+    let actual = format!("{:?}", optimized);
+    assert_eq!(expect, actual);
+    assert_eq!(pushed_filters(&optimized), vec!["(a > 1)"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_predicate_push_down_optimizer_not_through_limit() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "select number from (select number from numbers_mt(10) limit 5) where number > 1",
+    )?;
+
+    let mut predicate_push_down = PredicatePushDownOptimizer::create(ctx);
+    let optimized = predicate_push_down.optimize(&plan)?;
+
+    // filtering before the limit changes the result
+    assert!(pushed_filters(&optimized).is_empty());
+
+    Ok(())
+}
@@ -20,11 +20,13 @@ use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::EmptyPlan;
 use common_planners::Expression;
 use common_planners::ExpressionPlan;
+use common_planners::Extras;
 use common_planners::FilterPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
@@ -118,23 +120,33 @@ impl PlanRewriter for ProjectionPushDownImpl {
 
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
         // TODO: rewrite scan
-        self.get_projected_schema(plan.schema.as_ref())
-            .map(|projected_schema| {
-                PlanNode::ReadSource(ReadDataSourcePlan {
-                    db: plan.db.to_string(),
-                    table: plan.table.to_string(),
-                    table_id: plan.table_id,
-                    table_version: plan.table_version,
-                    schema: projected_schema,
-                    parts: plan.parts.clone(),
-                    statistics: plan.statistics.clone(),
-                    description: plan.description.to_string(),
-                    scan_plan: plan.scan_plan.clone(),
-                    remote: plan.remote,
-                    tbl_args: plan.tbl_args.clone(),
-                    push_downs: plan.push_downs.clone(),
-                })
-            })
+        let mut push_downs = plan.push_downs.clone().unwrap_or_else(Extras::default);
+        // the columns of the pushed down filters are read, even if nothing above requires them
+        self.collect_column_names_from_expr_vec(&push_downs.filters)?;
+
+        let schema = plan.schema();
+        let projection = self.get_projection(schema.as_ref());
+        let projected_schema = Self::get_projected_schema(schema.as_ref(), &projection);
+        // the projection of the push downs is of the table schema, while the schema of the
+        // plan may have been projected already
+        push_downs.projection = Some(match &push_downs.projection {
+            Some(prev) => projection.iter().map(|i| prev[*i]).collect(),
+            None => projection,
+        });
+
+        Ok(PlanNode::ReadSource(ReadDataSourcePlan {
+            table_info: TableInfo {
+                schema: projected_schema,
+                ..plan.table_info.clone()
+            },
+            parts: plan.parts.clone(),
+            statistics: plan.statistics.clone(),
+            description: plan.description.to_string(),
+            scan_plan: plan.scan_plan.clone(),
+            remote: plan.remote,
+            tbl_args: plan.tbl_args.clone(),
+            push_downs: Some(push_downs),
+        }))
     }
 }
 
@@ -167,7 +179,8 @@ impl ProjectionPushDownImpl {
         Ok(())
     }
 
-    fn get_projected_schema(&self, schema: &DataSchema) -> Result<DataSchemaRef> {
+    // Returns the indices of the required columns in `schema`, in ascending order
+    fn get_projection(&self, schema: &DataSchema) -> Vec<usize> {
         // Discard non-existing columns, e.g. when the column derives from aggregation
         let mut projection: Vec<usize> = self
             .required_columns
@@ -193,13 +206,15 @@ impl ProjectionPushDownImpl {
         }
         // sort the projection to get deterministic behavior
         projection.sort_unstable();
+        projection
+    }
 
-        // create the projected schema
+    fn get_projected_schema(schema: &DataSchema, projection: &[usize]) -> DataSchemaRef {
         let mut projected_fields: Vec<DataField> = Vec::with_capacity(projection.len());
-        for i in &projection {
+        for i in projection {
             projected_fields.push(schema.fields()[*i].clone());
         }
-        Ok(DataSchemaRefExt::create(projected_fields))
+        DataSchemaRefExt::create(projected_fields)
     }
 }
 
@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use common_meta_api_vo::TableInfo;
 use common_planners::*;
 use pretty_assertions::assert_eq;
 
@@ -91,15 +92,15 @@ fn test_projection_push_down_optimizer_2() -> Result<()> {
         Statistics::new_exact(total as usize, ((total) * size_of::<u64>() as u64) as usize);
     ctx.try_set_statistics(&statistics)?;
     let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
-        db: "system".to_string(),
-        table: "test".to_string(),
-        table_id: 0,
-        table_version: None,
-        schema: DataSchemaRefExt::create(vec![
-            DataField::new("a", DataType::String, false),
-            DataField::new("b", DataType::String, false),
-            DataField::new("c", DataType::String, false),
-        ]),
+        table_info: TableInfo::simple(
+            "system",
+            "test",
+            DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::String, false),
+                DataField::new("b", DataType::String, false),
+                DataField::new("c", DataType::String, false),
+            ]),
+        ),
         parts: generate_partitions(8, total as u64),
         statistics: statistics.clone(),
         description: format!(
@@ -146,19 +147,19 @@ fn test_projection_push_down_optimizer_3() -> Result<()> {
         Statistics::new_exact(total as usize, ((total) * size_of::<u64>() as u64) as usize);
     ctx.try_set_statistics(&statistics)?;
     let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
-        db: "system".to_string(),
-        table: "test".to_string(),
-        table_id: 0,
-        table_version: None,
-        schema: DataSchemaRefExt::create(vec![
-            DataField::new("a", DataType::String, false),
-            DataField::new("b", DataType::String, false),
-            DataField::new("c", DataType::String, false),
-            DataField::new("d", DataType::String, false),
-            DataField::new("e", DataType::String, false),
-            DataField::new("f", DataType::String, false),
-            DataField::new("g", DataType::String, false),
-        ]),
+        table_info: TableInfo::simple(
+            "system",
+            "test",
+            DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::String, false),
+                DataField::new("b", DataType::String, false),
+                DataField::new("c", DataType::String, false),
+                DataField::new("d", DataType::String, false),
+                DataField::new("e", DataType::String, false),
+                DataField::new("f", DataType::String, false),
+                DataField::new("g", DataType::String, false),
+            ]),
+        ),
         parts: generate_partitions(8, total as u64),
         statistics: statistics.clone(),
         description: format!(
@@ -223,3 +224,70 @@ fn test_projection_push_down_optimizer_4() -> Result<()> {
     assert_eq!(expect, actual);
     Ok(())
 }
+
+#[test]
+fn test_projection_push_down_optimizer_records_projection() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let statistics = Statistics::new_exact(8, 64);
+    let mut push_downs = Extras::default();
+    // a filter pushed down to the table, which is not evaluated above the scan
+    push_downs.filters = vec![col("e").eq(lit(1))];
+    let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
+        table_info: TableInfo::simple(
+            "system",
+            "test",
+            DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::String, false),
+                DataField::new("b", DataType::String, false),
+                DataField::new("c", DataType::String, false),
+                DataField::new("d", DataType::String, false),
+                DataField::new("e", DataType::String, false),
+            ]),
+        ),
+        parts: generate_partitions(8, 8),
+        statistics: statistics.clone(),
+        description: "(Read from system.test table)".to_string(),
+        scan_plan: Arc::new(ScanPlan::empty()),
+        remote: false,
+        tbl_args: None,
+        push_downs: Some(push_downs),
+    });
+
+    // SELECT d, a FROM test WHERE b > 1
+    let plan = PlanBuilder::from(&source_plan)
+        .filter(col("b").gt(lit(1)))?
+        .project(&[col("d"), col("a")])?
+        .build()?;
+
+    let mut projection_push_down = ProjectionPushDownOptimizer::create(ctx);
+    let optimized = projection_push_down.optimize(&plan)?;
+
+    let expect = "\
+        Projection: d:String, a:String\
+        \n  Filter: (b > 1)\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [a:String, b:String, d:String, e:String], statistics: [read_rows: 8, read_bytes: 64]";
+    let actual = format!("{:?}", optimized);
+    assert_eq!(expect, actual);
+
+    let projection = |plan: &PlanNode| match plan {
+        PlanNode::Projection(ProjectionPlan { input, .. }) => match input.as_ref() {
+            PlanNode::Filter(FilterPlan { input, .. }) => match input.as_ref() {
+                PlanNode::ReadSource(read_source) => read_source
+                    .push_downs
+                    .as_ref()
+                    .and_then(|extras| extras.projection.clone()),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+    assert_eq!(projection(&optimized), Some(vec![0, 1, 3, 4]));
+
+    // optimizing again keeps the indices of the table schema
+    let optimized = projection_push_down.optimize(&optimized)?;
+    assert_eq!(projection(&optimized), Some(vec![0, 1, 3, 4]));
+
+    Ok(())
+}
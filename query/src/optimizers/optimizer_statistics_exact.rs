@@ -14,13 +14,17 @@
 
 use std::sync::Arc;
 
+use bumpalo::Bump;
+use bytes::BytesMut;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataValue;
 use common_exception::Result;
+use common_functions::aggregates::StateAddr;
 use common_io::prelude::BinaryWrite;
 use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
+use common_planners::ColumnStatistics;
 use common_planners::Expression;
-use common_planners::ExpressionPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
@@ -39,74 +43,44 @@ pub struct StatisticsExactOptimizer {
 
 impl PlanRewriter for StatisticsExactImpl<'_> {
     fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
-        let new_plan = match (
-            &plan.group_expr[..],
-            &plan.aggr_expr[..],
-            plan.input.as_ref(),
-        ) {
-            (
-                [],
-                [Expression::AggregateFunction {
-                    ref op,
-                    distinct: false,
-                    ref args,
-                    ..
-                }],
-                PlanNode::Expression(ExpressionPlan { input, .. }),
-            ) if op == "count" && args.len() == 1 => match (&args[0], input.as_ref()) {
-                (Expression::Literal { .. }, PlanNode::ReadSource(read_source_plan))
-                    if read_source_plan.statistics.is_exact =>
-                {
-                    let db_name = "system";
-                    let table_name = "one";
-
-                    let dummy_read_plan =
-                        self.ctx
-                            .get_table(db_name, table_name)
-                            .and_then(|table_meta| {
-                                let table = table_meta.raw();
-                                let table_id = table_meta.meta_id();
-                                let table_version = table_meta.meta_ver();
-                                table
-                                    .schema()
-                                    .and_then(|ref schema| {
-                                        let tbl_scan_info = TableScanInfo {
-                                            table_name,
-                                            table_id,
-                                            table_version,
-                                            table_schema: schema.as_ref(),
-                                            table_args: None,
-                                        };
-                                        PlanBuilder::scan(db_name, tbl_scan_info, None, None)
-                                    })
-                                    .and_then(|builder| builder.build())
-                                    .and_then(|dummy_scan_plan| match dummy_scan_plan {
-                                        PlanNode::Scan(ref dummy_scan_plan) => table
-                                            .read_plan(
-                                                self.ctx.clone(),
-                                                Some(dummy_scan_plan.push_downs.clone()),
-                                                Some(self.ctx.get_settings().get_max_threads()?
-                                                    as usize),
-                                            )
-                                            .map(PlanNode::ReadSource),
-                                        _unreachable_plan => {
-                                            panic!("Logical error: cannot downcast to scan plan")
-                                        }
-                                    })
-                            })?;
-                    let mut body: Vec<u8> = Vec::new();
-                    body.write_uvarint(read_source_plan.statistics.read_rows as u64)?;
-                    let expr = Expression::create_literal(DataValue::String(Some(body)));
-                    PlanBuilder::from(&dummy_read_plan)
-                        .expression(&[expr.clone()], "Exact Statistics")?
-                        .project(&[expr.alias("count(0)")])?
-                        .build()?
+        // The inner aggregations are collapsed first, so that the outer one can count them.
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        if let (
+            [],
+            [aggr_expr
+            @
+            Expression::AggregateFunction {
+                ref op,
+                distinct: false,
+                ref args,
+                ..
+            }],
+        ) = (&plan.group_expr[..], &plan.aggr_expr[..])
+        {
+            if op == "count" && matches!(args[..], [Expression::Literal { .. }]) {
+                if let Some(rows) = exact_rows(&new_input) {
+                    return self.exact_count_plan(aggr_expr, rows);
                 }
-                _ => PlanNode::AggregatorPartial(plan.clone()),
-            },
-            (_, _, _) => PlanNode::AggregatorPartial(plan.clone()),
-        };
-        Ok(new_plan)
+            }
+
+            // min/max of a column read from a whole table, answered by the column statistics
+            let bound = match (op.as_str(), &args[..]) {
+                ("min", [Expression::Column(name)]) => {
+                    exact_column_statistics(&new_input, name).and_then(|s| s.min.clone())
+                }
+                ("max", [Expression::Column(name)]) => {
+                    exact_column_statistics(&new_input, name).and_then(|s| s.max.clone())
+                }
+                _ => None,
+            };
+            if let Some(value) = bound.filter(|v| !v.is_null()) {
+                return self.exact_min_max_plan(aggr_expr, &new_input.schema(), value);
+            }
+        }
+
+        PlanBuilder::from(&new_input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()
     }
 
     fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
@@ -120,20 +94,138 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
     }
 }
 
+impl StatisticsExactImpl<'_> {
+    fn dummy_read_plan(&self) -> Result<PlanNode> {
+        let db_name = "system";
+        let table_name = "one";
+
+        self.ctx
+            .get_table(db_name, table_name)
+            .and_then(|table_meta| {
+                let table = table_meta.raw();
+                let table_id = table_meta.meta_id();
+                let table_version = table_meta.meta_ver();
+                table
+                    .schema()
+                    .and_then(|ref schema| {
+                        let tbl_scan_info = TableScanInfo {
+                            table_name,
+                            table_id,
+                            table_version,
+                            table_schema: schema.as_ref(),
+                            table_args: None,
+                        };
+                        PlanBuilder::scan(db_name, tbl_scan_info, None, None)
+                    })
+                    .and_then(|builder| builder.build())
+                    .and_then(|dummy_scan_plan| match dummy_scan_plan {
+                        PlanNode::Scan(ref dummy_scan_plan) => table
+                            .read_plan(
+                                self.ctx.clone(),
+                                Some(dummy_scan_plan.push_downs.clone()),
+                                Some(self.ctx.get_settings().get_max_threads()? as usize),
+                            )
+                            .map(PlanNode::ReadSource),
+                        _unreachable_plan => {
+                            panic!("Logical error: cannot downcast to scan plan")
+                        }
+                    })
+            })
+    }
+
+    // Replaces the partial count with its state, which is read from the dummy table.
+    fn exact_count_plan(&self, aggr_expr: &Expression, rows: usize) -> Result<PlanNode> {
+        let mut body: Vec<u8> = Vec::new();
+        body.write_uvarint(rows as u64)?;
+        let expr = Expression::create_literal(DataValue::String(Some(body)));
+        PlanBuilder::from(&self.dummy_read_plan()?)
+            .expression(&[expr.clone()], "Exact Statistics")?
+            .project(&[expr.alias(&aggr_expr.column_name())])?
+            .build()
+    }
+
+    // Replaces the partial min/max with its state, built by feeding `value` to the function.
+    fn exact_min_max_plan(
+        &self,
+        aggr_expr: &Expression,
+        input_schema: &DataSchemaRef,
+        value: DataValue,
+    ) -> Result<PlanNode> {
+        let func = aggr_expr.to_aggregate_function(input_schema)?;
+        let arg_names = aggr_expr.to_aggregate_function_names()?;
+        let data_type = input_schema.field_with_name(&arg_names[0])?.data_type();
+        let arg = value.to_series_with_size(1)?.cast_with_type(data_type)?;
+
+        let arena = Bump::new();
+        let place: StateAddr = arena.alloc_layout(func.state_layout()).into();
+        func.init_state(place);
+        func.accumulate(place, &[arg], 1)?;
+
+        let mut body = BytesMut::new();
+        func.serialize(place, &mut body)?;
+        let expr = Expression::create_literal(DataValue::String(Some(body.to_vec())));
+        PlanBuilder::from(&self.dummy_read_plan()?)
+            .expression(&[expr.clone()], "Exact Statistics")?
+            .project(&[expr.alias(&aggr_expr.column_name())])?
+            .build()
+    }
+}
+
+// Returns the number of rows the plan produces, if it is known exactly without executing it.
+fn exact_rows(plan: &PlanNode) -> Option<usize> {
+    match plan {
+        PlanNode::ReadSource(plan) if plan.statistics.is_exact => Some(plan.statistics.read_rows),
+        PlanNode::Expression(plan) => exact_rows(plan.input.as_ref()),
+        PlanNode::Projection(plan) => exact_rows(plan.input.as_ref()),
+        PlanNode::Limit(plan) => {
+            let rows = exact_rows(plan.input.as_ref())?.saturating_sub(plan.offset);
+            Some(plan.n.map_or(rows, |n| rows.min(n)))
+        }
+        // an aggregation without group by yields one row, only trusted if its input is exact
+        PlanNode::AggregatorFinal(plan) if plan.group_expr.is_empty() => {
+            exact_rows(plan.input.as_ref()).map(|_| 1)
+        }
+        _ => None,
+    }
+}
+
+// Returns the statistics of the column `name` the plan produces, if they are exact and hold for
+// the plan, i.e. the column is passed through as is from a table read as a whole.
+fn exact_column_statistics<'a>(plan: &'a PlanNode, name: &str) -> Option<&'a ColumnStatistics> {
+    match plan {
+        PlanNode::ReadSource(plan) if plan.statistics.is_exact => {
+            let pruned = plan.push_downs.as_ref().map_or(false, |extras| {
+                !extras.filters.is_empty() || extras.limit.is_some()
+            });
+            match pruned {
+                true => None,
+                false => plan.statistics.column_statistics.get(name),
+            }
+        }
+        PlanNode::Expression(plan) if !redefines(&plan.exprs, name) => {
+            exact_column_statistics(plan.input.as_ref(), name)
+        }
+        PlanNode::Projection(plan) if !redefines(&plan.expr, name) => {
+            exact_column_statistics(plan.input.as_ref(), name)
+        }
+        _ => None,
+    }
+}
+
+// Whether a column named `name` is computed by one of `exprs`, e.g. by an alias, other than the
+// input column of the same name.
+fn redefines(exprs: &[Expression], name: &str) -> bool {
+    exprs.iter().any(|expr| {
+        expr.column_name() == name && !matches!(expr, Expression::Column(c) if c == name)
+    })
+}
+
 impl Optimizer for StatisticsExactOptimizer {
     fn name(&self) -> &str {
         "StatisticsExact"
     }
 
     fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
-        /*
-            TODO:
-                SELECT COUNT(1), COUNT(1) FROM (
-                    SELECT COUNT(1) FROM (
-                        SELECT * FROM system.settings LIMIT 1
-                    )
-                )
-        */
         let mut visitor = StatisticsExactImpl { ctx: &self.ctx };
         visitor.rewrite_plan_node(plan)
     }
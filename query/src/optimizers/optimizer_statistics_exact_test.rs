@@ -14,16 +14,24 @@
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::mem::size_of;
     use std::sync::Arc;
 
+    use common_base::tokio;
+    use common_catalog::ColStats;
     use common_datavalues::*;
     use common_exception::Result;
+    use common_meta_api_vo::TableInfo;
     use common_planners::*;
+    use futures::TryStreamExt;
     use pretty_assertions::assert_eq;
 
+    use crate::datasources::table::fuse::to_column_statistics;
     use crate::optimizers::optimizer_test::*;
     use crate::optimizers::*;
+    use crate::pipelines::processors::PipelineBuilder;
+    use crate::sql::PlanParser;
 
     #[test]
     fn test_statistics_exact_optimizer() -> Result<()> {
@@ -34,15 +42,15 @@ mod tests {
             Statistics::new_exact(total as usize, ((total) * size_of::<u64>() as u64) as usize);
         ctx.try_set_statistics(&statistics)?;
         let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
-            db: "system".to_string(),
-            table: "test".to_string(),
-            table_id: 0,
-            table_version: None,
-            schema: DataSchemaRefExt::create(vec![
-                DataField::new("a", DataType::String, false),
-                DataField::new("b", DataType::String, false),
-                DataField::new("c", DataType::String, false),
-            ]),
+            table_info: TableInfo::simple(
+                "system",
+                "test",
+                DataSchemaRefExt::create(vec![
+                    DataField::new("a", DataType::String, false),
+                    DataField::new("b", DataType::String, false),
+                    DataField::new("c", DataType::String, false),
+                ]),
+            ),
             parts: generate_partitions(8, total as u64),
             statistics: statistics.clone(),
             description: format!(
@@ -87,4 +95,198 @@ mod tests {
         assert_eq!(expect, actual);
         Ok(())
     }
+
+    #[test]
+    fn test_statistics_exact_optimizer_with_nested_count() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        let query = "select count(1) from (select count(1) from numbers(10))";
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+
+        let mut statistics_exact = StatisticsExactOptimizer::create(ctx);
+        let optimized = statistics_exact.optimize(&plan)?;
+
+        // the inner count yields exactly one row, so the outer one is collapsed as well
+        let expect = "\
+        Projection: count(1):UInt64\
+        \n  AggregatorFinal: groupBy=[[]], aggr=[[count(1)]]\
+        \n    Projection: 01 as count(1):String\
+        \n      Expression: 01:String (Exact Statistics)\
+        \n        ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]";
+        let actual = format!("{:?}", optimized);
+        assert_eq!(expect, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn test_statistics_exact_optimizer_with_inexact_statistics() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        // the statistics of system.settings are not exact
+        let query =
+            "select count(1) from (select count(1) from (select * from system.settings limit 1))";
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+
+        let mut statistics_exact = StatisticsExactOptimizer::create(ctx);
+        let optimized = statistics_exact.optimize(&plan)?;
+
+        assert_eq!(format!("{:?}", plan), format!("{:?}", optimized));
+        Ok(())
+    }
+
+    // A read of the whole fuse table, whose column `a` is of 10 rows, one of them null,
+    // ranging from 3 to 42.
+    fn fuse_source_plan(push_downs: Option<Extras>) -> PlanNode {
+        let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int32, true)]);
+        let mut col_stats = HashMap::new();
+        col_stats.insert(0, ColStats {
+            min: DataValue::Int32(Some(3)),
+            max: DataValue::Int32(Some(42)),
+            null_count: 1,
+            row_count: 10,
+            distinct_of_values: 9,
+            distinct_sketch: None,
+        });
+
+        let mut statistics = Statistics::new_exact(10, 40);
+        statistics.column_statistics = to_column_statistics(&schema, 10, &col_stats);
+        PlanNode::ReadSource(ReadDataSourcePlan {
+            table_info: TableInfo::simple("default", "t", schema),
+            parts: generate_partitions(2, 10),
+            statistics,
+            description: "".to_string(),
+            scan_plan: Arc::new(ScanPlan::empty()),
+            remote: true,
+            tbl_args: None,
+            push_downs,
+        })
+    }
+
+    fn aggregate_plan(source_plan: &PlanNode, op: &str) -> Result<PlanNode> {
+        let aggr_expr = Expression::AggregateFunction {
+            op: op.to_string(),
+            distinct: false,
+            params: vec![],
+            args: vec![col("a")],
+        };
+        PlanBuilder::from(source_plan)
+            .aggregate_partial(&[aggr_expr.clone()], &[])?
+            .aggregate_final(source_plan.schema(), &[aggr_expr.clone()], &[])?
+            .project(&[col(&aggr_expr.column_name())])?
+            .build()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_statistics_exact_optimizer_with_min_max() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        let tests = vec![
+            ("min", vec![
+                "+--------+",
+                "| min(a) |",
+                "+--------+",
+                "| 3      |",
+                "+--------+",
+            ]),
+            ("max", vec![
+                "+--------+",
+                "| max(a) |",
+                "+--------+",
+                "| 42     |",
+                "+--------+",
+            ]),
+        ];
+        for (op, expect) in tests {
+            let plan = aggregate_plan(&fuse_source_plan(None), op)?;
+
+            let mut statistics_exact = StatisticsExactOptimizer::create(ctx.clone());
+            let optimized = statistics_exact.optimize(&plan)?;
+
+            // the fuse table is not read, the state is read from the dummy table instead
+            let actual = format!("{:?}", optimized);
+            assert!(actual.contains("(Exact Statistics)"), "{}", actual);
+            assert!(!actual.contains("read_rows: 10"), "{}", actual);
+
+            let mut pipeline = PipelineBuilder::create(ctx.clone()).build(&optimized)?;
+            let stream = pipeline.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            common_datablocks::assert_blocks_eq(expect, result.as_slice());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_statistics_exact_optimizer_with_min_max_of_added_column() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        // `ALTER TABLE t ADD COLUMN c INT DEFAULT 100` upon 5 rows, then 5 rows inserted with
+        // c = 1..5, the statistics of c are of the inserted rows only, the others read 100
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::Int32, true),
+            DataField::new("c", DataType::Int32, false),
+        ]);
+        let mut col_stats = HashMap::new();
+        col_stats.insert(1, ColStats {
+            min: DataValue::Int32(Some(1)),
+            max: DataValue::Int32(Some(5)),
+            null_count: 0,
+            row_count: 5,
+            distinct_of_values: 5,
+            distinct_sketch: None,
+        });
+        let column_statistics = to_column_statistics(&schema, 10, &col_stats);
+        assert_eq!(column_statistics["c"].max, None);
+        assert_eq!(column_statistics["c"].min, None);
+
+        let mut statistics = Statistics::new_exact(10, 80);
+        statistics.column_statistics = column_statistics;
+        let source_plan = PlanNode::ReadSource(ReadDataSourcePlan {
+            table_info: TableInfo::simple("default", "t", schema),
+            parts: generate_partitions(2, 10),
+            statistics,
+            description: "".to_string(),
+            scan_plan: Arc::new(ScanPlan::empty()),
+            remote: true,
+            tbl_args: None,
+            push_downs: None,
+        });
+        for op in ["min", "max"] {
+            let aggr_expr = Expression::AggregateFunction {
+                op: op.to_string(),
+                distinct: false,
+                params: vec![],
+                args: vec![col("c")],
+            };
+            let plan = PlanBuilder::from(&source_plan)
+                .aggregate_partial(&[aggr_expr.clone()], &[])?
+                .aggregate_final(source_plan.schema(), &[aggr_expr.clone()], &[])?
+                .project(&[col(&aggr_expr.column_name())])?
+                .build()?;
+
+            // max(c) is 100, not 5, the table is read
+            let mut statistics_exact = StatisticsExactOptimizer::create(ctx.clone());
+            let optimized = statistics_exact.optimize(&plan)?;
+            assert_eq!(format!("{:?}", plan), format!("{:?}", optimized));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_statistics_exact_optimizer_with_min_max_of_filtered_read() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        // the column statistics are of the whole table, not of the rows filtered
+        let push_downs = Extras {
+            filters: vec![col("a").gt(lit(10))],
+            ..Extras::default()
+        };
+        for op in ["min", "max"] {
+            let plan = aggregate_plan(&fuse_source_plan(Some(push_downs.clone())), op)?;
+
+            let mut statistics_exact = StatisticsExactOptimizer::create(ctx.clone());
+            let optimized = statistics_exact.optimize(&plan)?;
+            assert_eq!(format!("{:?}", plan), format!("{:?}", optimized));
+        }
+        Ok(())
+    }
 }
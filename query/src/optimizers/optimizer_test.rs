@@ -23,6 +23,7 @@ pub fn generate_partitions(workers: u64, total: u64) -> Partitions {
         partitions.push(Part {
             name: format!("{}-{}-{}", total, 0, total,),
             version: 0,
+            coalesced: vec![],
         })
     } else {
         for part in 0..workers {
@@ -34,6 +35,7 @@ pub fn generate_partitions(workers: u64, total: u64) -> Partitions {
             partitions.push(Part {
                 name: format!("{}-{}-{}", total, part_begin, part_end,),
                 version: 0,
+                coalesced: vec![],
             })
         }
     }
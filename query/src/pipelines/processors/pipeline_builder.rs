@@ -131,24 +131,32 @@ impl PipelineBuilder {
 
     fn visit_expression(&mut self, plan: &ExpressionPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*plan.input)?;
+        let tz = self.ctx.get_settings().get_timezone()?;
         pipeline.add_simple_transform(|| {
-            Ok(Box::new(ExpressionTransform::try_create(
-                plan.input.schema(),
-                plan.schema.clone(),
-                plan.exprs.clone(),
-            )?))
+            Ok(Box::new(
+                ExpressionTransform::try_create(
+                    plan.input.schema(),
+                    plan.schema.clone(),
+                    plan.exprs.clone(),
+                )?
+                .with_timezone(tz.clone()),
+            ))
         })?;
         Ok(pipeline)
     }
 
     fn visit_projection(&mut self, node: &ProjectionPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*node.input)?;
+        let tz = self.ctx.get_settings().get_timezone()?;
         pipeline.add_simple_transform(|| {
-            Ok(Box::new(ProjectionTransform::try_create(
-                node.input.schema(),
-                node.schema(),
-                node.expr.clone(),
-            )?))
+            Ok(Box::new(
+                ProjectionTransform::try_create(
+                    node.input.schema(),
+                    node.schema(),
+                    node.expr.clone(),
+                )?
+                .with_timezone(tz.clone()),
+            ))
         })?;
         Ok(pipeline)
     }
@@ -207,24 +215,24 @@ impl PipelineBuilder {
 
     fn visit_filter(&mut self, node: &FilterPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*node.input)?;
+        let tz = self.ctx.get_settings().get_timezone()?;
         pipeline.add_simple_transform(|| {
-            Ok(Box::new(FilterTransform::try_create(
-                node.schema(),
-                node.predicate.clone(),
-                false,
-            )?))
+            Ok(Box::new(
+                FilterTransform::try_create(node.schema(), node.predicate.clone(), false)?
+                    .with_timezone(tz.clone()),
+            ))
         })?;
         Ok(pipeline)
     }
 
     fn visit_having(&mut self, node: &HavingPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*node.input)?;
+        let tz = self.ctx.get_settings().get_timezone()?;
         pipeline.add_simple_transform(|| {
-            Ok(Box::new(FilterTransform::try_create(
-                node.schema(),
-                node.predicate.clone(),
-                true,
-            )?))
+            Ok(Box::new(
+                FilterTransform::try_create(node.schema(), node.predicate.clone(), true)?
+                    .with_timezone(tz.clone()),
+            ))
         })?;
         Ok(pipeline)
     }
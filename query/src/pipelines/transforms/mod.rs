@@ -17,6 +17,7 @@ pub use transform_aggregator_partial::AggregatorPartialTransform;
 pub use transform_create_sets::CreateSetsTransform;
 pub use transform_create_sets::SubQueriesPuller;
 pub use transform_expression::ExpressionTransform;
+pub use transform_expression_evaluator::ExpressionEvaluator;
 pub use transform_expression_executor::ExpressionExecutor;
 pub use transform_filter::FilterTransform;
 pub use transform_group_by_final::GroupByFinalTransform;
@@ -34,6 +35,8 @@ mod transform_aggregator_final_test;
 #[cfg(test)]
 mod transform_aggregator_partial_test;
 #[cfg(test)]
+mod transform_expression_evaluator_test;
+#[cfg(test)]
 mod transform_expression_test;
 #[cfg(test)]
 mod transform_filter_test;
@@ -56,6 +59,7 @@ mod transform_aggregator_final;
 mod transform_aggregator_partial;
 mod transform_create_sets;
 mod transform_expression;
+mod transform_expression_evaluator;
 mod transform_expression_executor;
 mod transform_filter;
 mod transform_group_by_final;
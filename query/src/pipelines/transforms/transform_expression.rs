@@ -65,6 +65,11 @@ impl ExpressionTransform {
             executor,
         })
     }
+
+    pub fn with_timezone(mut self, tz: String) -> Self {
+        self.executor = self.executor.with_timezone(tz);
+        self
+    }
 }
 
 #[async_trait::async_trait]
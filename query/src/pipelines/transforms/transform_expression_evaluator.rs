@@ -0,0 +1,100 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::DataColumnWithField;
+use common_datavalues::prelude::Series;
+use common_datavalues::DataField;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::CastFunction;
+use common_functions::scalars::Function;
+use common_functions::scalars::FunctionFactory;
+use common_planners::Expression;
+
+/// ExpressionEvaluator evaluates a single expression against a block, without building an ExpressionChain.
+/// Literals, columns, unary/binary expressions, scalar functions and casts are covered.
+pub struct ExpressionEvaluator;
+
+impl ExpressionEvaluator {
+    pub fn eval(expr: &Expression, block: &DataBlock) -> Result<Series> {
+        Self::eval_column(expr, block)?.column().to_array()
+    }
+
+    fn eval_column(expr: &Expression, block: &DataBlock) -> Result<DataColumnWithField> {
+        let rows = block.num_rows();
+        match expr {
+            Expression::Alias(_, expr) => Self::eval_column(expr, block),
+            Expression::Column(name) => Ok(DataColumnWithField::new(
+                block.try_column_by_name(name)?.clone(),
+                block.schema().field_with_name(name)?.clone(),
+            )),
+            Expression::Literal {
+                value, data_type, ..
+            } => Ok(DataColumnWithField::new(
+                DataColumn::Constant(value.clone(), rows),
+                DataField::new(&expr.column_name(), data_type.clone(), value.is_null()),
+            )),
+            Expression::UnaryExpression { op, expr: arg } => {
+                let func = FunctionFactory::instance().get(op)?;
+                Self::eval_function(expr, func, &[arg.as_ref().clone()], block)
+            }
+            Expression::BinaryExpression { left, op, right } => {
+                let func = FunctionFactory::instance().get(op)?;
+                let args = [left.as_ref().clone(), right.as_ref().clone()];
+                Self::eval_function(expr, func, &args, block)
+            }
+            Expression::ScalarFunction { op, args } => {
+                let func = FunctionFactory::instance().get(op)?;
+                Self::eval_function(expr, func, args, block)
+            }
+            Expression::Cast {
+                expr: arg,
+                data_type,
+            } => {
+                let func = CastFunction::create("cast".to_string(), data_type.clone())?;
+                Self::eval_function(expr, func, &[arg.as_ref().clone()], block)
+            }
+            other => Err(ErrorCode::LogicalError(format!(
+                "Expression {:?} can not be evaluated by ExpressionEvaluator",
+                other
+            ))),
+        }
+    }
+
+    fn eval_function(
+        expr: &Expression,
+        func: Box<dyn Function>,
+        args: &[Expression],
+        block: &DataBlock,
+    ) -> Result<DataColumnWithField> {
+        let arg_columns = args
+            .iter()
+            .map(|arg| Self::eval_column(arg, block))
+            .collect::<Result<Vec<_>>>()?;
+        let arg_types = arg_columns
+            .iter()
+            .map(|c| c.data_type().clone())
+            .collect::<Vec<_>>();
+
+        let column = func.eval(&arg_columns, block.num_rows())?;
+        let field = DataField::new(
+            &expr.column_name(),
+            func.return_type(&arg_types)?,
+            func.nullable(block.schema().as_ref())?,
+        );
+        Ok(DataColumnWithField::new(column, field))
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+
+use crate::pipelines::transforms::*;
+
+fn eval_to_block(expr: &Expression, block: &DataBlock) -> Result<DataBlock> {
+    let series = ExpressionEvaluator::eval(expr, block)?;
+    let schema = DataSchemaRefExt::create(vec![DataField::new(
+        &expr.column_name(),
+        series.data_type(),
+        false,
+    )]);
+    Ok(DataBlock::create_by_array(schema, vec![series]))
+}
+
+#[test]
+fn test_expression_evaluator() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Int64, false),
+    ]);
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1i64, 6, 10]),
+        Series::new(vec![2i64, 3, 4]),
+    ]);
+
+    // a + b
+    let result = eval_to_block(&add(col("a"), col("b")), &block)?;
+    let expected = vec![
+        "+---------+",
+        "| (a + b) |",
+        "+---------+",
+        "| 3       |",
+        "| 9       |",
+        "| 14      |",
+        "+---------+",
+    ];
+    assert_blocks_eq(expected, &[result]);
+
+    // a > 5
+    let result = eval_to_block(&col("a").gt(lit(5i64)), &block)?;
+    let expected = vec![
+        "+---------+",
+        "| (a > 5) |",
+        "+---------+",
+        "| false   |",
+        "| true    |",
+        "| true    |",
+        "+---------+",
+    ];
+    assert_blocks_eq(expected, &[result]);
+
+    // literal, expanded to the rows of the block
+    let result = eval_to_block(&lit(7i64), &block)?;
+    let expected = vec![
+        "+---+", "| 7 |", "+---+", "| 7 |", "| 7 |", "| 7 |", "+---+",
+    ];
+    assert_blocks_eq(expected, &[result]);
+
+    // unknown columns are reported
+    assert!(ExpressionEvaluator::eval(&col("c"), &block).is_err());
+    Ok(())
+}
@@ -20,6 +20,7 @@ use common_datavalues::columns::DataColumn;
 use common_datavalues::prelude::DataColumnWithField;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Expression;
@@ -38,6 +39,8 @@ pub struct ExpressionExecutor {
     chain: Arc<ExpressionChain>,
     // whether to perform alias action in executor
     alias_project: bool,
+    // the timezone the functions take the DateTime values without a timezone in
+    timezone: Option<String>,
 }
 
 impl ExpressionExecutor {
@@ -56,9 +59,17 @@ impl ExpressionExecutor {
             output_schema,
             chain: Arc::new(chain),
             alias_project,
+            timezone: None,
         })
     }
 
+    /// Evaluates the functions on the DateTime values without a timezone in `tz`, e.g. the
+    /// timezone of the session. By default, they are in UTC.
+    pub fn with_timezone(mut self, tz: String) -> Self {
+        self.timezone = Some(tz);
+        self
+    }
+
     pub fn validate(&self) -> Result<()> {
         Ok(())
     }
@@ -115,7 +126,7 @@ impl ExpressionExecutor {
                                 "Arguments must be prepared before function transform",
                             )
                         })?;
-                        arg_columns.push(column);
+                        arg_columns.push(self.apply_timezone(column));
                     }
 
                     let func = f.to_function()?;
@@ -175,4 +186,19 @@ impl ExpressionExecutor {
             project_columns,
         ))
     }
+
+    fn apply_timezone(&self, column: DataColumnWithField) -> DataColumnWithField {
+        match (&self.timezone, column.data_type()) {
+            (Some(tz), DataType::DateTime32(None)) => {
+                let field = column.field();
+                let field = DataField::new(
+                    field.name(),
+                    DataType::DateTime32(Some(tz.clone())),
+                    field.is_nullable(),
+                );
+                DataColumnWithField::new(column.column().clone(), field)
+            }
+            _ => column,
+        }
+    }
 }
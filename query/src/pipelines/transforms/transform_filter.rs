@@ -61,6 +61,12 @@ impl FilterTransform {
             having,
         })
     }
+
+    pub fn with_timezone(mut self, tz: String) -> Self {
+        let executor = self.executor.as_ref().clone().with_timezone(tz);
+        self.executor = Arc::new(executor);
+        self
+    }
 }
 
 #[async_trait::async_trait]
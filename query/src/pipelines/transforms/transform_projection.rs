@@ -52,6 +52,11 @@ impl ProjectionTransform {
             input: Arc::new(EmptyProcessor::create()),
         })
     }
+
+    pub fn with_timezone(mut self, tz: String) -> Self {
+        self.executor = self.executor.with_timezone(tz);
+        self
+    }
 }
 
 #[async_trait::async_trait]
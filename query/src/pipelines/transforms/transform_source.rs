@@ -40,17 +40,16 @@ impl SourceTransform {
     }
 
     async fn read_table(&self, db: &str) -> Result<SendableDataBlockStream> {
-        let table_id = self.source_plan.table_id;
-        let table_ver = self.source_plan.table_version;
+        let table_info = &self.source_plan.table_info;
         let table = if self.source_plan.tbl_args.is_none() {
             self.ctx
-                .get_table_by_id(db, table_id, table_ver)?
+                .get_table_by_id(db, table_info.table_id, None)?
                 .raw()
                 .clone()
         } else {
             let func_meta = self
                 .ctx
-                .get_table_function(&self.source_plan.table, self.source_plan.tbl_args.clone())?;
+                .get_table_function(&table_info.name, self.source_plan.tbl_args.clone())?;
             func_meta.raw().clone().as_table()
         };
         let table_stream = table.read(self.ctx.clone(), &self.source_plan);
@@ -81,8 +80,8 @@ impl Processor for SourceTransform {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let db = self.source_plan.db.clone();
-        let table = self.source_plan.table.clone();
+        let db = self.source_plan.table_info.db.clone();
+        let table = self.source_plan.table_info.name.clone();
 
         tracing::debug!("execute, table:{:#}.{:#} ...", db, table);
 
@@ -90,7 +89,7 @@ impl Processor for SourceTransform {
         // Because the table may not support require columns
         Ok(Box::pin(CorrectWithSchemaStream::new(
             self.read_table(&db).await?,
-            self.source_plan.schema.clone(),
+            self.source_plan.schema(),
         )))
     }
 }
@@ -67,7 +67,10 @@ impl InteractiveWorkerBase {
         let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
 
         match plan {
-            PlanNode::InsertInto(insert) => Self::process_insert_query(insert, ch_ctx, ctx).await,
+            // the data of `INSERT INTO ... SELECT` is not sent by the client
+            PlanNode::InsertInto(insert) if insert.select_plan.is_none() => {
+                Self::process_insert_query(insert, ch_ctx, ctx).await
+            }
             _ => {
                 let start = Instant::now();
                 let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
@@ -152,27 +155,14 @@ pub struct FromClickHouseBlockStream {
 }
 
 impl futures::stream::Stream for FromClickHouseBlockStream {
-    type Item = DataBlock;
+    type Item = Result<DataBlock>;
 
     fn poll_next(
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        self.input.poll_next_unpin(cx).map(|x| match x {
-            Some(v) => {
-                let block = from_clickhouse_block(self.schema.clone(), v);
-                match block {
-                    Ok(block) => Some(block),
-                    Err(e) => {
-                        log::error!(
-                            "failed to convert ClickHouseBlock to block , breaking out, {:?}",
-                            e
-                        );
-                        None
-                    }
-                }
-            }
-            _ => None,
-        })
+        self.input
+            .poll_next_unpin(cx)
+            .map(|x| x.map(|v| from_clickhouse_block(self.schema.clone(), v)))
     }
 }
@@ -21,6 +21,7 @@ mod mysql_handler_test;
 mod mysql_handler;
 mod mysql_interactive_worker;
 mod mysql_metrics;
+mod mysql_prepared_statement;
 mod mysql_session;
 mod reject_connection;
 mod writers;
@@ -28,6 +28,8 @@ use mysql::Conn;
 use mysql::FromRowError;
 use mysql::Row;
 
+use crate::servers::mysql::mysql_session::connection_error;
+use crate::servers::mysql::mysql_session::is_disconnect;
 use crate::servers::MySQLHandler;
 use crate::tests::SessionManagerBuilder;
 
@@ -48,6 +50,174 @@ async fn test_use_database_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_prepared_statement() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+
+    let statement = connection
+        .prep("SELECT number FROM numbers(?) WHERE number != ?")
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare error")?;
+    assert_eq!(statement.num_params(), 2);
+
+    let received_data: Vec<u64> = connection
+        .exec(&statement, (3, 1))
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+    assert_eq!(received_data, vec![0, 2]);
+
+    let received_data: Vec<u64> = connection
+        .exec(&statement, (5, 4))
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+    assert_eq!(received_data, vec![0, 1, 2, 3]);
+
+    // placeholders in quotes are not parameters
+    let received_data: Vec<String> = connection
+        .exec("SELECT '?' FROM numbers(?)", (1,))
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+    assert_eq!(received_data, vec!["?"]);
+
+    // parameters are literals, quotes in them can not end the literal
+    for param in ["x' OR 1=1 --", "x\\' OR 1=1 --", "x'); SELECT ('1"] {
+        let received_data: Vec<String> = connection
+            .exec("SELECT ? FROM numbers(2) WHERE number = 0", (param,))
+            .map_err_to_code(ErrorCode::UnknownException, || "Execute error")?;
+        assert_eq!(received_data, vec![param]);
+    }
+
+    connection
+        .close(statement)
+        .map_err_to_code(ErrorCode::UnknownException, || "Close error")?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_insert_affected_rows() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+
+    query::<EmptyRow>(&mut connection, "CREATE TABLE t1(c1 UInt64) ENGINE = Null")?;
+    query::<EmptyRow>(&mut connection, "INSERT INTO t1 VALUES (1), (2), (3)")?;
+    assert_eq!(connection.affected_rows(), 3);
+
+    query::<EmptyRow>(
+        &mut connection,
+        "INSERT INTO t1 SELECT number FROM numbers(5)",
+    )?;
+    assert_eq!(connection.affected_rows(), 5);
+
+    // the statements writing nothing affect no rows
+    query::<EmptyRow>(&mut connection, "SET max_threads = 1")?;
+    assert_eq!(connection.affected_rows(), 0);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_datetime_in_session_timezone() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+
+    // 2021-08-26 17:46:40 UTC
+    let sql = "SELECT toDateTime(1630000000)";
+    let received_data: Vec<String> = query(&mut connection, sql)?;
+    assert_eq!(received_data, vec!["2021-08-26 17:46:40"]);
+
+    // 2021-08-31 16:30:00 UTC, it is 2021-09-01 00:30:00 in Asia/Shanghai
+    let functions_sql = "SELECT toYYYYMM(toDateTime(1630427400 + number)), \
+        toStartOfDay(toDateTime(1630427400 + number)), toDayOfMonth(toDateTime(1630427400)) \
+        FROM numbers(1)";
+    let received_data: Vec<(u32, String, u8)> = query(&mut connection, functions_sql)?;
+    assert_eq!(received_data, vec![(
+        202108,
+        "2021-08-31 00:00:00".to_string(),
+        31
+    )]);
+
+    query::<EmptyRow>(&mut connection, "SET timezone='Asia/Shanghai'")?;
+    let received_data: Vec<String> = query(&mut connection, sql)?;
+    assert_eq!(received_data, vec!["2021-08-27 01:46:40"]);
+
+    // the functions take the DateTime values in the timezone of the session
+    let received_data: Vec<(u32, String, u8)> = query(&mut connection, functions_sql)?;
+    assert_eq!(received_data, vec![(
+        202109,
+        "2021-09-01 00:00:00".to_string(),
+        1
+    )]);
+
+    // the unknown timezone is rejected, the session keeps its timezone
+    assert!(query::<EmptyRow>(&mut connection, "SET timezone='Mars/Olympus'").is_err());
+    let received_data: Vec<String> = query(&mut connection, sql)?;
+    assert_eq!(received_data, vec!["2021-08-27 01:46:40"]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_idle_connection_is_closed() -> Result<()> {
+    let mut handler = MySQLHandler::create(
+        SessionManagerBuilder::create()
+            .max_sessions(1)
+            .mysql_handler_idle_timeout(1)
+            .build()?,
+    );
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+    let received_data: Vec<String> = query(&mut connection, "SELECT database()")?;
+    assert_eq!(received_data, vec!["default"]);
+
+    // Wait for the connection to be reaped
+    std::thread::sleep(Duration::from_secs(3));
+    assert!(query::<EmptyRow>(&mut connection, "SELECT 1").is_err());
+
+    // The session is released for new connections
+    let mut connection = create_connection(runnable_server.port())?;
+    let received_data: Vec<u8> = query(&mut connection, "SELECT 1")?;
+    assert_eq!(received_data, vec![1]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_long_query_is_cancelled() -> Result<()> {
+    let mut handler =
+        MySQLHandler::create(SessionManagerBuilder::create().max_sessions(1).build()?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+    query::<EmptyRow>(&mut connection, "SET max_execute_time = 1")?;
+
+    let result = connection.query::<u64, &str>("SELECT sum(number) FROM numbers_mt(100000000000)");
+    match result {
+        Ok(_) => assert!(false, "Expected the query to be cancelled"),
+        Err(error) => assert!(error
+            .to_string()
+            .contains("exceeds max_execute_time of 1 seconds")),
+    }
+
+    // The connection is still usable
+    let received_data: Vec<u8> = query(&mut connection, "SELECT 1")?;
+    assert_eq!(received_data, vec![1]);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_rejected_session_with_sequence() -> Result<()> {
     let mut handler =
@@ -146,6 +316,59 @@ async fn test_rejected_session_with_parallel() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_active_connections() -> Result<()> {
+    let sessions = SessionManagerBuilder::create().max_sessions(2).build()?;
+    let mut handler = MySQLHandler::create(sessions.clone());
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+    let received_data: Vec<u8> = query(&mut connection, "SELECT 1")?;
+    assert_eq!(received_data, vec![1]);
+    assert_eq!(sessions.get_active_connections(), 1);
+
+    // The client quits (by COM_QUIT), the connection is torn down
+    drop(connection);
+    for _ in 0..50 {
+        if sessions.get_active_connections() == 0 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    assert_eq!(sessions.get_active_connections(), 0);
+    // a clean quit is not logged as an error
+    assert_eq!(sessions.get_failed_connections(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_disconnect_errors() {
+    // the client goes away, or the session is killed
+    for kind in [
+        std::io::ErrorKind::ConnectionReset,
+        std::io::ErrorKind::BrokenPipe,
+        std::io::ErrorKind::ConnectionAborted,
+        std::io::ErrorKind::UnexpectedEof,
+    ] {
+        let error = ErrorCode::from(std::io::Error::new(kind, "platform dependent message"));
+        let error = connection_error(error, Some(kind));
+        assert!(is_disconnect(&error), "{:?}", error);
+    }
+    assert!(is_disconnect(&ErrorCode::AbortedSession(
+        "Aborting session"
+    )));
+
+    // the unexpected ones are still logged as errors
+    assert!(!is_disconnect(&ErrorCode::LogicalError("Logical error")));
+    let kind = std::io::ErrorKind::PermissionDenied;
+    let error = connection_error(ErrorCode::from(std::io::Error::from(kind)), Some(kind));
+    assert!(!is_disconnect(&error), "{:?}", error);
+    let error = connection_error(ErrorCode::LogicalError("Logical error"), None);
+    assert!(!is_disconnect(&error), "{:?}", error);
+}
+
 fn query<T: FromRow>(connection: &mut Conn, query: &str) -> Result<Vec<T>> {
     connection
         .query::<T, &str>(query)
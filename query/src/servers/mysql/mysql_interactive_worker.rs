@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
 use std::time::Instant;
 
 use common_base::tokio;
@@ -22,6 +25,9 @@ use common_exception::Result;
 use common_io::prelude::*;
 use common_planners::PlanNode;
 use metrics::histogram;
+use msql_srv::Column;
+use msql_srv::ColumnFlags;
+use msql_srv::ColumnType;
 use msql_srv::ErrorKind;
 use msql_srv::InitWriter;
 use msql_srv::MysqlShim;
@@ -32,19 +38,26 @@ use rand::RngCore;
 use tokio_stream::StreamExt;
 
 use crate::interpreters::InterpreterFactory;
+use crate::servers::mysql::mysql_prepared_statement::PreparedStatement;
+use crate::servers::mysql::mysql_session::ConnectionActivity;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
+use crate::servers::mysql::writers::QueryResult;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sessions::SessionRef;
+use crate::sql::DfStatement;
 use crate::sql::PlanParser;
 
 struct InteractiveWorkerBase<W: std::io::Write> {
     session: SessionRef,
     generic_hold: PhantomData<W>,
+    statements: HashMap<u32, PreparedStatement>,
+    next_statement_id: u32,
 }
 
 pub struct InteractiveWorker<W: std::io::Write> {
     session: SessionRef,
+    activity: Arc<ConnectionActivity>,
     base: InteractiveWorkerBase<W>,
     version: String,
     salt: [u8; 20],
@@ -116,6 +129,7 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
     }
 
     fn on_prepare(&mut self, query: &str, writer: StatementMetaWriter<W>) -> Result<()> {
+        let _activity = self.activity.enter();
         if self.session.is_aborting() {
             writer.error(
                 ErrorKind::ER_ABORTING_CONNECTION,
@@ -136,6 +150,7 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
         param: ParamParser,
         writer: QueryResultWriter<W>,
     ) -> Result<()> {
+        let _activity = self.activity.enter();
         if self.session.is_aborting() {
             writer.error(
                 ErrorKind::ER_ABORTING_CONNECTION,
@@ -155,6 +170,7 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
     }
 
     fn on_query(&mut self, query: &str, writer: QueryResultWriter<W>) -> Result<()> {
+        let _activity = self.activity.enter();
         if self.session.is_aborting() {
             writer.error(
                 ErrorKind::ER_ABORTING_CONNECTION,
@@ -166,7 +182,8 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
             ));
         }
 
-        let mut writer = DFQueryResultWriter::create(writer);
+        let tz = self.session.get_settings().get_tz()?;
+        let mut writer = DFQueryResultWriter::create(writer, tz);
 
         match InteractiveWorkerBase::<W>::build_runtime() {
             Ok(runtime) => {
@@ -192,6 +209,7 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
     }
 
     fn on_init(&mut self, database_name: &str, writer: InitWriter<W>) -> Result<()> {
+        let _activity = self.activity.enter();
         if self.session.is_aborting() {
             writer.error(
                 ErrorKind::ER_ABORTING_CONNECTION,
@@ -208,30 +226,69 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
 }
 
 impl<W: std::io::Write> InteractiveWorkerBase<W> {
-    fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Prepare is not support in Databend.".as_bytes(),
-        )?;
+    fn do_prepare(&mut self, query: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
+        let statement = match PreparedStatement::try_create(query) {
+            Ok(statement) => statement,
+            Err(error) => {
+                writer.error(ErrorKind::ER_PARSE_ERROR, error.message().as_bytes())?;
+                return Ok(());
+            }
+        };
+
+        // the types of the parameters are given by the client on execution
+        let params = (0..statement.params_count())
+            .map(|_| Column {
+                table: String::new(),
+                column: "?".to_string(),
+                coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+                colflags: ColumnFlags::empty(),
+            })
+            .collect::<Vec<_>>();
+
+        self.next_statement_id = self.next_statement_id.wrapping_add(1);
+        let id = self.next_statement_id;
+        self.statements.insert(id, statement);
+        writer.reply(id, &params, &[])?;
         Ok(())
     }
 
     fn do_execute(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
+        id: u32,
+        params: ParamParser<'_>,
         writer: QueryResultWriter<'_, W>,
     ) -> Result<()> {
-        writer.error(
-            ErrorKind::ER_UNKNOWN_ERROR,
-            "Execute is not support in Databend.".as_bytes(),
-        )?;
-        Ok(())
+        let tz = self.session.get_settings().get_tz()?;
+        let mut writer = DFQueryResultWriter::create(writer, tz);
+        let bound = match self.statements.get(&id) {
+            None => Err(ErrorCode::BadArguments(format!(
+                "Unknown prepared statement id: {}",
+                id
+            ))),
+            Some(statement) => statement
+                .bind(params)
+                .map(|bound| (statement.query().to_string(), bound)),
+        };
+
+        let (query, statement) = match bound {
+            Ok(bound) => bound,
+            Err(error) => return writer.write(Err(error)),
+        };
+
+        match Self::build_runtime() {
+            Ok(runtime) => {
+                let blocks = runtime.block_on(self.do_execute_statement(&query, &statement));
+                writer.write(blocks)
+            }
+            Err(error) => writer.write(Err(error)),
+        }
     }
 
-    fn do_close(&mut self, _: u32) {}
+    fn do_close(&mut self, id: u32) {
+        self.statements.remove(&id);
+    }
 
-    async fn do_query(&mut self, query: &str) -> Result<(Vec<DataBlock>, String)> {
+    async fn do_query(&mut self, query: &str) -> Result<QueryResult> {
         log::debug!("{}", query);
 
         let context = self.session.create_context().await?;
@@ -245,15 +302,19 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
             .find(|v| v.error_code.is_some())
             .and_then(|x| x.error_code)
         {
-            None => Self::exec_query(plan, &context).await,
-            Some(hint_error_code) => match Self::exec_query(plan, &context).await {
+            None => self.exec_query_with_timeout(plan, &context).await,
+            Some(hint_error_code) => match self.exec_query_with_timeout(plan, &context).await {
                 Ok(_) => Err(ErrorCode::UnexpectedError(format!(
                     "Expected server error code: {} but got: Ok.",
                     hint_error_code
                 ))),
                 Err(error_code) => {
                     if hint_error_code == error_code.code() {
-                        Ok((vec![DataBlock::empty()], String::from("")))
+                        Ok(QueryResult::create(
+                            vec![DataBlock::empty()],
+                            0,
+                            String::from(""),
+                        ))
                     } else {
                         let actual_code = error_code.code();
                         Err(error_code.add_message(format!(
@@ -266,10 +327,47 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         }
     }
 
+    async fn do_execute_statement(
+        &mut self,
+        query: &str,
+        statement: &DfStatement,
+    ) -> Result<QueryResult> {
+        log::debug!("{}", query);
+
+        let context = self.session.create_context().await?;
+        context.attach_query_str(query);
+
+        let plan = PlanParser::create(context.clone()).statement_to_plan(statement);
+        self.exec_query_with_timeout(plan, &context).await
+    }
+
+    async fn exec_query_with_timeout(
+        &self,
+        plan: Result<PlanNode>,
+        context: &DatabendQueryContextRef,
+    ) -> Result<QueryResult> {
+        let max_execute_time = context.get_settings().get_max_execute_time()?;
+        if max_execute_time == 0 {
+            return Self::exec_query(plan, context).await;
+        }
+
+        let timeout = Duration::from_secs(max_execute_time);
+        match tokio::time::timeout(timeout, Self::exec_query(plan, context)).await {
+            Ok(query_result) => query_result,
+            Err(_) => {
+                self.session.force_kill_query();
+                Err(ErrorCode::Timeout(format!(
+                    "Query is cancelled, as it exceeds max_execute_time of {} seconds",
+                    max_execute_time
+                )))
+            }
+        }
+    }
+
     async fn exec_query(
         plan: Result<PlanNode>,
         context: &DatabendQueryContextRef,
-    ) -> Result<(Vec<DataBlock>, String)> {
+    ) -> Result<QueryResult> {
         let instant = Instant::now();
 
         let interpreter = InterpreterFactory::get(context.clone(), plan?)?;
@@ -281,7 +379,10 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
 
         let collector = data_stream.collect::<Result<Vec<DataBlock>>>();
         let query_result = collector.await;
-        query_result.map(|data| (data, Self::extra_info(context, instant)))
+        query_result.map(|data| {
+            let affected_rows = context.get_affected_rows() as u64;
+            QueryResult::create(data, affected_rows, Self::extra_info(context, instant))
+        })
     }
 
     fn extra_info(context: &DatabendQueryContextRef, instant: Instant) -> String {
@@ -319,7 +420,7 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
 }
 
 impl<W: std::io::Write> InteractiveWorker<W> {
-    pub fn create(session: SessionRef) -> InteractiveWorker<W> {
+    pub fn create(session: SessionRef, activity: Arc<ConnectionActivity>) -> InteractiveWorker<W> {
         let mut bs = vec![0u8; 20];
         let mut rng = rand::thread_rng();
         rng.fill_bytes(bs.as_mut());
@@ -334,9 +435,12 @@ impl<W: std::io::Write> InteractiveWorker<W> {
 
         InteractiveWorker::<W> {
             session: session.clone(),
+            activity,
             base: InteractiveWorkerBase::<W> {
                 session,
                 generic_hold: PhantomData::default(),
+                statements: HashMap::new(),
+                next_statement_id: 0,
             },
             salt: scramble,
             // TODO: version
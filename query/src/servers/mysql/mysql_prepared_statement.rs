@@ -0,0 +1,143 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDateTime;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use msql_srv::ParamParser;
+use msql_srv::Value;
+use msql_srv::ValueInner;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+use sqlparser::tokenizer::Tokenizer;
+
+use crate::sql::DfParser;
+use crate::sql::DfStatement;
+
+/// A statement prepared by `COM_STMT_PREPARE`.
+///
+/// The statement is tokenized once, each `COM_STMT_EXECUTE` binds the parameters as literal
+/// tokens in place of the `?` placeholders, so a parameter is never read as SQL. Binding happens
+/// before planning, because the arguments of table functions, e.g. `numbers(?)`, are resolved
+/// by the planner.
+pub struct PreparedStatement {
+    query: String,
+    tokens: Vec<Token>,
+    // The positions of the placeholders in `tokens`.
+    placeholders: Vec<usize>,
+}
+
+impl PreparedStatement {
+    pub fn try_create(query: &str) -> Result<PreparedStatement> {
+        let dialect = GenericDialect {};
+        let tokens = Tokenizer::new(&dialect, query)
+            .tokenize()
+            .map_err(|e| ErrorCode::from(ParserError::from(e)))?;
+        let placeholders = tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| **token == Token::Char('?'))
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>();
+
+        let statement = PreparedStatement {
+            query: query.to_string(),
+            tokens,
+            placeholders,
+        };
+
+        // report syntax errors on prepare rather than on every execute
+        let nulls = statement.placeholders.iter().map(|_| Self::null());
+        statement.parse(nulls.collect())?;
+        Ok(statement)
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn params_count(&self) -> usize {
+        self.placeholders.len()
+    }
+
+    /// The statement, with the parameters in place of the placeholders.
+    pub fn bind(&self, params: ParamParser) -> Result<DfStatement> {
+        let literals = params
+            .into_iter()
+            .take(self.params_count())
+            .map(|param| Self::literal(param.value))
+            .collect::<Result<Vec<_>>>()?;
+
+        if literals.len() != self.params_count() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Prepared statement expects {} parameters, but got {}",
+                self.params_count(),
+                literals.len()
+            )));
+        }
+        self.parse(literals)
+    }
+
+    fn parse(&self, literals: Vec<Vec<Token>>) -> Result<DfStatement> {
+        let mut tokens = Vec::with_capacity(self.tokens.len() + literals.len());
+        let mut from = 0;
+        for (pos, literal) in self.placeholders.iter().zip(literals) {
+            tokens.extend_from_slice(&self.tokens[from..*pos]);
+            tokens.extend(literal);
+            from = pos + 1;
+        }
+        tokens.extend_from_slice(&self.tokens[from..]);
+
+        let mut statements = DfParser::parse_tokens(tokens)?;
+        match statements.len() {
+            1 => Ok(statements.remove(0)),
+            _ => Err(ErrorCode::SyntaxException(
+                "Prepared statement only supports single query",
+            )),
+        }
+    }
+
+    fn null() -> Vec<Token> {
+        vec![Token::make_keyword("NULL")]
+    }
+
+    fn number<T: ToString>(v: T) -> Vec<Token> {
+        let v = v.to_string();
+        match v.strip_prefix('-') {
+            Some(abs) => vec![Token::Minus, Token::Number(abs.to_string(), false)],
+            None => vec![Token::Number(v, false)],
+        }
+    }
+
+    fn string(v: String) -> Vec<Token> {
+        vec![Token::SingleQuotedString(v)]
+    }
+
+    fn literal(value: Value) -> Result<Vec<Token>> {
+        match value.into_inner() {
+            ValueInner::NULL => Ok(Self::null()),
+            ValueInner::Int(v) => Ok(Self::number(v)),
+            ValueInner::UInt(v) => Ok(Self::number(v)),
+            ValueInner::Double(v) if v.is_finite() => Ok(Self::number(v)),
+            ValueInner::Bytes(v) => Ok(Self::string(String::from_utf8_lossy(v).into_owned())),
+            ValueInner::Date(_) => Ok(Self::string(NaiveDateTime::from(value).date().to_string())),
+            ValueInner::Datetime(_) => Ok(Self::string(NaiveDateTime::from(value).to_string())),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unsupported prepared statement parameter: {:?}",
+                value
+            ))),
+        }
+    }
+}
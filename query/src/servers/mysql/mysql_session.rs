@@ -12,41 +12,147 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+use std::io::Read;
+use std::io::Write;
 use std::net::Shutdown;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
+use common_base::tokio;
 use common_base::tokio::net::TcpStream;
+use common_exception::exception::ABORT_CONNECTION;
 use common_exception::exception::ABORT_SESSION;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
+use common_infallible::Mutex;
 use msql_srv::MysqlIntermediary;
 
 use crate::servers::mysql::mysql_interactive_worker::InteractiveWorker;
 use crate::sessions::SessionRef;
 
+/// Tracks when a connection last handled a command, for the idle timeout.
+pub struct ConnectionActivity {
+    busy: AtomicBool,
+    closed: AtomicBool,
+    last_active: Mutex<Instant>,
+}
+
+impl ConnectionActivity {
+    pub fn create() -> Arc<ConnectionActivity> {
+        Arc::new(ConnectionActivity {
+            busy: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            last_active: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Marks the connection busy until the returned guard is dropped.
+    pub fn enter(self: &Arc<Self>) -> ActivityGuard {
+        self.busy.store(true, Ordering::Relaxed);
+        ActivityGuard(self.clone())
+    }
+
+    fn idle_for(&self) -> Duration {
+        match self.busy.load(Ordering::Relaxed) {
+            true => Duration::ZERO,
+            false => self.last_active.lock().elapsed(),
+        }
+    }
+}
+
+pub struct ActivityGuard(Arc<ConnectionActivity>);
+
+impl Drop for ActivityGuard {
+    fn drop(&mut self) {
+        *self.0.last_active.lock() = Instant::now();
+        self.0.busy.store(false, Ordering::Relaxed);
+    }
+}
+
 pub struct MySQLConnection;
 
 impl MySQLConnection {
     pub fn run_on_stream(session: SessionRef, stream: TcpStream) -> Result<()> {
         let blocking_stream = Self::convert_stream(stream)?;
         MySQLConnection::attach_session(&session, &blocking_stream)?;
+        let connection_guard = session.get_sessions_manager().enter_connection();
+
+        let activity = ConnectionActivity::create();
+        let idle_timeout = session.get_config().query.mysql_handler_idle_timeout;
+        if idle_timeout > 0 {
+            MySQLConnection::watch_idle(
+                session.clone(),
+                activity.clone(),
+                Duration::from_secs(idle_timeout),
+            );
+        }
+
         std::thread::spawn(move || {
-            MySQLConnection::session_executor(session, blocking_stream);
+            MySQLConnection::session_executor(session, blocking_stream, activity);
+            drop(connection_guard);
         });
 
         Ok(())
     }
 
-    fn session_executor(session: SessionRef, blocking_stream: std::net::TcpStream) {
-        let interactive_worker = InteractiveWorker::create(session);
-        if let Err(error) = MysqlIntermediary::run_on_tcp(interactive_worker, blocking_stream) {
-            if error.code() != ABORT_SESSION {
+    fn session_executor(
+        session: SessionRef,
+        blocking_stream: std::net::TcpStream,
+        activity: Arc<ConnectionActivity>,
+    ) {
+        let session_id = session.get_id();
+        let sessions = session.get_sessions_manager();
+        let interactive_worker = InteractiveWorker::create(session, activity.clone());
+        let res = ConnectionStream::create(blocking_stream).and_then(|(reader, writer)| {
+            let io_error = reader.io_error.clone();
+            MysqlIntermediary::run_on(interactive_worker, reader, writer)
+                .map_err(|error| connection_error(error, *io_error.lock()))
+        });
+        match res {
+            Ok(_) => log::debug!("MySQL connection of session {} is closed", session_id),
+            Err(error) if is_disconnect(&error) => log::debug!(
+                "MySQL connection of session {} is disconnected: {}",
+                session_id,
+                error
+            ),
+            Err(error) => {
+                sessions.fail_connection();
                 log::error!(
                     "Unexpected error occurred during query execution: {:?}",
                     error
-                );
+                )
             }
         };
+        activity.closed.store(true, Ordering::Relaxed);
+    }
+
+    // Closes the connection, by the io shutdown of the session, once it is idle for too long.
+    fn watch_idle(session: SessionRef, activity: Arc<ConnectionActivity>, timeout: Duration) {
+        let interval = timeout.min(Duration::from_secs(1));
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                if activity.closed.load(Ordering::Relaxed) || session.is_aborting() {
+                    break;
+                }
+
+                if activity.idle_for() >= timeout {
+                    log::info!(
+                        "MySQL connection of session {} is idle for more than {:?}, closing it",
+                        session.get_id(),
+                        timeout
+                    );
+                    // kill waits for the io shutdown, which is run by another task
+                    let _ = tokio::task::spawn_blocking(move || session.force_kill_session()).await;
+                    break;
+                }
+            }
+        });
     }
 
     fn attach_session(session: &SessionRef, blocking_stream: &std::net::TcpStream) -> Result<()> {
@@ -77,3 +183,81 @@ impl MySQLConnection {
         Ok(stream)
     }
 }
+
+/// The socket of a connection, which keeps the kind of the first io error of it.
+///
+/// `MysqlIntermediary` returns the io errors converted into `ErrorCode`s, which keep only their
+/// messages, the kind tells whether the connection ends as the peer goes away.
+struct ConnectionStream {
+    stream: std::net::TcpStream,
+    io_error: Arc<Mutex<Option<io::ErrorKind>>>,
+}
+
+impl ConnectionStream {
+    /// The reader and the writer of the stream, which share the kind of the first io error.
+    fn create(stream: std::net::TcpStream) -> Result<(Self, Self)> {
+        let io_error = Arc::new(Mutex::new(None));
+        let reader = ConnectionStream {
+            stream: stream.try_clone()?,
+            io_error: io_error.clone(),
+        };
+        let writer = ConnectionStream { stream, io_error };
+        Ok((reader, writer))
+    }
+
+    fn record<T>(&self, res: io::Result<T>) -> io::Result<T> {
+        if let Err(error) = &res {
+            if error.kind() != io::ErrorKind::Interrupted {
+                self.io_error.lock().get_or_insert(error.kind());
+            }
+        }
+        res
+    }
+}
+
+impl Read for ConnectionStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let res = self.stream.read(buf);
+        // the end of the stream is not an error of the read, but of the packet being read
+        if matches!(res, Ok(0)) && !buf.is_empty() {
+            self.io_error
+                .lock()
+                .get_or_insert(io::ErrorKind::UnexpectedEof);
+        }
+        self.record(res)
+    }
+}
+
+impl Write for ConnectionStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let res = self.stream.write(buf);
+        self.record(res)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let res = self.stream.flush();
+        self.record(res)
+    }
+}
+
+/// The error a connection ends by, it is an `ErrorCode::AbortedConnection` if the io of the
+/// connection fails as the peer goes away, or the socket is shut down under the reader.
+pub(crate) fn connection_error(error: ErrorCode, io_error: Option<io::ErrorKind>) -> ErrorCode {
+    match io_error {
+        Some(
+            io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::UnexpectedEof,
+        ) => ErrorCode::AbortedConnection(error.message()),
+        _ => error,
+    }
+}
+
+/// Whether the connection ends by an error which is not a failure of the server: the session is
+/// killed (and its io shut down), or the client goes away without COM_QUIT.
+///
+/// The io errors are classified by `connection_error`.
+pub(crate) fn is_disconnect(error: &ErrorCode) -> bool {
+    error.code() == ABORT_SESSION || error.code() == ABORT_CONNECTION
+}
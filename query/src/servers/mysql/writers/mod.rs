@@ -17,3 +17,4 @@ mod query_result_writer;
 
 pub use self::init_result_writer::DFInitResultWriter;
 pub use self::query_result_writer::DFQueryResultWriter;
+pub use self::query_result_writer::QueryResult;
@@ -25,19 +25,44 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use msql_srv::*;
 
+use crate::sessions::parse_timezone;
+
+/// The result of a query, the statements without a result set, e.g. INSERT, report the rows
+/// they write as the affected rows of the OK packet.
+pub struct QueryResult {
+    blocks: Vec<DataBlock>,
+    affected_rows: u64,
+    extra_info: String,
+}
+
+impl QueryResult {
+    pub fn create(blocks: Vec<DataBlock>, affected_rows: u64, extra_info: String) -> QueryResult {
+        QueryResult {
+            blocks,
+            affected_rows,
+            extra_info,
+        }
+    }
+}
+
 pub struct DFQueryResultWriter<'a, W: std::io::Write> {
     inner: Option<QueryResultWriter<'a, W>>,
+    // the DateTime values without a timezone are rendered in the timezone of the session
+    tz: Tz,
 }
 
 impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
-    pub fn create(inner: QueryResultWriter<'a, W>) -> DFQueryResultWriter<'a, W> {
-        DFQueryResultWriter::<'a, W> { inner: Some(inner) }
+    pub fn create(inner: QueryResultWriter<'a, W>, tz: Tz) -> DFQueryResultWriter<'a, W> {
+        DFQueryResultWriter::<'a, W> {
+            inner: Some(inner),
+            tz,
+        }
     }
 
-    pub fn write(&mut self, query_result: Result<(Vec<DataBlock>, String)>) -> Result<()> {
+    pub fn write(&mut self, query_result: Result<QueryResult>) -> Result<()> {
         if let Some(writer) = self.inner.take() {
             match query_result {
-                Ok((blocks, extra_info)) => Self::ok(blocks, extra_info, writer)?,
+                Ok(query_result) => Self::ok(query_result, &self.tz, writer)?,
                 Err(error) => Self::err(&error, writer)?,
             }
         }
@@ -45,12 +70,19 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
     }
 
     fn ok(
-        blocks: Vec<DataBlock>,
-        extra_info: String,
+        query_result: QueryResult,
+        session_tz: &Tz,
         dataset_writer: QueryResultWriter<'a, W>,
     ) -> Result<()> {
+        let QueryResult {
+            blocks,
+            affected_rows,
+            extra_info,
+        } = query_result;
+
         // XXX: num_columns == 0 may is error?
         let default_response = OkResponse {
+            affected_rows,
             info: extra_info,
             ..Default::default()
         };
@@ -157,9 +189,10 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                                     row_writer.write_col(v.to_date(&utc).naive_local())?
                                 }
                                 (DataType::DateTime32(tz), DataValue::UInt32(Some(v))) => {
-                                    let tz = tz.clone();
-                                    let tz = tz.unwrap_or_else(|| "UTC".to_string());
-                                    let tz: Tz = tz.parse().unwrap();
+                                    let tz = match tz {
+                                        Some(tz) => parse_timezone(tz)?,
+                                        None => *session_tz,
+                                    };
                                     row_writer.write_col(v.to_date_time(&tz).naive_local())?
                                 }
                                 (DataType::String, DataValue::String(Some(v))) => {
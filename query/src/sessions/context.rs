@@ -93,6 +93,15 @@ impl DatabendQueryContext {
         self.shared.progress.as_ref().get_and_reset()
     }
 
+    /// The rows written by the query, e.g. inserted, reported to the clients as the affected rows.
+    pub fn set_affected_rows(&self, rows: usize) {
+        self.shared.affected_rows.store(rows, Ordering::Relaxed);
+    }
+
+    pub fn get_affected_rows(&self) -> usize {
+        self.shared.affected_rows.load(Ordering::Relaxed)
+    }
+
     // Some table can estimate the approx total rows, such as NumbersTable
     pub fn add_total_rows_approx(&self, total_rows: usize) {
         self.shared
@@ -214,6 +223,10 @@ impl DatabendQueryContext {
         self.shared.attach_query_str(query);
     }
 
+    pub fn get_query_str(&self) -> String {
+        self.shared.get_query_str()
+    }
+
     pub fn attach_query_plan(&self, query_plan: &PlanNode) {
         self.shared.attach_query_plan(query_plan);
     }
@@ -221,6 +234,21 @@ impl DatabendQueryContext {
     pub fn get_sessions_manager(self: &Arc<Self>) -> SessionManagerRef {
         self.shared.session.get_sessions_manager()
     }
+
+    /// Runs the blocking (or CPU heavy) function on the blocking threads of the query runtime,
+    /// so that it does not starve the async tasks of the query, see `try_spawn_blocking`.
+    pub fn spawn_blocking<F, T>(&self, f: F) -> impl Future<Output = Result<T>>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let handle = self.try_spawn_blocking(f);
+        async move {
+            handle?
+                .await
+                .map_err(|join_error| ErrorCode::TokioError(join_error.to_string()))
+        }
+    }
 }
 
 impl TrySpawn for DatabendQueryContext {
@@ -233,6 +261,14 @@ impl TrySpawn for DatabendQueryContext {
     {
         Ok(self.shared.try_get_runtime()?.spawn(task))
     }
+
+    fn try_spawn_blocking<F, R>(&self, f: F) -> Result<JoinHandle<R>>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.shared.try_get_runtime()?.try_spawn_blocking(f)
+    }
 }
 
 impl std::fmt::Debug for DatabendQueryContext {
@@ -14,14 +14,15 @@
 
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Instant;
 
+use common_base::GlobalUniqName;
 use common_base::Progress;
 use common_base::Runtime;
 use common_exception::Result;
 use common_infallible::RwLock;
 use common_planners::PlanNode;
 use futures::future::AbortHandle;
-use uuid::Uuid;
 
 use crate::catalogs::impls::DatabaseCatalog;
 use crate::clusters::ClusterRef;
@@ -29,6 +30,9 @@ use crate::configs::Config;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 
+/// The prefix of the ids generated for the queries.
+pub const QUERY_ID_PREFIX: &str = "q-";
+
 /// Data that needs to be shared in a query context.
 /// This is very useful, for example, for queries:
 ///     USE database_1;
@@ -41,6 +45,7 @@ use crate::sessions::Settings;
 pub struct DatabendQueryContextShared {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) progress: Arc<Progress>,
+    pub(in crate::sessions) affected_rows: Arc<AtomicUsize>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
@@ -50,6 +55,7 @@ pub struct DatabendQueryContextShared {
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
+    pub(in crate::sessions) created_at: Instant,
 }
 
 impl DatabendQueryContextShared {
@@ -60,8 +66,11 @@ impl DatabendQueryContextShared {
     ) -> Arc<DatabendQueryContextShared> {
         Arc::new(DatabendQueryContextShared {
             conf,
-            init_query_id: Arc::new(RwLock::new(Uuid::new_v4().to_string())),
+            init_query_id: Arc::new(RwLock::new(GlobalUniqName::unique_with_prefix(
+                QUERY_ID_PREFIX,
+            ))),
             progress: Arc::new(Progress::create()),
+            affected_rows: Arc::new(AtomicUsize::new(0)),
             session,
             cluster_cache,
             runtime: Arc::new(RwLock::new(None)),
@@ -70,6 +79,7 @@ impl DatabendQueryContextShared {
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
             running_plan: Arc::new(RwLock::new(None)),
+            created_at: Instant::now(),
         })
     }
 
@@ -112,7 +122,10 @@ impl DatabendQueryContextShared {
             None => {
                 let settings = self.get_settings();
                 let max_threads = settings.get_max_threads()? as usize;
-                let runtime = Arc::new(Runtime::with_worker_threads(max_threads)?);
+                let runtime = Arc::new(Runtime::with_worker_threads_named(
+                    max_threads,
+                    "query-worker",
+                )?);
                 *query_runtime = Some(runtime.clone());
                 Ok(runtime)
             }
@@ -124,6 +137,11 @@ impl DatabendQueryContextShared {
         *running_query = Some(query.to_string());
     }
 
+    pub fn get_query_str(&self) -> String {
+        let running_query = self.running_query.read();
+        running_query.clone().unwrap_or_default()
+    }
+
     pub fn attach_query_plan(&self, plan: &PlanNode) {
         let mut running_plan = self.running_plan.write();
         *running_plan = Some(plan.clone());
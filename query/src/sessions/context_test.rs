@@ -0,0 +1,50 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::tokio;
+use common_base::TrySpawn;
+use common_exception::Result;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_context_spawn_blocking() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let test_thread = std::thread::current().id();
+    let query_ctx = ctx.clone();
+    let (query_thread, blocking_thread) = ctx
+        .try_spawn(async move {
+            let query_thread = std::thread::current().id();
+            let blocking_thread = query_ctx
+                .spawn_blocking(|| {
+                    let thread = std::thread::current();
+                    (thread.id(), thread.name().map(String::from))
+                })
+                .await?;
+            Result::Ok((query_thread, blocking_thread))
+        })?
+        .await
+        .unwrap()?;
+
+    // a blocking thread of the query runtime, not one of its workers
+    let (blocking_thread, blocking_thread_name) = blocking_thread;
+    assert_ne!(blocking_thread, test_thread);
+    assert_ne!(blocking_thread, query_thread);
+    assert!(blocking_thread_name.unwrap().starts_with("query-worker"));
+
+    // the result of the blocking function is returned
+    let sum = ctx.spawn_blocking(|| (1..=100).sum::<u64>()).await?;
+    assert_eq!(sum, 5050);
+
+    Ok(())
+}
@@ -14,3 +14,5 @@
 
 pub static METRIC_SESSION_CONNECT_NUMBERS: &str = "session.connect_numbers";
 pub static METRIC_SESSION_CLOSE_NUMBERS: &str = "session.close_numbers";
+pub static METRIC_SESSION_ACTIVE_CONNECTIONS: &str = "session.active_connections";
+pub static METRIC_SESSION_FAILED_CONNECTIONS: &str = "session.failed_connections";
@@ -15,9 +15,13 @@
 #[macro_use]
 mod macros;
 
+#[cfg(test)]
+mod context_test;
+
 mod context;
 mod context_shared;
 mod metrics;
+mod query_log;
 mod session;
 mod session_info;
 mod session_ref;
@@ -29,9 +33,13 @@ mod settings;
 pub use context::DatabendQueryContext;
 pub use context::DatabendQueryContextRef;
 pub use context_shared::DatabendQueryContextShared;
+pub use query_log::QueryLogRecord;
+pub use query_log::QUERY_LOG_CAPACITY;
 pub use session::Session;
 pub use session_info::ProcessInfo;
 pub use session_ref::SessionRef;
+pub use sessions::ConnectionGuard;
 pub use sessions::SessionManager;
 pub use sessions::SessionManagerRef;
+pub use settings::parse_timezone;
 pub use settings::Settings;
@@ -0,0 +1,33 @@
+// Copyright 2020 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+/// The number of the latest completed queries kept by the `system.query_log` table.
+pub const QUERY_LOG_CAPACITY: usize = 1024;
+
+/// A completed query, successful or not.
+#[derive(Clone, Debug)]
+pub struct QueryLogRecord {
+    pub query_id: String,
+    // the plan of the query, e.g. `Select` or `CreateTable`
+    pub kind: String,
+    pub sql: String,
+    pub start_time: SystemTime,
+    pub duration: Duration,
+    pub read_rows: usize,
+    pub read_bytes: usize,
+    pub error: Option<String>,
+}
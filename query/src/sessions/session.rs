@@ -174,6 +174,10 @@ impl Session {
         self.mutable_state.lock().session_settings.clone()
     }
 
+    pub fn get_config(self: &Arc<Self>) -> Config {
+        self.config.clone()
+    }
+
     pub fn get_sessions_manager(self: &Arc<Self>) -> SessionManagerRef {
         self.sessions.clone()
     }
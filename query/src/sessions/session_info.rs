@@ -14,6 +14,7 @@
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::sessions::session::MutableStatus;
 use crate::sessions::Session;
@@ -28,6 +29,10 @@ pub struct ProcessInfo {
     pub settings: Arc<Settings>,
     pub client_address: Option<SocketAddr>,
     pub session_extra_info: Option<String>,
+    // the query running in the session, if any
+    pub query_id: Option<String>,
+    pub query: Option<String>,
+    pub query_elapsed: Option<Duration>,
 }
 
 impl Session {
@@ -37,6 +42,7 @@ impl Session {
     }
 
     fn to_process_info(self: &Arc<Self>, status: &MutableStatus) -> ProcessInfo {
+        let context_shared = status.context_shared.as_ref();
         ProcessInfo {
             id: self.id.clone(),
             typ: self.typ.clone(),
@@ -45,6 +51,9 @@ impl Session {
             settings: status.session_settings.clone(),
             client_address: status.client_host,
             session_extra_info: self.process_extra_info(status),
+            query_id: context_shared.map(|shared| shared.init_query_id.read().clone()),
+            query: context_shared.and_then(|shared| shared.running_query.read().clone()),
+            query_elapsed: context_shared.map(|shared| shared.created_at.elapsed()),
         }
     }
 
@@ -16,26 +16,38 @@ use std::collections::hash_map::Entry::Occupied;
 use std::collections::hash_map::Entry::Vacant;
 use std::collections::HashMap;
 use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use common_base::tokio;
 use common_base::tokio::sync::mpsc::Receiver;
+use common_base::GlobalUniqName;
+use common_base::RingBuffer;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
 use futures::future::Either;
 use metrics::counter;
+use metrics::gauge;
 
 use crate::catalogs::impls::DatabaseCatalog;
 use crate::catalogs::Catalog;
 use crate::clusters::ClusterDiscoveryRef;
 use crate::configs::Config;
 use crate::datasources::database::example::ExampleDatabaseEngine;
+use crate::datasources::table::fuse::SegmentCache;
+use crate::datasources::table::fuse::SegmentCacheRef;
+use crate::sessions::query_log::QueryLogRecord;
+use crate::sessions::query_log::QUERY_LOG_CAPACITY;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
 use crate::users::UserManagerRef;
 
+/// The prefix of the ids generated for the sessions.
+pub const SESSION_ID_PREFIX: &str = "sess-";
+
 pub struct SessionManager {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) discovery: ClusterDiscoveryRef,
@@ -44,6 +56,10 @@ pub struct SessionManager {
 
     pub(in crate::sessions) max_sessions: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
+    pub(in crate::sessions) query_log: Arc<RingBuffer<QueryLogRecord>>,
+    pub(in crate::sessions) segment_cache: SegmentCacheRef,
+    pub(in crate::sessions) active_connections: Arc<AtomicUsize>,
+    pub(in crate::sessions) failed_connections: AtomicUsize,
 }
 
 pub type SessionManagerRef = Arc<SessionManager>;
@@ -66,6 +82,11 @@ impl SessionManager {
             user,
             max_sessions: max_active_sessions,
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_active_sessions))),
+            query_log: Arc::new(RingBuffer::with_capacity(QUERY_LOG_CAPACITY)),
+            // resized by the `segment_cache_size` setting of the readers
+            segment_cache: SegmentCache::create(0),
+            active_connections: Arc::new(AtomicUsize::new(0)),
+            failed_connections: AtomicUsize::new(0),
         }))
     }
 
@@ -86,6 +107,42 @@ impl SessionManager {
         self.catalog.clone()
     }
 
+    // The latest completed queries of all the sessions.
+    pub fn get_query_log(self: &Arc<Self>) -> Arc<RingBuffer<QueryLogRecord>> {
+        self.query_log.clone()
+    }
+
+    // The segments of the fuse tables cached for all the queries.
+    pub fn get_segment_cache(self: &Arc<Self>) -> SegmentCacheRef {
+        self.segment_cache.clone()
+    }
+
+    /// Counts a client connection as active until the returned guard is dropped, at the
+    /// teardown of the connection.
+    pub fn enter_connection(self: &Arc<Self>) -> ConnectionGuard {
+        let active = self.active_connections.fetch_add(1, Ordering::Relaxed) + 1;
+        gauge!(
+            super::metrics::METRIC_SESSION_ACTIVE_CONNECTIONS,
+            active as f64
+        );
+        ConnectionGuard(self.active_connections.clone())
+    }
+
+    pub fn get_active_connections(self: &Arc<Self>) -> usize {
+        self.active_connections.load(Ordering::Relaxed)
+    }
+
+    /// Counts a client connection torn down by an unexpected error, the disconnects of the
+    /// clients are not counted.
+    pub fn fail_connection(self: &Arc<Self>) {
+        counter!(super::metrics::METRIC_SESSION_FAILED_CONNECTIONS, 1);
+        self.failed_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_failed_connections(self: &Arc<Self>) -> usize {
+        self.failed_connections.load(Ordering::Relaxed)
+    }
+
     pub fn create_session(self: &Arc<Self>, typ: impl Into<String>) -> Result<SessionRef> {
         counter!(super::metrics::METRIC_SESSION_CONNECT_NUMBERS, 1);
 
@@ -97,7 +154,7 @@ impl SessionManager {
             false => {
                 let session = Session::try_create(
                     self.conf.clone(),
-                    uuid::Uuid::new_v4().to_string(),
+                    GlobalUniqName::unique_with_prefix(SESSION_ID_PREFIX),
                     typ.into(),
                     self.clone(),
                 )?;
@@ -139,6 +196,15 @@ impl SessionManager {
             .map(|session| SessionRef::create(session.clone()))
     }
 
+    /// Get the session running the query of `query_id`, if any.
+    pub fn get_session_by_query_id(self: &Arc<Self>, query_id: &str) -> Option<SessionRef> {
+        let sessions = self.active_sessions.read();
+        sessions
+            .values()
+            .find(|session| session.process_info().query_id.as_deref() == Some(query_id))
+            .map(|session| SessionRef::create(session.clone()))
+    }
+
     #[allow(clippy::ptr_arg)]
     pub fn destroy_session(self: &Arc<Self>, session_id: &String) {
         counter!(super::metrics::METRIC_SESSION_CLOSE_NUMBERS, 1);
@@ -193,3 +259,15 @@ impl SessionManager {
         }
     }
 }
+
+pub struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        let active = self.0.fetch_sub(1, Ordering::Relaxed) - 1;
+        gauge!(
+            super::metrics::METRIC_SESSION_ACTIVE_CONNECTIONS,
+            active as f64
+        );
+    }
+}
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use chrono_tz::Tz;
 use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -29,9 +30,17 @@ impl Settings {
     apply_macros! { apply_getter_setter_settings, apply_initial_settings, apply_update_settings,
         ("max_block_size", u64, 10000, "Maximum block size for reading"),
         ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically."),
+        ("read_batch_size", u64, 10000, "Maximum number of rows of the blocks produced by reading a table."),
+        ("read_bite_size", u64, 1, "Number of partitions a table read takes from the query at a time."),
+        ("max_parts_per_query", u64, 100000, "The maximum number of partitions a table read is scheduled with. Beyond it, adjacent partitions are coalesced. 0 means unlimited."),
+        ("max_execute_time", u64, 0, "Maximum execution time of a query in seconds, beyond it the query is cancelled. 0 means unlimited."),
         ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds"),
+        ("max_stage_dispatch_time", u64, 300, "Maximum time in seconds dispatching the stages of a distributed query to the nodes may take, beyond it the query is cancelled. 0 means unlimited."),
+        ("max_remote_stage_retries", u64, 2, "Maximum number of times the stage of a distributed query is re-issued to a node it fails to reach. 0 means no retry."),
         ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query."),
-        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.")
+        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query."),
+        ("segment_cache_size", u64, 1024, "Maximum number of the segments of the fuse tables cached by the node, shared by all the queries. 0 disables the cache."),
+        ("timezone", String, "UTC", "Timezone of the session, the DateTime values without a timezone are rendered and evaluated by the date functions in it, e.g. Asia/Shanghai. By default, it is UTC.")
     }
 
     pub fn try_create() -> Result<Arc<Settings>> {
@@ -45,6 +54,11 @@ impl Settings {
         Ok(settings)
     }
 
+    /// The `timezone` setting parsed.
+    pub fn get_tz(&self) -> Result<Tz> {
+        parse_timezone(&self.get_timezone()?)
+    }
+
     pub fn iter(&self) -> SettingsIterator {
         SettingsIterator {
             settings: self.inner.get_settings(),
@@ -228,7 +242,7 @@ impl SettingsBase {
     }
 
     #[allow(unused)]
-    pub fn try_update_string(&self, key: &'static str, val: &str) -> Result<()> {
+    pub fn try_update_string(&self, key: &'static str, val: String) -> Result<()> {
         let mut settings = self.settings.write();
         let setting_val = settings
             .get(key)
@@ -236,7 +250,7 @@ impl SettingsBase {
 
         if let DataValue::Struct(values) = setting_val {
             let v = DataValue::Struct(vec![
-                DataValue::String(Some(val.as_bytes().to_vec())),
+                DataValue::String(Some(val.into_bytes())),
                 values[1].clone(),
                 values[2].clone(),
             ]);
@@ -246,7 +260,7 @@ impl SettingsBase {
     }
 
     #[allow(unused)]
-    pub fn try_get_string(&self, key: &str) -> Result<Vec<u8>> {
+    pub fn try_get_string(&self, key: &str) -> Result<String> {
         let settings = self.settings.read();
         let setting_val = settings
             .get(key)
@@ -254,7 +268,7 @@ impl SettingsBase {
 
         if let DataValue::Struct(values) = setting_val {
             if let DataValue::String(Some(result)) = values[0].clone() {
-                return Ok(result);
+                return String::from_utf8(result).map_err(ErrorCode::from);
             }
         }
 
@@ -283,6 +297,11 @@ impl SettingsBase {
     }
 }
 
+pub fn parse_timezone(tz: &str) -> Result<Tz> {
+    tz.parse::<Tz>()
+        .map_err(|_| ErrorCode::BadArguments(format!("Unknown timezone: {:?}", tz)))
+}
+
 pub struct SettingsIterator {
     settings: Vec<DataValue>,
     index: usize,
@@ -32,6 +32,7 @@ use common_planners::rebase_expr_from_input;
 use common_planners::resolve_aliases_to_exprs;
 use common_planners::sort_to_inner_expr;
 use common_planners::unwrap_alias_exprs;
+use common_planners::AlterTableAddColumnPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DescribeTablePlan;
@@ -41,11 +42,14 @@ use common_planners::ExplainPlan;
 use common_planners::Expression;
 use common_planners::InsertIntoPlan;
 use common_planners::KillPlan;
+use common_planners::OptimizeTablePlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::SelectPlan;
 use common_planners::SettingPlan;
 use common_planners::ShowCreateTablePlan;
+use common_planners::ShowDatabasesPlan;
+use common_planners::ShowTablesPlan;
 use common_planners::TableScanInfo;
 use common_planners::TruncateTablePlan;
 use common_planners::UseDatabasePlan;
@@ -54,6 +58,9 @@ use common_streams::Source;
 use common_streams::ValueSource;
 use common_tracing::tracing;
 use nom::FindSubstring;
+use sqlparser::ast::AlterTableOperation;
+use sqlparser::ast::ColumnDef;
+use sqlparser::ast::ColumnOption;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
@@ -62,9 +69,14 @@ use sqlparser::ast::Query;
 use sqlparser::ast::Statement;
 use sqlparser::ast::TableFactor;
 use sqlparser::ast::UnaryOperator;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Tokenizer;
 
 use crate::catalogs::Catalog;
 use crate::functions::ContextFunction;
+use crate::optimizers::RequireColumnsVisitor;
+use crate::pipelines::transforms::ExpressionExecutor;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sql::sql_statement::DfCreateTable;
 use crate::sql::sql_statement::DfDropDatabase;
@@ -75,6 +87,7 @@ use crate::sql::DfDropTable;
 use crate::sql::DfExplain;
 use crate::sql::DfHint;
 use crate::sql::DfKillStatement;
+use crate::sql::DfOptimizeTable;
 use crate::sql::DfParser;
 use crate::sql::DfShowCreateTable;
 use crate::sql::DfShowDatabases;
@@ -130,37 +143,10 @@ impl PlanParser {
             DfStatement::DescribeTable(v) => self.sql_describe_table_to_plan(v),
             DfStatement::DropTable(v) => self.sql_drop_table_to_plan(v),
             DfStatement::TruncateTable(v) => self.sql_truncate_table_to_plan(v),
+            DfStatement::OptimizeTable(v) => self.sql_optimize_table_to_plan(v),
             DfStatement::UseDatabase(v) => self.sql_use_database_to_plan(v),
             DfStatement::ShowCreateTable(v) => self.sql_show_create_table_to_plan(v),
-            DfStatement::ShowTables(df) => {
-                let show_sql = match df {
-                    DfShowTables::All => {
-                        format!(
-                            "SELECT name FROM system.tables where database = '{}' ORDER BY database, name",
-                            self.ctx.get_current_database()
-                        )
-                    }
-                    DfShowTables::Like(i) => {
-                        format!(
-                            "SELECT name FROM system.tables where database = '{}' AND name LIKE {} ORDER BY database, name",
-                            self.ctx.get_current_database(), i,
-                        )
-                    }
-                    DfShowTables::Where(e) => {
-                        format!(
-                            "SELECT name FROM system.tables where database = '{}' AND ({}) ORDER BY database, name",
-                            self.ctx.get_current_database(), e,
-                        )
-                    }
-                    DfShowTables::FromOrIn(name) => {
-                        format!(
-                            "SELECT name FROM system.tables where database = '{}' ORDER BY database, name",
-                            name.0[0].value.clone()
-                        )
-                    }
-                };
-                self.build_from_sql(show_sql.as_str())
-            }
+            DfStatement::ShowTables(v) => self.sql_show_tables_to_plan(v),
             DfStatement::ShowSettings(_) => self.build_from_sql("SELECT name FROM system.settings"),
             DfStatement::ShowProcessList(_) => {
                 self.build_from_sql("SELECT * FROM system.processes")
@@ -189,6 +175,16 @@ impl PlanParser {
                 self.insert_to_plan(table_name, columns, source, &format_sql)
             }
 
+            Statement::AlterTable { name, operation } => match operation {
+                AlterTableOperation::AddColumn { column_def } => {
+                    self.alter_table_add_column_to_plan(name, column_def)
+                }
+                _ => Result::Err(ErrorCode::SyntaxException(format!(
+                    "Unsupported alter table operation {}",
+                    operation
+                ))),
+            },
+
             _ => Result::Err(ErrorCode::SyntaxException(format!(
                 "Unsupported statement {:?}",
                 statement
@@ -227,12 +223,54 @@ impl PlanParser {
         }))
     }
 
+    /// DfShowTables to plan
+    #[tracing::instrument(level = "info", skip(self, show), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_show_tables_to_plan(&self, show: &DfShowTables) -> Result<PlanNode> {
+        // The listing and `LIKE` filter are answered by the catalog,
+        // `WHERE` falls back to a query on `system.tables`.
+        let db = self.ctx.get_current_database();
+        match show {
+            DfShowTables::All => Ok(PlanNode::ShowTables(ShowTablesPlan { db, like: None })),
+            DfShowTables::Like(pattern) => Ok(PlanNode::ShowTables(ShowTablesPlan {
+                db,
+                like: Some(pattern.value.clone()),
+            })),
+            DfShowTables::FromOrIn(name) => Ok(PlanNode::ShowTables(ShowTablesPlan {
+                db: name.0[0].value.clone(),
+                like: None,
+            })),
+            DfShowTables::Where(expr) => self.build_from_sql(
+                format!(
+                    "SELECT name FROM system.tables where database = '{}' AND ({}) ORDER BY database, name",
+                    db, expr,
+                )
+                .as_str(),
+            ),
+        }
+    }
+
     /// DfShowDatabase to plan
     #[tracing::instrument(level = "info", skip(self, show), fields(ctx.id = self.ctx.get_id().as_str()))]
     pub fn sql_show_databases_to_plan(&self, show: &DfShowDatabases) -> Result<PlanNode> {
+        // Plain `SHOW DATABASES` and `SHOW DATABASES LIKE '<pattern>'` are answered by the catalog,
+        // other filters fall back to a query on `system.databases`.
         let where_clause = match &show.where_opt {
+            None => return Ok(PlanNode::ShowDatabases(ShowDatabasesPlan { like: None })),
+            Some(sqlparser::ast::Expr::BinaryOp {
+                left,
+                op: sqlparser::ast::BinaryOperator::Like,
+                right,
+            }) if matches!(left.as_ref(), sqlparser::ast::Expr::Identifier(v) if v.value == "name") => {
+                match right.as_ref() {
+                    sqlparser::ast::Expr::Value(sqlparser::ast::Value::SingleQuotedString(v)) => {
+                        return Ok(PlanNode::ShowDatabases(ShowDatabasesPlan {
+                            like: Some(v.clone()),
+                        }));
+                    }
+                    _ => format!("WHERE {}", show.where_opt.as_ref().unwrap()),
+                }
+            }
             Some(expr) => format!("WHERE {}", expr),
-            None => String::from(""),
         };
 
         self.build_from_sql(
@@ -255,6 +293,7 @@ impl PlanParser {
         Ok(PlanNode::DropDatabase(DropDatabasePlan {
             if_exists: drop.if_exists,
             db: name,
+            cascade: drop.cascade,
         }))
     }
 
@@ -298,8 +337,19 @@ impl PlanParser {
             .columns
             .iter()
             .map(|column| {
-                SQLCommon::make_data_type(&column.data_type)
-                    .map(|data_type| DataField::new(&column.name.value, data_type, false))
+                let data_type = SQLCommon::make_data_type(&column.data_type)?;
+                let default_expr = column
+                    .options
+                    .iter()
+                    .find_map(|option| match &option.option {
+                        ColumnOption::Default(expr) => Some(expr.to_string()),
+                        _ => None,
+                    });
+                let field = DataField::new(&column.name.value, data_type, false)
+                    .with_default_expr(default_expr);
+                // fails early if the default value can not be evaluated
+                self.column_default_value(&field)?;
+                Ok(field)
             })
             .collect::<Result<Vec<DataField>>>()?;
 
@@ -372,6 +422,7 @@ impl PlanParser {
             DataField::new("Field", DataType::String, false),
             DataField::new("Type", DataType::String, false),
             DataField::new("Null", DataType::String, false),
+            DataField::new("Default", DataType::String, false),
         ]);
 
         Ok(PlanNode::DescribeTable(DescribeTablePlan {
@@ -400,6 +451,113 @@ impl PlanParser {
         }))
     }
 
+    /// ALTER TABLE ... ADD COLUMN to plan.
+    /// The new column is nullable unless NOT NULL is given, so that existing rows can read it as NULL.
+    /// With a DEFAULT, the existing rows read the default value, as evaluated here, instead.
+    #[tracing::instrument(level = "info", skip(self, name, column), fields(ctx.id = self.ctx.get_id().as_str()))]
+    fn alter_table_add_column_to_plan(
+        &self,
+        name: &ObjectName,
+        column: &ColumnDef,
+    ) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Alter table name is empty"));
+        }
+        let mut table = name.0[0].value.clone();
+        if name.0.len() > 1 {
+            db = table;
+            table = name.0[1].value.clone();
+        }
+
+        let mut nullable = true;
+        let mut default_expr = None;
+        for option in column.options.iter() {
+            match &option.option {
+                ColumnOption::Null => nullable = true,
+                ColumnOption::NotNull => nullable = false,
+                ColumnOption::Default(expr) => default_expr = Some(expr.to_string()),
+                other => {
+                    return Result::Err(ErrorCode::SyntaxException(format!(
+                        "Unsupported column option {}",
+                        other
+                    )))
+                }
+            }
+        }
+
+        let data_type = SQLCommon::make_data_type(&column.data_type)?;
+        let field =
+            DataField::new(&column.name.value, data_type, nullable).with_default_expr(default_expr);
+        // evaluated once, the existing rows read the same value whenever they are read
+        let default_value = self.column_default_value(&field)?;
+        let field = field.with_default_value(default_value.as_ref())?;
+        Ok(PlanNode::AlterTableAddColumn(AlterTableAddColumnPlan {
+            db,
+            table,
+            field,
+        }))
+    }
+
+    /// Evaluates the default value of `field`, None if the field has no default expression.
+    /// The expression must not reference any column, it is evaluated as a constant and cast to
+    /// the type of the field.
+    pub fn column_default_value(&self, field: &DataField) -> Result<Option<DataValue>> {
+        let default_expr = match field.default_expr() {
+            None => return Ok(None),
+            Some(default_expr) => default_expr,
+        };
+
+        let dialect = GenericDialect {};
+        let expr = Tokenizer::new(&dialect, default_expr)
+            .tokenize()
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|tokens| {
+                Parser::new(tokens, &dialect)
+                    .parse_expr()
+                    .map_err(|e| e.to_string())
+            })
+            .map_err(|e| {
+                ErrorCode::SyntaxException(format!(
+                    "Invalid default value of column `{}`: {}",
+                    field.name(),
+                    e
+                ))
+            })?;
+        let expression = self.sql_to_rex(&expr, &DataSchema::empty(), None)?;
+
+        let mut visitor = RequireColumnsVisitor::default();
+        visitor = expression.accept(visitor)?;
+        if !visitor.required_columns.is_empty() {
+            return Err(ErrorCode::BadArguments(format!(
+                "Default value of column `{}` must be a constant expression, got {}",
+                field.name(),
+                default_expr
+            )));
+        }
+
+        // evaluated upon a single dummy row, as the constant folding does
+        let input_schema =
+            DataSchemaRefExt::create(vec![DataField::new("_dummy", DataType::UInt8, false)]);
+        let output_schema =
+            DataSchemaRefExt::create(vec![expression.to_data_field(&input_schema)?]);
+        let executor = ExpressionExecutor::try_create(
+            "Column default value",
+            input_schema.clone(),
+            output_schema,
+            vec![expression],
+            false,
+        )?;
+        let dummy_columns = vec![DataColumn::Constant(DataValue::UInt8(Some(1)), 1)];
+        let block = executor.execute(&DataBlock::create(input_schema, dummy_columns))?;
+        let value = block
+            .column(0)
+            .cast_with_type(field.data_type())?
+            .to_values()?
+            .remove(0);
+        Ok(Some(value))
+    }
+
     // DfTruncateTable to plan.
     #[tracing::instrument(level = "info", skip(self, truncate), fields(ctx.id = self.ctx.get_id().as_str()))]
     pub fn sql_truncate_table_to_plan(&self, truncate: &DfTruncateTable) -> Result<PlanNode> {
@@ -418,6 +576,24 @@ impl PlanParser {
         Ok(PlanNode::TruncateTable(TruncateTablePlan { db, table }))
     }
 
+    // DfOptimizeTable to plan.
+    #[tracing::instrument(level = "info", skip(self, optimize), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_optimize_table_to_plan(&self, optimize: &DfOptimizeTable) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if optimize.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException(
+                "OptimizeTable table name is empty",
+            ));
+        }
+        let mut table = optimize.name.0[0].value.clone();
+        if optimize.name.0.len() > 1 {
+            db = table;
+            table = optimize.name.0[1].value.clone();
+        }
+
+        Ok(PlanNode::OptimizeTable(OptimizeTablePlan { db, table }))
+    }
+
     #[tracing::instrument(level = "info", skip(self, table_name, columns, source), fields(ctx.id = self.ctx.get_id().as_str()))]
     fn insert_to_plan(
         &self,
@@ -447,7 +623,8 @@ impl PlanParser {
             schema = DataSchemaRefExt::create(fields);
         }
 
-        let mut input_stream = futures::stream::iter::<Vec<DataBlock>>(vec![]);
+        let mut input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![]);
+        let mut select_plan = None;
 
         if let Some(source) = source {
             if let sqlparser::ast::SetExpr::Values(_vs) = &source.body {
@@ -461,11 +638,13 @@ impl PlanParser {
                 loop {
                     let block = source.read()?;
                     match block {
-                        Some(b) => blocks.push(b),
+                        Some(b) => blocks.push(Ok(b)),
                         None => break,
                     }
                 }
                 input_stream = futures::stream::iter(blocks);
+            } else {
+                select_plan = Some(Box::new(self.query_to_plan(source)?));
             }
         }
 
@@ -474,6 +653,7 @@ impl PlanParser {
             tbl_name,
             tbl_id,
             schema,
+            select_plan,
             input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
         };
         Ok(PlanNode::InsertInto(plan_node))
@@ -1056,7 +1236,7 @@ impl PlanParser {
 
                 let op = e.name.to_string();
                 if AggregateFunctionFactory::instance().check(&op) {
-                    let args = match op.to_lowercase().as_str() {
+                    let mut args = match op.to_lowercase().as_str() {
                         "count" => args
                             .iter()
                             .map(|c| match c {
@@ -1067,7 +1247,7 @@ impl PlanParser {
                         _ => args,
                     };
 
-                    let params = e
+                    let mut params = e
                         .params
                         .iter()
                         .map(|v| {
@@ -1083,6 +1263,14 @@ impl PlanParser {
                         })
                         .collect::<Result<Vec<_>>>()?;
 
+                    // quantile(expr, level) is the same as quantile(level)(expr)
+                    if op.eq_ignore_ascii_case("quantile") && params.is_empty() && args.len() == 2 {
+                        if let Expression::Literal { value, .. } = &args[1] {
+                            params = vec![value.clone()];
+                            args.truncate(1);
+                        }
+                    }
+
                     return Ok(Expression::AggregateFunction {
                         op,
                         distinct: e.distinct,
@@ -1184,6 +1372,9 @@ impl PlanParser {
             let variable = variable.value.clone();
             let value = match value {
                 sqlparser::ast::SetVariableValue::Ident(v) => v.value.clone(),
+                sqlparser::ast::SetVariableValue::Literal(
+                    sqlparser::ast::Value::SingleQuotedString(v),
+                ) => v.clone(),
                 sqlparser::ast::SetVariableValue::Literal(v) => v.to_string(),
             };
             vars.push(VarValue { variable, value });
@@ -42,13 +42,19 @@ fn test_plan_parser() -> Result<()> {
         Test {
             name: "drop-database-passed",
             sql: "DROP DATABASE db1",
-            expect: "Drop database db1, if_exists:false",
+            expect: "Drop database db1, if_exists:false, cascade:false",
             error: "",
         },
         Test {
             name: "drop-database-if-exists-passed",
             sql: "DROP DATABASE IF EXISTS db1",
-            expect: "Drop database db1, if_exists:true",
+            expect: "Drop database db1, if_exists:true, cascade:false",
+            error: "",
+        },
+        Test {
+            name: "drop-database-cascade-passed",
+            sql: "DROP DATABASE IF EXISTS db1 CASCADE",
+            expect: "Drop database db1, if_exists:true, cascade:true",
             error: "",
         },
         Test {
@@ -154,7 +160,7 @@ fn test_plan_parser() -> Result<()> {
             error: "Code: 25, displayText = Unknown table: 't'.",
         },
         Test {
-            name: "insert-subquery-not-supported",
+            name: "insert-select",
             sql: "insert into t select * from t",
             expect: "",
             error: "Code: 25, displayText = Unknown table: 't'.",
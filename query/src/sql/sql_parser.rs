@@ -45,6 +45,7 @@ use crate::sql::DfDropTable;
 use crate::sql::DfExplain;
 use crate::sql::DfHint;
 use crate::sql::DfKillStatement;
+use crate::sql::DfOptimizeTable;
 use crate::sql::DfShowCreateTable;
 use crate::sql::DfShowDatabases;
 use crate::sql::DfShowProcessList;
@@ -78,9 +79,14 @@ impl<'a> DfParser<'a> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
         let tokens = tokenizer.tokenize()?;
 
-        Ok(DfParser {
+        Ok(DfParser::new_with_tokens(tokens, dialect))
+    }
+
+    /// Parse the tokens of an already tokenized SQL with dialect
+    pub fn new_with_tokens(tokens: Vec<Token>, dialect: &'a dyn Dialect) -> Self {
+        DfParser {
             parser: Parser::new(tokens, dialect),
-        })
+        }
     }
 
     /// Parse a SQL statement and produce a set of statements with dialect
@@ -98,41 +104,55 @@ impl<'a> DfParser<'a> {
         dialect: &dyn Dialect,
     ) -> Result<(Vec<DfStatement>, Vec<DfHint>), ParserError> {
         let mut parser = DfParser::new_with_dialect(sql, dialect)?;
+        let stmts = parser.parse_statements()?;
+
+        let mut hints = Vec::new();
+
+        let mut parser = DfParser::new_with_dialect(sql, dialect)?;
+        loop {
+            let token = parser.parser.next_token_no_skip();
+            match token {
+                Some(Token::Whitespace(Whitespace::SingleLineComment { comment, prefix })) => {
+                    hints.push(DfHint::create_from_comment(comment, prefix));
+                }
+                Some(Token::Whitespace(Whitespace::Newline)) | Some(Token::EOF) | None => break,
+                _ => continue,
+            }
+        }
+        Ok((stmts, hints))
+    }
+
+    /// Parse the tokens of an already tokenized SQL and produce a set of statements
+    pub fn parse_tokens(tokens: Vec<Token>) -> Result<Vec<DfStatement>, ErrorCode> {
+        let dialect = &GenericDialect {};
+        let start = Instant::now();
+        let stmts = DfParser::new_with_tokens(tokens, dialect).parse_statements()?;
+        histogram!(super::metrics::METRIC_PARSER_USEDTIME, start.elapsed());
+        Ok(stmts)
+    }
+
+    fn parse_statements(&mut self) -> Result<Vec<DfStatement>, ParserError> {
         let mut stmts = Vec::new();
 
         let mut expecting_statement_delimiter = false;
         loop {
             // ignore empty statements (between successive statement delimiters)
-            while parser.parser.consume_token(&Token::SemiColon) {
+            while self.parser.consume_token(&Token::SemiColon) {
                 expecting_statement_delimiter = false;
             }
 
-            if parser.parser.peek_token() == Token::EOF {
+            if self.parser.peek_token() == Token::EOF {
                 break;
             }
             if expecting_statement_delimiter {
-                return parser.expected("end of statement", parser.parser.peek_token());
+                return self.expected("end of statement", self.parser.peek_token());
             }
 
-            let statement = parser.parse_statement()?;
+            let statement = self.parse_statement()?;
             stmts.push(statement);
             expecting_statement_delimiter = true;
         }
-
-        let mut hints = Vec::new();
-
-        let mut parser = DfParser::new_with_dialect(sql, dialect)?;
-        loop {
-            let token = parser.parser.next_token_no_skip();
-            match token {
-                Some(Token::Whitespace(Whitespace::SingleLineComment { comment, prefix })) => {
-                    hints.push(DfHint::create_from_comment(comment, prefix));
-                }
-                Some(Token::Whitespace(Whitespace::Newline)) | Some(Token::EOF) | None => break,
-                _ => continue,
-            }
-        }
-        Ok((stmts, hints))
+        Ok(stmts)
     }
 
     /// Report unexpected token
@@ -207,6 +227,7 @@ impl<'a> DfParser<'a> {
                         // Use database
                         "USE" => self.parse_use_database(),
                         "KILL" => self.parse_kill_query(),
+                        "OPTIMIZE" => self.parse_optimize(),
                         _ => self.expected("Keyword", self.parser.peek_token()),
                     },
                     _ => {
@@ -236,6 +257,10 @@ impl<'a> DfParser<'a> {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "COST" => {
+                    self.parser.next_token();
+                    ExplainType::Cost
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
@@ -426,10 +451,12 @@ impl<'a> DfParser<'a> {
     fn parse_drop_database(&mut self) -> Result<DfStatement, ParserError> {
         let if_exists = self.parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
         let db_name = self.parser.parse_object_name()?;
+        let cascade = self.parser.parse_keyword(Keyword::CASCADE);
 
         let drop = DfDropDatabase {
             if_exists,
             name: db_name,
+            cascade,
         };
 
         Ok(DfStatement::DropDatabase(drop))
@@ -557,6 +584,19 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    // OPTIMIZE TABLE
+    fn parse_optimize(&mut self) -> Result<DfStatement, ParserError> {
+        match self.consume_token("OPTIMIZE") {
+            true if self.parser.parse_keyword(Keyword::TABLE) => {
+                let table_name = self.parser.parse_object_name()?;
+                let optimize = DfOptimizeTable { name: table_name };
+                Ok(DfStatement::OptimizeTable(optimize))
+            }
+            true => self.expected("optimize statement", self.parser.peek_token()),
+            false => self.expected("Must OPTIMIZE", self.parser.peek_token()),
+        }
+    }
+
     fn consume_token(&mut self, expected: &str) -> bool {
         if self.parser.peek_token().to_string().to_uppercase() == *expected.to_uppercase() {
             self.parser.next_token();
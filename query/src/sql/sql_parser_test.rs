@@ -101,6 +101,7 @@ fn drop_database() -> Result<()> {
         let expected = DfStatement::DropDatabase(DfDropDatabase {
             if_exists: false,
             name: ObjectName(vec![Ident::new("db1")]),
+            cascade: false,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -109,6 +110,16 @@ fn drop_database() -> Result<()> {
         let expected = DfStatement::DropDatabase(DfDropDatabase {
             if_exists: true,
             name: ObjectName(vec![Ident::new("db1")]),
+            cascade: false,
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+    {
+        let sql = "DROP DATABASE db1 CASCADE";
+        let expected = DfStatement::DropDatabase(DfDropDatabase {
+            if_exists: false,
+            name: ObjectName(vec![Ident::new("db1")]),
+            cascade: true,
         });
         expect_parse_ok(sql, expected)?;
     }
@@ -298,6 +309,32 @@ fn truncate_table() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn optimize_table() -> Result<()> {
+    {
+        let sql = "OPTIMIZE TABLE t1";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "OPTIMIZE TABLE db1.t1";
+        let expected = DfStatement::OptimizeTable(DfOptimizeTable {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+        });
+        expect_parse_ok(sql, expected)?;
+    }
+
+    {
+        let sql = "OPTIMIZE t1";
+        assert!(DfParser::parse_sql(sql).is_err());
+    }
+
+    Ok(())
+}
+
 #[test]
 fn hint_test() -> Result<()> {
     {
@@ -82,6 +82,11 @@ pub struct DfTruncateTable {
     pub name: ObjectName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfOptimizeTable {
+    pub name: ObjectName,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateDatabase {
     pub if_not_exists: bool,
@@ -94,6 +99,7 @@ pub struct DfCreateDatabase {
 pub struct DfDropDatabase {
     pub if_exists: bool,
     pub name: ObjectName,
+    pub cascade: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -126,6 +132,7 @@ pub enum DfStatement {
     DescribeTable(DfDescribeTable),
     DropTable(DfDropTable),
     TruncateTable(DfTruncateTable),
+    OptimizeTable(DfOptimizeTable),
 
     // Settings.
     ShowSettings(DfShowSettings),
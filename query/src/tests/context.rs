@@ -22,10 +22,25 @@ use crate::configs::Config;
 use crate::sessions::DatabendQueryContext;
 use crate::sessions::DatabendQueryContextRef;
 use crate::sessions::DatabendQueryContextShared;
+use crate::sessions::SessionManagerRef;
 use crate::tests::SessionManagerBuilder;
 
 pub fn try_create_context() -> Result<DatabendQueryContextRef> {
     let sessions = SessionManagerBuilder::create().build()?;
+    try_create_context_with_sessions(sessions)
+}
+
+/// The tables of the context, which store their data to the local disk, store it under `data_path`.
+pub fn try_create_context_with_data_path(data_path: &str) -> Result<DatabendQueryContextRef> {
+    let sessions = SessionManagerBuilder::create()
+        .disk_data_path(data_path)
+        .build()?;
+    try_create_context_with_sessions(sessions)
+}
+
+fn try_create_context_with_sessions(
+    sessions: SessionManagerRef,
+) -> Result<DatabendQueryContextRef> {
     let dummy_session = sessions.create_session("TestSession")?;
 
     let context = DatabendQueryContext::from_shared(DatabendQueryContextShared::try_create(
@@ -24,6 +24,7 @@ pub use catalog::try_create_catalog;
 pub use context::try_create_cluster_context;
 pub use context::try_create_context;
 pub use context::try_create_context_with_config;
+pub use context::try_create_context_with_data_path;
 pub use context::ClusterDescriptor;
 pub use number::NumberTestData;
 pub use parquet::ParquetTestData;
@@ -56,6 +56,12 @@ impl SessionManagerBuilder {
         SessionManagerBuilder::inner_create(new_config)
     }
 
+    pub fn mysql_handler_idle_timeout(self, seconds: u64) -> SessionManagerBuilder {
+        let mut new_config = self.config.clone();
+        new_config.query.mysql_handler_idle_timeout = seconds;
+        SessionManagerBuilder::inner_create(new_config)
+    }
+
     pub fn rpc_tls_server_key(self, value: impl Into<String>) -> SessionManagerBuilder {
         let mut new_config = self.config.clone();
         new_config.query.rpc_tls_server_key = value.into();
@@ -86,6 +92,12 @@ impl SessionManagerBuilder {
         SessionManagerBuilder::inner_create(new_config)
     }
 
+    pub fn disk_data_path(self, path: impl Into<String>) -> SessionManagerBuilder {
+        let mut new_config = self.config.clone();
+        new_config.storage.disk.data_path = path.into();
+        SessionManagerBuilder::inner_create(new_config)
+    }
+
     pub fn log_dir_with_relative(self, path: impl Into<String>) -> SessionManagerBuilder {
         let mut new_config = self.config.clone();
         new_config.log.log_dir = env::current_dir()
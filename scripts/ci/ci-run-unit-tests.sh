@@ -5,5 +5,10 @@
 SCRIPT_PATH="$(cd "$(dirname "$0")" >/dev/null 2>&1 && pwd)"
 cd "$SCRIPT_PATH/../../" || exit
 
+set -e
+
 echo "Starting unit tests"
 cargo test
+
+echo "Starting unit tests of the optional features"
+cargo test -p common-datavalues --features rayon
@@ -6,3 +6,9 @@ DROP DATABASE db;
 DROP DATABASE IF EXISTS db;
 
 DROP DATABASE db; -- {ErrorCode 3}
+
+CREATE DATABASE db ENGINE = default;
+CREATE TABLE db.t(c1 int) ENGINE = Null;
+DROP DATABASE db; -- {ErrorCode 4011}
+DROP DATABASE db CASCADE;
+SELECT COUNT(1) FROM system.tables WHERE database = 'db';
@@ -16,11 +16,11 @@ SELECT * FROM t;
 
 SELECT * FROM t2;
 
-DROP DATABASE db1;
+DROP DATABASE db1 CASCADE;
 CREATE DATABASE db1;
 USE db1;
 
 CREATE TABLE IF NOT EXISTS t2(a varchar, b varchar) Engine = remote;
 SELECT * FROM t2;
 
-DROP DATABASE IF EXISTS db1;
+DROP DATABASE IF EXISTS db1 CASCADE;